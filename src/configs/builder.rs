@@ -0,0 +1,225 @@
+//! # Configuration Builder
+//!
+//! Explicit composition of configuration sources into a [`ConfigManager`].
+//!
+//! Layers are merged in a fixed order, lowest to highest precedence:
+//!
+//! 1. defaults (`with_defaults`)
+//! 2. cloud `commonAll`, then the selected cloud section (`with_cloud`)
+//! 3. local file (`with_file`), then encrypted local file (`with_encrypted_file`)
+//! 4. environment variables (`with_env` / `with_env_options`)
+//! 5. `--set` command-line overrides (`with_args`)
+//!
+//! Only the sources that were added take part; in particular env vars are not
+//! read unless requested. Arrays set by several layers are replaced unless an
+//! [`ArrayStrategy`] is chosen, globally or per key path.
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::configs::ConfigBuilder;
+//! # use serde_json::json;
+//! # async fn demo() -> Result<(), rs_lib_ng::core::error::NgError> {
+//! let manager = ConfigBuilder::new()
+//!     .with_defaults(json!({ "nasdaq": { "retry": 2 } }))
+//!     .with_cloud("https://example.com/config.enc")
+//!     .with_file("config.json")
+//!     .with_env("WEBLIB_")
+//!     .build()
+//!     .await?;
+//! # Ok(()) }
+//! ```
+
+use figment::{Figment, providers::{Format, Json}};
+use serde_json::{Value, json};
+use std::time::Duration;
+
+use crate::configs::merge::{self, ArrayStrategy, MergePolicy};
+use crate::configs::{cli, cloud, resolve_config_name, ConfigDefaults, ConfigManager, ConfigSource, EnvOptions, Provenance};
+use crate::core::error::NgError;
+
+/// Per-URL timeout used when failing over between several cloud URLs.
+pub const DEFAULT_CLOUD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fluent builder composing configuration sources.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    defaults: ConfigDefaults,
+    cloud_urls: Vec<String>,
+    cloud_name: Option<String>,
+    cloud_timeout: Option<Duration>,
+    file: Option<String>,
+    encrypted_file: Option<String>,
+    env: Option<EnvOptions>,
+    args: Vec<String>,
+    merge: MergePolicy,
+}
+
+impl ConfigBuilder {
+    /// Creates a builder without any sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deep-merges a defaults document (a JSON value or [`ConfigDefaults`]).
+    pub fn with_defaults(mut self, defaults: impl Into<ConfigDefaults>) -> Self {
+        self.defaults = self.defaults.with_value(defaults.into().into_value());
+        self
+    }
+
+    /// Adds a cloud URL. Further calls add mirrors, tried in order.
+    pub fn with_cloud(mut self, url: &str) -> Self {
+        self.cloud_urls.push(url.to_string());
+        self
+    }
+
+    /// Selects the cloud section explicitly (see [`resolve_config_name`]).
+    pub fn with_cloud_name(mut self, name: &str) -> Self {
+        self.cloud_name = Some(name.to_string());
+        self
+    }
+
+    /// Bounds each cloud download. Defaults to none for a single URL and to
+    /// [`DEFAULT_CLOUD_TIMEOUT`] when failing over between several.
+    pub fn with_cloud_timeout(mut self, timeout: Duration) -> Self {
+        self.cloud_timeout = Some(timeout);
+        self
+    }
+
+    /// Adds a plain JSON file. The file must exist.
+    pub fn with_file(mut self, path: &str) -> Self {
+        self.file = Some(path.to_string());
+        self
+    }
+
+    /// Adds a file in one of the cloud payload formats, decrypted with
+    /// `WEBLIB_AES_PASSWORD`. The whole document is used.
+    pub fn with_encrypted_file(mut self, path: &str) -> Self {
+        self.encrypted_file = Some(path.to_string());
+        self
+    }
+
+    /// Reads env vars with `prefix` and the default `__` nesting separator.
+    pub fn with_env(self, prefix: &str) -> Self {
+        self.with_env_options(EnvOptions::new().with_prefix(prefix))
+    }
+
+    /// Reads env vars with a custom prefix and nesting separator.
+    pub fn with_env_options(mut self, env: EnvOptions) -> Self {
+        self.env = Some(env);
+        self
+    }
+
+    /// Sets how arrays defined by several layers are combined. Defaults to
+    /// [`ArrayStrategy::Replace`].
+    pub fn with_array_strategy(mut self, arrays: ArrayStrategy) -> Self {
+        self.merge = self.merge.with_default(arrays);
+        self
+    }
+
+    /// Sets the array strategy for one dot-separated key path, e.g. a symbol list
+    /// that every layer should extend.
+    pub fn with_array_strategy_at(mut self, path: &str, arrays: ArrayStrategy) -> Self {
+        self.merge = self.merge.with_path(path, arrays);
+        self
+    }
+
+    /// Applies `--set key.path=value` arguments last. Other arguments are ignored.
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.args.extend(args.into_iter().map(|a| a.as_ref().to_string()));
+        self
+    }
+
+    /// Loads all sources and builds the manager.
+    pub async fn build(self) -> Result<ConfigManager, NgError> {
+        let cloud = match self.cloud_urls.as_slice() {
+            [] => None,
+            [url] => {
+                let payload = match self.cloud_timeout {
+                    Some(t) => tokio::time::timeout(t, cloud::load_remote_json(url)).await
+                        .map_err(|_| NgError::ConfigError(format!("{}: timed out after {:?}", url, t)))??,
+                    None => cloud::load_remote_json(url).await?,
+                };
+                Some((payload, url.clone()))
+            }
+            urls => {
+                let urls: Vec<&str> = urls.iter().map(String::as_str).collect();
+                let timeout = self.cloud_timeout.unwrap_or(DEFAULT_CLOUD_TIMEOUT);
+                Some(cloud::load_first_available(&urls, timeout).await?)
+            }
+        };
+        self.assemble(cloud)
+    }
+
+    /// Builds the manager without network access.
+    ///
+    /// Fails if a cloud source was added; use [`build`](Self::build) instead.
+    pub fn build_local(self) -> Result<ConfigManager, NgError> {
+        if !self.cloud_urls.is_empty() {
+            return Err(NgError::ConfigError("Cloud sources require ConfigBuilder::build".into()));
+        }
+        self.assemble(None)
+    }
+
+    fn assemble(self, cloud: Option<(Value, String)>) -> Result<ConfigManager, NgError> {
+        let mut data = self.defaults.into_value();
+        let mut provenance = Provenance::new();
+        provenance.record(&data, &ConfigSource::Default);
+        let mut sources = Vec::new();
+
+        if let Some((full_json, url)) = cloud {
+            let name = resolve_config_name(self.cloud_name.as_deref());
+            for section in ["commonAll", name.as_str()] {
+                if let Some(layer) = full_json.get(section) {
+                    let source = ConfigSource::Cloud { url: url.clone(), section: section.to_string() };
+                    provenance.record(layer, &source);
+                }
+            }
+            let mut sections = full_json.get("commonAll").cloned().unwrap_or_else(|| json!({}));
+            if let Some(specific) = full_json.get(&name) {
+                merge::deep_merge_policy(&mut sections, specific.clone(), &self.merge);
+            }
+            merge::deep_merge_policy(&mut data, sections, &self.merge);
+            sources.push(format!("cloud:{}#{}", url, name));
+        }
+
+        if let Some(path) = &self.file {
+            if !std::path::Path::new(path).exists() {
+                return Err(NgError::ConfigError(format!("Local file not found: {}", path)));
+            }
+            let layer: Value = Figment::from(Json::file(path)).extract()
+                .map_err(|e| NgError::ConfigError(e.to_string()))?;
+            provenance.record(&layer, &ConfigSource::File(path.clone()));
+            merge::deep_merge_policy(&mut data, layer, &self.merge);
+            sources.push(format!("local:{}", path));
+        }
+
+        if let Some(path) = &self.encrypted_file {
+            if !std::path::Path::new(path).exists() {
+                return Err(NgError::ConfigError(format!("Local file not found: {}", path)));
+            }
+            let layer = cloud::load_encrypted_file(path)?;
+            provenance.record(&layer, &ConfigSource::File(path.clone()));
+            merge::deep_merge_policy(&mut data, layer, &self.merge);
+            sources.push(format!("local-encrypted:{}", path));
+        }
+
+        if let Some(env) = &self.env {
+            let layer: Value = Figment::from(env.provider()).extract()
+                .map_err(|e| NgError::ConfigError(e.to_string()))?;
+            provenance.record(&layer, &ConfigSource::Env(env.prefix().to_string()));
+            merge::deep_merge_policy(&mut data, layer, &self.merge);
+        }
+
+        let overrides = cli::parse_args(&self.args)?;
+        cli::apply_overrides(&mut data, &overrides);
+        for o in &overrides {
+            provenance.record_path(&o.path, &o.value, &ConfigSource::Cli);
+        }
+
+        let source_info = if sources.is_empty() { "defaults".to_string() } else { sources.join("+") };
+        Ok(ConfigManager::from_parts(data, provenance, source_info))
+    }
+}
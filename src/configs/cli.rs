@@ -0,0 +1,87 @@
+//! # Command-Line Configuration Overrides
+//!
+//! Parses `--set key.path=value` arguments into a nested JSON document that is
+//! merged on top of file and environment sources.
+//!
+//! Accepted forms are `--set key=value` and `--set=key=value`. Values are parsed
+//! as JSON when possible (`--set nasdaq.retry=5` yields a number), otherwise
+//! they are kept as strings. Unrelated arguments are ignored so the raw
+//! `std::env::args()` of a binary can be passed unchanged.
+
+use serde_json::{Map, Value};
+use crate::core::error::NgError;
+
+/// A single `key.path=value` override.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliOverride {
+    /// Dot-separated key path (e.g. `nasdaq.timeout`).
+    pub path: String,
+    /// Parsed value.
+    pub value: Value,
+}
+
+impl CliOverride {
+    /// Parses a `key.path=value` expression.
+    pub fn parse(expr: &str) -> Result<Self, NgError> {
+        let (path, raw) = expr.split_once('=')
+            .ok_or_else(|| NgError::ConfigError(format!("Invalid --set expression '{}': expected key=value", expr)))?;
+        let path = path.trim();
+        if path.is_empty() || path.split('.').any(|seg| seg.is_empty()) {
+            return Err(NgError::ConfigError(format!("Invalid --set key path '{}'", path)));
+        }
+        let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+        Ok(Self { path: path.to_string(), value })
+    }
+}
+
+/// Extracts all `--set` overrides from an argument list, in order.
+pub fn parse_args<I, S>(args: I) -> Result<Vec<CliOverride>, NgError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut out = Vec::new();
+    let mut iter = args.into_iter();
+    while let Some(arg) = iter.next() {
+        let arg = arg.as_ref();
+        if let Some(expr) = arg.strip_prefix("--set=") {
+            out.push(CliOverride::parse(expr)?);
+        } else if arg == "--set" {
+            let expr = iter.next()
+                .ok_or_else(|| NgError::ConfigError("Missing value after --set".into()))?;
+            out.push(CliOverride::parse(expr.as_ref())?);
+        }
+    }
+    Ok(out)
+}
+
+/// Writes `value` at the dot-separated `path`, creating intermediate objects.
+///
+/// Non-object values found along the path are replaced by objects.
+pub fn set_path(target: &mut Value, path: &str, value: Value) {
+    let mut cursor = target;
+    let mut segments = path.split('.').peekable();
+    while let Some(seg) = segments.next() {
+        if !cursor.is_object() {
+            *cursor = Value::Object(Map::new());
+        }
+        let obj = cursor.as_object_mut().expect("cursor was just made an object");
+        if segments.peek().is_none() {
+            obj.insert(seg.to_string(), value);
+            return;
+        }
+        cursor = obj.entry(seg.to_string()).or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+/// Reads the value at the dot-separated `path`, if every segment exists.
+pub fn get_path<'a>(target: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(target, |cursor, seg| cursor.get(seg))
+}
+
+/// Applies overrides to a configuration document, later entries winning.
+pub fn apply_overrides(target: &mut Value, overrides: &[CliOverride]) {
+    for o in overrides {
+        set_path(target, &o.path, o.value.clone());
+    }
+}
@@ -1,35 +1,294 @@
+//! # Cloud Configuration Loader
+//!
+//! Downloads and decrypts remote configuration payloads. The same payload
+//! layouts are accepted for encrypted local files.
+//!
+//! Two payload layouts are supported:
+//!
+//! * **Legacy (v1)**: two lines, base64 IV and base64 AES-256-CBC ciphertext,
+//!   decrypted with the hex key in `WEBLIB_AES_PASSWORD`.
+//! * **Versioned (v2)**: a header line followed by base64 nonce/IV and ciphertext.
+//!   The header selects the cipher and key derivation, e.g.
+//!   `WEBLIB-ENC/2 cipher=aes-256-gcm kdf=pbkdf2-sha256 iter=600000 salt=<base64>`.
+//!
+//! Supported ciphers: `aes-256-cbc`, `aes-256-gcm`.
+//! Supported KDFs: `raw` (hex key), `pbkdf2-sha256` (`iter`, `salt`),
+//! `argon2id` (`m`, `t`, `p`, `salt`).
+//!
+//! With the `age` feature, [age](https://age-encryption.org) files (binary or
+//! ASCII-armored) are accepted as well and recognised by their magic. The secret
+//! is then an `AGE-SECRET-KEY-1...` identity or a passphrase.
+
 use aes::Aes256;
+use aes_gcm::{Aes256Gcm, Nonce, aead::{Aead, KeyInit}};
 use base64::{Engine as _, engine::general_purpose};
 use cbc::Decryptor;
 use cipher::{BlockDecryptMut, KeyIvInit, block_padding::Pkcs7};
 use serde_json::Value;
+use sha2::Sha256;
+use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
 use crate::core::error::NgError;
 
-pub async fn load_remote_json(url: &str) -> Result<Value, NgError> {
-    let password = env::var("WEBLIB_AES_PASSWORD")
-        .map_err(|_| NgError::ConfigError("Missing WEBLIB_AES_PASSWORD".into()))?;
+/// Magic prefix of the versioned header line.
+const HEADER_MAGIC: &str = "WEBLIB-ENC/";
 
-    let client = reqwest::Client::new();
-    let response = client.get(url).send().await
-        .map_err(|e| NgError::ConfigError(format!("Network Error: {}", e)))?;
+/// Magic prefix of binary age files.
+const AGE_MAGIC: &[u8] = b"age-encryption.org/";
 
-    let content = response.text().await
-        .map_err(|e| NgError::ConfigError(format!("Read Error: {}", e)))?;
+/// Magic prefix of ASCII-armored age files.
+const AGE_ARMOR_MAGIC: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+
+/// Default PBKDF2 iteration count when the header omits `iter`.
+const DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Symmetric cipher used for the payload body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CipherKind {
+    /// AES-256 in CBC mode with PKCS#7 padding (unauthenticated).
+    Aes256Cbc,
+    /// AES-256 in GCM mode (authenticated).
+    Aes256Gcm,
+}
+
+/// Key derivation applied to the configured secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyDerivation {
+    /// The secret is a hex-encoded 32-byte key.
+    Raw,
+    /// PBKDF2-HMAC-SHA256 over a passphrase.
+    Pbkdf2 { iterations: u32, salt: Vec<u8> },
+    /// Argon2id over a passphrase.
+    Argon2id { m_cost: u32, t_cost: u32, p_cost: u32, salt: Vec<u8> },
+}
+
+/// Parsed encryption header of a remote payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadHeader {
+    /// Format version (1 for the legacy header-less layout).
+    pub version: u32,
+    /// Cipher used for the body.
+    pub cipher: CipherKind,
+    /// Key derivation applied to the secret.
+    pub kdf: KeyDerivation,
+}
+
+impl PayloadHeader {
+    /// Header implied by header-less (v1) payloads.
+    pub fn legacy() -> Self {
+        Self { version: 1, cipher: CipherKind::Aes256Cbc, kdf: KeyDerivation::Raw }
+    }
+
+    /// Parses a `WEBLIB-ENC/<version> key=value ...` header line.
+    pub fn parse(line: &str) -> Result<Self, NgError> {
+        let rest = line.strip_prefix(HEADER_MAGIC)
+            .ok_or_else(|| NgError::ConfigError("Missing encryption header".into()))?;
+
+        let mut parts = rest.split_whitespace();
+        let version: u32 = parts.next()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| NgError::ConfigError("Invalid encryption header version".into()))?;
+        if version != 2 {
+            return Err(NgError::ConfigError(format!("Unsupported encryption header version: {}", version)));
+        }
+
+        let fields: HashMap<&str, &str> = parts.filter_map(|p| p.split_once('=')).collect();
+
+        let cipher = match fields.get("cipher").copied().unwrap_or("aes-256-gcm") {
+            "aes-256-gcm" => CipherKind::Aes256Gcm,
+            "aes-256-cbc" => CipherKind::Aes256Cbc,
+            other => return Err(NgError::ConfigError(format!("Unsupported cipher: {}", other))),
+        };
+
+        let salt = || -> Result<Vec<u8>, NgError> {
+            let s = fields.get("salt")
+                .ok_or_else(|| NgError::ConfigError("Missing KDF salt".into()))?;
+            general_purpose::STANDARD.decode(s).map_err(|_| NgError::ConfigError("Invalid KDF salt".into()))
+        };
+        let num = |key: &str, default: u32| -> Result<u32, NgError> {
+            match fields.get(key) {
+                Some(v) => v.parse().map_err(|_| NgError::ConfigError(format!("Invalid KDF parameter '{}'", key))),
+                None => Ok(default),
+            }
+        };
+
+        let kdf = match fields.get("kdf").copied().unwrap_or("raw") {
+            "raw" => KeyDerivation::Raw,
+            "pbkdf2-sha256" => KeyDerivation::Pbkdf2 {
+                iterations: num("iter", DEFAULT_PBKDF2_ITERATIONS)?,
+                salt: salt()?,
+            },
+            "argon2id" => KeyDerivation::Argon2id {
+                m_cost: num("m", argon2::Params::DEFAULT_M_COST)?,
+                t_cost: num("t", argon2::Params::DEFAULT_T_COST)?,
+                p_cost: num("p", argon2::Params::DEFAULT_P_COST)?,
+                salt: salt()?,
+            },
+            other => return Err(NgError::ConfigError(format!("Unsupported KDF: {}", other))),
+        };
+
+        Ok(Self { version, cipher, kdf })
+    }
+}
+
+/// Derives the 32-byte AES key from the configured secret.
+fn derive_key(secret: &str, kdf: &KeyDerivation) -> Result<[u8; 32], NgError> {
+    let mut key = [0u8; 32];
+    match kdf {
+        KeyDerivation::Raw => {
+            let key_vec = hex::decode(secret.trim()).map_err(|_| NgError::ConfigError("Invalid Key Hex".into()))?;
+            if key_vec.len() < 32 {
+                return Err(NgError::ConfigError("Invalid Key Length: expected 32 bytes".into()));
+            }
+            key.copy_from_slice(&key_vec[..32]);
+        }
+        KeyDerivation::Pbkdf2 { iterations, salt } => {
+            pbkdf2::pbkdf2_hmac::<Sha256>(secret.as_bytes(), salt, *iterations, &mut key);
+        }
+        KeyDerivation::Argon2id { m_cost, t_cost, p_cost, salt } => {
+            let params = argon2::Params::new(*m_cost, *t_cost, *p_cost, Some(32))
+                .map_err(|e| NgError::ConfigError(format!("Invalid Argon2 parameters: {}", e)))?;
+            argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                .hash_password_into(secret.as_bytes(), salt, &mut key)
+                .map_err(|e| NgError::ConfigError(format!("Key derivation failed: {}", e)))?;
+        }
+    }
+    Ok(key)
+}
+
+/// Decrypts an encrypted configuration payload into JSON.
+///
+/// `content` is the full text of the payload (legacy or versioned layout) and
+/// `secret` is either the hex key or the passphrase, depending on the header.
+pub fn decrypt_payload(content: &str, secret: &str) -> Result<Value, NgError> {
+    let mut lines: Vec<&str> = content.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+
+    let header = match lines.first() {
+        Some(first) if first.starts_with(HEADER_MAGIC) => {
+            let h = PayloadHeader::parse(first)?;
+            lines.remove(0);
+            h
+        }
+        _ => PayloadHeader::legacy(),
+    };
 
-    let lines: Vec<&str> = content.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
     if lines.len() < 2 {
         return Err(NgError::ConfigError("Invalid S3 file format: expected IV and Ciphertext".into()));
     }
 
     let iv = general_purpose::STANDARD.decode(lines[0]).map_err(|_| NgError::ConfigError("Invalid IV".into()))?;
     let ciphertext = general_purpose::STANDARD.decode(lines[1]).map_err(|_| NgError::ConfigError("Invalid Ciphertext".into()))?;
-    let key_vec = hex::decode(password.trim()).map_err(|_| NgError::ConfigError("Invalid Key Hex".into()))?;
+    let key = derive_key(secret, &header.kdf)?;
+
+    let decrypted_data = match header.cipher {
+        CipherKind::Aes256Cbc => {
+            if iv.len() < 16 {
+                return Err(NgError::ConfigError("Invalid IV".into()));
+            }
+            let decryptor = Decryptor::<Aes256>::new((&key).into(), (&iv[..16]).into());
+            let mut buf = ciphertext.to_vec();
+            decryptor.decrypt_padded_mut::<Pkcs7>(&mut buf)
+                .map_err(|e| NgError::ConfigError(format!("Decryption failed: {:?}", e)))?
+                .to_vec()
+        }
+        CipherKind::Aes256Gcm => {
+            if iv.len() != 12 {
+                return Err(NgError::ConfigError("Invalid GCM nonce: expected 12 bytes".into()));
+            }
+            let gcm = Aes256Gcm::new((&key).into());
+            gcm.decrypt(Nonce::from_slice(&iv), ciphertext.as_ref())
+                .map_err(|_| NgError::ConfigError("Decryption failed: authentication tag mismatch".into()))?
+        }
+    };
 
-    let decryptor = Decryptor::<Aes256>::new((&key_vec[..32]).into(), (&iv[..16]).into());
-    let mut buf = ciphertext.to_vec();
-    let decrypted_data = decryptor.decrypt_padded_mut::<Pkcs7>(&mut buf)
-        .map_err(|e| NgError::ConfigError(format!("Decryption failed: {:?}", e)))?;
+    serde_json::from_slice(&decrypted_data).map_err(|e| NgError::ConfigError(e.to_string()))
+}
+
+/// Returns true if `content` is an age file (binary or armored).
+pub fn is_age_payload(content: &[u8]) -> bool {
+    let content = content.trim_ascii_start();
+    content.starts_with(AGE_MAGIC) || content.starts_with(AGE_ARMOR_MAGIC)
+}
+
+/// Decrypts a raw payload, dispatching on its magic: age files go to the age
+/// decryptor, anything else to [`decrypt_payload`].
+pub fn decrypt_bytes(content: &[u8], secret: &str) -> Result<Value, NgError> {
+    if is_age_payload(content) {
+        return decrypt_age(content, secret);
+    }
+    let text = std::str::from_utf8(content)
+        .map_err(|_| NgError::ConfigError("Invalid payload: not UTF-8 text".into()))?;
+    decrypt_payload(text, secret)
+}
+
+#[cfg(feature = "age")]
+fn decrypt_age(content: &[u8], secret: &str) -> Result<Value, NgError> {
+    let secret = secret.trim();
+    let plain = if secret.starts_with("AGE-SECRET-KEY-") {
+        let identity: age::x25519::Identity = secret.parse()
+            .map_err(|e| NgError::ConfigError(format!("Invalid age identity: {}", e)))?;
+        age::decrypt(&identity, content)
+    } else {
+        let identity = age::scrypt::Identity::new(age::secrecy::SecretString::from(secret.to_string()));
+        age::decrypt(&identity, content)
+    }
+    .map_err(|e| NgError::ConfigError(format!("Decryption failed: {}", e)))?;
+
+    serde_json::from_slice(&plain).map_err(|e| NgError::ConfigError(e.to_string()))
+}
+
+#[cfg(not(feature = "age"))]
+fn decrypt_age(_content: &[u8], _secret: &str) -> Result<Value, NgError> {
+    Err(NgError::ConfigError("age-encrypted payload requires the `age` feature".into()))
+}
+
+/// Reads the decryption secret from `WEBLIB_AES_PASSWORD`.
+fn secret_from_env() -> Result<String, NgError> {
+    env::var("WEBLIB_AES_PASSWORD")
+        .map_err(|_| NgError::ConfigError("Missing WEBLIB_AES_PASSWORD".into()))
+}
+
+/// Reads and decrypts a local file in any supported payload layout.
+pub fn load_encrypted_file(path: &str) -> Result<Value, NgError> {
+    let password = secret_from_env()?;
+    let content = std::fs::read(path)
+        .map_err(|e| NgError::ConfigError(format!("Local file read failed: {}: {}", path, e)))?;
 
-    serde_json::from_slice(decrypted_data).map_err(|e| NgError::ConfigError(e.to_string()))
+    decrypt_bytes(&content, &password)
+}
+
+pub async fn load_remote_json(url: &str) -> Result<Value, NgError> {
+    let password = secret_from_env()?;
+
+    let client = reqwest::Client::new();
+    let response = client.get(url).send().await
+        .map_err(|e| NgError::ConfigError(format!("Network Error: {}", e)))?
+        .error_for_status()
+        .map_err(|e| NgError::ConfigError(format!("HTTP Error: {}", e)))?;
+
+    let content = response.bytes().await
+        .map_err(|e| NgError::ConfigError(format!("Read Error: {}", e)))?;
+
+    decrypt_bytes(&content, &password)
+}
+
+/// Tries `urls` in order and returns the first payload that downloads and
+/// decrypts within `timeout`, together with the URL that served it.
+///
+/// Fails with a `ConfigError` listing every attempted URL and its failure.
+pub async fn load_first_available(urls: &[&str], timeout: Duration) -> Result<(Value, String), NgError> {
+    if urls.is_empty() {
+        return Err(NgError::ConfigError("No cloud config URLs given".into()));
+    }
+
+    let mut failures = Vec::new();
+    for url in urls {
+        match tokio::time::timeout(timeout, load_remote_json(url)).await {
+            Ok(Ok(value)) => return Ok((value, url.to_string())),
+            Ok(Err(e)) => failures.push(format!("{}: {}", url, e)),
+            Err(_) => failures.push(format!("{}: timed out after {:?}", url, timeout)),
+        }
+    }
+    Err(NgError::ConfigError(format!("All cloud config sources failed: {}", failures.join("; "))))
 }
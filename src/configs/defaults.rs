@@ -0,0 +1,60 @@
+//! # Programmatic Configuration Defaults
+//!
+//! Application-registered default values that sit below every other
+//! configuration source, so keys the operator did not set still resolve.
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use crate::configs::{cli, merge};
+use crate::core::error::NgError;
+
+/// Builder for the lowest-precedence configuration layer.
+#[derive(Debug, Clone)]
+pub struct ConfigDefaults {
+    value: Value,
+}
+
+impl Default for ConfigDefaults {
+    fn default() -> Self {
+        Self { value: json!({}) }
+    }
+}
+
+impl From<Value> for ConfigDefaults {
+    fn from(value: Value) -> Self {
+        Self::new().with_value(value)
+    }
+}
+
+impl ConfigDefaults {
+    /// Creates an empty defaults layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deep-merges a JSON document into the defaults.
+    pub fn with_value(mut self, value: Value) -> Self {
+        merge::deep_merge(&mut self.value, value);
+        self
+    }
+
+    /// Deep-merges a serializable struct into the defaults.
+    pub fn with_struct<T: Serialize>(self, defaults: &T) -> Result<Self, NgError> {
+        let value = serde_json::to_value(defaults)
+            .map_err(|e| NgError::ConfigError(format!("Invalid defaults: {}", e)))?;
+        Ok(self.with_value(value))
+    }
+
+    /// Sets a single default at a dot-separated key path (e.g. `nasdaq.retry`).
+    pub fn set<T: Serialize>(mut self, path: &str, value: T) -> Result<Self, NgError> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| NgError::ConfigError(format!("Invalid default for '{}': {}", path, e)))?;
+        cli::set_path(&mut self.value, path, value);
+        Ok(self)
+    }
+
+    /// Returns the accumulated defaults document.
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+}
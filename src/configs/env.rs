@@ -0,0 +1,61 @@
+//! # Environment Variable Source
+//!
+//! Naming convention of the environment layer. By default variables prefixed
+//! with `WEBLIB_` are merged, with `__` separating nested keys
+//! (`WEBLIB_NASDAQ__TIMEOUT` sets `nasdaq.timeout`). Embedding products can
+//! choose their own prefix and separator.
+
+use figment::providers::Env;
+
+/// Default prefix of configuration env vars.
+pub const DEFAULT_ENV_PREFIX: &str = "WEBLIB_";
+
+/// Default separator between nested key segments.
+pub const DEFAULT_ENV_SEPARATOR: &str = "__";
+
+/// Prefix and nesting separator of the environment layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvOptions {
+    prefix: String,
+    separator: String,
+}
+
+impl Default for EnvOptions {
+    fn default() -> Self {
+        Self { prefix: DEFAULT_ENV_PREFIX.to_string(), separator: DEFAULT_ENV_SEPARATOR.to_string() }
+    }
+}
+
+impl EnvOptions {
+    /// Creates options with the `WEBLIB_` prefix and `__` separator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the prefix; it is stripped before the key is split.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Sets the separator between nested key segments.
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Returns the configured prefix.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the configured separator.
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Builds the figment provider reading matching variables.
+    pub(crate) fn provider(&self) -> Env {
+        Env::prefixed(&self.prefix).split(&self.separator)
+    }
+}
@@ -0,0 +1,94 @@
+//! # Configuration Merging
+//!
+//! Recursive merging of JSON configuration layers.
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// How arrays present in both layers are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayStrategy {
+    /// The overlay array replaces the target array.
+    #[default]
+    Replace,
+    /// Overlay elements are appended to the target array.
+    Append,
+    /// Overlay elements are appended unless an equal element is already present.
+    Unique,
+}
+
+/// Recursively merges `overlay` into `target`.
+///
+/// Objects are merged key by key; any other overlay value (including arrays
+/// and `null`) replaces the target value.
+pub fn deep_merge(target: &mut Value, overlay: Value) {
+    deep_merge_with(target, overlay, ArrayStrategy::Replace);
+}
+
+/// Like [`deep_merge`], combining arrays found in both layers per `arrays`.
+pub fn deep_merge_with(target: &mut Value, overlay: Value, arrays: ArrayStrategy) {
+    deep_merge_policy(target, overlay, &MergePolicy::new().with_default(arrays));
+}
+
+/// Array strategies per key path, with a fallback for unlisted paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergePolicy {
+    default: ArrayStrategy,
+    paths: HashMap<String, ArrayStrategy>,
+}
+
+impl MergePolicy {
+    /// Creates a policy replacing all arrays.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the strategy for arrays without a path-specific entry.
+    pub fn with_default(mut self, arrays: ArrayStrategy) -> Self {
+        self.default = arrays;
+        self
+    }
+
+    /// Sets the strategy for the array at the dot-separated `path`.
+    pub fn with_path(mut self, path: &str, arrays: ArrayStrategy) -> Self {
+        self.paths.insert(path.to_string(), arrays);
+        self
+    }
+
+    /// Returns the strategy applied to the array at `path`.
+    pub fn strategy_for(&self, path: &str) -> ArrayStrategy {
+        self.paths.get(path).copied().unwrap_or(self.default)
+    }
+}
+
+/// Like [`deep_merge`], combining arrays found in both layers per `policy`.
+pub fn deep_merge_policy(target: &mut Value, overlay: Value, policy: &MergePolicy) {
+    merge_at(target, overlay, policy, "");
+}
+
+fn merge_at(target: &mut Value, overlay: Value, policy: &MergePolicy, path: &str) {
+    match (target, overlay) {
+        (Value::Object(t), Value::Object(o)) => {
+            for (k, v) in o {
+                match t.get_mut(&k) {
+                    Some(existing) => {
+                        let child = if path.is_empty() { k } else { format!("{}.{}", path, k) };
+                        merge_at(existing, v, policy, &child);
+                    }
+                    None => {
+                        t.insert(k, v);
+                    }
+                }
+            }
+        }
+        (Value::Array(t), Value::Array(o)) if policy.strategy_for(path) != ArrayStrategy::Replace => {
+            let arrays = policy.strategy_for(path);
+            for v in o {
+                if arrays == ArrayStrategy::Append || !t.contains(&v) {
+                    t.push(v);
+                }
+            }
+        }
+        (t, o) => *t = o,
+    }
+}
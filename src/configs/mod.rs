@@ -1,60 +1,273 @@
 use serde_json::{Value, json};
-use figment::{Figment, providers::{Format, Json, Env}};
 use arc_swap::ArcSwap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use crate::core::error::NgError;
 
+pub mod builder;
+pub mod cli;
 pub mod cloud;
+pub mod defaults;
+pub mod env;
+pub mod merge;
+pub mod provenance;
+pub mod redact;
+
+pub use builder::ConfigBuilder;
+pub use defaults::ConfigDefaults;
+pub use env::EnvOptions;
+pub use provenance::{ConfigSource, Provenance};
 
 pub struct ConfigManager {
     current: ArcSwap<Value>,
-    _source_info: String,
+    source_info: String,
+    provenance: RwLock<Provenance>,
+    secret_paths: RwLock<Vec<String>>,
 }
 
 impl ConfigManager {
+    fn from_parts(data: Value, provenance: Provenance, source_info: String) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(data),
+            source_info,
+            provenance: RwLock::new(provenance),
+            secret_paths: RwLock::new(redact::DEFAULT_SECRET_PATHS.iter().map(|p| p.to_string()).collect()),
+        }
+    }
+
+    /// Starts a [`ConfigBuilder`] for explicit source composition.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+
     /// LOCAL: Merges file + WEBLIB_ env vars. Fails if file missing.
     pub fn get_local_config(path: &str) -> Result<Self, NgError> {
-        if !std::path::Path::new(path).exists() {
-            return Err(NgError::ConfigError(format!("Local file not found: {}", path)));
-        }
+        Self::get_local_config_with_defaults(path, ConfigDefaults::new())
+    }
+
+    /// LOCAL + DEFAULTS: Like `get_local_config`, with `defaults` merged below the
+    /// file and env vars so unset keys fall back to the registered values.
+    pub fn get_local_config_with_defaults(path: &str, defaults: ConfigDefaults) -> Result<Self, NgError> {
+        ConfigBuilder::new()
+            .with_defaults(defaults)
+            .with_file(path)
+            .with_env(env::DEFAULT_ENV_PREFIX)
+            .build_local()
+    }
 
-        let data: Value = Figment::new()
-            .merge(Json::file(path))
-            .merge(Env::prefixed("WEBLIB_").split("__"))
-            .extract()
-            .map_err(|e| NgError::ConfigError(e.to_string()))?;
+    /// LOCAL + ENV OPTIONS: Like `get_local_config`, reading env vars with the
+    /// prefix and nesting separator of `env` instead of `WEBLIB_` and `__`.
+    pub fn get_local_config_with_env(path: &str, env: EnvOptions) -> Result<Self, NgError> {
+        ConfigBuilder::new().with_file(path).with_env_options(env).build_local()
+    }
 
-        Ok(Self {
-            current: ArcSwap::from_pointee(data),
-            _source_info: format!("local:{}", path),
-        })
+    /// LOCAL (encrypted): Decrypts a file in the cloud payload format and merges
+    /// WEBLIB_ env vars on top. The whole document is used; no sections are selected.
+    pub fn get_local_config_encrypted(path: &str) -> Result<Self, NgError> {
+        ConfigBuilder::new()
+            .with_encrypted_file(path)
+            .with_env(env::DEFAULT_ENV_PREFIX)
+            .build_local()
     }
 
     /// CLOUD: Downloads, decrypts, and extracts (Binary-Name + commonAll)
     pub async fn get_cloud_config(url: &str) -> Result<Self, NgError> {
-        let full_json = cloud::load_remote_json(url).await?;
-        
-        // Binary name selection
-        let bin_name = std::env::current_exe()
-            .ok().and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
-            .unwrap_or_else(|| "default".to_string());
-
-        let common = full_json.get("commonAll").cloned().unwrap_or(json!({}));
-        let specific = full_json.get(&bin_name).cloned().unwrap_or(json!({}));
-
-        // Merge logic: specific overrides common
-        let mut merged = common;
-        if let (Some(m), Some(s)) = (merged.as_object_mut(), specific.as_object()) {
-            for (k, v) in s { m.insert(k.clone(), v.clone()); }
+        ConfigBuilder::new().with_cloud(url).build().await
+    }
+
+    /// CLOUD (named): Like `get_cloud_config`, but selects the `name` section
+    /// explicitly instead of deriving it from the binary name.
+    pub async fn get_cloud_config_named(url: &str, name: &str) -> Result<Self, NgError> {
+        ConfigBuilder::new().with_cloud(url).with_cloud_name(name).build().await
+    }
+
+    /// CLOUD + DEFAULTS: Like `get_cloud_config`, with `defaults` deep-merged below
+    /// the cloud payload.
+    pub async fn get_cloud_config_with_defaults(url: &str, defaults: ConfigDefaults) -> Result<Self, NgError> {
+        ConfigBuilder::new().with_defaults(defaults).with_cloud(url).build().await
+    }
+
+    /// CLOUD (failover): Like `get_cloud_config`, trying a primary URL and its
+    /// mirrors in order, each bounded by `timeout`.
+    ///
+    /// `source_info()` names the URL that succeeded.
+    pub async fn get_cloud_config_failover(urls: &[&str], timeout: Duration) -> Result<Self, NgError> {
+        if urls.is_empty() {
+            return Err(NgError::ConfigError("No cloud config URLs given".into()));
+        }
+        urls.iter()
+            .fold(ConfigBuilder::new(), |b, url| b.with_cloud(url))
+            .with_cloud_timeout(timeout)
+            .build()
+            .await
+    }
+
+    /// LAYERED: Merges the cloud payload, the local file and WEBLIB_ env vars.
+    ///
+    /// Precedence (lowest to highest): cloud `commonAll`, cloud binary section,
+    /// local file, env vars. The local file thus acts as a host-specific overlay on
+    /// the shared cloud settings. Both sources are required.
+    pub async fn get_layered_config(local_path: &str, cloud_url: &str) -> Result<Self, NgError> {
+        ConfigBuilder::new()
+            .with_cloud(cloud_url)
+            .with_file(local_path)
+            .with_env(env::DEFAULT_ENV_PREFIX)
+            .build()
+            .await
+    }
+
+    /// LOCAL + CLI: Like `get_local_config`, then applies `--set key.path=value`
+    /// arguments on top, so they take precedence over both file and env.
+    pub fn get_local_config_with_args<I, S>(path: &str, args: I) -> Result<Self, NgError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        ConfigBuilder::new()
+            .with_file(path)
+            .with_env(env::DEFAULT_ENV_PREFIX)
+            .with_args(args)
+            .build_local()
+    }
+
+    /// Applies `--set key.path=value` arguments to the current configuration.
+    ///
+    /// Arguments other than `--set` are ignored, so `std::env::args()` can be
+    /// passed directly. On a parse error the configuration is left unchanged.
+    pub fn apply_cli_args<I, S>(&self, args: I) -> Result<(), NgError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let overrides = cli::parse_args(args)?;
+        if overrides.is_empty() {
+            return Ok(());
         }
+        let mut data = (*self.current.load_full()).clone();
+        cli::apply_overrides(&mut data, &overrides);
+        self.current.store(Arc::new(data));
 
-        Ok(Self {
-            current: ArcSwap::from_pointee(merged),
-            _source_info: format!("cloud:{}", url),
-        })
+        let mut provenance = self.provenance.write().unwrap_or_else(|e| e.into_inner());
+        for o in &overrides {
+            provenance.record_path(&o.path, &o.value, &ConfigSource::Cli);
+        }
+        Ok(())
     }
 
     pub fn get(&self) -> Arc<Value> {
         self.current.load_full()
     }
+
+    /// Checks that all `keys` are present, failing with one report of every problem.
+    ///
+    /// Each key is a dot-separated path, optionally followed by `:<type>` where
+    /// type is one of `string`, `number`, `integer`, `bool`, `array`, `object`
+    /// (e.g. `"nasdaq.timeout:integer"`). `null` values count as missing.
+    pub fn require(&self, keys: &[&str]) -> Result<(), NgError> {
+        let data = self.get();
+        let problems: Vec<String> = keys.iter().filter_map(|spec| check_required(&data, spec)).collect();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(NgError::ConfigError(format!("Required config keys failed validation: {}", problems.join("; "))))
+    }
+
+    /// Marks additional dot-separated paths (with `*` wildcards) as secret.
+    pub fn add_secret_paths(&self, paths: &[&str]) {
+        let mut secret_paths = self.secret_paths.write().unwrap_or_else(|e| e.into_inner());
+        secret_paths.extend(paths.iter().map(|p| p.to_string()));
+    }
+
+    /// Returns the merged configuration with all secret paths replaced by `"***"`,
+    /// suitable for startup logs or a debug endpoint.
+    pub fn snapshot_redacted(&self) -> Value {
+        let mut data = (*self.get()).clone();
+        redact::redact_paths(&mut data, &self.secret_paths.read().unwrap_or_else(|e| e.into_inner()));
+        data
+    }
+
+    /// Describes how the manager was loaded, e.g. `local:config.json` or
+    /// `cloud:<url>#<section>`.
+    pub fn source_info(&self) -> &str {
+        &self.source_info
+    }
+
+    /// Returns the layer that supplied the value at the dot-separated `path`.
+    pub fn provenance(&self, path: &str) -> Option<ConfigSource> {
+        self.provenance.read().unwrap_or_else(|e| e.into_inner()).get(path).cloned()
+    }
+
+    /// Returns the source of every leaf value, e.g. for a startup debug dump.
+    pub fn provenance_report(&self) -> Provenance {
+        self.provenance.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Validates one `path[:type]` spec, returning a problem description on failure.
+fn check_required(data: &Value, spec: &str) -> Option<String> {
+    let (path, expected) = match spec.split_once(':') {
+        Some((p, t)) => (p, Some(t)),
+        None => (spec, None),
+    };
+    let value = match cli::get_path(data, path) {
+        None | Some(Value::Null) => return Some(format!("{} (missing)", path)),
+        Some(v) => v,
+    };
+    let expected = expected?;
+    let ok = match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "bool" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        other => return Some(format!("{} (unknown type '{}')", path, other)),
+    };
+    if ok {
+        return None;
+    }
+    let found = match value {
+        Value::String(_) => "string",
+        Value::Number(_) => "number",
+        Value::Bool(_) => "bool",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+        Value::Null => "null",
+    };
+    Some(format!("{} (expected {}, found {})", path, expected, found))
+}
+
+/// Resolves the cloud section name used for binary-specific settings.
+///
+/// Precedence: `explicit` argument, then the `WEBLIB_CONFIG_NAME` env var, then
+/// the file stem of `current_exe()`, then `"default"`.
+pub fn resolve_config_name(explicit: Option<&str>) -> String {
+    if let Some(name) = explicit {
+        return name.to_string();
+    }
+    if let Ok(name) = std::env::var("WEBLIB_CONFIG_NAME")
+        && !name.trim().is_empty()
+    {
+        return name.trim().to_string();
+    }
+    std::env::current_exe()
+        .ok().and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Extracts `commonAll` deep-merged with the `name` section of a cloud payload.
+///
+/// Nested objects are merged key by key, so a section setting `nasdaq.timeout`
+/// keeps the other `nasdaq.*` keys of `commonAll`. Arrays are replaced.
+pub fn select_sections(full_json: &Value, name: &str) -> Value {
+    select_sections_with(full_json, name, merge::ArrayStrategy::Replace)
+}
+
+/// Like [`select_sections`], combining arrays present in both sections per `arrays`.
+pub fn select_sections_with(full_json: &Value, name: &str, arrays: merge::ArrayStrategy) -> Value {
+    let mut merged = full_json.get("commonAll").cloned().unwrap_or(json!({}));
+    if let Some(specific) = full_json.get(name) {
+        merge::deep_merge_with(&mut merged, specific.clone(), arrays);
+    }
+    merged
 }
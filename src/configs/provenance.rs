@@ -0,0 +1,123 @@
+//! # Configuration Provenance
+//!
+//! Records which layer supplied each leaf value of the merged configuration,
+//! so "where did this value come from" can be answered at runtime.
+//!
+//! Keys are dot-separated paths to leaves (scalars and arrays). An empty
+//! object sets nothing and gets no entry at any depth. When a later layer
+//! replaces a subtree, the provenance of the replaced leaves is dropped with it.
+//!
+//! An array is a single leaf attributed to the last layer that set it. With
+//! `ArrayStrategy::Append` or `Unique` the merged array holds elements from
+//! several layers, but provenance still names only that last layer; elements
+//! are not attributed individually.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Configuration layer that supplied a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Programmatic defaults registered via `ConfigDefaults`.
+    Default,
+    /// A local file (plain or encrypted).
+    File(String),
+    /// Environment variables with the given prefix.
+    Env(String),
+    /// A cloud payload, identified by URL and selected section.
+    Cloud { url: String, section: String },
+    /// A `--set` command-line override.
+    Cli,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(path) => write!(f, "file:{}", path),
+            ConfigSource::Env(prefix) => write!(f, "env:{}*", prefix),
+            ConfigSource::Cloud { url, section } => write!(f, "cloud:{}#{}", url, section),
+            ConfigSource::Cli => write!(f, "cli"),
+        }
+    }
+}
+
+/// Leaf path to source mapping of a merged configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    leaves: BTreeMap<String, ConfigSource>,
+}
+
+impl Provenance {
+    /// Creates an empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attributes every leaf of `layer` to `source`, overriding earlier layers.
+    pub fn record(&mut self, layer: &Value, source: &ConfigSource) {
+        self.record_at(String::new(), layer, source);
+    }
+
+    /// Attributes the value written at `path` (dot-separated) to `source`.
+    pub fn record_path(&mut self, path: &str, value: &Value, source: &ConfigSource) {
+        // Ancestors that were leaves are now objects.
+        let mut prefix = String::new();
+        for seg in path.split('.') {
+            if !prefix.is_empty() {
+                self.leaves.remove(&prefix);
+                prefix.push('.');
+            }
+            prefix.push_str(seg);
+        }
+        // `--set` replaces the subtree, so an empty object still clears it.
+        self.clear(path);
+        self.record_at(path.to_string(), value, source);
+    }
+
+    fn record_at(&mut self, path: String, value: &Value, source: &ConfigSource) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                if !path.is_empty() {
+                    self.leaves.remove(&path);
+                }
+                for (k, v) in map {
+                    let child = if path.is_empty() { k.clone() } else { format!("{}.{}", path, k) };
+                    self.record_at(child, v, source);
+                }
+            }
+            // An empty object (e.g. `{}` defaults, an env layer without
+            // matching vars or `{"db": {}}`) merges as a no-op and sets nothing.
+            Value::Object(_) => {}
+            _ => {
+                self.clear(&path);
+                self.leaves.insert(path, source.clone());
+            }
+        }
+    }
+
+    /// Drops the entry at `path` and every entry below it.
+    fn clear(&mut self, path: &str) {
+        let nested = format!("{}.", path);
+        self.leaves.retain(|k, _| k != path && !k.starts_with(&nested));
+    }
+
+    /// Returns the source of the value at `path`.
+    ///
+    /// A path below a leaf (e.g. into a string) resolves to that leaf's source.
+    pub fn get(&self, path: &str) -> Option<&ConfigSource> {
+        let mut candidate = path;
+        loop {
+            if let Some(src) = self.leaves.get(candidate) {
+                return Some(src);
+            }
+            candidate = &candidate[..candidate.rfind('.')?];
+        }
+    }
+
+    /// Returns all leaf paths with their sources, sorted by path.
+    pub fn entries(&self) -> &BTreeMap<String, ConfigSource> {
+        &self.leaves
+    }
+}
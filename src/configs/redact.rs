@@ -0,0 +1,42 @@
+//! # Secret Redaction
+//!
+//! Masks secret values in configuration documents before they are logged or
+//! served from a debug endpoint.
+//!
+//! Secret paths are dot-separated; a `*` segment matches any key at that level
+//! (e.g. `*.api_key` masks `nasdaq.api_key` and `cnn.api_key`). A masked value is
+//! replaced by `"***"` whatever its type, including whole subtrees.
+
+use serde_json::Value;
+
+/// Replacement for redacted values.
+pub const REDACTED: &str = "***";
+
+/// Paths redacted by every manager.
+///
+/// `aes_password` is where the decryption secret lands when the default env
+/// layer picks up `WEBLIB_AES_PASSWORD`.
+pub const DEFAULT_SECRET_PATHS: &[&str] = &["aes_password"];
+
+/// Replaces every value matching one of `paths` with [`REDACTED`].
+pub fn redact_paths<S: AsRef<str>>(target: &mut Value, paths: &[S]) {
+    for path in paths {
+        let segments: Vec<&str> = path.as_ref().split('.').collect();
+        redact(target, &segments);
+    }
+}
+
+fn redact(target: &mut Value, segments: &[&str]) {
+    let Some((head, rest)) = segments.split_first() else {
+        *target = Value::String(REDACTED.to_string());
+        return;
+    };
+    let Some(obj) = target.as_object_mut() else { return };
+    if *head == "*" {
+        for child in obj.values_mut() {
+            redact(child, rest);
+        }
+    } else if let Some(child) = obj.get_mut(*head) {
+        redact(child, rest);
+    }
+}
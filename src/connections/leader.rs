@@ -0,0 +1,306 @@
+//! # Leader Election
+//!
+//! Lease-based coordination so that only one of several collector instances
+//! (possibly in different regions) actively polls providers while the others
+//! stand by.
+//!
+//! An instance becomes leader by acquiring a time-limited lease from a
+//! [`LeaseBackend`] and keeps it by renewing before expiry. If the leader dies,
+//! its lease expires and a standby takes over on its next attempt.
+//!
+//! Backends:
+//! * [`FileLease`] — a lock file on a shared filesystem (feature `leader`).
+//! * `RedisLease` — a Redis key with `SET NX PX` semantics (feature `leader-redis`).
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::{info, warn};
+
+/// Storage for a single named lease.
+///
+/// All operations are scoped to `holder`, the unique id of the calling instance.
+pub trait LeaseBackend: Send + Sync {
+    /// Acquires the lease if it is free, expired, or already held by `holder`.
+    /// Returns `true` if `holder` owns the lease afterwards.
+    fn try_acquire<'a>(&'a self, holder: &'a str, ttl: Duration) -> BoxFuture<'a, Result<bool, NgError>>;
+
+    /// Extends the lease if it is still held by `holder`.
+    /// Returns `false` if the lease was lost to another instance.
+    fn renew<'a>(&'a self, holder: &'a str, ttl: Duration) -> BoxFuture<'a, Result<bool, NgError>>;
+
+    /// Releases the lease if it is held by `holder`.
+    fn release<'a>(&'a self, holder: &'a str) -> BoxFuture<'a, Result<(), NgError>>;
+}
+
+/// Contents of a lease lock file.
+#[derive(Debug, Serialize, Deserialize)]
+struct LeaseRecord {
+    holder: String,
+    expires_at_ms: i64,
+}
+
+/// Lease stored as a JSON lock file on a (shared) filesystem.
+///
+/// Every read-check-write runs under an exclusive lock on a sibling
+/// `<path>.guard` file, so two instances racing for an expired lease cannot
+/// both take it. The guard is never deleted; the OS drops the lock when its
+/// holder exits, even if it crashed.
+#[derive(Clone)]
+pub struct FileLease {
+    path: PathBuf,
+}
+
+impl FileLease {
+    /// Creates a lease backed by the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn read(&self) -> Option<LeaseRecord> {
+        let content = std::fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn write(&self, holder: &str, ttl: Duration) -> Result<(), NgError> {
+        let record = LeaseRecord {
+            holder: holder.to_string(),
+            expires_at_ms: chrono::Utc::now().timestamp_millis() + ttl.as_millis() as i64,
+        };
+        let body = serde_json::to_string(&record).map_err(|e| NgError::InternalError(e.to_string()))?;
+        // Write to a sibling file and rename so readers never see a partial record.
+        // The random part keeps instances on other hosts with the same pid apart.
+        let tmp = self.sibling(&format!("{}.{:016x}.tmp", std::process::id(), rand::random::<u64>()));
+        std::fs::write(&tmp, body)
+            .and_then(|_| std::fs::rename(&tmp, &self.path))
+            .map_err(|e| NgError::InternalError(format!("Lease write failed: {}", e)))
+    }
+
+    fn sibling(&self, suffix: &str) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    /// Runs `op` while holding the guard lock, on the blocking pool since
+    /// waiting for another instance blocks the thread.
+    async fn guarded<T, F>(&self, op: F) -> Result<T, NgError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&FileLease) -> Result<T, NgError> + Send + 'static,
+    {
+        let lease = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let guard = std::fs::OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(lease.sibling("guard"))
+                .and_then(|file| file.lock().map(|_| file))
+                .map_err(|e| NgError::InternalError(format!("Lease lock failed: {}", e)))?;
+            let result = op(&lease);
+            drop(guard);
+            result
+        })
+        .await
+        .map_err(|e| NgError::InternalError(format!("Lease task failed: {}", e)))?
+    }
+}
+
+impl LeaseBackend for FileLease {
+    fn try_acquire<'a>(&'a self, holder: &'a str, ttl: Duration) -> BoxFuture<'a, Result<bool, NgError>> {
+        let holder = holder.to_string();
+        Box::pin(self.guarded(move |lease| {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            match lease.read() {
+                Some(rec) if rec.holder != holder && rec.expires_at_ms > now_ms => Ok(false),
+                _ => lease.write(&holder, ttl).map(|_| true),
+            }
+        }))
+    }
+
+    fn renew<'a>(&'a self, holder: &'a str, ttl: Duration) -> BoxFuture<'a, Result<bool, NgError>> {
+        let holder = holder.to_string();
+        Box::pin(self.guarded(move |lease| match lease.read() {
+            Some(rec) if rec.holder == holder => lease.write(&holder, ttl).map(|_| true),
+            _ => Ok(false),
+        }))
+    }
+
+    fn release<'a>(&'a self, holder: &'a str) -> BoxFuture<'a, Result<(), NgError>> {
+        let holder = holder.to_string();
+        Box::pin(self.guarded(move |lease| {
+            if lease.read().is_some_and(|rec| rec.holder == holder) {
+                std::fs::remove_file(&lease.path)
+                    .map_err(|e| NgError::InternalError(format!("Lease release failed: {}", e)))?;
+            }
+            Ok(())
+        }))
+    }
+}
+
+/// Lease stored as a Redis key holding the leader id.
+#[cfg(feature = "leader-redis")]
+pub struct RedisLease {
+    client: redis::Client,
+    key: String,
+}
+
+#[cfg(feature = "leader-redis")]
+impl RedisLease {
+    /// Creates a lease stored under `key` on the Redis server at `url`.
+    pub fn new(url: &str, key: &str) -> Result<Self, NgError> {
+        let client = redis::Client::open(url)
+            .map_err(|e| NgError::ConfigError(format!("Invalid Redis URL: {}", e)))?;
+        Ok(Self { client, key: key.to_string() })
+    }
+
+    async fn conn(&self) -> Result<redis::aio::MultiplexedConnection, NgError> {
+        self.client.get_multiplexed_async_connection().await
+            .map_err(|e| NgError::InternalError(format!("Redis connection failed: {}", e)))
+    }
+}
+
+#[cfg(feature = "leader-redis")]
+impl LeaseBackend for RedisLease {
+    fn try_acquire<'a>(&'a self, holder: &'a str, ttl: Duration) -> BoxFuture<'a, Result<bool, NgError>> {
+        Box::pin(async move {
+            if self.renew(holder, ttl).await? {
+                return Ok(true);
+            }
+            let mut conn = self.conn().await?;
+            let set: Option<String> = redis::cmd("SET")
+                .arg(&self.key).arg(holder).arg("NX").arg("PX").arg(ttl.as_millis() as u64)
+                .query_async(&mut conn).await
+                .map_err(|e| NgError::InternalError(format!("Redis SET failed: {}", e)))?;
+            Ok(set.is_some())
+        })
+    }
+
+    fn renew<'a>(&'a self, holder: &'a str, ttl: Duration) -> BoxFuture<'a, Result<bool, NgError>> {
+        Box::pin(async move {
+            let mut conn = self.conn().await?;
+            let script = redis::Script::new(
+                "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('PEXPIRE', KEYS[1], ARGV[2]) else return 0 end",
+            );
+            let renewed: i64 = script.key(&self.key).arg(holder).arg(ttl.as_millis() as u64)
+                .invoke_async(&mut conn).await
+                .map_err(|e| NgError::InternalError(format!("Redis renew failed: {}", e)))?;
+            Ok(renewed == 1)
+        })
+    }
+
+    fn release<'a>(&'a self, holder: &'a str) -> BoxFuture<'a, Result<(), NgError>> {
+        Box::pin(async move {
+            let mut conn = self.conn().await?;
+            let script = redis::Script::new(
+                "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) else return 0 end",
+            );
+            let _: i64 = script.key(&self.key).arg(holder)
+                .invoke_async(&mut conn).await
+                .map_err(|e| NgError::InternalError(format!("Redis release failed: {}", e)))?;
+            Ok(())
+        })
+    }
+}
+
+/// Runs the acquire/renew loop for one instance and publishes its leadership state.
+pub struct LeaderElector {
+    backend: Arc<dyn LeaseBackend>,
+    holder_id: String,
+    ttl: Duration,
+    renew_interval: Duration,
+    logger: Logger,
+    state: watch::Sender<bool>,
+}
+
+impl LeaderElector {
+    /// Creates an elector for `holder_id` with a 15s lease renewed every 5s.
+    pub fn new(logger: Logger, backend: Arc<dyn LeaseBackend>, holder_id: &str) -> Self {
+        let (state, _) = watch::channel(false);
+        Self {
+            backend,
+            holder_id: holder_id.to_string(),
+            ttl: Duration::from_secs(15),
+            renew_interval: Duration::from_secs(5),
+            logger,
+            state,
+        }
+    }
+
+    /// Sets the lease duration and the renew/retry interval.
+    ///
+    /// The interval should be well below the TTL so a healthy leader never
+    /// lets its lease lapse.
+    pub fn with_timing(mut self, ttl: Duration, renew_interval: Duration) -> Self {
+        self.ttl = ttl;
+        self.renew_interval = renew_interval;
+        self
+    }
+
+    /// Returns true if this instance currently holds the lease.
+    pub fn is_leader(&self) -> bool {
+        *self.state.borrow()
+    }
+
+    /// Subscribes to leadership changes (`true` = leader, `false` = standby).
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.state.subscribe()
+    }
+
+    /// Performs one acquire-or-renew step and returns the resulting state.
+    ///
+    /// Backend errors demote the instance to standby, since it can no longer
+    /// prove it holds the lease.
+    pub async fn tick(&self) -> bool {
+        let was_leader = self.is_leader();
+        let result = if was_leader {
+            self.backend.renew(&self.holder_id, self.ttl).await
+        } else {
+            self.backend.try_acquire(&self.holder_id, self.ttl).await
+        };
+
+        let leader = match result {
+            Ok(l) => l,
+            Err(e) => {
+                warn!(self.logger, "Leader lease operation failed", "holder" => &self.holder_id, "error" => e.to_string());
+                false
+            }
+        };
+
+        if leader != was_leader {
+            info!(
+                self.logger,
+                if leader { "Acquired leadership" } else { "Lost leadership" },
+                "holder" => &self.holder_id
+            );
+            self.state.send_replace(leader);
+        }
+        leader
+    }
+
+    /// Runs the election loop forever.
+    pub async fn run(&self) {
+        loop {
+            self.tick().await;
+            tokio::time::sleep(self.renew_interval).await;
+        }
+    }
+
+    /// Releases the lease (if held) and switches to standby.
+    pub async fn resign(&self) -> Result<(), NgError> {
+        self.backend.release(&self.holder_id).await?;
+        if self.is_leader() {
+            info!(self.logger, "Resigned leadership", "holder" => &self.holder_id);
+            self.state.send_replace(false);
+        }
+        Ok(())
+    }
+}
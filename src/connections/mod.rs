@@ -1 +1,2 @@
-// Connections stub
\ No newline at end of file
+#[cfg(feature = "leader")]
+pub mod leader;
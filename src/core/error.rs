@@ -3,11 +3,82 @@
 //! This module defines the central `NgError` type used throughout the library.
 //! It leverages `thiserror` for error message formatting and `serde` for serialization.
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use thiserror::Error;
 
+/// Nasdaq code returned in `bCodeMessage` when the requested symbol does not exist.
+const NASDAQ_CODE_INVALID_SYMBOL: i64 = 1001;
+
+/// What a `bCodeMessage` entry reports, for branching without matching text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BCodeKind {
+    /// The requested symbol does not exist.
+    InvalidSymbol,
+    /// The symbol exists but Nasdaq has nothing for this request.
+    NoData,
+    /// Nasdaq refused the request because of its rate; retrying later may succeed.
+    Throttled,
+    /// Any other code.
+    Other,
+}
+
+/// A single entry from the Nasdaq `status.bCodeMessage` array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BCodeMessage {
+    /// The numeric business code reported by Nasdaq.
+    #[serde(default)]
+    pub code: i64,
+    /// The human-readable message paired with the code.
+    #[serde(default)]
+    pub error_message: String,
+}
+
+impl BCodeMessage {
+    /// Parses the `bCodeMessage` value of a Nasdaq status block.
+    ///
+    /// Nasdaq sends an array of `{ code, errorMessage }` objects, but older
+    /// endpoints may send a bare string or `null`. Strings are mapped to an
+    /// entry with code `0`; anything unrecognized yields an empty list.
+    pub fn parse_list(value: &Value) -> Vec<BCodeMessage> {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|item| serde_json::from_value(item.clone()).ok())
+                .collect(),
+            Value::String(msg) => vec![BCodeMessage { code: 0, error_message: msg.clone() }],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Classifies the entry by its code, or by its message for codes without
+    /// a fixed meaning.
+    pub fn kind(&self) -> BCodeKind {
+        if self.code == NASDAQ_CODE_INVALID_SYMBOL {
+            return BCodeKind::InvalidSymbol;
+        }
+        let msg = self.error_message.to_ascii_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| msg.contains(n));
+        if msg.contains("symbol") && has(&["not exist", "invalid", "not found"]) {
+            BCodeKind::InvalidSymbol
+        } else if has(&["too many", "rate limit", "throttl", "try again later"]) {
+            BCodeKind::Throttled
+        } else if has(&["no data", "not available", "no record", "not found"]) {
+            BCodeKind::NoData
+        } else {
+            BCodeKind::Other
+        }
+    }
+
+    /// Returns true if this entry reports an unknown or invalid symbol.
+    pub fn is_invalid_symbol(&self) -> bool {
+        self.kind() == BCodeKind::InvalidSymbol
+    }
+}
+
 /// Central error type for the `rs_lib_ng` library.
-#[derive(Debug, Error, Serialize)]
+#[derive(Debug, Clone, Error, Serialize)]
 pub enum NgError {
     /// Error related to configuration loading or merging.
     #[error("Configuration error: {0}")]
@@ -41,10 +112,38 @@ pub enum NgError {
         r_code: i64,
         /// The endpoint URL that was called.
         endpoint: String,
+        /// Typed entries parsed from `status.bCodeMessage`.
+        messages: Vec<BCodeMessage>,
         /// The full JSON response body for deeper inspection.
         response: serde_json::Value,
     },
 
+    /// A request, including all retries and backoff sleeps, did not finish
+    /// within its `total_deadline`.
+    #[error("Request to {url} exceeded its total deadline of {deadline_ms}ms")]
+    DeadlineExceeded {
+        /// The target URL that was requested.
+        url: String,
+        /// The configured deadline in milliseconds.
+        deadline_ms: u64,
+    },
+
+    /// The request was cancelled through its `CancellationToken` before it
+    /// completed (including during backoff or Retry-After waits).
+    #[error("Request to {url} was cancelled")]
+    Cancelled {
+        /// The target URL that was requested.
+        url: String,
+    },
+
+    /// A retry was needed but the client's shared retry budget was empty,
+    /// typically during a sustained upstream outage.
+    #[error("Retry budget exhausted for {url}")]
+    RetryBudgetExhausted {
+        /// The target URL that was requested.
+        url: String,
+    },
+
     /// Error returned when the JSON structure is missing expected mandatory fields.
     #[error("Malformed Nasdaq API response structure at {endpoint}: {details}")]
     MalformedResponse {
@@ -53,4 +152,46 @@ pub enum NgError {
         /// Description of why the structure was considered malformed.
         details: String,
     },
-}
\ No newline at end of file
+}
+
+impl NgError {
+    /// Returns the typed `bCodeMessage` entries of a Nasdaq business error.
+    ///
+    /// Any other variant yields an empty slice.
+    pub fn b_code_messages(&self) -> &[BCodeMessage] {
+        match self {
+            NgError::NasdaqBusinessError { messages, .. } => messages,
+            _ => &[],
+        }
+    }
+
+    /// Classifies a Nasdaq business error by its first entry of a known kind,
+    /// or `Other` when none is known. Any other variant yields `None`.
+    pub fn b_code_kind(&self) -> Option<BCodeKind> {
+        match self {
+            NgError::NasdaqBusinessError { messages, .. } => Some(
+                messages
+                    .iter()
+                    .map(BCodeMessage::kind)
+                    .find(|k| *k != BCodeKind::Other)
+                    .unwrap_or(BCodeKind::Other),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is a Nasdaq business error reporting an invalid symbol.
+    pub fn is_invalid_symbol(&self) -> bool {
+        self.b_code_messages().iter().any(BCodeMessage::is_invalid_symbol)
+    }
+
+    /// Returns true if this is a Nasdaq business error reporting no data for the request.
+    pub fn is_no_data(&self) -> bool {
+        self.b_code_messages().iter().any(|m| m.kind() == BCodeKind::NoData)
+    }
+
+    /// Returns true if this is a Nasdaq business error reporting throttling.
+    pub fn is_throttled(&self) -> bool {
+        self.b_code_messages().iter().any(|m| m.kind() == BCodeKind::Throttled)
+    }
+}
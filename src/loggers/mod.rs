@@ -22,6 +22,7 @@ macro_rules! log_base {
             if $level < cfg.level {
                 // do nothing if below configured level
             } else {
+                #[allow(unused_mut)]
                 let mut ctx = std::collections::HashMap::new();
                 $(
                     // Convert value to serde_json::Value; fallback to Null on error
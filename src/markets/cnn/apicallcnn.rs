@@ -6,9 +6,11 @@
 
 use reqwest::header::{HeaderMap, HeaderValue, HeaderName};
 use serde_json::Value;
-use crate::retrieve::ky_http::{KyHttp, KyOptions};
+use std::sync::Arc;
+use crate::retrieve::ky_http::{KyHttp, KyOptions, RequestOverrides};
 use crate::core::error::NgError;
 use crate::loggers::Logger;
+use crate::markets::headers::{HeaderProfile, HeaderProfileSource, HeaderRotation};
 use crate::warn;
 
 /// Adapter for CNN APIs supporting flexible endpoints and custom header management.
@@ -22,6 +24,8 @@ pub struct CnnApi {
     logger: Logger,
     /// Internal storage for request headers.
     headers: HeaderMap,
+    /// Browser fingerprints, rotated when requests get blocked.
+    profiles: Arc<HeaderRotation>,
 }
 
 impl CnnApi {
@@ -34,33 +38,41 @@ impl CnnApi {
             http: KyHttp::new(logger.clone()),
             logger,
             headers: HeaderMap::new(),
+            profiles: Arc::new(HeaderRotation::default()),
         };
         // Initialize with default header set
         api.set_default_headers();
         api
     }
 
+    /// Rotates through `source`'s browser profiles instead of the built-in ones.
+    pub fn with_header_profiles(mut self, source: &dyn HeaderProfileSource) -> Self {
+        self.profiles = Arc::new(HeaderRotation::new(source));
+        self
+    }
+
+    /// The browser profiles this adapter rotates through.
+    pub fn header_profiles(&self) -> &HeaderRotation {
+        &self.profiles
+    }
+
     /// Sets the internal headers to a default set of browser-mimicry headers.
     /// 
-    /// These headers mimic a standard Windows Chrome browser to prevent 
-    /// requests from being flagged as automated traffic by CDN filters.
+    /// The browser fingerprint itself (user agent, client hints, language)
+    /// comes from the current header profile, so that it can be rotated when
+    /// CDN filters flag it as automated traffic.
     fn set_default_headers(&mut self) {
         let headers = [
             ("authority", "api.nasdaq.com"),
             ("accept", "application/json, text/plain, */*"),
-            ("accept-language", "en-US,en;q=0.9,el-GR;q=0.8,el;q=0.7,it;q=0.6"),
             ("cache-control", "no-cache"),
             ("dnt", "1"),
             ("origin", "https://www.nasdaq.com"),
             ("pragma", "no-cache"),
             ("referer", "https://www.nasdaq.com/"),
-            ("sec-ch-ua", r#""Google Chrome";v="119", "Chromium";v="119", "Not?A_Brand";v="24""#),
-            ("sec-ch-ua-mobile", "?0"),
-            ("sec-ch-ua-platform", "\"Windows\""),
             ("sec-fetch-dest", "empty"),
             ("sec-fetch-mode", "cors"),
             ("sec-fetch-site", "same-site"),
-            ("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36"),
         ];
 
         for (k, v) in headers {
@@ -78,20 +90,28 @@ impl CnnApi {
     /// * `value` - The header value string.
     pub fn set_header(&mut self, key: &str, value: &str) {
         // Attempt to parse the key into a valid HeaderName to resolve lifetime issues
-        if let Ok(name) = HeaderName::from_bytes(key.as_bytes()) {
-            if let Ok(val) = HeaderValue::from_str(value) {
-                // HeaderName is owned and satisfies the IntoHeaderName trait bound
-                self.headers.insert(name, val);
-            }
+        if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
+            && let Ok(val) = HeaderValue::from_str(value)
+        {
+            // HeaderName is owned and satisfies the IntoHeaderName trait bound
+            self.headers.insert(name, val);
         }
     }
 
-    /// Returns a clone of the current header set.
+    /// Returns the header set sent with the next request: the current
+    /// profile's fingerprint, overridden by the adapter's own headers.
     ///
     /// Useful for inspecting the state of the adapter or passing headers
     /// to other internal components.
     pub fn get_headers(&self) -> HeaderMap {
-        self.headers.clone()
+        self.headers_for(self.profiles.current())
+    }
+
+    fn headers_for(&self, profile: &HeaderProfile) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        profile.apply(&mut headers);
+        headers.extend(self.headers.clone());
+        headers
     }
 
     /// Executes an asynchronous GET request to the specified CNN endpoint.
@@ -107,15 +127,22 @@ impl CnnApi {
     /// Returns [`NgError::NonJsonResponse`] if the server returns non-JSON content 
     /// or a non-success HTTP status code.
     pub async fn call(&self, endpoint: &str, options: Option<KyOptions>) -> Result<Value, NgError> {
-        // Corrected: Uses new_with_opts to match ky_http.rs implementation
-        let api_resp = if let Some(opts) = options {
-            // Create a transient instance with the provided overrides
-            let transient_http = KyHttp::new_with_opts(self.logger.clone(), Some(opts));
-            transient_http.get::<Value>(endpoint, self.get_headers()).await?
-        } else {
-            // Use the persistent instance with default settings
-            self.http.get::<Value>(endpoint, self.get_headers()).await?
-        };
+        match options {
+            // Transient instance with the provided overrides, sharing the connection pool
+            Some(opts) => self.call_on(&self.http.with_options(opts)?, endpoint).await,
+            None => self.call_on(&self.http, endpoint).await,
+        }
+    }
+
+    /// Like [`call`](Self::call), with only the timeout, retry count or backoff
+    /// cap changed for this call.
+    pub async fn call_with(&self, endpoint: &str, overrides: &RequestOverrides) -> Result<Value, NgError> {
+        self.call_on(&self.http.with_overrides(overrides), endpoint).await
+    }
+
+    async fn call_on(&self, http: &KyHttp, endpoint: &str) -> Result<Value, NgError> {
+        // A blocked response switches to the next browser profile and is retried once
+        let api_resp = self.profiles.get(http, endpoint, |p| self.headers_for(p), &self.logger).await?;
 
         // Validate the response status and content type
         if !api_resp.success {
@@ -3,17 +3,59 @@
 //! Provides a high-level interface for retrieving the CNN Fear & Greed Index.
 //! It supports fetching current status and historical graph data, transforming 
 //! raw API responses into structured domain models.
+//!
+//! [`FearAndGreed::start_polling`] keeps a watch channel up to date with the
+//! latest status, notifying only when the score or rating changes. With
+//! [`start_polling_with_sessions`](FearAndGreed::start_polling_with_sessions),
+//! polling slows down outside the regular session, following the events of
+//! a `MarketSessionScheduler`.
+//!
+//! [`FearAndGreed::fetch_cached`] lets several consumers in one process share
+//! a single recent fetch, and reports the age of what it returns.
+//!
+//! [`FearAndGreed::backfill_full_history`] walks back through dated calls to
+//! rebuild the daily history to a given date, persisting its progress so an
+//! interrupted backfill resumes where it stopped.
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use chrono::{DateTime, Utc, TimeZone};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc, TimeZone};
+use chrono_tz::US::Eastern;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch, Mutex};
 use crate::markets::cnn::apicallcnn::CnnApi;
+use crate::markets::lastgood::{LastGood, LastGoodCache};
 use crate::retrieve::ky_http::KyOptions;
+use crate::retrieve::rate_limit::{RateLimit, RateLimiter};
+use crate::retrieve::url_builder::UrlBuilder;
 use crate::core::error::NgError;
 use crate::loggers::Logger;
+use crate::markets::nasdaq::calendar::TradingCalendar;
+use crate::markets::sessions::SessionEvent;
+use crate::{info, warn};
+
+/// CNN Fear & Greed graph data endpoint.
+pub const GRAPHDATA_URL: &str = "https://production.dataviz.cnn.io/index/fearandgreed/graphdata";
+
+/// Key of the backfill progress in a [`LastGoodCache`].
+pub const BACKFILL_PROGRESS_KEY: &str = "fear-and-greed-backfill";
+
+/// How far back each backfill call starts from the previous one.
+const BACKFILL_STEP_DAYS: i64 = 180;
+
+/// A backfill's readings so far, and the oldest date requested.
+#[derive(Serialize, Deserialize)]
+struct BackfillProgress {
+    reached: NaiveDate,
+    points: Vec<FngData>,
+}
 
 /// Represents a single measurement of the Fear & Greed index or one of its components.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FngData {
     /// The specific date and time the reading was recorded.
     pub date: DateTime<Utc>,
@@ -38,18 +80,122 @@ pub struct FearAndGreedStatus {
     pub stock_price_breadth: FngData,
     /// Put and Call Options (Put/call ratio).
     pub put_call_options: FngData,
+    /// Junk Bond Demand (junk vs investment-grade bond yield spread).
+    pub junk_bond_demand: FngData,
+    /// Safe Haven Demand (stocks vs bonds 20-day return difference).
+    pub safe_haven_demand: FngData,
+    /// Market Volatility (VIX vs its 50-day moving average).
+    pub market_volatility_vix: FngData,
+    /// Historical readings of each sub-indicator.
+    pub component_history: FngComponentHistory,
     /// Previous market close index value.
     pub previous_close: f64,
     /// Average index value from one week ago.
     pub previous_1_week: f64,
 }
 
+impl FearAndGreedStatus {
+    /// Writes the index history and every component history as CSV, with
+    /// the header `series,date,value,rating`. `series` is
+    /// `fear_and_greed` for the index and the field name for components;
+    /// `date` is RFC 3339 UTC.
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> Result<(), NgError> {
+        let io = |e: std::io::Error| NgError::InternalError(format!("Fear & Greed CSV write failed: {}", e));
+        writeln!(writer, "series,date,value,rating").map_err(io)?;
+        for (name, points) in self.series() {
+            for p in points {
+                writeln!(writer, "{},{},{},{}", name, p.date.to_rfc3339(), p.value, csv_field(&p.rating)).map_err(io)?;
+            }
+        }
+        writer.flush().map_err(io)
+    }
+
+    /// Writes the same rows as [`to_csv`](Self::to_csv) as a Snappy-compressed
+    /// Parquet file with the columns `series` (string), `date` (UTC timestamp
+    /// in milliseconds), `value` (double) and `rating` (string).
+    #[cfg(feature = "parquet")]
+    pub fn to_parquet<W: Write + Send>(&self, writer: W) -> Result<(), NgError> {
+        use parquet::basic::Compression;
+        use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+        use parquet::file::properties::WriterProperties;
+        use parquet::file::writer::SerializedFileWriter;
+        use parquet::schema::parser::parse_message_type;
+
+        const SCHEMA: &str = "message fear_and_greed {
+            REQUIRED BYTE_ARRAY series (UTF8);
+            REQUIRED INT64 date (TIMESTAMP(MILLIS, true));
+            REQUIRED DOUBLE value;
+            REQUIRED BYTE_ARRAY rating (UTF8);
+        }";
+        let pq = |e: parquet::errors::ParquetError| NgError::InternalError(format!("Fear & Greed Parquet write failed: {}", e));
+
+        let rows: Vec<(&str, &FngData)> = self.series().into_iter().flat_map(|(name, points)| points.iter().map(move |p| (name, p))).collect();
+        let series: Vec<ByteArray> = rows.iter().map(|(name, _)| ByteArray::from(*name)).collect();
+        let dates: Vec<i64> = rows.iter().map(|(_, p)| p.date.timestamp_millis()).collect();
+        let values: Vec<f64> = rows.iter().map(|(_, p)| p.value).collect();
+        let ratings: Vec<ByteArray> = rows.iter().map(|(_, p)| ByteArray::from(p.rating.as_str())).collect();
+
+        let schema = Arc::new(parse_message_type(SCHEMA).map_err(pq)?);
+        let props = Arc::new(WriterProperties::builder().set_compression(Compression::SNAPPY).build());
+        let mut file = SerializedFileWriter::new(writer, schema, props).map_err(pq)?;
+        let mut group = file.next_row_group().map_err(pq)?;
+        let mut index = 0;
+        while let Some(mut column) = group.next_column().map_err(pq)? {
+            match index {
+                0 => column.typed::<ByteArrayType>().write_batch(&series, None, None),
+                1 => column.typed::<Int64Type>().write_batch(&dates, None, None),
+                2 => column.typed::<DoubleType>().write_batch(&values, None, None),
+                _ => column.typed::<ByteArrayType>().write_batch(&ratings, None, None),
+            }
+            .map_err(pq)?;
+            column.close().map_err(pq)?;
+            index += 1;
+        }
+        group.close().map_err(pq)?;
+        file.close().map_err(pq)?;
+        Ok(())
+    }
+
+    /// The exported series in output order, named as in the CSV `series` column.
+    fn series(&self) -> [(&'static str, &[FngData]); 8] {
+        let c = &self.component_history;
+        [
+            ("fear_and_greed", &self.history),
+            ("market_momentum", &c.market_momentum),
+            ("stock_price_strength", &c.stock_price_strength),
+            ("stock_price_breadth", &c.stock_price_breadth),
+            ("put_call_options", &c.put_call_options),
+            ("junk_bond_demand", &c.junk_bond_demand),
+            ("safe_haven_demand", &c.safe_haven_demand),
+            ("market_volatility_vix", &c.market_volatility_vix),
+        ]
+    }
+}
+
+/// Historical readings of the seven sub-indicators, oldest first as sent by CNN.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FngComponentHistory {
+    pub market_momentum: Vec<FngData>,
+    pub stock_price_strength: Vec<FngData>,
+    pub stock_price_breadth: Vec<FngData>,
+    pub put_call_options: Vec<FngData>,
+    pub junk_bond_demand: Vec<FngData>,
+    pub safe_haven_demand: Vec<FngData>,
+    pub market_volatility_vix: Vec<FngData>,
+}
+
 /// Service orchestrator for CNN Fear & Greed data retrieval.
 pub struct FearAndGreed {
     /// Internal API client for CNN endpoints.
     api: CnnApi,
     /// Shared logger for diagnostic tracking.
     logger: Logger,
+    /// Graph data endpoint; dated requests append the date as a segment.
+    graphdata_url: String,
+    /// Last successful `fetch_latest`, shared by `fetch_cached` callers.
+    cache: Mutex<Option<LastGood<FearAndGreedStatus>>>,
+    /// Paces the dated calls of a backfill.
+    backfill_limiter: RateLimiter,
 }
 
 impl FearAndGreed {
@@ -61,9 +207,25 @@ impl FearAndGreed {
         Self {
             api: CnnApi::new(logger.clone()),
             logger,
+            graphdata_url: GRAPHDATA_URL.to_string(),
+            cache: Mutex::new(None),
+            backfill_limiter: RateLimiter::new(RateLimit { requests: 1, per: Duration::from_secs(5) }),
         }
     }
 
+    /// Points the service at another graph data endpoint (default
+    /// `GRAPHDATA_URL`), e.g. a mock server in tests.
+    pub fn with_graphdata_url(mut self, url: &str) -> Self {
+        self.graphdata_url = url.to_string();
+        self
+    }
+
+    /// Paces backfill calls at `limit` instead of one call every 5 seconds.
+    pub fn with_backfill_rate(mut self, limit: RateLimit) -> Self {
+        self.backfill_limiter = RateLimiter::new(limit);
+        self
+    }
+
     /// Fetches the latest Fear & Greed index and sub-indicators.
     ///
     /// This method uses the base `graphdata` endpoint which contains 
@@ -72,9 +234,45 @@ impl FearAndGreed {
     /// # Arguments
     /// * `options` - Optional [`KyOptions`] for overriding request behavior.
     pub async fn fetch_latest(&self, options: Option<KyOptions>) -> Result<FearAndGreedStatus, NgError> {
-        let url = "https://production.dataviz.cnn.io/index/fearandgreed/graphdata";
-        let raw = self.api.call(url, options).await?;
-        self.map_response(raw, url)
+        let raw = self.api.call(&self.graphdata_url, options).await?;
+        self.map_response(raw, &self.graphdata_url)
+    }
+
+    /// Fetches only the current score, rating and timestamp, without
+    /// mapping the history or the sub-indicators. Meant for frequent polls.
+    pub async fn fetch_current(&self, options: Option<KyOptions>) -> Result<FngData, NgError> {
+        let raw = self.api.call(&self.graphdata_url, options).await?;
+        self.primary_reading(&raw, &self.graphdata_url).map(|(_, current)| current)
+    }
+
+    /// The status last fetched by this method if younger than `ttl`,
+    /// otherwise a fresh [`fetch_latest`](Self::fetch_latest). Concurrent
+    /// callers wait for one shared fetch.
+    ///
+    /// `fetched_at` and `age()` tell how old the status is. When the refetch
+    /// fails, the previous status is returned marked stale with the error;
+    /// with nothing cached, the error itself.
+    pub async fn fetch_cached(&self, ttl: Duration, options: Option<KyOptions>) -> Result<LastGood<FearAndGreedStatus>, NgError> {
+        let mut cache = self.cache.lock().await;
+        if let Some(cached) = cache.as_ref()
+            && cached.age().to_std().is_ok_and(|age| age < ttl)
+        {
+            return Ok(cached.clone());
+        }
+        match self.fetch_latest(options).await {
+            Ok(status) => {
+                let fresh = LastGood { value: status, fetched_at: Utc::now(), stale_error: None };
+                *cache = Some(fresh.clone());
+                Ok(fresh)
+            }
+            Err(e) => match cache.as_ref() {
+                Some(cached) => {
+                    warn!(self.logger, "Fear & Greed refetch failed, serving cached status", "error" => e.to_string(), "fetched_at" => cached.fetched_at.to_rfc3339());
+                    Ok(LastGood { stale_error: Some(e.to_string()), ..cached.clone() })
+                }
+                None => Err(e),
+            },
+        }
     }
 
     /// Fetches historical Fear & Greed data for a specific date.
@@ -83,11 +281,202 @@ impl FearAndGreed {
     /// * `date` - The target date in `%Y-%m-%d` format.
     /// * `options` - Optional [`KyOptions`] for request configuration.
     pub async fn fetch_at_date(&self, date: &str, options: Option<KyOptions>) -> Result<FearAndGreedStatus, NgError> {
-        let url = format!("https://production.dataviz.cnn.io/index/fearandgreed/graphdata/{}", date);
+        let url = UrlBuilder::parse(&self.graphdata_url)?.segment(date).build();
         let raw = self.api.call(&url, options).await?;
         self.map_response(raw, &url)
     }
 
+    /// Fetches the daily index history from `start` to `end` (both included),
+    /// sorted by date with one reading per day.
+    ///
+    /// A dated call returns history from its date onwards; when it stops
+    /// short of `end`, the next call starts the day after its last reading.
+    /// Overlapping readings are deduplicated per UTC day, the later call winning.
+    pub async fn fetch_range(&self, start: NaiveDate, end: NaiveDate, options: Option<KyOptions>) -> Result<Vec<FngData>, NgError> {
+        let today = Utc::now().date_naive();
+        let mut by_day: BTreeMap<NaiveDate, FngData> = BTreeMap::new();
+        let mut from = start;
+        while from <= end {
+            let status = self.fetch_at_date(&from.format("%Y-%m-%d").to_string(), options.clone()).await?;
+            let last = status.history.iter().map(|p| p.date.date_naive()).max();
+            for point in status.history {
+                let day = point.date.date_naive();
+                if (start..=end).contains(&day) {
+                    by_day.insert(day, point);
+                }
+            }
+            // Stop once CNN has nothing newer to give.
+            match last {
+                Some(day) if day >= from && day < end && day < today => from = day + ChronoDuration::days(1),
+                _ => break,
+            }
+        }
+        Ok(by_day.into_values().collect())
+    }
+
+    /// Rebuilds the daily index history from `since` to today, sorted with
+    /// one reading per day.
+    ///
+    /// Starting from the latest history, each dated call, paced by the
+    /// backfill rate, starts 180 days before the previous one, until `since`
+    /// is reached or CNN returns nothing older. Newer readings win where the
+    /// windows overlap.
+    ///
+    /// With `progress`, the readings and the oldest date requested are stored
+    /// under [`BACKFILL_PROGRESS_KEY`] after every call. A later backfill
+    /// resumes from there, only refreshing the latest history; failing to
+    /// store progress is logged and does not stop the backfill.
+    pub async fn backfill_full_history(
+        &self,
+        since: NaiveDate,
+        progress: Option<&LastGoodCache>,
+        options: Option<KyOptions>,
+    ) -> Result<Vec<FngData>, NgError> {
+        let stored = match progress.map(|cache| cache.load::<BackfillProgress>(BACKFILL_PROGRESS_KEY)) {
+            Some(Ok(stored)) => stored.map(|s| s.value),
+            Some(Err(e)) => {
+                warn!(self.logger, "Fear & Greed backfill progress unreadable, starting over", "error" => e.to_string());
+                None
+            }
+            None => None,
+        };
+        let stored_reached = stored.as_ref().map(|s| s.reached);
+        let mut by_day: BTreeMap<NaiveDate, FngData> =
+            stored.into_iter().flat_map(|s| s.points).map(|p| (p.date.date_naive(), p)).collect();
+
+        let latest = self.fetch_latest(options.clone()).await?;
+        let latest_start = latest.history.iter().map(|p| p.date.date_naive()).min();
+        by_day.extend(latest.history.into_iter().map(|p| (p.date.date_naive(), p)));
+        let mut reached = stored_reached.or(latest_start).unwrap_or_else(|| Utc::now().date_naive());
+
+        while reached > since {
+            let from = (reached - ChronoDuration::days(BACKFILL_STEP_DAYS)).max(since);
+            self.backfill_limiter.acquire(BACKFILL_PROGRESS_KEY).await;
+            let status = self.fetch_at_date(&from.format("%Y-%m-%d").to_string(), options.clone()).await?;
+            let mut older = false;
+            for point in status.history {
+                let day = point.date.date_naive();
+                older |= day < reached;
+                by_day.entry(day).or_insert(point);
+            }
+            reached = from;
+            info!(self.logger, "Fear & Greed backfill progress", "reached" => reached.to_string(), "days" => by_day.len());
+            if let Some(cache) = progress {
+                let snapshot = BackfillProgress { reached, points: by_day.values().cloned().collect() };
+                if let Err(e) = cache.store(BACKFILL_PROGRESS_KEY, &snapshot) {
+                    warn!(self.logger, "Fear & Greed backfill progress not saved", "error" => e.to_string());
+                }
+            }
+            // CNN has no older readings.
+            if !older {
+                break;
+            }
+        }
+        Ok(by_day.into_iter().filter(|(day, _)| *day >= since).map(|(_, p)| p).collect())
+    }
+
+    /// Polls [`fetch_latest`](Self::fetch_latest) every `interval` (plus up
+    /// to 10% jitter) in a spawned task. The receiver holds the latest status
+    /// and is notified only when the score or rating changes. Failed polls
+    /// are logged and skipped. Polling stops once every receiver is dropped.
+    pub fn start_polling(self: &Arc<Self>, interval: Duration) -> watch::Receiver<Option<FearAndGreedStatus>> {
+        self.spawn_poller(interval, None)
+    }
+
+    /// Like [`start_polling`](Self::start_polling), but polls every
+    /// `off_hours_interval` outside the regular session. `events` (from
+    /// `MarketSessionScheduler::subscribe`) tells when the session opens and
+    /// closes; until the first event, the embedded calendar decides. A
+    /// regular open triggers a poll right away.
+    pub fn start_polling_with_sessions(
+        self: &Arc<Self>,
+        interval: Duration,
+        off_hours_interval: Duration,
+        events: broadcast::Receiver<SessionEvent>,
+    ) -> watch::Receiver<Option<FearAndGreedStatus>> {
+        self.spawn_poller(interval, Some((off_hours_interval, events)))
+    }
+
+    fn spawn_poller(
+        self: &Arc<Self>,
+        interval: Duration,
+        sessions: Option<(Duration, broadcast::Receiver<SessionEvent>)>,
+    ) -> watch::Receiver<Option<FearAndGreedStatus>> {
+        let (sender, receiver) = watch::channel(None);
+        let service = self.clone();
+        tokio::spawn(async move { service.poll(sender, interval, sessions).await });
+        receiver
+    }
+
+    async fn poll(
+        &self,
+        sender: watch::Sender<Option<FearAndGreedStatus>>,
+        interval: Duration,
+        mut sessions: Option<(Duration, broadcast::Receiver<SessionEvent>)>,
+    ) {
+        let mut in_session = in_regular_session(&TradingCalendar::fallback(), Utc::now());
+        loop {
+            match self.fetch_latest(None).await {
+                Ok(status) => {
+                    sender.send_if_modified(|last| {
+                        let changed = last.as_ref().is_none_or(|l| {
+                            l.current.value != status.current.value || l.current.rating != status.current.rating
+                        });
+                        if changed {
+                            info!(self.logger, "Fear & Greed changed", "score" => status.current.value, "rating" => status.current.rating.as_str());
+                            *last = Some(status);
+                        }
+                        changed
+                    });
+                }
+                Err(e) => warn!(self.logger, "Fear & Greed poll failed", "error" => e.to_string()),
+            }
+
+            let wait = match &sessions {
+                Some((off_hours, _)) if !in_session => *off_hours,
+                _ => interval,
+            };
+            let max_jitter = (wait / 10).as_millis() as u64;
+            let sleep = tokio::time::sleep(wait + Duration::from_millis(rand::thread_rng().gen_range(0..=max_jitter)));
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    _ = sender.closed() => return,
+                    event = next_event(&mut sessions) => {
+                        let was_in_session = in_session;
+                        in_session = matches!(event, SessionEvent::RegularOpen { .. });
+                        if in_session && !was_in_session {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// The `fear_and_greed` block of a response and its current reading.
+    fn primary_reading<'a>(&self, json: &'a Value, url: &str) -> Result<(&'a Value, FngData), NgError> {
+        // Validate the presence of the primary data block
+        let fg_primary = json.get("fear_and_greed").ok_or_else(|| {
+            warn!(self.logger, "Malformed Fear & Greed response structure", "url" => url);
+            NgError::MalformedResponse {
+                endpoint: url.to_string(),
+                details: "Missing 'fear_and_greed' root key".to_string(),
+            }
+        })?;
+
+        let current = FngData {
+            date: fg_primary["timestamp"].as_str()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(Utc::now),
+            value: fg_primary["score"].as_f64().unwrap_or(0.0),
+            rating: fg_primary["rating"].as_str().unwrap_or("unknown").to_string(),
+        };
+        Ok((fg_primary, current))
+    }
+
     /// Maps raw JSON response into a typed [`FearAndGreedStatus`].
     ///
     /// This handles the transformation of CNN's `x` (milliseconds) and `y` (value) 
@@ -105,35 +494,10 @@ impl FearAndGreed {
             }
         };
 
-        // Validate the presence of the primary data block
-        let fg_primary = json.get("fear_and_greed").ok_or_else(|| NgError::MalformedResponse {
-            endpoint: url.to_string(),
-            details: "Missing 'fear_and_greed' root key".to_string(),
-        })?;
-
-        // Construct current primary reading
-        let current = FngData {
-            date: fg_primary["timestamp"].as_str()
-                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
-                .map(|t| t.with_timezone(&Utc))
-                .unwrap_or_else(Utc::now),
-            value: fg_primary["score"].as_f64().unwrap_or(0.0),
-            rating: fg_primary["rating"].as_str().unwrap_or("unknown").to_string(),
-        };
+        let (fg_primary, current) = self.primary_reading(&json, url)?;
 
         // Map historical time-series (transforming x and y)
-        let mut history = Vec::new();
-        if let Some(data_points) = json["fear_and_greed_historical"]["data"].as_array() {
-            for point in data_points {
-                if let (Some(x), Some(y)) = (point["x"].as_f64(), point["y"].as_f64()) {
-                    history.push(FngData {
-                        date: Utc.timestamp_millis_opt(x as i64).unwrap(),
-                        value: y,
-                        rating: point["rating"].as_str().unwrap_or("").to_string(),
-                    });
-                }
-            }
-        }
+        let history = series(&json["fear_and_greed_historical"]);
 
         Ok(FearAndGreedStatus {
             current,
@@ -142,8 +506,69 @@ impl FearAndGreed {
             stock_price_strength: extract_indicator("stock_price_strength"),
             stock_price_breadth: extract_indicator("stock_price_breadth"),
             put_call_options: extract_indicator("put_call_options"),
+            junk_bond_demand: extract_indicator("junk_bond_demand"),
+            safe_haven_demand: extract_indicator("safe_haven_demand"),
+            market_volatility_vix: extract_indicator("market_volatility_vix"),
+            component_history: FngComponentHistory {
+                market_momentum: series(&json["market_momentum_sp500"]),
+                stock_price_strength: series(&json["stock_price_strength"]),
+                stock_price_breadth: series(&json["stock_price_breadth"]),
+                put_call_options: series(&json["put_call_options"]),
+                junk_bond_demand: series(&json["junk_bond_demand"]),
+                safe_haven_demand: series(&json["safe_haven_demand"]),
+                market_volatility_vix: series(&json["market_volatility_vix"]),
+            },
             previous_close: fg_primary["previous_close"].as_f64().unwrap_or(0.0),
             previous_1_week: fg_primary["previous_1_week"].as_f64().unwrap_or(0.0),
         })
     }
-}
\ No newline at end of file
+}
+
+/// Maps the `data` array of a graph block, whose points carry `x` (epoch
+/// milliseconds) and `y` (value). Points missing either are skipped.
+fn series(block: &Value) -> Vec<FngData> {
+    block["data"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|point| {
+            Some(FngData {
+                date: Utc.timestamp_millis_opt(point["x"].as_f64()? as i64).single()?,
+                value: point["y"].as_f64()?,
+                rating: point["rating"].as_str().unwrap_or("").to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Whether `now` falls in the regular session of `calendar`.
+fn in_regular_session(calendar: &TradingCalendar, now: DateTime<Utc>) -> bool {
+    calendar
+        .session_times(now.with_timezone(&Eastern).date_naive())
+        .is_some_and(|t| t.regular_open <= now && now < t.regular_close)
+}
+
+/// The next session event; never resolves without a live event source.
+async fn next_event(sessions: &mut Option<(Duration, broadcast::Receiver<SessionEvent>)>) -> SessionEvent {
+    if let Some((_, events)) = sessions {
+        loop {
+            match events.recv().await {
+                Ok(event) => return event,
+                // Missed events are superseded by the next one.
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+    std::future::pending().await
+}
+
+/// Quotes `raw` if it contains a separator, quote or line break.
+fn csv_field(raw: &str) -> std::borrow::Cow<'_, str> {
+    if raw.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", raw.replace('"', "\"\"")).into()
+    } else {
+        raw.into()
+    }
+}
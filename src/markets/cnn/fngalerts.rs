@@ -0,0 +1,249 @@
+//! # Fear & Greed Threshold Alerts
+//!
+//! [`FngAlerter`] watches the readings of a `FearAndGreed` poller and raises
+//! an [`FngAlert`] when the index falls below or rises above a configured
+//! threshold, or moves into another [`RatingBand`]. Alerts go to registered
+//! callbacks and to a broadcast channel.
+//!
+//! Hysteresis keeps a reading hovering around a boundary from flapping: once
+//! the index went below the low threshold, it must climb `hysteresis` points
+//! above it before another crossing is reported (likewise for the high
+//! threshold), and a band change counts only once the index is `hysteresis`
+//! points past the band edge.
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::markets::cnn::fearandgreed::FearAndGreed;
+//! # use rs_lib_ng::markets::cnn::fngalerts::FngAlerter;
+//! # use std::sync::Arc;
+//! # async fn run(fng: Arc<FearAndGreed>, logger: rs_lib_ng::loggers::Logger) {
+//! let alerter = FngAlerter::new(logger).below(20.0).above(80.0).with_hysteresis(2.0);
+//! let mut alerts = alerter.subscribe();
+//! alerter.start(&fng, std::time::Duration::from_secs(300));
+//! while let Ok(alert) = alerts.recv().await {
+//!     println!("{:?}", alert);
+//! }
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+use crate::info;
+use crate::loggers::Logger;
+use crate::markets::cnn::fearandgreed::{FearAndGreed, FearAndGreedStatus, FngData};
+
+/// Alerts kept for slow subscribers before they start missing some.
+const CHANNEL_CAPACITY: usize = 32;
+
+/// Upper edges of CNN's rating bands, from extreme fear to greed.
+const BAND_EDGES: [f64; 4] = [25.0, 45.0, 55.0, 75.0];
+
+/// Callback run for every alert.
+pub type AlertFn = Arc<dyn Fn(&FngAlert) + Send + Sync>;
+
+/// CNN's five rating bands of the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RatingBand {
+    ExtremeFear,
+    Fear,
+    Neutral,
+    Greed,
+    ExtremeGreed,
+}
+
+impl RatingBand {
+    const ALL: [RatingBand; 5] = [
+        RatingBand::ExtremeFear,
+        RatingBand::Fear,
+        RatingBand::Neutral,
+        RatingBand::Greed,
+        RatingBand::ExtremeGreed,
+    ];
+
+    /// The band of an index value: below 25 is extreme fear, below 45 fear,
+    /// up to 55 neutral, up to 75 greed, and above that extreme greed.
+    pub fn of(value: f64) -> Self {
+        Self::ALL[Self::index_of(value)]
+    }
+
+    /// The rating as CNN writes it, e.g. `"extreme fear"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RatingBand::ExtremeFear => "extreme fear",
+            RatingBand::Fear => "fear",
+            RatingBand::Neutral => "neutral",
+            RatingBand::Greed => "greed",
+            RatingBand::ExtremeGreed => "extreme greed",
+        }
+    }
+
+    fn index_of(value: f64) -> usize {
+        // Neutral includes both of its edges, as in CNN's scale.
+        match value {
+            v if v < BAND_EDGES[0] => 0,
+            v if v < BAND_EDGES[1] => 1,
+            v if v <= BAND_EDGES[2] => 2,
+            v if v <= BAND_EDGES[3] => 3,
+            _ => 4,
+        }
+    }
+}
+
+/// A threshold crossing or rating change, with the reading that caused it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FngAlert {
+    /// The index fell below the low threshold.
+    CrossedBelow { threshold: f64, reading: FngData },
+    /// The index rose above the high threshold.
+    CrossedAbove { threshold: f64, reading: FngData },
+    /// The index moved into another rating band.
+    RatingChanged { from: RatingBand, to: RatingBand, reading: FngData },
+}
+
+/// Alert state: which thresholds already fired and the confirmed band.
+#[derive(Debug, Default)]
+struct AlertState {
+    below: bool,
+    above: bool,
+    band: Option<RatingBand>,
+}
+
+/// Turns Fear & Greed readings into alerts.
+pub struct FngAlerter {
+    low: Option<f64>,
+    high: Option<f64>,
+    hysteresis: f64,
+    state: AlertState,
+    callbacks: Vec<AlertFn>,
+    sender: broadcast::Sender<FngAlert>,
+    logger: Logger,
+}
+
+impl FngAlerter {
+    /// An alerter reporting rating band changes only, without hysteresis.
+    pub fn new(logger: Logger) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            low: None,
+            high: None,
+            hysteresis: 0.0,
+            state: AlertState::default(),
+            callbacks: Vec::new(),
+            sender,
+            logger,
+        }
+    }
+
+    /// Alerts when the index falls below `threshold`.
+    pub fn below(mut self, threshold: f64) -> Self {
+        self.low = Some(threshold);
+        self
+    }
+
+    /// Alerts when the index rises above `threshold`.
+    pub fn above(mut self, threshold: f64) -> Self {
+        self.high = Some(threshold);
+        self
+    }
+
+    /// Points the index must move back past a threshold or band edge before
+    /// it counts again.
+    pub fn with_hysteresis(mut self, points: f64) -> Self {
+        self.hysteresis = points.max(0.0);
+        self
+    }
+
+    /// Runs `callback` for every alert, before it is broadcast.
+    pub fn on_alert(mut self, callback: AlertFn) -> Self {
+        self.callbacks.push(callback);
+        self
+    }
+
+    /// A new receiver of all alerts raised from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<FngAlert> {
+        self.sender.subscribe()
+    }
+
+    /// Feeds one reading and returns the alerts it raised, after running
+    /// the callbacks and broadcasting them.
+    ///
+    /// A reading already beyond a threshold alerts on its own; the first
+    /// reading only sets the band that later changes are measured from.
+    pub fn observe(&mut self, reading: &FngData) -> Vec<FngAlert> {
+        let value = reading.value;
+        let h = self.hysteresis;
+        let mut alerts = Vec::new();
+
+        if let Some(low) = self.low {
+            if !self.state.below && value < low {
+                self.state.below = true;
+                alerts.push(FngAlert::CrossedBelow { threshold: low, reading: reading.clone() });
+            } else if self.state.below && value >= low + h {
+                self.state.below = false;
+            }
+        }
+        if let Some(high) = self.high {
+            if !self.state.above && value > high {
+                self.state.above = true;
+                alerts.push(FngAlert::CrossedAbove { threshold: high, reading: reading.clone() });
+            } else if self.state.above && value <= high - h {
+                self.state.above = false;
+            }
+        }
+
+        let to = RatingBand::of(value);
+        match self.state.band {
+            None => self.state.band = Some(to),
+            Some(from) if from != to && past_band_edge(from, to, value, h) => {
+                self.state.band = Some(to);
+                alerts.push(FngAlert::RatingChanged { from, to, reading: reading.clone() });
+            }
+            Some(_) => {}
+        }
+
+        for alert in &alerts {
+            info!(self.logger, "Fear & Greed alert", "alert" => format!("{:?}", alert));
+            for callback in &self.callbacks {
+                callback(alert);
+            }
+            // No subscribers is not an error.
+            let _ = self.sender.send(alert.clone());
+        }
+        alerts
+    }
+
+    /// Observes every status published on `updates` in a spawned task, until
+    /// the poller stops.
+    pub fn spawn(mut self, mut updates: watch::Receiver<Option<FearAndGreedStatus>>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                let current = updates.borrow_and_update().as_ref().map(|s| s.current.clone());
+                if let Some(reading) = current {
+                    self.observe(&reading);
+                }
+                if updates.changed().await.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+
+    /// Starts polling `service` every `interval` and alerts on its readings.
+    pub fn start(self, service: &Arc<FearAndGreed>, interval: Duration) -> JoinHandle<()> {
+        self.spawn(service.start_polling(interval))
+    }
+}
+
+/// Whether `value`, in band `to`, is at least `h` points past the edge it
+/// crossed coming from band `from`.
+fn past_band_edge(from: RatingBand, to: RatingBand, value: f64, h: f64) -> bool {
+    let (from, to) = (from as usize, to as usize);
+    if to > from {
+        value >= BAND_EDGES[to - 1] + h
+    } else {
+        value <= BAND_EDGES[to] - h
+    }
+}
@@ -0,0 +1,165 @@
+//! # Fear & Greed Series Statistics
+//!
+//! [`FngSeries`] wraps a history of Fear & Greed readings, sorted by date,
+//! with the analytics every consumer needs: moving averages, extremes, the
+//! percentile rank of the latest reading and streaks of fear or greed days.
+//!
+//! Readings are classified with CNN's bands: below 45 is fear, above 55 is
+//! greed, anything between is neutral. Extreme fear and greed count as fear
+//! and greed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::markets::cnn::fearandgreed::FngData;
+
+/// Readings below this are fear.
+const FEAR_BELOW: f64 = 45.0;
+
+/// Readings above this are greed.
+const GREED_ABOVE: f64 = 55.0;
+
+/// Sentiment band of a reading.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Sentiment {
+    Fear,
+    Neutral,
+    Greed,
+}
+
+impl Sentiment {
+    /// The band of index value `value`.
+    pub fn of(value: f64) -> Self {
+        if value < FEAR_BELOW {
+            Sentiment::Fear
+        } else if value > GREED_ABOVE {
+            Sentiment::Greed
+        } else {
+            Sentiment::Neutral
+        }
+    }
+}
+
+/// Consecutive readings in the same band.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub sentiment: Sentiment,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Number of readings, i.e. days for a daily history.
+    pub len: usize,
+}
+
+/// Fear & Greed readings sorted by date, oldest first.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FngSeries {
+    points: Vec<FngData>,
+}
+
+impl From<Vec<FngData>> for FngSeries {
+    fn from(points: Vec<FngData>) -> Self {
+        Self::new(points)
+    }
+}
+
+impl FngSeries {
+    /// Sorts `points` by date.
+    pub fn new(mut points: Vec<FngData>) -> Self {
+        points.sort_by_key(|p| p.date);
+        Self { points }
+    }
+
+    /// All readings, oldest first.
+    pub fn points(&self) -> &[FngData] {
+        &self.points
+    }
+
+    /// Number of readings.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Whether there are no readings.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The most recent reading.
+    pub fn latest(&self) -> Option<&FngData> {
+        self.points.last()
+    }
+
+    /// Mean of the last `window` readings; `None` with fewer readings.
+    pub fn sma(&self, window: usize) -> Option<f64> {
+        if window == 0 || self.points.len() < window {
+            return None;
+        }
+        let tail = &self.points[self.points.len() - window..];
+        Some(tail.iter().map(|p| p.value).sum::<f64>() / window as f64)
+    }
+
+    /// Moving average of `window` readings at each reading from the
+    /// `window`-th on, dated like that reading.
+    pub fn rolling_sma(&self, window: usize) -> Vec<(DateTime<Utc>, f64)> {
+        if window == 0 {
+            return Vec::new();
+        }
+        self.points
+            .windows(window)
+            .map(|w| (w[window - 1].date, w.iter().map(|p| p.value).sum::<f64>() / window as f64))
+            .collect()
+    }
+
+    /// The lowest reading (the earliest on ties).
+    pub fn min(&self) -> Option<&FngData> {
+        self.points.iter().reduce(|a, b| if b.value < a.value { b } else { a })
+    }
+
+    /// The highest reading (the earliest on ties).
+    pub fn max(&self) -> Option<&FngData> {
+        self.points.iter().reduce(|a, b| if b.value > a.value { b } else { a })
+    }
+
+    /// Percentage (0-100) of readings at or below `value`.
+    pub fn percentile_rank(&self, value: f64) -> Option<f64> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let at_or_below = self.points.iter().filter(|p| p.value <= value).count();
+        Some(at_or_below as f64 / self.points.len() as f64 * 100.0)
+    }
+
+    /// Percentile rank of the latest reading within the series.
+    pub fn current_percentile(&self) -> Option<f64> {
+        self.percentile_rank(self.latest()?.value)
+    }
+
+    /// All runs of readings in the same band, oldest first.
+    pub fn streaks(&self) -> Vec<Streak> {
+        let mut streaks: Vec<Streak> = Vec::new();
+        for point in &self.points {
+            let sentiment = Sentiment::of(point.value);
+            match streaks.last_mut() {
+                Some(last) if last.sentiment == sentiment => {
+                    last.end = point.date;
+                    last.len += 1;
+                }
+                _ => streaks.push(Streak { sentiment, start: point.date, end: point.date, len: 1 }),
+            }
+        }
+        streaks
+    }
+
+    /// The run the latest reading belongs to.
+    pub fn current_streak(&self) -> Option<Streak> {
+        self.streaks().pop()
+    }
+
+    /// The longest run in `sentiment` (the earliest on ties).
+    pub fn longest_streak(&self, sentiment: Sentiment) -> Option<Streak> {
+        self.streaks()
+            .into_iter()
+            .filter(|s| s.sentiment == sentiment)
+            .reduce(|a, b| if b.len > a.len { b } else { a })
+    }
+}
@@ -1,2 +1,5 @@
 pub mod apicallcnn;
-pub mod fearandgreed;
\ No newline at end of file
+pub mod fearandgreed;
+pub mod fngalerts;
+pub mod fngseries;
+pub mod worldmarkets;
@@ -0,0 +1,123 @@
+//! # CNN Premarket Futures and World Indices
+//!
+//! Typed access to the dataviz summaries behind CNN's markets pages: US
+//! index futures (traded while the cash market is closed, hence the
+//! "premarket" view) and the main indices of the Americas, Europe and Asia.
+//!
+//! Both endpoints return an array of instruments with the same fields, mapped
+//! to [`CnnQuote`]. Rows without a symbol are skipped.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::cnn::apicallcnn::CnnApi;
+use crate::retrieve::ky_http::KyOptions;
+use crate::warn;
+
+/// Base of CNN's markets dataviz endpoints.
+pub const MARKETS_URL: &str = "https://production.dataviz.cnn.io/markets/";
+
+/// Latest value of a future or an index, as reported by CNN.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CnnQuote {
+    /// CNN's symbol, e.g. `"SPX"` or `"ES1!"`.
+    pub symbol: String,
+    /// Display name, e.g. `"S&P 500"`.
+    pub name: String,
+    pub price: Option<f64>,
+    pub previous_close: Option<f64>,
+    pub change: Option<f64>,
+    /// Percentage change from the previous close, e.g. `1.25` for +1.25%.
+    pub percent_change: Option<f64>,
+    pub last_updated: Option<DateTime<Utc>>,
+    /// Trading status as sent, e.g. `"open"` or `"closed"`; empty when absent.
+    pub status: String,
+}
+
+impl CnnQuote {
+    /// Whether CNN reports the instrument as trading.
+    pub fn is_open(&self) -> bool {
+        self.status.eq_ignore_ascii_case("open")
+    }
+}
+
+/// Service fetching CNN's futures and world index summaries.
+pub struct WorldMarkets {
+    api: CnnApi,
+    logger: Logger,
+    base_url: String,
+}
+
+impl WorldMarkets {
+    /// Creates the service with its own `CnnApi`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(CnnApi::new(logger.clone()), logger)
+    }
+
+    /// Creates the service on top of an existing adapter, e.g. one with
+    /// custom header profiles.
+    pub fn with_api(api: CnnApi, logger: Logger) -> Self {
+        Self { api, logger, base_url: MARKETS_URL.to_string() }
+    }
+
+    /// Points the service at another base URL (default `MARKETS_URL`), e.g.
+    /// a mock server in tests.
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = if url.ends_with('/') { url.to_string() } else { format!("{}/", url) };
+        self
+    }
+
+    /// US index futures (S&P 500, Nasdaq-100, Dow), quoted around the clock.
+    pub async fn fetch_futures(&self, options: Option<KyOptions>) -> Result<Vec<CnnQuote>, NgError> {
+        self.fetch_summary("futures", options).await
+    }
+
+    /// Main indices of the Americas, Europe and Asia.
+    pub async fn fetch_world_indices(&self, options: Option<KyOptions>) -> Result<Vec<CnnQuote>, NgError> {
+        self.fetch_summary("world", options).await
+    }
+
+    async fn fetch_summary(&self, market: &str, options: Option<KyOptions>) -> Result<Vec<CnnQuote>, NgError> {
+        let url = format!("{}{}/summary/latest", self.base_url, market);
+        let raw = self.api.call(&url, options).await?;
+        let rows = raw.as_array().ok_or_else(|| {
+            warn!(self.logger, "Malformed CNN markets response", "url" => url.as_str());
+            NgError::MalformedResponse {
+                endpoint: url.clone(),
+                details: "Expected an array of instruments".to_string(),
+            }
+        })?;
+        Ok(rows.iter().filter_map(quote).collect())
+    }
+}
+
+/// Maps one instrument row; `None` without a symbol.
+fn quote(row: &Value) -> Option<CnnQuote> {
+    let symbol = row["symbol"].as_str().filter(|s| !s.is_empty())?;
+    let price = row["current_price"].as_f64();
+    let previous_close = row["prev_close_price"].as_f64();
+    let change = row["price_change_from_prev_close"]
+        .as_f64()
+        .or_else(|| Some(price? - previous_close?));
+    // Computed rather than read: CNN's own field is a ratio on some feeds.
+    let percent_change = match (change, previous_close) {
+        (Some(change), Some(prev)) if prev != 0.0 => Some(change / prev * 100.0),
+        _ => None,
+    };
+    Some(CnnQuote {
+        symbol: symbol.to_string(),
+        name: row["name"].as_str().unwrap_or(symbol).to_string(),
+        price,
+        previous_close,
+        change,
+        percent_change,
+        last_updated: row["last_price_timestamp"]
+            .as_str()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc)),
+        status: row["status"].as_str().unwrap_or("").to_string(),
+    })
+}
@@ -0,0 +1,140 @@
+//! # Pagination Cursor State
+//!
+//! Persists the last-seen cursor of paginated market endpoints so that
+//! collectors restarting mid-day only process rows they have not seen yet.
+//!
+//! A [`CursorStore`] maps an endpoint key (e.g. `"insiders:AAPL"`) to a typed
+//! [`Cursor`]. [`fetch_new_since_last`] wraps a page fetch: it passes the stored
+//! cursor to the fetcher, drops rows at or before it, and advances the cursor to
+//! the newest row returned.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::core::error::NgError;
+
+/// Position of the newest processed row of an endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum Cursor {
+    /// Publication or transaction time of the newest row.
+    Timestamp(DateTime<Utc>),
+    /// Monotonic row offset or numeric id.
+    Offset(u64),
+    /// Opaque continuation token; only equality is meaningful.
+    Token(String),
+}
+
+impl Cursor {
+    /// Returns true if `self` is strictly newer than `other`.
+    ///
+    /// Cursors of different kinds are never ordered. Tokens are opaque, so a
+    /// token is considered newer whenever it differs.
+    pub fn is_after(&self, other: &Cursor) -> bool {
+        match (self, other) {
+            (Cursor::Timestamp(a), Cursor::Timestamp(b)) => a > b,
+            (Cursor::Offset(a), Cursor::Offset(b)) => a > b,
+            (Cursor::Token(a), Cursor::Token(b)) => a != b,
+            _ => false,
+        }
+    }
+}
+
+/// Cursor state per endpoint key, optionally persisted as a JSON file.
+#[derive(Debug, Default)]
+pub struct CursorStore {
+    cursors: Mutex<HashMap<String, Cursor>>,
+    path: Option<PathBuf>,
+}
+
+impl CursorStore {
+    /// Creates an in-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store persisted at `path`, loading existing cursors if present.
+    pub fn with_file(path: impl Into<PathBuf>) -> Result<Self, NgError> {
+        let path = path.into();
+        let cursors = if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| NgError::InternalError(format!("Cursor store read failed: {}", e)))?;
+            serde_json::from_str(&content)
+                .map_err(|e| NgError::InternalError(format!("Corrupt cursor store: {}", e)))?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self { cursors: Mutex::new(cursors), path: Some(path) })
+    }
+
+    /// Returns the stored cursor of an endpoint.
+    pub fn get(&self, key: &str) -> Option<Cursor> {
+        self.cursors.lock().unwrap_or_else(|e| e.into_inner()).get(key).cloned()
+    }
+
+    /// Stores the cursor of an endpoint and persists the store.
+    pub fn set(&self, key: &str, cursor: Cursor) -> Result<(), NgError> {
+        let mut cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        cursors.insert(key.to_string(), cursor);
+        self.persist(&cursors)
+    }
+
+    /// Forgets the cursor of an endpoint, so the next fetch starts from scratch.
+    pub fn clear(&self, key: &str) -> Result<(), NgError> {
+        let mut cursors = self.cursors.lock().unwrap_or_else(|e| e.into_inner());
+        cursors.remove(key);
+        self.persist(&cursors)
+    }
+
+    fn persist(&self, cursors: &HashMap<String, Cursor>) -> Result<(), NgError> {
+        let Some(path) = &self.path else { return Ok(()) };
+        let body = serde_json::to_string_pretty(cursors).map_err(|e| NgError::InternalError(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, body)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| NgError::InternalError(format!("Cursor store write failed: {}", e)))
+    }
+}
+
+/// Fetches rows of `key` newer than its stored cursor and advances the cursor.
+///
+/// `fetch` receives the stored cursor (or `None` on first run) and may use it to
+/// narrow the request. `cursor_of` extracts each row's cursor; rows without one
+/// are kept. The cursor is only advanced after a successful fetch, so a failed
+/// or interrupted run is retried from the same position.
+pub async fn fetch_new_since_last<T, F, Fut, C>(
+    store: &CursorStore,
+    key: &str,
+    fetch: F,
+    cursor_of: C,
+) -> Result<Vec<T>, NgError>
+where
+    F: FnOnce(Option<Cursor>) -> Fut,
+    Fut: Future<Output = Result<Vec<T>, NgError>>,
+    C: Fn(&T) -> Option<Cursor>,
+{
+    let last = store.get(key);
+    let rows = fetch(last.clone()).await?;
+
+    let fresh: Vec<T> = rows
+        .into_iter()
+        .filter(|row| match (&last, cursor_of(row)) {
+            (Some(last), Some(c)) => c.is_after(last),
+            _ => true,
+        })
+        .collect();
+
+    let newest = fresh.iter().filter_map(&cursor_of).fold(None, |acc: Option<Cursor>, c| match acc {
+        Some(a) if !c.is_after(&a) => Some(a),
+        _ => Some(c),
+    });
+    if let Some(c) = newest {
+        store.set(key, c)?;
+    }
+
+    Ok(fresh)
+}
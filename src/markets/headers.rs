@@ -0,0 +1,193 @@
+//! # Browser Header Profiles
+//!
+//! Browser fingerprints (user agent, client hints, accept-language) that the
+//! `NasdaqApi` and `CnnApi` adapters send with every request. CDN bot filters
+//! eventually flag any single frozen header set, so each adapter rotates
+//! through a [`HeaderRotation`]: when a response looks blocked (HTTP 403 or an
+//! "Access Denied" page), the adapter switches to the next profile and
+//! retries the request once.
+//!
+//! Profiles come from a [`HeaderProfileSource`]: [`BuiltinProfiles`] by
+//! default, or any other implementation (e.g. profiles loaded from
+//! configuration, since `HeaderProfile` is serde-compatible).
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::retrieve::ky_http::{ApiResponse, KyHttp};
+use crate::warn;
+
+/// One browser fingerprint: a named set of headers.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct HeaderProfile {
+    pub name: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl HeaderProfile {
+    /// An empty profile called `name`.
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), headers: Vec::new() }
+    }
+
+    /// Adds a header; later values for the same name win.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Inserts the profile's headers into `map`, replacing existing values.
+    /// Invalid names or values are skipped.
+    pub fn apply(&self, map: &mut HeaderMap) {
+        for (k, v) in &self.headers {
+            if let (Ok(name), Ok(value)) = (k.parse::<HeaderName>(), HeaderValue::from_str(v)) {
+                map.insert(name, value);
+            }
+        }
+    }
+}
+
+/// Supplies the profiles an adapter rotates through.
+pub trait HeaderProfileSource: Send + Sync {
+    fn profiles(&self) -> Vec<HeaderProfile>;
+}
+
+impl HeaderProfileSource for Vec<HeaderProfile> {
+    fn profiles(&self) -> Vec<HeaderProfile> {
+        self.clone()
+    }
+}
+
+/// Desktop Chrome, Edge, Firefox and Safari fingerprints compiled into the crate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinProfiles;
+
+impl HeaderProfileSource for BuiltinProfiles {
+    fn profiles(&self) -> Vec<HeaderProfile> {
+        let chromium = |name: &str, brand: &str, platform: &str, ua: &str| {
+            HeaderProfile::new(name)
+                .header("user-agent", ua)
+                .header("accept-language", "en-US,en;q=0.9")
+                .header("sec-ch-ua", brand)
+                .header("sec-ch-ua-mobile", "?0")
+                .header("sec-ch-ua-platform", platform)
+        };
+        vec![
+            chromium(
+                "chrome-windows",
+                r#""Google Chrome";v="131", "Chromium";v="131", "Not_A Brand";v="24""#,
+                "\"Windows\"",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36",
+            ),
+            chromium(
+                "chrome-macos",
+                r#""Google Chrome";v="131", "Chromium";v="131", "Not_A Brand";v="24""#,
+                "\"macOS\"",
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36",
+            ),
+            chromium(
+                "edge-windows",
+                r#""Microsoft Edge";v="131", "Chromium";v="131", "Not_A Brand";v="24""#,
+                "\"Windows\"",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36 Edg/131.0.0.0",
+            ),
+            // Firefox and Safari send no client hints.
+            HeaderProfile::new("firefox-windows")
+                .header("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:133.0) Gecko/20100101 Firefox/133.0")
+                .header("accept-language", "en-US,en;q=0.5"),
+            HeaderProfile::new("safari-macos")
+                .header("user-agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.1 Safari/605.1.15")
+                .header("accept-language", "en-US,en;q=0.9"),
+        ]
+    }
+}
+
+/// The profiles of one adapter and the one currently in use.
+#[derive(Debug)]
+pub struct HeaderRotation {
+    profiles: Vec<HeaderProfile>,
+    current: AtomicUsize,
+}
+
+impl Default for HeaderRotation {
+    fn default() -> Self {
+        Self::new(&BuiltinProfiles)
+    }
+}
+
+impl HeaderRotation {
+    /// Rotation over `source`'s profiles, starting with the first. An empty
+    /// source falls back to [`BuiltinProfiles`].
+    pub fn new(source: &dyn HeaderProfileSource) -> Self {
+        let mut profiles = source.profiles();
+        if profiles.is_empty() {
+            profiles = BuiltinProfiles.profiles();
+        }
+        Self { profiles, current: AtomicUsize::new(0) }
+    }
+
+    /// The profile in use.
+    pub fn current(&self) -> &HeaderProfile {
+        &self.profiles[self.index()]
+    }
+
+    /// All profiles, in rotation order.
+    pub fn profiles(&self) -> &[HeaderProfile] {
+        &self.profiles
+    }
+
+    /// Switches to the next profile and returns it.
+    pub fn rotate(&self) -> &HeaderProfile {
+        &self.profiles[self.rotate_after(self.index())]
+    }
+
+    fn index(&self) -> usize {
+        self.current.load(Ordering::Relaxed) % self.profiles.len()
+    }
+
+    /// Moves past profile `used`, unless another request already did; returns
+    /// the profile index now in use.
+    fn rotate_after(&self, used: usize) -> usize {
+        let next = (used + 1) % self.profiles.len();
+        match self.current.compare_exchange(used, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => next,
+            Err(now) => now % self.profiles.len(),
+        }
+    }
+
+    /// GETs `url` with `headers(profile)`. If the response looks blocked,
+    /// rotates to the next profile and retries once.
+    pub(crate) async fn get(
+        &self,
+        http: &KyHttp,
+        url: &str,
+        headers: impl Fn(&HeaderProfile) -> HeaderMap,
+        logger: &Logger,
+    ) -> Result<ApiResponse<Value>, NgError> {
+        let used = self.index();
+        let resp = http.get::<Value>(url, headers(&self.profiles[used])).await?;
+        if self.profiles.len() < 2 || !is_blocked(&resp) {
+            return Ok(resp);
+        }
+        let next = self.rotate_after(used);
+        warn!(
+            logger,
+            "Request looks blocked, rotating header profile",
+            "url" => url,
+            "status" => resp.status,
+            "profile" => self.profiles[next].name.as_str()
+        );
+        http.get::<Value>(url, headers(&self.profiles[next])).await
+    }
+}
+
+/// A 403, or an error page from the CDN's bot filter.
+fn is_blocked(resp: &ApiResponse<Value>) -> bool {
+    !resp.success
+        && (resp.status == 403
+            || resp.error_body.as_deref().is_some_and(|b| b.to_ascii_lowercase().contains("access denied")))
+}
@@ -0,0 +1,184 @@
+//! # Last-Good Response Cache
+//!
+//! Keeps the most recent successful payload of each endpoint on disk, so a
+//! failed fetch can fall back to it. Dashboards prefer a slightly old number,
+//! clearly marked as such, to a blank.
+//!
+//! [`LastGoodCache::fetch_or_stale`] wraps any service call: a success is
+//! stored and returned as fresh; on failure, the stored payload is returned
+//! with the error and its age, or the error itself if nothing usable is stored.
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::markets::lastgood::LastGoodCache;
+//! # use rs_lib_ng::markets::nasdaq::marketstatus::MarketStatus;
+//! # async fn run(status: MarketStatus, logger: rs_lib_ng::loggers::Logger) -> Result<(), rs_lib_ng::NgError> {
+//! let cache = LastGoodCache::new("/var/cache/markets", logger)?;
+//! let result = cache.fetch_or_stale("market-info", || status.fetch_status(None)).await?;
+//! if result.is_stale() {
+//!     println!("showing data from {} ago", status.format_duration(result.age()));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::warn;
+
+/// Stored form of one endpoint's payload.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    stored_at: DateTime<Utc>,
+    payload: Value,
+}
+
+/// A payload with the time it was fetched; `stale_error` is set when it was
+/// served from the cache because the live fetch failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastGood<T> {
+    pub value: T,
+    pub fetched_at: DateTime<Utc>,
+    pub stale_error: Option<String>,
+}
+
+impl<T> LastGood<T> {
+    /// Whether the live fetch failed and `value` came from the cache.
+    pub fn is_stale(&self) -> bool {
+        self.stale_error.is_some()
+    }
+
+    /// Time since `value` was fetched.
+    pub fn age(&self) -> ChronoDuration {
+        Utc::now().signed_duration_since(self.fetched_at)
+    }
+}
+
+/// Directory of last successful payloads, one JSON file per endpoint key.
+pub struct LastGoodCache {
+    dir: PathBuf,
+    max_age: Option<Duration>,
+    logger: Logger,
+}
+
+impl LastGoodCache {
+    /// Uses (and creates, if needed) `dir` for the cache files.
+    pub fn new(dir: impl Into<PathBuf>, logger: Logger) -> Result<Self, NgError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| NgError::InternalError(format!("Last-good cache dir '{}' unusable: {}", dir.display(), e)))?;
+        Ok(Self { dir, max_age: None, logger })
+    }
+
+    /// Never serves payloads older than `max_age`; by default any age is served.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// The cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Runs `fetch`. A success is stored under `key` and returned as fresh. A
+    /// failure returns the stored payload marked stale, or the fetch error
+    /// when nothing (young enough) is stored.
+    ///
+    /// Failing to write the cache is logged and does not fail the call.
+    pub async fn fetch_or_stale<T, F, Fut>(&self, key: &str, fetch: F) -> Result<LastGood<T>, NgError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, NgError>>,
+    {
+        match fetch().await {
+            Ok(value) => {
+                let fetched_at = Utc::now();
+                if let Err(e) = self.write(key, &value, fetched_at) {
+                    warn!(self.logger, "Last-good cache write failed", "key" => key, "error" => e.to_string());
+                }
+                Ok(LastGood { value, fetched_at, stale_error: None })
+            }
+            Err(error) => match self.load::<T>(key) {
+                Ok(Some(stored)) => {
+                    warn!(self.logger, "Fetch failed, serving last good payload", "key" => key, "error" => error.to_string(), "stored_at" => stored.fetched_at.to_rfc3339());
+                    Ok(LastGood { stale_error: Some(error.to_string()), ..stored })
+                }
+                Ok(None) => Err(error),
+                Err(e) => {
+                    warn!(self.logger, "Last-good cache unreadable", "key" => key, "error" => e.to_string());
+                    Err(error)
+                }
+            },
+        }
+    }
+
+    /// The stored payload of `key`, if any and not older than the maximum age.
+    pub fn load<T: DeserializeOwned>(&self, key: &str) -> Result<Option<LastGood<T>>, NgError> {
+        let path = self.path_of(key);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(NgError::InternalError(format!("Last-good cache read failed: {}", e))),
+        };
+        let entry: Entry = serde_json::from_str(&content)
+            .map_err(|e| NgError::InternalError(format!("Corrupt last-good cache entry '{}': {}", key, e)))?;
+        let too_old = self.max_age.is_some_and(|max| {
+            Utc::now().signed_duration_since(entry.stored_at).to_std().is_ok_and(|age| age > max)
+        });
+        if too_old {
+            return Ok(None);
+        }
+        let value = serde_json::from_value(entry.payload)
+            .map_err(|e| NgError::InternalError(format!("Last-good cache entry '{}' has another shape: {}", key, e)))?;
+        Ok(Some(LastGood { value, fetched_at: entry.stored_at, stale_error: None }))
+    }
+
+    /// Stores `value` as the last good payload of `key`, fetched now.
+    pub fn store<T: Serialize>(&self, key: &str, value: &T) -> Result<(), NgError> {
+        self.write(key, value, Utc::now())
+    }
+
+    /// Forgets the stored payload of `key`.
+    pub fn remove(&self, key: &str) -> Result<(), NgError> {
+        match std::fs::remove_file(self.path_of(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(NgError::InternalError(format!("Last-good cache remove failed: {}", e)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn write<T: Serialize>(&self, key: &str, value: &T, stored_at: DateTime<Utc>) -> Result<(), NgError> {
+        let payload = serde_json::to_value(value).map_err(|e| NgError::InternalError(e.to_string()))?;
+        let body = serde_json::to_string(&Entry { stored_at, payload }).map_err(|e| NgError::InternalError(e.to_string()))?;
+        let path = self.path_of(key);
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, body)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|e| NgError::InternalError(format!("Last-good cache write failed: {}", e)))
+    }
+
+    /// File of `key`; characters other than ASCII letters, digits, `-` and
+    /// `.` are hex-escaped, so distinct keys never share a file.
+    fn path_of(&self, key: &str) -> PathBuf {
+        let mut name = String::with_capacity(key.len() + 5);
+        for b in key.bytes() {
+            if b.is_ascii_alphanumeric() || b == b'-' || b == b'.' {
+                name.push(b as char);
+            } else {
+                name.push_str(&format!("_{:02x}", b));
+            }
+        }
+        name.push_str(".json");
+        self.dir.join(name)
+    }
+}
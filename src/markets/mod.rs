@@ -1,2 +1,7 @@
 pub mod nasdaq;
-pub mod cnn;
\ No newline at end of file
+pub mod cnn;
+pub mod cursors;
+pub mod headers;
+pub mod lastgood;
+pub mod sessions;
+pub mod snapshots;
@@ -4,52 +4,152 @@
 //! Nasdaq API endpoints, handling mandatory headers, and validating business-level status codes.
 
 use reqwest::header::{HeaderMap, HeaderValue, HeaderName};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use crate::retrieve::ky_http::{KyHttp, KyOptions};
-use crate::core::error::NgError;
+use std::sync::Arc;
+use crate::retrieve::ky_http::{KyHttp, KyOptions, RequestOverrides};
+use crate::retrieve::url_builder::UrlBuilder;
+use crate::core::error::{BCodeMessage, NgError};
 use crate::loggers::Logger; // Using the public re-export
-use crate::warn;
+use crate::markets::headers::{HeaderProfile, HeaderProfileSource, HeaderRotation};
+use crate::markets::nasdaq::{limits, parse};
+use crate::{error, warn};
+
+/// Root of the Nasdaq JSON API; endpoint paths are appended to it.
+pub const NASDAQ_API_BASE: &str = "https://api.nasdaq.com/api/";
+
+/// Asset class of a Nasdaq symbol, sent as the `assetclass` query parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetClass {
+    Stocks,
+    Etf,
+    MutualFunds,
+    Index,
+    Commodities,
+    Currencies,
+    FixedIncome,
+}
+
+impl AssetClass {
+    /// The value Nasdaq expects, e.g. `"stocks"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssetClass::Stocks => "stocks",
+            AssetClass::Etf => "etf",
+            AssetClass::MutualFunds => "mutualfunds",
+            AssetClass::Index => "index",
+            AssetClass::Commodities => "commodities",
+            AssetClass::Currencies => "currencies",
+            AssetClass::FixedIncome => "fixedincome",
+        }
+    }
+
+    /// Parses a Nasdaq asset label in any case, e.g. `"STOCKS"` or `"etf"`.
+    pub fn parse(label: &str) -> Option<Self> {
+        [
+            AssetClass::Stocks,
+            AssetClass::Etf,
+            AssetClass::MutualFunds,
+            AssetClass::Index,
+            AssetClass::Commodities,
+            AssetClass::Currencies,
+            AssetClass::FixedIncome,
+        ]
+        .into_iter()
+        .find(|class| class.as_str().eq_ignore_ascii_case(label.trim()))
+    }
+}
+
+impl std::fmt::Display for AssetClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Adapter for the Nasdaq API providing robust error handling and header management.
+#[derive(Clone)]
 pub struct NasdaqApi {
     /// Internal resilient HTTP client instance.
     http: KyHttp,
     /// Logger handle for structured diagnostic output.
     logger: Logger,
+    /// Base URL typed endpoints are built from.
+    base_url: String,
+    /// Browser fingerprints, rotated when requests get blocked; shared by clones.
+    profiles: Arc<HeaderRotation>,
 }
 
 impl NasdaqApi {
     /// Creates a new instance of `NasdaqApi`.
     ///
+    /// Requests draw on the process-wide budget of
+    /// [`NasdaqLimits`](crate::markets::nasdaq::limits::NasdaqLimits), shared
+    /// with every other adapter.
+    ///
     /// # Arguments
     ///
     /// * `logger` - A cloneable `Logger` instance used for all internal telemetry.
     pub fn new(logger: Logger) -> Self {
         Self {
-            http: KyHttp::new(logger.clone()),
+            http: KyHttp::new_with_opts(logger.clone(), Some(limits::apply(KyOptions::default()))),
             logger,
+            base_url: NASDAQ_API_BASE.to_string(),
+            profiles: Arc::new(HeaderRotation::default()),
         }
     }
 
-    /// Internal helper to construct the mandatory headers required for Nasdaq API requests.
-    fn get_nasdaq_headers(&self) -> HeaderMap {
+    /// Rotates through `source`'s browser profiles instead of the built-in ones.
+    pub fn with_header_profiles(mut self, source: &dyn HeaderProfileSource) -> Self {
+        self.profiles = Arc::new(HeaderRotation::new(source));
+        self
+    }
+
+    /// The browser profiles this adapter rotates through.
+    pub fn header_profiles(&self) -> &HeaderRotation {
+        &self.profiles
+    }
+
+    /// Points typed endpoints at another base URL (a mirror, or a mock server in tests).
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// The base URL typed endpoints are built from.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Starts an endpoint URL from the base URL and the given path segments.
+    pub fn endpoint<I, S>(&self, segments: I) -> Result<UrlBuilder, NgError>
+    where
+        I: IntoIterator<Item = S>,
+        S: std::fmt::Display,
+    {
+        Ok(UrlBuilder::parse(&self.base_url)?.segments(segments))
+    }
+
+    pub(crate) fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Internal helper to construct the mandatory headers required for Nasdaq API requests,
+    /// with the browser fingerprint of `profile`.
+    fn get_nasdaq_headers(profile: &HeaderProfile) -> HeaderMap {
         let mut h = HeaderMap::new();
         let headers = [
             ("authority", "api.nasdaq.com"),
             ("accept", "application/json, text/plain, */*"),
-            ("accept-language", "en-US,en;q=0.9,el-GR;q=0.8,el;q=0.7,it;q=0.6"),
             ("cache-control", "no-cache"),
             ("dnt", "1"),
             ("origin", "https://www.nasdaq.com"),
             ("pragma", "no-cache"),
             ("referer", "https://www.nasdaq.com/"),
-            ("sec-ch-ua", r#""Google Chrome";v="119", "Chromium";v="119", "Not?A_Brand";v="24""#),
-            ("sec-ch-ua-mobile", "?0"),
-            ("sec-ch-ua-platform", "\"Windows\""),
             ("sec-fetch-dest", "empty"),
             ("sec-fetch-mode", "cors"),
             ("sec-fetch-site", "same-site"),
-            ("user-agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Safari/537.36"),
         ];
 
         for (k, v) in headers {
@@ -57,6 +157,7 @@ impl NasdaqApi {
                 h.insert(name, value);
             }
         }
+        profile.apply(&mut h);
         h
     }
 
@@ -64,27 +165,87 @@ impl NasdaqApi {
     ///
     /// This method validates that the response is valid JSON and that the internal 
     /// `rCode` is 200. If an override for `KyOptions` is provided, a transient 
-    /// HTTP instance is created for that specific call.
+    /// HTTP instance sharing the client's connection pool is used for that call.
+    /// It keeps the process-wide Nasdaq limits unless the override sets its own
+    /// semaphore or rate limiter.
     ///
     /// # Arguments
     ///
     /// * `endpoint` - The full URL string for the Nasdaq endpoint.
     /// * `options` - Optional `KyOptions` to override global client settings.
     pub async fn call(&self, endpoint: &str, options: Option<KyOptions>) -> Result<Value, NgError> {
+        // Overrides get a transient instance that keeps the shared connection pool
+        match options {
+            Some(opts) => self.call_on(&self.http.with_options(limits::apply(opts))?, endpoint).await,
+            None => self.call_on(&self.http, endpoint).await,
+        }
+    }
+
+    /// Like [`call`](Self::call), returning only the `data` block of the response.
+    ///
+    /// A missing or `null` `data` block is reported as `MalformedResponse`.
+    pub async fn call_data(&self, endpoint: &str, options: Option<KyOptions>) -> Result<Value, NgError> {
+        let mut body = self.call(endpoint, options).await?;
+        match body.get_mut("data").map(Value::take) {
+            Some(data) if !data.is_null() => Ok(data),
+            _ => Err(NgError::MalformedResponse {
+                endpoint: endpoint.to_string(),
+                details: "Missing 'data' field".to_string(),
+            }),
+        }
+    }
+
+    /// Like [`call_data`](Self::call_data), deserializing the `data` block into `T`.
+    ///
+    /// A block of another shape is reported as `MalformedResponse` whose
+    /// details name the offending value, e.g. `JSON error at /rows/3/volume: ...`.
+    pub async fn call_typed<T: DeserializeOwned>(&self, endpoint: &str, options: Option<KyOptions>) -> Result<T, NgError> {
+        let data = self.call_data(endpoint, options).await?;
+        self.decode(endpoint, &data)
+    }
+
+    /// Deserializes `value`, a block of the `endpoint` response, into `T`.
+    pub(crate) fn decode<T: DeserializeOwned>(&self, endpoint: &str, value: &Value) -> Result<T, NgError> {
+        self.decode_at(endpoint, value, "")
+    }
+
+    /// Deserializes the row array at `pointer` of `data`; a missing or `null`
+    /// table (nothing to report) is empty.
+    pub(crate) fn decode_rows<T: DeserializeOwned>(&self, endpoint: &str, data: &Value, pointer: &str) -> Result<Vec<T>, NgError> {
+        match data.pointer(pointer) {
+            None | Some(Value::Null) => Ok(Vec::new()),
+            Some(rows) => self.decode_at(endpoint, rows, pointer),
+        }
+    }
+
+    /// Errors name the offending value's path, prefixed with `base`, the
+    /// pointer of `value` in its block.
+    fn decode_at<T: DeserializeOwned>(&self, endpoint: &str, value: &Value, base: &str) -> Result<T, NgError> {
+        parse::typed(value).map_err(|(path, e)| {
+            let path = format!("{}{}", base, path);
+            error!(self.logger, "Deserialization error in Nasdaq response", "url" => endpoint, "path" => path.as_str(), "error" => e.to_string());
+            let at = if path.is_empty() { String::new() } else { format!(" at {}", path) };
+            NgError::MalformedResponse {
+                endpoint: endpoint.to_string(),
+                details: format!("JSON error{}: {}", at, e),
+            }
+        })
+    }
+
+    /// Like [`call`](Self::call), with only the timeout, retry count or backoff
+    /// cap changed for this call; the shared client is used as-is otherwise.
+    pub async fn call_with(&self, endpoint: &str, overrides: &RequestOverrides) -> Result<Value, NgError> {
+        self.call_on(&self.http.with_overrides(overrides), endpoint).await
+    }
+
+    async fn call_on(&self, http: &KyHttp, endpoint: &str) -> Result<Value, NgError> {
         // Simple validation for the endpoint parameter
         if !endpoint.starts_with("http") {
             return Err(NgError::HttpError(format!("Invalid URL provided: {}", endpoint)));
         }
 
-        // Logic to handle transient options via the "new instance way" per company policy
-        let api_resp = if let Some(opts) = options {
-            // Fix: Wrapping opts in Some() to match KyHttp::new_with_opts signature
-            let transient_http = KyHttp::new_with_opts(self.logger.clone(), Some(opts));
-            transient_http.get::<Value>(endpoint, self.get_nasdaq_headers()).await?
-        } else {
-            // Use the shared persistent instance
-            self.http.get::<Value>(endpoint, self.get_nasdaq_headers()).await?
-        };
+        // A blocked response switches to the next browser profile and is retried once
+        let api_resp = self.profiles.get(http, endpoint, Self::get_nasdaq_headers, &self.logger).await?;
 
         // 1. Check for valid JSON content (success flag indicates parsing succeeded)
         if !api_resp.success {
@@ -131,9 +292,12 @@ impl NasdaqApi {
                     "context" => error_meta.to_string()
                 );
 
+                let messages = BCodeMessage::parse_list(&body["status"]["bCodeMessage"]);
+
                 Err(NgError::NasdaqBusinessError {
                     r_code: code,
                     endpoint: endpoint.to_string(),
+                    messages,
                     response: body,
                 })
             }
@@ -0,0 +1,332 @@
+//! # Nasdaq Market Calendar Module
+//!
+//! Exchange holidays and early-close days as a typed [`TradingCalendar`].
+//!
+//! [`MarketCalendar::fetch`] reads the holiday schedule from Nasdaq
+//! (`/api/market-info/holidays`). [`TradingCalendar::fallback`] is a table
+//! compiled into the crate, and [`MarketCalendar::fetch_or_fallback`] uses it
+//! whenever the API is unavailable, so session logic keeps working offline.
+//! Fetched entries take precedence over the table for the dates they cover.
+//!
+//! [`TradingCalendar::session_times`] derives a day's pre-market, regular and
+//! after-hours boundaries from the calendar alone, for use without the API.
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::US::Eastern;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use crate::markets::nasdaq::parse::date;
+use crate::retrieve::ky_http::KyOptions;
+use crate::warn;
+
+/// Regular close, Eastern Time.
+const REGULAR_CLOSE: (u32, u32) = (16, 0);
+
+/// Pre-market and regular open, Eastern Time.
+const PRE_MARKET_OPEN: (u32, u32) = (4, 0);
+const REGULAR_OPEN: (u32, u32) = (9, 30);
+
+/// After-hours close on a full day, Eastern Time.
+const AFTER_HOURS_CLOSE: (u32, u32) = (20, 0);
+
+/// Early close when the schedule names no time; it has been 1:00 p.m. for decades.
+const EARLY_CLOSE: (u32, u32) = (13, 0);
+
+/// `(year, month, day, name, early close (h, m) or None if closed)`.
+type FallbackDay = (i32, u32, u32, &'static str, Option<(u32, u32)>);
+
+/// Embedded schedule.
+const FALLBACK: &[FallbackDay] = &[
+    (2025, 1, 1, "New Year's Day", None),
+    (2025, 1, 9, "National Day of Mourning", None),
+    (2025, 1, 20, "Martin Luther King, Jr. Day", None),
+    (2025, 2, 17, "Washington's Birthday", None),
+    (2025, 4, 18, "Good Friday", None),
+    (2025, 5, 26, "Memorial Day", None),
+    (2025, 6, 19, "Juneteenth National Independence Day", None),
+    (2025, 7, 3, "Independence Day Eve", Some((13, 0))),
+    (2025, 7, 4, "Independence Day", None),
+    (2025, 9, 1, "Labor Day", None),
+    (2025, 11, 27, "Thanksgiving Day", None),
+    (2025, 11, 28, "Day After Thanksgiving", Some((13, 0))),
+    (2025, 12, 24, "Christmas Eve", Some((13, 0))),
+    (2025, 12, 25, "Christmas Day", None),
+    (2026, 1, 1, "New Year's Day", None),
+    (2026, 1, 19, "Martin Luther King, Jr. Day", None),
+    (2026, 2, 16, "Washington's Birthday", None),
+    (2026, 4, 3, "Good Friday", None),
+    (2026, 5, 25, "Memorial Day", None),
+    (2026, 6, 19, "Juneteenth National Independence Day", None),
+    (2026, 7, 3, "Independence Day (observed)", None),
+    (2026, 9, 7, "Labor Day", None),
+    (2026, 11, 26, "Thanksgiving Day", None),
+    (2026, 11, 27, "Day After Thanksgiving", Some((13, 0))),
+    (2026, 12, 24, "Christmas Eve", Some((13, 0))),
+    (2026, 12, 25, "Christmas Day", None),
+    (2027, 1, 1, "New Year's Day", None),
+    (2027, 1, 18, "Martin Luther King, Jr. Day", None),
+    (2027, 2, 15, "Washington's Birthday", None),
+    (2027, 3, 26, "Good Friday", None),
+    (2027, 5, 31, "Memorial Day", None),
+    (2027, 6, 18, "Juneteenth National Independence Day (observed)", None),
+    (2027, 7, 5, "Independence Day (observed)", None),
+    (2027, 9, 6, "Labor Day", None),
+    (2027, 11, 25, "Thanksgiving Day", None),
+    (2027, 11, 26, "Day After Thanksgiving", Some((13, 0))),
+    (2027, 12, 24, "Christmas Day (observed)", None),
+];
+
+/// How a listed day deviates from a regular session.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DayKind {
+    /// No trading.
+    Closed,
+    /// Trading ends early, at this Eastern Time.
+    EarlyClose(NaiveTime),
+}
+
+/// One holiday or early-close day.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MarketHoliday {
+    pub date: NaiveDate,
+    pub name: String,
+    pub kind: DayKind,
+}
+
+/// Session boundaries of one trading day.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SessionTimes {
+    pub pre_market_open: DateTime<Utc>,
+    pub regular_open: DateTime<Utc>,
+    pub regular_close: DateTime<Utc>,
+    pub after_hours_close: DateTime<Utc>,
+}
+
+/// A trading day; `early_close` is its close time (ET) if trading ends early.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TradingDay {
+    pub date: NaiveDate,
+    pub early_close: Option<NaiveTime>,
+}
+
+impl TradingDay {
+    /// Whether trading ends early on this day.
+    pub fn is_early_close(&self) -> bool {
+        self.early_close.is_some()
+    }
+}
+
+/// Trading days from `from` to `to` (both included) by the embedded schedule.
+/// Use [`TradingCalendar::trading_days`] for a fetched calendar.
+pub fn trading_days(from: NaiveDate, to: NaiveDate) -> Vec<TradingDay> {
+    TradingCalendar::fallback().trading_days(from, to).collect()
+}
+
+/// Holidays and early closes by date, with trading-day helpers.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct TradingCalendar {
+    days: BTreeMap<NaiveDate, MarketHoliday>,
+}
+
+impl TradingCalendar {
+    /// Builds a calendar from a list of days; later duplicates replace earlier ones.
+    pub fn from_days(days: impl IntoIterator<Item = MarketHoliday>) -> Self {
+        Self { days: days.into_iter().map(|d| (d.date, d)).collect() }
+    }
+
+    /// The schedule compiled into the crate.
+    pub fn fallback() -> Self {
+        Self::from_days(FALLBACK.iter().filter_map(|&(y, m, d, name, early)| {
+            let kind = match early {
+                None => DayKind::Closed,
+                Some((h, min)) => DayKind::EarlyClose(NaiveTime::from_hms_opt(h, min, 0)?),
+            };
+            Some(MarketHoliday { date: NaiveDate::from_ymd_opt(y, m, d)?, name: name.to_string(), kind })
+        }))
+    }
+
+    /// Adds `other`'s days, replacing entries for the same dates.
+    pub fn merge(&mut self, other: TradingCalendar) {
+        self.days.extend(other.days);
+    }
+
+    /// All listed days, in date order.
+    pub fn days(&self) -> impl Iterator<Item = &MarketHoliday> {
+        self.days.values()
+    }
+
+    /// Full-day closures, in date order.
+    pub fn holidays(&self) -> impl Iterator<Item = &MarketHoliday> {
+        self.days().filter(|d| d.kind == DayKind::Closed)
+    }
+
+    /// Early-close days, in date order.
+    pub fn early_closes(&self) -> impl Iterator<Item = &MarketHoliday> {
+        self.days().filter(|d| matches!(d.kind, DayKind::EarlyClose(_)))
+    }
+
+    /// The listed entry for `date`, if any.
+    pub fn day(&self, date: NaiveDate) -> Option<&MarketHoliday> {
+        self.days.get(&date)
+    }
+
+    /// Whether any day of `year` is listed; a calendar that does not cover a
+    /// year treats all its weekdays as regular sessions.
+    pub fn covers(&self, year: i32) -> bool {
+        self.days.keys().any(|d| d.year() == year)
+    }
+
+    /// Weekday that is not a full-day closure.
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+            && !matches!(self.day(date).map(|d| d.kind), Some(DayKind::Closed))
+    }
+
+    /// Regular or early close time of `date` (ET); `None` on non-trading days.
+    pub fn close_time(&self, date: NaiveDate) -> Option<NaiveTime> {
+        if !self.is_trading_day(date) {
+            return None;
+        }
+        match self.day(date).map(|d| d.kind) {
+            Some(DayKind::EarlyClose(at)) => Some(at),
+            _ => NaiveTime::from_hms_opt(REGULAR_CLOSE.0, REGULAR_CLOSE.1, 0),
+        }
+    }
+
+    /// Trading days from `from` to `to` (both included), in date order.
+    /// Weekends and closures are skipped; early closes are flagged.
+    pub fn trading_days(&self, from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = TradingDay> + '_ {
+        from.iter_days().take_while(move |d| *d <= to).filter(|d| self.is_trading_day(*d)).map(|date| TradingDay {
+            date,
+            early_close: match self.day(date).map(|d| d.kind) {
+                Some(DayKind::EarlyClose(at)) => Some(at),
+                _ => None,
+            },
+        })
+    }
+
+    /// Session boundaries of `date`; `None` on non-trading days. After hours
+    /// end at 8:00 p.m. ET, or an hour after an early close.
+    pub fn session_times(&self, date: NaiveDate) -> Option<SessionTimes> {
+        let close = self.close_time(date)?;
+        let at = |(h, m): (u32, u32)| NaiveTime::from_hms_opt(h, m, 0);
+        let regular_close = at(REGULAR_CLOSE)?;
+        let after_hours_close = if close < regular_close { close + ChronoDuration::hours(1) } else { at(AFTER_HOURS_CLOSE)? };
+        let local = |t: NaiveTime| Eastern.from_local_datetime(&date.and_time(t)).earliest().map(|t| t.with_timezone(&Utc));
+        Some(SessionTimes {
+            pre_market_open: local(at(PRE_MARKET_OPEN)?)?,
+            regular_open: local(at(REGULAR_OPEN)?)?,
+            regular_close: local(close)?,
+            after_hours_close: local(after_hours_close)?,
+        })
+    }
+
+    /// First trading day strictly after `date`.
+    pub fn next_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut d = date + ChronoDuration::days(1);
+        // There are never more than a handful of closed days in a row.
+        for _ in 0..14 {
+            if self.is_trading_day(d) {
+                break;
+            }
+            d += ChronoDuration::days(1);
+        }
+        d
+    }
+
+    /// Last trading day strictly before `date`.
+    pub fn previous_trading_day(&self, date: NaiveDate) -> NaiveDate {
+        let mut d = date - ChronoDuration::days(1);
+        for _ in 0..14 {
+            if self.is_trading_day(d) {
+                break;
+            }
+            d -= ChronoDuration::days(1);
+        }
+        d
+    }
+}
+
+/// Parses a schedule status such as `"Closed"` or `"Early Close 1:00 p.m."`.
+fn parse_kind(status: &str) -> Option<DayKind> {
+    let lower = status.trim().to_ascii_lowercase();
+    if lower.starts_with("closed") {
+        return Some(DayKind::Closed);
+    }
+    if !lower.contains("early") && !lower.contains("close") {
+        return None;
+    }
+    let Some(start) = lower.find(|c: char| c.is_ascii_digit()) else {
+        return NaiveTime::from_hms_opt(EARLY_CLOSE.0, EARLY_CLOSE.1, 0).map(DayKind::EarlyClose);
+    };
+    let clock: String = lower[start..].chars().take_while(|c| c.is_ascii_digit() || *c == ':').collect();
+    let (h, m) = clock.split_once(':').unwrap_or((&clock, "0"));
+    let mut hour: u32 = h.parse().ok()?;
+    let rest = lower[start + clock.len()..].replace('.', "");
+    if rest.trim_start().starts_with("pm") && hour < 12 {
+        hour += 12;
+    }
+    NaiveTime::from_hms_opt(hour, m.parse().ok()?, 0).map(DayKind::EarlyClose)
+}
+
+/// Service fetching the exchange holiday schedule from Nasdaq.
+pub struct MarketCalendar {
+    api: NasdaqApi,
+    logger: Logger,
+}
+
+impl MarketCalendar {
+    /// Creates a new instance of `MarketCalendar`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
+        Self { api, logger }
+    }
+
+    /// Fetches the published schedule. Rows whose date or status cannot be
+    /// parsed are skipped.
+    pub async fn fetch(&self, options: Option<KyOptions>) -> Result<TradingCalendar, NgError> {
+        let endpoint = self.api.endpoint(["market-info", "holidays"])?.build();
+        let data = self.api.call_data(&endpoint, options).await?;
+
+        let rows = data.get("rows").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+        let days: Vec<MarketHoliday> = rows
+            .iter()
+            .filter_map(|row| {
+                let text = |k: &str| row.get(k).and_then(Value::as_str);
+                Some(MarketHoliday {
+                    date: date(text("date")?)?,
+                    name: text("holiday").unwrap_or_default().trim().to_string(),
+                    kind: parse_kind(text("status")?)?,
+                })
+            })
+            .collect();
+        if days.is_empty() {
+            return Err(NgError::MalformedResponse {
+                endpoint,
+                details: "Holiday schedule has no usable rows".to_string(),
+            });
+        }
+        Ok(TradingCalendar::from_days(days))
+    }
+
+    /// The fallback table, overlaid with the published schedule when it can
+    /// be fetched. Never fails.
+    pub async fn fetch_or_fallback(&self, options: Option<KyOptions>) -> TradingCalendar {
+        let mut calendar = TradingCalendar::fallback();
+        match self.fetch(options).await {
+            Ok(fetched) => calendar.merge(fetched),
+            Err(e) => warn!(self.logger, "Holiday schedule unavailable, using embedded calendar", "error" => e.to_string()),
+        }
+        calendar
+    }
+}
@@ -0,0 +1,90 @@
+//! # Nasdaq Commodities Module
+//!
+//! Front-month futures quotes of common commodities (crude oil, natural gas,
+//! gold, silver, copper) via the quote endpoint with `assetclass=commodities`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::quote::Quote;
+use crate::retrieve::ky_http::KyOptions;
+
+/// A commodity future quoted by Nasdaq.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Commodity {
+    CrudeOil,
+    BrentCrude,
+    NaturalGas,
+    Gold,
+    Silver,
+    Copper,
+}
+
+impl Commodity {
+    /// All commodities, in display order.
+    pub const ALL: [Commodity; 6] = [
+        Commodity::CrudeOil,
+        Commodity::BrentCrude,
+        Commodity::NaturalGas,
+        Commodity::Gold,
+        Commodity::Silver,
+        Commodity::Copper,
+    ];
+
+    /// The Nasdaq symbol (contract root and exchange), e.g. `"CL:NMX"`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Commodity::CrudeOil => "CL:NMX",
+            Commodity::BrentCrude => "BZ:NMX",
+            Commodity::NaturalGas => "NG:NMX",
+            Commodity::Gold => "GC:CMX",
+            Commodity::Silver => "SI:CMX",
+            Commodity::Copper => "HG:CMX",
+        }
+    }
+}
+
+/// Latest price of a commodity future.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommodityQuote {
+    pub commodity: Commodity,
+    /// Name as reported, e.g. `"Crude Oil"`.
+    pub name: String,
+    pub price: Option<f64>,
+    pub net_change: Option<f64>,
+    pub percentage_change: Option<f64>,
+    /// As displayed by Nasdaq, e.g. `"Feb 23, 2026 5:00 PM ET"`.
+    pub last_trade_timestamp: String,
+}
+
+/// Service fetching commodity quotes from Nasdaq.
+pub struct Commodities {
+    quote: Quote,
+}
+
+impl Commodities {
+    /// Creates a new instance of `Commodities`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { quote: Quote::with_api(api) }
+    }
+
+    /// Latest price of `commodity`.
+    pub async fn fetch(&self, commodity: Commodity, options: Option<KyOptions>) -> Result<CommodityQuote, NgError> {
+        let data = self.quote.fetch(commodity.symbol(), AssetClass::Commodities, options).await?;
+        Ok(CommodityQuote {
+            commodity,
+            name: data.company_name,
+            price: data.primary_data.last_sale_price,
+            net_change: data.primary_data.net_change,
+            percentage_change: data.primary_data.percentage_change,
+            last_trade_timestamp: data.primary_data.last_trade_timestamp,
+        })
+    }
+}
@@ -0,0 +1,124 @@
+//! # Nasdaq Company Module
+//!
+//! Typed company profile of one symbol: who the company is (from
+//! `/api/company/{symbol}/company-profile`) and its key statistics (from
+//! `/api/quote/{symbol}/summary`).
+//!
+//! Both endpoints report each field as a `{ "label", "value" }` pair of
+//! display strings; [`Company`] reads the values and parses the numbers.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::{date, number};
+use crate::retrieve::ky_http::KyOptions;
+
+/// Key statistics from the quote summary. Percentages are in percent.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct KeyStats {
+    pub exchange: String,
+    pub market_cap: Option<f64>,
+    pub pe_ratio: Option<f64>,
+    pub forward_pe: Option<f64>,
+    pub earnings_per_share: Option<f64>,
+    pub annualized_dividend: Option<f64>,
+    pub dividend_yield: Option<f64>,
+    pub ex_dividend_date: Option<NaiveDate>,
+    pub beta: Option<f64>,
+    pub average_volume: Option<u64>,
+    pub one_year_target: Option<f64>,
+    pub fifty_two_week_high: Option<f64>,
+    pub fifty_two_week_low: Option<f64>,
+}
+
+/// Profile of one company. Fields Nasdaq does not report are empty.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CompanyProfile {
+    pub symbol: String,
+    pub name: String,
+    pub sector: String,
+    pub industry: String,
+    pub description: String,
+    pub website: String,
+    pub address: String,
+    pub phone: String,
+    pub key_stats: KeyStats,
+}
+
+/// Service fetching company profiles from Nasdaq.
+pub struct Company {
+    api: NasdaqApi,
+}
+
+impl Company {
+    /// Creates a new instance of `Company`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { api }
+    }
+
+    /// Profile and key statistics of `symbol`. The two endpoints are fetched
+    /// concurrently; either failing fails the call.
+    pub async fn fetch_profile(&self, symbol: &str, options: Option<KyOptions>) -> Result<CompanyProfile, NgError> {
+        let profile_endpoint = self.api.endpoint(["company", symbol, "company-profile"])?.build();
+        let summary_endpoint = self
+            .api
+            .endpoint(["quote", symbol, "summary"])?
+            .query("assetclass", AssetClass::Stocks)
+            .build();
+        let (profile, summary) = tokio::try_join!(
+            self.api.call_data(&profile_endpoint, options.clone()),
+            self.api.call_data(&summary_endpoint, options),
+        )?;
+
+        let text = |key: &str| labelled(&profile, key).unwrap_or_default().to_string();
+        let stat = |key: &str| labelled(&summary, &format!("summaryData/{}", key));
+        let stat_number = |key: &str| stat(key).and_then(number);
+        // e.g. "$288.62/$169.21"
+        let (high, low) = stat("FiftTwoWeekHighLow")
+            .and_then(|range| range.split_once('/'))
+            .map_or((None, None), |(h, l)| (number(h), number(l)));
+        let pick = |own: String, key: &str| if own.is_empty() { stat(key).unwrap_or_default().to_string() } else { own };
+
+        Ok(CompanyProfile {
+            symbol: pick(text("Symbol"), "Symbol").to_ascii_uppercase(),
+            name: text("CompanyName"),
+            sector: pick(text("Sector"), "Sector"),
+            industry: pick(text("Industry"), "Industry"),
+            description: text("CompanyDescription"),
+            website: text("CompanyUrl"),
+            address: text("Address"),
+            phone: text("Phone"),
+            key_stats: KeyStats {
+                exchange: stat("Exchange").unwrap_or_default().to_string(),
+                market_cap: stat_number("MarketCap"),
+                pe_ratio: stat_number("PERatio"),
+                forward_pe: stat_number("ForwardPE1Yr"),
+                earnings_per_share: stat_number("EarningsPerShare"),
+                annualized_dividend: stat_number("AnnualizedDividend"),
+                dividend_yield: stat_number("Yield"),
+                ex_dividend_date: stat("ExDividendDate").and_then(date),
+                beta: stat_number("Beta"),
+                average_volume: stat_number("AverageVolume").filter(|v| *v >= 0.0).map(|v| v as u64),
+                one_year_target: stat_number("OneYrTarget"),
+                fifty_two_week_high: high,
+                fifty_two_week_low: low,
+            },
+        })
+    }
+}
+
+/// The trimmed `value` of the labelled field at `path` (`/`-separated keys);
+/// `None` when missing or a placeholder such as `"N/A"`.
+fn labelled<'a>(data: &'a Value, path: &str) -> Option<&'a str> {
+    let value = data.pointer(&format!("/{}/value", path))?.as_str()?.trim();
+    (!matches!(value, "" | "N/A" | "--")).then_some(value)
+}
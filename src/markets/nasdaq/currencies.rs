@@ -0,0 +1,108 @@
+//! # Nasdaq Currencies Module
+//!
+//! Spot exchange rates such as EUR/USD via the quote endpoint with
+//! `assetclass=currencies`. Pairs are quoted under their six-letter symbol
+//! (`"EURUSD"`).
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::quote::Quote;
+use crate::retrieve::ky_http::KyOptions;
+
+/// A currency pair of two ISO 4217 codes, e.g. EUR/USD.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct CurrencyPair {
+    base: String,
+    quote: String,
+}
+
+impl CurrencyPair {
+    /// A pair of two three-letter codes in any case; anything else is a `ConfigError`.
+    pub fn new(base: &str, quote: &str) -> Result<Self, NgError> {
+        let code = |c: &str| {
+            let c = c.trim().to_ascii_uppercase();
+            if c.len() == 3 && c.bytes().all(|b| b.is_ascii_alphabetic()) {
+                Ok(c)
+            } else {
+                Err(NgError::ConfigError(format!("Invalid currency code '{}'", c)))
+            }
+        };
+        Ok(Self { base: code(base)?, quote: code(quote)? })
+    }
+
+    /// Parses a six-letter symbol such as `"EURUSD"` or `"eur/usd"`.
+    pub fn parse(symbol: &str) -> Result<Self, NgError> {
+        let compact: String = symbol.chars().filter(|c| !matches!(c, '/' | ' ')).collect();
+        match (compact.get(..3), compact.get(3..)) {
+            (Some(base), Some(quote)) => Self::new(base, quote),
+            _ => Err(NgError::ConfigError(format!("Invalid currency pair '{}'", symbol))),
+        }
+    }
+
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    pub fn quote(&self) -> &str {
+        &self.quote
+    }
+
+    /// The Nasdaq symbol, e.g. `"EURUSD"`.
+    pub fn symbol(&self) -> String {
+        format!("{}{}", self.base, self.quote)
+    }
+}
+
+impl std::fmt::Display for CurrencyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.base, self.quote)
+    }
+}
+
+/// Latest rate of a currency pair: units of `quote` per unit of `base`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CurrencyQuote {
+    pub pair: CurrencyPair,
+    pub rate: Option<f64>,
+    pub net_change: Option<f64>,
+    pub percentage_change: Option<f64>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    /// As displayed by Nasdaq, e.g. `"Feb 23, 2026 5:00 PM ET"`.
+    pub last_trade_timestamp: String,
+}
+
+/// Service fetching exchange rates from Nasdaq.
+pub struct Currencies {
+    quote: Quote,
+}
+
+impl Currencies {
+    /// Creates a new instance of `Currencies`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { quote: Quote::with_api(api) }
+    }
+
+    /// Latest rate of `pair`.
+    pub async fn fetch(&self, pair: &CurrencyPair, options: Option<KyOptions>) -> Result<CurrencyQuote, NgError> {
+        let data = self.quote.fetch(&pair.symbol(), AssetClass::Currencies, options).await?;
+        let detail = data.primary_data;
+        Ok(CurrencyQuote {
+            pair: pair.clone(),
+            rate: detail.last_sale_price,
+            net_change: detail.net_change,
+            percentage_change: detail.percentage_change,
+            bid: detail.bid_price,
+            ask: detail.ask_price,
+            last_trade_timestamp: detail.last_trade_timestamp,
+        })
+    }
+}
@@ -1 +1,2 @@
-pub mod yahoostreaming;
\ No newline at end of file
+pub mod pricing;
+pub mod yahoostreaming;
@@ -1,63 +1,444 @@
-use futures_util::{StreamExt, SinkExt};
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+//! # Yahoo Finance Streaming
+//!
+//! Live quotes from Yahoo's WebSocket streamer. Each text frame carries a
+//! base64-encoded protobuf [`PricingData`] message, either bare or, with
+//! `version=2`, inside a `{"type":"pricing","message":...}` envelope;
+//! [`YahooQuote::from_frame`] decodes both.
+//!
+//! [`YahooStreaming::quotes`] hands the updates out as a `Stream`, and
+//! [`YahooStreaming::stream_quotes`] sends them to an mpsc channel. Yahoo
+//! drops connections regularly; both reconnect with exponential backoff and
+//! subscribe again, so callers do not manage the connection lifecycle. Pings
+//! keep idle connections open, and a connection that goes silent during
+//! market hours is replaced. A `CancellationToken` stops the stream cleanly.
+
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::US::Eastern;
+use futures_util::{SinkExt, Stream, StreamExt};
+use prost::Message as _;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::time::Instant;
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream};
 use crate::loggers::Logger;
 use crate::core::error::NgError;
-use crate::error;
-use serde_json::json;
+use crate::markets::nasdaq::datafeeds::pricing::pricing_data::MarketHoursType;
+use crate::markets::nasdaq::calendar::TradingCalendar;
+use crate::markets::nasdaq::datafeeds::pricing::PricingData;
+use crate::retrieve::clock::{Clock, TokioClock};
+use crate::{error, info, warn};
+use serde_json::{json, Value};
 use base64::{Engine as _, engine::general_purpose};
 
+/// Yahoo's streamer endpoint.
+pub const STREAM_URL: &str = "wss://streamer.finance.yahoo.com/?version=2";
+
+/// Trading session a streamed quote belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarketHours {
+    PreMarket,
+    Regular,
+    PostMarket,
+    Extended,
+}
+
+impl From<MarketHoursType> for MarketHours {
+    fn from(raw: MarketHoursType) -> Self {
+        match raw {
+            MarketHoursType::PreMarket => MarketHours::PreMarket,
+            MarketHoursType::RegularMarket => MarketHours::Regular,
+            MarketHoursType::PostMarket => MarketHours::PostMarket,
+            MarketHoursType::ExtendedHoursMarket => MarketHours::Extended,
+        }
+    }
+}
+
+/// One streamed price update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YahooQuote {
+    pub symbol: String,
+    pub price: f64,
+    pub change: f64,
+    /// Percentage change from the previous close, e.g. `1.25` for +1.25%.
+    pub change_percent: f64,
+    pub day_volume: i64,
+    pub market_hours: MarketHours,
+    pub time: DateTime<Utc>,
+    /// Empty when Yahoo omits them, as it does for most updates after the first.
+    pub exchange: String,
+    pub currency: String,
+}
+
+impl YahooQuote {
+    /// Decodes one text frame of the stream.
+    pub fn from_frame(text: &str) -> Result<Self, NgError> {
+        let malformed = |details: String| NgError::MalformedResponse { endpoint: STREAM_URL.to_string(), details };
+        let payload = if text.trim_start().starts_with('{') {
+            let envelope: Value = serde_json::from_str(text).map_err(|e| malformed(format!("Invalid frame envelope: {}", e)))?;
+            envelope["message"]
+                .as_str()
+                .ok_or_else(|| malformed("Frame envelope without 'message'".to_string()))?
+                .to_string()
+        } else {
+            text.to_string()
+        };
+        let bytes = general_purpose::STANDARD
+            .decode(payload.trim())
+            .map_err(|e| malformed(format!("Invalid base64 frame: {}", e)))?;
+        let data = PricingData::decode(bytes.as_slice()).map_err(|e| malformed(format!("Invalid PricingData: {}", e)))?;
+        Ok(Self::from(data))
+    }
+}
+
+impl From<PricingData> for YahooQuote {
+    fn from(data: PricingData) -> Self {
+        Self {
+            price: widen(data.price),
+            change: widen(data.change),
+            change_percent: widen(data.change_percent),
+            day_volume: data.day_volume,
+            market_hours: data.market_hours().into(),
+            time: Utc.timestamp_millis_opt(data.time).single().unwrap_or(DateTime::UNIX_EPOCH),
+            symbol: data.id,
+            exchange: data.exchange,
+            currency: data.currency,
+        }
+    }
+}
+
+/// The `f64` closest to the decimal `value` was sent as, so `189.34f32`
+/// becomes `189.34` rather than `189.33999633789062`.
+fn widen(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(value as f64)
+}
+
+/// Receives decoded quotes, or the error of a frame that did not decode.
+pub type QuoteSender = mpsc::Sender<Result<YahooQuote, NgError>>;
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Quotes buffered for a slow `quotes()` consumer.
+const QUOTE_BUFFER: usize = 256;
+
+/// Why a connection ended without an error.
+enum Ended {
+    Server,
+    /// The consumer went away or the stream was cancelled.
+    Stopped,
+}
+
+/// Changes the symbols of a running stream. Cheap to clone; every clone
+/// controls the same stream.
+#[derive(Clone)]
+pub struct StreamHandle {
+    symbols: Arc<watch::Sender<BTreeSet<String>>>,
+}
+
+impl StreamHandle {
+    /// Adds `symbols`; a live connection subscribes to the new ones at once.
+    pub fn subscribe(&self, symbols: &[&str]) {
+        self.symbols.send_if_modified(|set| {
+            let before = set.len();
+            set.extend(symbols.iter().map(|s| s.to_string()));
+            set.len() != before
+        });
+    }
+
+    /// Removes `symbols`; a live connection unsubscribes from them at once.
+    pub fn unsubscribe(&self, symbols: &[&str]) {
+        self.symbols.send_if_modified(|set| {
+            let before = set.len();
+            set.retain(|s| !symbols.contains(&s.as_str()));
+            set.len() != before
+        });
+    }
+
+    /// The symbols streamed, sorted.
+    pub fn symbols(&self) -> Vec<String> {
+        self.symbols.borrow().iter().cloned().collect()
+    }
+}
+
+/// Connection to Yahoo's streamer, reconnecting whenever it drops.
 pub struct YahooStreaming {
     uri: String,
     logger: Logger,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_reconnects: Option<u32>,
+    reconnects: AtomicU64,
+    /// Current symbol set, shared with every `StreamHandle`.
+    symbols: Arc<watch::Sender<BTreeSet<String>>>,
+    heartbeat: Duration,
+    stale_timeout: Option<Duration>,
+    /// Time source deciding whether the market is open.
+    clock: Arc<dyn Clock>,
+    /// Stops the stream cleanly once cancelled.
+    cancellation: CancellationToken,
 }
 
 impl YahooStreaming {
     pub fn new(logger: Logger) -> Self {
         Self {
-            uri: "wss://streamer.finance.yahoo.com/?version=2".to_string(),
+            uri: STREAM_URL.to_string(),
             logger,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            max_reconnects: None,
+            reconnects: AtomicU64::new(0),
+            symbols: Arc::new(watch::channel(BTreeSet::new()).0),
+            heartbeat: Duration::from_secs(30),
+            stale_timeout: Some(Duration::from_secs(60)),
+            clock: Arc::new(TokioClock),
+            cancellation: CancellationToken::new(),
         }
     }
 
-    /// Connects to the stream and processes incoming price data
-    pub async fn stream_quotes(&self, symbols: Vec<&str>) -> Result<(), NgError> {
-        let (mut ws_stream, _) = connect_async(&self.uri).await
-            .map_err(|e| NgError::InternalError(format!("WS Connection Failed: {}", e)))?;
+    /// Connects to another streamer URL (default `STREAM_URL`), e.g. a local
+    /// server in tests.
+    pub fn with_uri(mut self, uri: &str) -> Self {
+        self.uri = uri.to_string();
+        self
+    }
+
+    /// Waits `initial` before the first reconnect, doubling up to `max` while
+    /// reconnects keep failing (defaults 1 second and 1 minute).
+    pub fn with_reconnect_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sends a WebSocket ping every `interval` (default 30 seconds), keeping
+    /// idle connections open. Pings from the server are answered
+    /// automatically.
+    pub fn with_heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Reconnects when no data frame arrives for `timeout` during market
+    /// hours (pre-market open to after-hours close on the embedded calendar);
+    /// `None` disables the check. Yahoo silently stops sending on dead
+    /// connections. Default 60 seconds.
+    pub fn with_stale_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.stale_timeout = timeout;
+        self
+    }
 
-        // Yahoo requires a JSON subscription message
-        let subscribe_msg = json!({ "subscribe": symbols }).to_string();
-        ws_stream.send(Message::Text(subscribe_msg)).await
-            .map_err(|e| NgError::InternalError(format!("Failed to send subscription: {}", e)))?;
+    /// Time source for the market hours check.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Stops streaming once `token` is cancelled, e.g. at application
+    /// shutdown: the connection is closed with a close frame and
+    /// [`stream_quotes`](Self::stream_quotes) returns `Ok`. Quotes already
+    /// sent stay in the channel for the consumer to drain.
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Gives up after `max` reconnects in a row without receiving data;
+    /// by default it never gives up.
+    pub fn with_max_reconnects(mut self, max: u32) -> Self {
+        self.max_reconnects = Some(max);
+        self
+    }
 
-        println!("📡 Yahoo WebSocket active for: {:?}", symbols);
+    /// Reconnects made since this streamer was created.
+    pub fn reconnect_count(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// A handle changing the symbols of this streamer's connection, also
+    /// while it runs. Reconnects subscribe to the current set.
+    pub fn handle(&self) -> StreamHandle {
+        StreamHandle { symbols: self.symbols.clone() }
+    }
+
+    /// Streams decoded quotes for `symbols`, as a `Stream` fed by a spawned
+    /// [`stream_quotes`](Self::stream_quotes). Undecodable frames are `Err`
+    /// items; the stream ends after the final error, should the streamer
+    /// give up reconnecting, or once cancelled. Dropping the stream stops
+    /// the task.
+    pub fn quotes(self: &Arc<Self>, symbols: Vec<&str>) -> impl Stream<Item = Result<YahooQuote, NgError>> + Send + 'static {
+        let (sender, receiver) = mpsc::channel(QUOTE_BUFFER);
+        self.handle().subscribe(&symbols);
+        let streamer = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = streamer.stream_quotes(Vec::new(), sender.clone()).await {
+                let _ = sender.send(Err(e)).await;
+            }
+        });
+        futures_util::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|item| (item, receiver))
+        })
+    }
+
+    /// Connects to the stream and sends each decoded quote to `sender`,
+    /// or the decoding error for frames that are not valid `PricingData`.
+    ///
+    /// When the server closes the connection or it fails, the streamer
+    /// reconnects after a backoff and subscribes again. `symbols` are added
+    /// to the streamer's set, which [`handle`](Self::handle) can change. The
+    /// backoff restarts once a connection delivers a data frame. Returns
+    /// `Ok` once the receiver is dropped or the cancellation token is
+    /// cancelled, after closing the connection.
+    ///
+    /// # Errors
+    /// Only once the maximum number of reconnects in a row is exceeded, with
+    /// the last connection error.
+    pub async fn stream_quotes(&self, symbols: Vec<&str>, sender: QuoteSender) -> Result<(), NgError> {
+        self.handle().subscribe(&symbols);
+        let mut failures = 0u32;
+        loop {
+            let mut received = false;
+            let reason = match self.run_connection(&sender, &mut received).await {
+                Ok(Ended::Stopped) => return Ok(()),
+                Ok(Ended::Server) => "closed by server".to_string(),
+                Err(e) => e.to_string(),
+            };
+            if received {
+                failures = 0;
+            }
+            if self.max_reconnects.is_some_and(|max| failures >= max) {
+                error!(self.logger, "Yahoo stream gave up reconnecting", "attempts" => failures, "error" => reason.as_str());
+                return Err(NgError::InternalError(format!(
+                    "Yahoo stream lost after {} reconnect attempts: {}",
+                    failures, reason
+                )));
+            }
 
-        while let Some(msg) = ws_stream.next().await {
+            let delay = self.backoff(failures);
+            failures += 1;
+            warn!(self.logger, "Yahoo stream lost, reconnecting", "reason" => reason.as_str(), "delay_ms" => delay.as_millis() as u64);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = sender.closed() => return Ok(()),
+                _ = self.cancellation.cancelled() => return Ok(()),
+            }
+            self.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// One connection: subscribes and forwards frames until the server
+    /// closes it, the consumer goes away or cancels, or it fails. Symbol set changes
+    /// are sent as they happen. `received` is set on the first data frame.
+    async fn run_connection(&self, sender: &QuoteSender, received: &mut bool) -> Result<Ended, NgError> {
+        let connected = tokio::select! {
+            connected = connect_async(&self.uri) => connected,
+            _ = self.cancellation.cancelled() => return Ok(Ended::Stopped),
+        };
+        let (mut ws_stream, _) = connected
+            .map_err(|e| NgError::InternalError(format!("WS Connection Failed: {}", e)))?;
+
+        let mut watched = self.symbols.subscribe();
+        let mut subscribed = BTreeSet::new();
+        let wanted = watched.borrow_and_update().clone();
+        self.sync_symbols(&mut ws_stream, &mut subscribed, wanted).await?;
+        info!(self.logger, "Yahoo WebSocket active", "symbols" => subscribed.iter().cloned().collect::<Vec<_>>().join(","));
+
+        let mut heartbeat = tokio::time::interval_at(Instant::now() + self.heartbeat, self.heartbeat);
+        let mut last_data = Instant::now();
+        loop {
+            let stale_at = self.stale_timeout.map(|timeout| last_data + timeout);
+            let msg = tokio::select! {
+                msg = ws_stream.next() => msg,
+                _ = sender.closed() => None,
+                _ = self.cancellation.cancelled() => None,
+                _ = heartbeat.tick() => {
+                    ws_stream.send(Message::Ping(Vec::new())).await
+                        .map_err(|e| NgError::InternalError(format!("Failed to send ping: {}", e)))?;
+                    continue;
+                }
+                _ = tokio::time::sleep_until(stale_at.unwrap_or_else(Instant::now)), if stale_at.is_some() => {
+                    let silent = last_data.elapsed();
+                    if self.in_market_hours() {
+                        warn!(self.logger, "Yahoo stream stale, forcing reconnect", "silent_ms" => silent.as_millis() as u64);
+                        return Err(NgError::InternalError(format!("No data for {} ms", silent.as_millis())));
+                    }
+                    // Quiet is normal while the market is closed.
+                    last_data = Instant::now();
+                    continue;
+                }
+                Ok(()) = watched.changed() => {
+                    let wanted = watched.borrow_and_update().clone();
+                    self.sync_symbols(&mut ws_stream, &mut subscribed, wanted).await?;
+                    continue;
+                }
+            };
             match msg {
-                Ok(Message::Text(text)) => {
-                    // 1. Decode Base64
-                    if let Ok(bin_data) = general_purpose::STANDARD.decode(&text) {
-                        // 2. Map to Protobuf (PricingData)
-                        // This assumes you have the generated prost code in your crate
-                        self.handle_proto_data(bin_data);
+                Some(Ok(Message::Text(text))) => {
+                    *received = true;
+                    last_data = Instant::now();
+                    let quote = YahooQuote::from_frame(&text);
+                    if let Err(e) = &quote {
+                        warn!(self.logger, "Undecodable Yahoo frame", "error" => e.to_string());
+                    }
+                    if sender.send(quote).await.is_err() {
+                        break;
                     }
                 }
-                Ok(Message::Close(frame)) => {
-                    println!("🚪 Connection closed by server: {:?}", frame);
-                    break;
+                Some(Ok(Message::Close(frame))) => {
+                    info!(self.logger, "Yahoo stream closed by server", "frame" => format!("{:?}", frame));
+                    return Ok(Ended::Server);
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     error!(self.logger, "WS Stream Error", "error" => e.to_string());
                     return Err(NgError::InternalError(e.to_string()));
                 }
-                _ => {}
+                Some(Ok(_)) => {}
+                None if sender.is_closed() || self.cancellation.is_cancelled() => break,
+                None => return Ok(Ended::Server),
             }
         }
+        // Closing flushes pending frames; failing to is of no consequence now.
+        let _ = ws_stream.close(None).await;
+        info!(self.logger, "Yahoo stream stopped");
+        Ok(Ended::Stopped)
+    }
+
+    /// Subscribes to the symbols of `wanted` missing from `subscribed` and
+    /// unsubscribes from those no longer wanted.
+    async fn sync_symbols(&self, ws_stream: &mut WsStream, subscribed: &mut BTreeSet<String>, wanted: BTreeSet<String>) -> Result<(), NgError> {
+        let added: Vec<&String> = wanted.difference(subscribed).collect();
+        let removed: Vec<&String> = subscribed.difference(&wanted).collect();
+        // Yahoo takes JSON subscription messages
+        for (action, symbols) in [("subscribe", added), ("unsubscribe", removed)] {
+            if symbols.is_empty() {
+                continue;
+            }
+            ws_stream.send(Message::Text(json!({ action: symbols }).to_string())).await
+                .map_err(|e| NgError::InternalError(format!("Failed to send {}: {}", action, e)))?;
+            info!(self.logger, "Yahoo stream symbols changed", "action" => action, "symbols" => symbols.len());
+        }
+        *subscribed = wanted;
         Ok(())
     }
 
-    fn handle_proto_data(&self, _data: Vec<u8>) {
-        // Here you would call: PricingData::decode(&data[..])
-        // For now, we log the receipt of binary packets
-        println!("📦 Received binary update ({} bytes)", _data.len());
+    /// Whether "now" is between pre-market open and after-hours close.
+    fn in_market_hours(&self) -> bool {
+        let now = self.clock.now();
+        TradingCalendar::fallback()
+            .session_times(now.with_timezone(&Eastern).date_naive())
+            .is_some_and(|t| t.pre_market_open <= now && now < t.after_hours_close)
+    }
+
+    /// Wait before reconnect number `failures` (0-based) in a row, plus up
+    /// to 10% jitter.
+    fn backoff(&self, failures: u32) -> Duration {
+        let base = self.initial_backoff.saturating_mul(2u32.saturating_pow(failures.min(16))).min(self.max_backoff);
+        let max_jitter = (base / 10).as_millis() as u64;
+        base + Duration::from_millis(rand::thread_rng().gen_range(0..=max_jitter))
     }
 }
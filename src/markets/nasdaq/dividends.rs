@@ -0,0 +1,140 @@
+//! # Nasdaq Dividends Module
+//!
+//! Dividend history of one symbol (`/api/quote/{symbol}/dividends`) and the
+//! market-wide dividend calendar of one day (`/api/calendar/dividends`), as
+//! typed events with `chrono` dates. Dates Nasdaq shows as `"N/A"` are `None`.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::{de_date, de_number, de_string};
+use crate::retrieve::ky_http::KyOptions;
+
+/// One dividend, from either the history or the calendar endpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct DividendEvent {
+    pub symbol: String,
+    /// Only set by the calendar endpoint.
+    pub company_name: String,
+    pub ex_date: Option<NaiveDate>,
+    pub record_date: Option<NaiveDate>,
+    pub payment_date: Option<NaiveDate>,
+    pub declaration_date: Option<NaiveDate>,
+    /// Amount per share.
+    pub amount: Option<f64>,
+    /// e.g. `"Cash"`; only set by the history endpoint.
+    pub kind: String,
+    /// Only set by the history endpoint.
+    pub currency: String,
+}
+
+/// `data.dividends.rows[]` of the history endpoint.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryRow {
+    #[serde(default, deserialize_with = "de_date")]
+    ex_or_eff_date: Option<NaiveDate>,
+    #[serde(default, rename = "type", deserialize_with = "de_string")]
+    kind: String,
+    #[serde(default, deserialize_with = "de_number")]
+    amount: Option<f64>,
+    #[serde(default, deserialize_with = "de_date")]
+    declaration_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "de_date")]
+    record_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "de_date")]
+    payment_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "de_string")]
+    currency: String,
+}
+
+/// `data.calendar.rows[]` of the calendar endpoint.
+#[derive(Deserialize)]
+struct CalendarRow {
+    #[serde(default, deserialize_with = "de_string")]
+    symbol: String,
+    #[serde(default, rename = "companyName", deserialize_with = "de_string")]
+    company_name: String,
+    #[serde(default, rename = "dividend_Ex_Date", deserialize_with = "de_date")]
+    ex_date: Option<NaiveDate>,
+    #[serde(default, rename = "record_Date", deserialize_with = "de_date")]
+    record_date: Option<NaiveDate>,
+    #[serde(default, rename = "payment_Date", deserialize_with = "de_date")]
+    payment_date: Option<NaiveDate>,
+    #[serde(default, rename = "announcement_Date", deserialize_with = "de_date")]
+    announcement_date: Option<NaiveDate>,
+    #[serde(default, rename = "dividend_Rate", deserialize_with = "de_number")]
+    rate: Option<f64>,
+}
+
+/// Service fetching dividend data from Nasdaq.
+pub struct Dividends {
+    api: NasdaqApi,
+}
+
+impl Dividends {
+    /// Creates a new instance of `Dividends`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { api }
+    }
+
+    /// Past and announced dividends of `symbol`, newest first.
+    pub async fn fetch_history(&self, symbol: &str, asset_class: AssetClass, options: Option<KyOptions>) -> Result<Vec<DividendEvent>, NgError> {
+        let endpoint = self
+            .api
+            .endpoint(["quote", symbol, "dividends"])?
+            .query("assetclass", asset_class)
+            .build();
+        let data = self.api.call_data(&endpoint, options).await?;
+        let rows: Vec<HistoryRow> = self.api.decode_rows(&endpoint, &data, "/dividends/rows")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| DividendEvent {
+                symbol: symbol.to_string(),
+                company_name: String::new(),
+                ex_date: r.ex_or_eff_date,
+                record_date: r.record_date,
+                payment_date: r.payment_date,
+                declaration_date: r.declaration_date,
+                amount: r.amount,
+                kind: r.kind,
+                currency: r.currency,
+            })
+            .collect())
+    }
+
+    /// Dividends going ex on `date`, across all symbols.
+    pub async fn fetch_calendar(&self, date: NaiveDate, options: Option<KyOptions>) -> Result<Vec<DividendEvent>, NgError> {
+        let endpoint = self
+            .api
+            .endpoint(["calendar", "dividends"])?
+            .query("date", date.format("%Y-%m-%d"))
+            .build();
+        let data = self.api.call_data(&endpoint, options).await?;
+        let rows: Vec<CalendarRow> = self.api.decode_rows(&endpoint, &data, "/calendar/rows")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| DividendEvent {
+                symbol: r.symbol,
+                company_name: r.company_name,
+                ex_date: r.ex_date,
+                record_date: r.record_date,
+                payment_date: r.payment_date,
+                declaration_date: r.announcement_date,
+                amount: r.rate,
+                kind: String::new(),
+                currency: String::new(),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,82 @@
+//! # Nasdaq Earnings Module
+//!
+//! Typed earnings surprise history of one company from
+//! `/api/company/{symbol}/earnings-surprise`: reported EPS against the
+//! consensus forecast for the last fiscal quarters, newest first.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use crate::markets::nasdaq::parse::{de_date, de_number, de_string};
+use crate::retrieve::ky_http::KyOptions;
+
+/// One reported quarter. Nasdaq field names are accepted on input; the field
+/// names below are used on output and accepted as well.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EarningsSurprise {
+    /// Fiscal quarter as labelled by Nasdaq, e.g. `"Dec 2025"`.
+    #[serde(rename(deserialize = "fiscalQtrEnd"), alias = "fiscalQuarterEnd", deserialize_with = "de_string")]
+    pub fiscal_quarter_end: String,
+    #[serde(deserialize_with = "de_date")]
+    pub date_reported: Option<NaiveDate>,
+    /// Reported EPS.
+    #[serde(rename(deserialize = "eps"), alias = "actualEps", deserialize_with = "de_number")]
+    pub actual_eps: Option<f64>,
+    #[serde(rename(deserialize = "consensusForecast"), alias = "estimatedEps", deserialize_with = "de_number")]
+    pub estimated_eps: Option<f64>,
+    /// `(actual - estimate) / |estimate|`, in percent.
+    #[serde(rename(deserialize = "percentageSurprise"), alias = "surprisePercent", deserialize_with = "de_number")]
+    pub surprise_percent: Option<f64>,
+}
+
+impl EarningsSurprise {
+    /// Reported minus estimated EPS.
+    pub fn surprise(&self) -> Option<f64> {
+        Some(self.actual_eps? - self.estimated_eps?)
+    }
+
+    /// Whether the company beat the consensus forecast.
+    pub fn is_beat(&self) -> Option<bool> {
+        self.surprise().map(|s| s > 0.0)
+    }
+}
+
+/// Service fetching earnings data from Nasdaq.
+pub struct Earnings {
+    api: NasdaqApi,
+}
+
+impl Earnings {
+    /// Creates a new instance of `Earnings`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { api }
+    }
+
+    /// EPS surprises of `symbol`, newest quarter first. A company without
+    /// reported quarters returns an empty list. When Nasdaq omits the
+    /// surprise percentage, it is computed from the two EPS values.
+    pub async fn fetch_surprises(&self, symbol: &str, options: Option<KyOptions>) -> Result<Vec<EarningsSurprise>, NgError> {
+        let endpoint = self.api.endpoint(["company", symbol, "earnings-surprise"])?.build();
+        let data = self.api.call_data(&endpoint, options).await?;
+
+        let mut rows: Vec<EarningsSurprise> = self.api.decode_rows(&endpoint, &data, "/earningsSurpriseTable/rows")?;
+        for row in &mut rows {
+            if row.surprise_percent.is_none()
+                && let (Some(surprise), Some(estimate)) = (row.surprise(), row.estimated_eps)
+                && estimate != 0.0
+            {
+                row.surprise_percent = Some(surprise / estimate.abs() * 100.0);
+            }
+        }
+        Ok(rows)
+    }
+}
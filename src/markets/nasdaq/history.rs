@@ -0,0 +1,279 @@
+//! # Nasdaq History Module
+//!
+//! Typed OHLCV candles for a symbol.
+//!
+//! Daily, weekly and monthly candles come from `/api/quote/{symbol}/historical`,
+//! which is fetched page by page (`offset` / `limit`) until the whole range is
+//! covered; weekly and monthly candles are aggregated from the daily rows.
+//! Minute candles come from `/api/quote/{symbol}/chart`, which only covers the
+//! latest session, and are aggregated from its trade points.
+//!
+//! Nasdaq reports times as Eastern Time wall clock (chart points are ET
+//! wall-clock milliseconds, not real epoch values); all candles carry UTC
+//! timestamps. Daily candles are stamped with the 09:30 ET open of their day.
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::US::Eastern;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::number;
+use crate::retrieve::ky_http::KyOptions;
+use crate::{debug, warn};
+
+/// Rows requested per page of the historical endpoint.
+pub const HISTORY_PAGE_SIZE: usize = 500;
+
+/// One OHLCV candle.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Candle {
+    /// Start of the candle, in UTC.
+    pub time: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: u64,
+}
+
+/// Period covered by a chart, ending today (Eastern Time) unless explicit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartRange {
+    /// The latest session only.
+    Intraday,
+    Days(u32),
+    Months(u32),
+    Years(u32),
+    YearToDate,
+    /// Inclusive ET dates.
+    Between { from: NaiveDate, to: NaiveDate },
+}
+
+impl ChartRange {
+    /// Inclusive `(from, to)` ET dates of the range as seen on `today`.
+    pub fn dates(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let back = |months: u32| today.checked_sub_months(Months::new(months)).unwrap_or(NaiveDate::MIN);
+        match *self {
+            ChartRange::Intraday => (today, today),
+            ChartRange::Days(n) => (today - chrono::Duration::days(i64::from(n)), today),
+            ChartRange::Months(n) => (back(n), today),
+            ChartRange::Years(n) => (back(n.saturating_mul(12)), today),
+            ChartRange::YearToDate => (NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(today), today),
+            ChartRange::Between { from, to } => (from, to),
+        }
+    }
+}
+
+/// Width of one candle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartInterval {
+    /// `n`-minute candles; only valid with [`ChartRange::Intraday`].
+    Minutes(u32),
+    Daily,
+    /// ISO weeks.
+    Weekly,
+    /// Calendar months.
+    Monthly,
+}
+
+/// Service fetching historical candles from Nasdaq.
+pub struct History {
+    api: NasdaqApi,
+    logger: Logger,
+    page_size: usize,
+}
+
+impl History {
+    /// Creates a new instance of `History`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
+        Self { api, logger, page_size: HISTORY_PAGE_SIZE }
+    }
+
+    /// Overrides the rows requested per page (at least 1).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Fetches the candles of `symbol` over `range`, oldest first.
+    ///
+    /// `ChartInterval::Minutes` requires `ChartRange::Intraday`; other
+    /// combinations of a minute interval are rejected with `ConfigError`.
+    pub async fn fetch_chart(
+        &self,
+        symbol: &str,
+        asset_class: AssetClass,
+        range: ChartRange,
+        interval: ChartInterval,
+        options: Option<KyOptions>,
+    ) -> Result<Vec<Candle>, NgError> {
+        match interval {
+            ChartInterval::Minutes(0) => Err(NgError::ConfigError("Chart interval must be at least one minute".into())),
+            ChartInterval::Minutes(n) if range == ChartRange::Intraday => {
+                let points = self.fetch_intraday(symbol, asset_class, options).await?;
+                Ok(aggregate_minutes(&points, n))
+            }
+            ChartInterval::Minutes(_) => Err(NgError::ConfigError(
+                "Minute candles are only available for ChartRange::Intraday".into(),
+            )),
+            ChartInterval::Daily | ChartInterval::Weekly | ChartInterval::Monthly => {
+                let today = Utc::now().with_timezone(&Eastern).date_naive();
+                let (from, to) = range.dates(today);
+                let daily = self.fetch_daily(symbol, asset_class, from, to, options).await?;
+                Ok(match interval {
+                    ChartInterval::Weekly => aggregate(&daily, |d| {
+                        let week = d.iso_week();
+                        (week.year(), week.week())
+                    }),
+                    ChartInterval::Monthly => aggregate(&daily, |d| (d.year(), d.month())),
+                    _ => daily.into_iter().map(|(_, c)| c).collect(),
+                })
+            }
+        }
+    }
+
+    /// Daily candles between `from` and `to`, walking every page.
+    async fn fetch_daily(
+        &self,
+        symbol: &str,
+        asset_class: AssetClass,
+        from: NaiveDate,
+        to: NaiveDate,
+        options: Option<KyOptions>,
+    ) -> Result<Vec<(NaiveDate, Candle)>, NgError> {
+        let mut candles = Vec::new();
+        let mut offset = 0usize;
+        loop {
+            let endpoint = self
+                .api
+                .endpoint(["quote", symbol, "historical"])?
+                .query("assetclass", asset_class)
+                .query("fromdate", from.format("%Y-%m-%d"))
+                .query("todate", to.format("%Y-%m-%d"))
+                .query("offset", offset)
+                .query("limit", self.page_size)
+                .build();
+            let data = self.api.call_data(&endpoint, options.clone()).await?;
+
+            let rows = data.pointer("/tradesTable/rows").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+            let total = data.get("totalRecords").and_then(Value::as_u64).map(|t| t as usize);
+            for row in rows {
+                match daily_row(row) {
+                    Some(candle) => candles.push(candle),
+                    None => warn!(self.logger, "Skipping unparsable Nasdaq history row", "symbol" => symbol, "row" => row.to_string()),
+                }
+            }
+            offset += rows.len();
+            debug!(self.logger, "Fetched Nasdaq history page", "symbol" => symbol, "rows" => rows.len(), "offset" => offset);
+
+            if rows.len() < self.page_size || total.is_some_and(|t| offset >= t) {
+                break;
+            }
+        }
+        // Nasdaq lists the newest day first; overlapping pages may repeat a day.
+        candles.sort_by_key(|(date, _)| *date);
+        candles.dedup_by_key(|(date, _)| *date);
+        Ok(candles)
+    }
+
+    /// Trade points of the latest session as `(ET wall clock, price, shares)`.
+    pub(crate) async fn fetch_intraday(
+        &self,
+        symbol: &str,
+        asset_class: AssetClass,
+        options: Option<KyOptions>,
+    ) -> Result<Vec<(NaiveDateTime, f64, u64)>, NgError> {
+        let endpoint = self
+            .api
+            .endpoint(["quote", symbol, "chart"])?
+            .query("assetclass", asset_class)
+            .build();
+        let data = self.api.call_data(&endpoint, options).await?;
+
+        let mut points: Vec<_> = data
+            .get("chart")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|p| {
+                let wall = DateTime::from_timestamp_millis(p.get("x")?.as_i64()?)?.naive_utc();
+                let price = p.get("y")?.as_f64()?;
+                let shares = p.pointer("/z/shares").and_then(Value::as_str).and_then(number).unwrap_or(0.0);
+                Some((wall, price, shares.max(0.0) as u64))
+            })
+            .collect();
+        points.sort_by_key(|(wall, _, _)| *wall);
+        Ok(points)
+    }
+}
+
+/// An ET wall-clock time as UTC; the earlier instant when ambiguous (DST fall-back).
+pub(crate) fn eastern_to_utc(wall: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Eastern.from_local_datetime(&wall).earliest().map(|t| t.with_timezone(&Utc))
+}
+
+/// One `tradesTable` row, e.g. `{"date": "02/23/2026", "open": "$189.10", ...}`.
+fn daily_row(row: &Value) -> Option<(NaiveDate, Candle)> {
+    let field = |k: &str| row.get(k).and_then(Value::as_str).and_then(number);
+    let date = NaiveDate::parse_from_str(row.get("date")?.as_str()?.trim(), "%m/%d/%Y").ok()?;
+    let open_at = NaiveTime::from_hms_opt(9, 30, 0)?;
+    let candle = Candle {
+        time: eastern_to_utc(date.and_time(open_at))?,
+        open: field("open")?,
+        high: field("high")?,
+        low: field("low")?,
+        close: field("close")?,
+        volume: field("volume").map(|v| v.max(0.0) as u64).unwrap_or(0),
+    };
+    Some((date, candle))
+}
+
+/// Merges consecutive daily candles that share `key`.
+fn aggregate<K: PartialEq>(daily: &[(NaiveDate, Candle)], key: impl Fn(NaiveDate) -> K) -> Vec<Candle> {
+    let mut out: Vec<(K, Candle)> = Vec::new();
+    for (date, candle) in daily {
+        let k = key(*date);
+        match out.last_mut() {
+            Some((last, merged)) if *last == k => merge(merged, candle),
+            _ => out.push((k, candle.clone())),
+        }
+    }
+    out.into_iter().map(|(_, c)| c).collect()
+}
+
+fn merge(into: &mut Candle, next: &Candle) {
+    into.high = into.high.max(next.high);
+    into.low = into.low.min(next.low);
+    into.close = next.close;
+    into.volume += next.volume;
+}
+
+/// Buckets sorted trade points into `minutes`-wide candles.
+fn aggregate_minutes(points: &[(NaiveDateTime, f64, u64)], minutes: u32) -> Vec<Candle> {
+    let width = i64::from(minutes) * 60;
+    let mut out: Vec<(i64, Candle)> = Vec::new();
+    for (wall, price, shares) in points {
+        let bucket = wall.and_utc().timestamp().div_euclid(width) * width;
+        let point = Candle { time: DateTime::<Utc>::MIN_UTC, open: *price, high: *price, low: *price, close: *price, volume: *shares };
+        match out.last_mut() {
+            Some((last, merged)) if *last == bucket => merge(merged, &point),
+            _ => {
+                let Some(time) = DateTime::from_timestamp(bucket, 0).and_then(|t| eastern_to_utc(t.naive_utc())) else {
+                    continue;
+                };
+                out.push((bucket, Candle { time, ..point }));
+            }
+        }
+    }
+    out.into_iter().map(|(_, c)| c).collect()
+}
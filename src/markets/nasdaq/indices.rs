@@ -0,0 +1,124 @@
+//! # Nasdaq Index Module
+//!
+//! Quotes and intraday values of the major US indices (Nasdaq-100, Nasdaq
+//! Composite, S&P 500, Dow Jones Industrial Average and Russell 2000).
+//!
+//! Index symbols only resolve with `assetclass=index`; [`Indices`] picks the
+//! symbol and asset class, so callers just name the [`MarketIndex`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::history::{eastern_to_utc, History};
+use crate::markets::nasdaq::quote::Quote;
+use crate::retrieve::ky_http::KyOptions;
+
+/// A major US index.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarketIndex {
+    Nasdaq100,
+    NasdaqComposite,
+    Sp500,
+    DowJones,
+    Russell2000,
+}
+
+impl MarketIndex {
+    /// All indices, in display order.
+    pub const ALL: [MarketIndex; 5] = [
+        MarketIndex::Nasdaq100,
+        MarketIndex::NasdaqComposite,
+        MarketIndex::Sp500,
+        MarketIndex::DowJones,
+        MarketIndex::Russell2000,
+    ];
+
+    /// The Nasdaq symbol, e.g. `"NDX"`.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            MarketIndex::Nasdaq100 => "NDX",
+            MarketIndex::NasdaqComposite => "COMP",
+            MarketIndex::Sp500 => "SPX",
+            MarketIndex::DowJones => "INDU",
+            MarketIndex::Russell2000 => "RUT",
+        }
+    }
+}
+
+/// Latest value of an index.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IndexQuote {
+    pub index: MarketIndex,
+    /// Name as reported, e.g. `"NASDAQ-100"`.
+    pub name: String,
+    pub value: Option<f64>,
+    pub net_change: Option<f64>,
+    pub percentage_change: Option<f64>,
+    /// As displayed by Nasdaq, e.g. `"Feb 23, 2026 4:00 PM ET"`.
+    pub last_trade_timestamp: String,
+    pub market_status: String,
+}
+
+/// One intraday index value.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct IndexPoint {
+    pub time: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// Service fetching index quotes and intraday values from Nasdaq.
+pub struct Indices {
+    quote: Quote,
+    history: History,
+}
+
+impl Indices {
+    /// Creates a new instance of `Indices`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self {
+            quote: Quote::with_api(api.clone()),
+            history: History::with_api(api),
+        }
+    }
+
+    /// Latest value of `index`.
+    pub async fn fetch_quote(&self, index: MarketIndex, options: Option<KyOptions>) -> Result<IndexQuote, NgError> {
+        let data = self.quote.fetch(index.symbol(), AssetClass::Index, options).await?;
+        Ok(IndexQuote {
+            index,
+            name: data.company_name,
+            value: data.primary_data.last_sale_price,
+            net_change: data.primary_data.net_change,
+            percentage_change: data.primary_data.percentage_change,
+            last_trade_timestamp: data.primary_data.last_trade_timestamp,
+            market_status: data.market_status,
+        })
+    }
+
+    /// Latest values of several indices, in the given order. Fails on the
+    /// first index that cannot be fetched.
+    pub async fn fetch_quotes(&self, indices: &[MarketIndex], options: Option<KyOptions>) -> Result<Vec<IndexQuote>, NgError> {
+        let mut quotes = Vec::with_capacity(indices.len());
+        for index in indices {
+            quotes.push(self.fetch_quote(*index, options.clone()).await?);
+        }
+        Ok(quotes)
+    }
+
+    /// Intraday values of `index` for the latest session, oldest first.
+    pub async fn fetch_intraday(&self, index: MarketIndex, options: Option<KyOptions>) -> Result<Vec<IndexPoint>, NgError> {
+        let points = self.history.fetch_intraday(index.symbol(), AssetClass::Index, options).await?;
+        Ok(points
+            .into_iter()
+            .filter_map(|(wall, value, _)| Some(IndexPoint { time: eastern_to_utc(wall)?, value }))
+            .collect())
+    }
+}
@@ -0,0 +1,127 @@
+//! # Nasdaq Insider Trading Module
+//!
+//! Typed insider transactions of one company from
+//! `/api/company/{symbol}/insider-trades`, newest first.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use crate::markets::nasdaq::parse::{de_count, de_date, de_number, de_string};
+use crate::retrieve::ky_http::KyOptions;
+
+/// Most transactions requested per call.
+pub const INSIDER_TRADES_LIMIT: usize = 1000;
+
+/// What the insider did, as reported by Nasdaq. Serialized as the Nasdaq label.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InsiderTransactionType {
+    Buy,
+    Sell,
+    AutomaticBuy,
+    AutomaticSell,
+    OptionExercise,
+    /// Any other label, e.g. `"Disposition (Non Open Market)"`.
+    Other(String),
+    #[default]
+    Unknown,
+}
+
+impl InsiderTransactionType {
+    fn parse(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "" => InsiderTransactionType::Unknown,
+            "buy" => InsiderTransactionType::Buy,
+            "sell" => InsiderTransactionType::Sell,
+            "automatic buy" => InsiderTransactionType::AutomaticBuy,
+            "automatic sell" => InsiderTransactionType::AutomaticSell,
+            "option execute" | "option exercise" => InsiderTransactionType::OptionExercise,
+            _ => InsiderTransactionType::Other(label.trim().to_string()),
+        }
+    }
+
+    /// The Nasdaq label, e.g. `"Automatic Sell"`.
+    pub fn label(&self) -> &str {
+        match self {
+            InsiderTransactionType::Buy => "Buy",
+            InsiderTransactionType::Sell => "Sell",
+            InsiderTransactionType::AutomaticBuy => "Automatic Buy",
+            InsiderTransactionType::AutomaticSell => "Automatic Sell",
+            InsiderTransactionType::OptionExercise => "Option Execute",
+            InsiderTransactionType::Other(label) => label,
+            InsiderTransactionType::Unknown => "",
+        }
+    }
+}
+
+impl Serialize for InsiderTransactionType {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.label())
+    }
+}
+
+impl<'de> Deserialize<'de> for InsiderTransactionType {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Ok(Self::parse(&de_string(d)?))
+    }
+}
+
+/// One insider transaction. Nasdaq field names are accepted on input; the
+/// field names below are used on output and accepted as well.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InsiderTransaction {
+    /// Insider name as filed, e.g. `"COOK TIMOTHY D"`.
+    #[serde(rename(deserialize = "insider"), alias = "insiderName", deserialize_with = "de_string")]
+    pub insider_name: String,
+    /// Role at the company, e.g. `"Chief Executive Officer"`.
+    #[serde(deserialize_with = "de_string")]
+    pub relation: String,
+    pub transaction_type: InsiderTransactionType,
+    /// `"Direct"` or `"Indirect"`.
+    #[serde(rename(deserialize = "ownType"), alias = "ownership", deserialize_with = "de_string")]
+    pub ownership: String,
+    #[serde(rename(deserialize = "sharesTraded"), alias = "shares", deserialize_with = "de_count")]
+    pub shares: Option<u64>,
+    #[serde(rename(deserialize = "lastPrice"), alias = "price", deserialize_with = "de_number")]
+    pub price: Option<f64>,
+    #[serde(deserialize_with = "de_count")]
+    pub shares_held: Option<u64>,
+    #[serde(rename(deserialize = "lastDate"), alias = "date", deserialize_with = "de_date")]
+    pub date: Option<NaiveDate>,
+}
+
+/// Service fetching insider activity from Nasdaq.
+pub struct Insiders {
+    api: NasdaqApi,
+}
+
+impl Insiders {
+    /// Creates a new instance of `Insiders`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { api }
+    }
+
+    /// Insider transactions of `symbol`, newest first. A company without
+    /// reported trades returns an empty list.
+    pub async fn fetch(&self, symbol: &str, options: Option<KyOptions>) -> Result<Vec<InsiderTransaction>, NgError> {
+        let endpoint = self
+            .api
+            .endpoint(["company", symbol, "insider-trades"])?
+            .query("limit", INSIDER_TRADES_LIMIT)
+            .query("type", "all")
+            .query("sortColumn", "lastDate")
+            .query("sortOrder", "DESC")
+            .build();
+        let data = self.api.call_data(&endpoint, options).await?;
+
+        self.api.decode_rows(&endpoint, &data, "/transactionTable/rows")
+    }
+}
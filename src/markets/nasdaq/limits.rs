@@ -0,0 +1,139 @@
+//! # Nasdaq Request Limits
+//!
+//! One request budget for the whole process: every [`NasdaqApi`] (and so
+//! every `markets::nasdaq` service) draws on the same concurrency permits and
+//! the same minimum spacing between requests, however many adapters exist.
+//! Adding endpoint modules therefore does not raise the request rate Nasdaq
+//! sees.
+//!
+//! The budget is fixed on first use. To change the defaults, call
+//! [`NasdaqLimits::install`] at startup, before any Nasdaq service is built:
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::configs::ConfigManager;
+//! # use rs_lib_ng::markets::nasdaq::limits::NasdaqLimits;
+//! # fn setup(config: &ConfigManager) -> Result<(), rs_lib_ng::NgError> {
+//! // Reads `nasdaq.concurrency` and `nasdaq.min_interval_ms`.
+//! NasdaqLimits::from_config(config)?.install()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`NasdaqApi`]: crate::markets::nasdaq::apicallnasdaq::NasdaqApi
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+use crate::configs::cli::get_path;
+use crate::configs::ConfigManager;
+use crate::core::error::NgError;
+use crate::retrieve::ky_http::KyOptions;
+use crate::retrieve::rate_limit::{RateLimit, RateLimiter};
+
+/// Requests in flight at once, across all Nasdaq adapters.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Minimum spacing between two Nasdaq requests.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Rate limiter bucket all Nasdaq requests are paced in.
+const BUCKET: &str = "nasdaq";
+
+/// Process-wide Nasdaq request budget.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct NasdaqLimits {
+    /// Requests in flight at once (at least one).
+    pub concurrency: usize,
+    /// Minimum time between the starts of two requests; zero disables pacing.
+    pub min_interval: Duration,
+}
+
+impl Default for NasdaqLimits {
+    fn default() -> Self {
+        Self { concurrency: DEFAULT_CONCURRENCY, min_interval: DEFAULT_MIN_INTERVAL }
+    }
+}
+
+impl NasdaqLimits {
+    /// Reads `nasdaq.concurrency` and `nasdaq.min_interval_ms`; missing keys
+    /// keep their defaults, values of the wrong type are a `ConfigError`.
+    pub fn from_config(config: &ConfigManager) -> Result<Self, NgError> {
+        let data = config.get();
+        let read = |path: &str| match get_path(&data, path) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(v) => v
+                .as_u64()
+                .map(Some)
+                .ok_or_else(|| NgError::ConfigError(format!("'{}' must be a non-negative integer", path))),
+        };
+        let mut limits = Self::default();
+        if let Some(n) = read("nasdaq.concurrency")? {
+            limits.concurrency = n as usize;
+        }
+        if let Some(ms) = read("nasdaq.min_interval_ms")? {
+            limits.min_interval = Duration::from_millis(ms);
+        }
+        Ok(limits)
+    }
+
+    /// Makes these the process-wide limits. Fails with `ConfigError` once the
+    /// limits are in use, i.e. after the first `NasdaqApi` was created or
+    /// after a previous `install`.
+    pub fn install(self) -> Result<(), NgError> {
+        let mut installed = false;
+        SHARED.get_or_init(|| {
+            installed = true;
+            SharedLimits::new(self)
+        });
+        if installed {
+            Ok(())
+        } else {
+            Err(NgError::ConfigError("Nasdaq limits are already in use".into()))
+        }
+    }
+
+    /// The limits in effect, installing the defaults if none were.
+    pub fn current() -> Self {
+        shared().limits
+    }
+}
+
+/// Permits and pacing shared by every Nasdaq adapter.
+struct SharedLimits {
+    limits: NasdaqLimits,
+    semaphore: Arc<Semaphore>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl SharedLimits {
+    fn new(limits: NasdaqLimits) -> Self {
+        let pacing = RateLimit { requests: 1, per: limits.min_interval };
+        Self {
+            limits,
+            semaphore: Arc::new(Semaphore::new(limits.concurrency.max(1))),
+            limiter: Arc::new(RateLimiter::new(pacing)),
+        }
+    }
+}
+
+static SHARED: OnceLock<SharedLimits> = OnceLock::new();
+
+fn shared() -> &'static SharedLimits {
+    SHARED.get_or_init(|| SharedLimits::new(NasdaqLimits::default()))
+}
+
+/// `opts` drawing on the shared permits and pacing. A semaphore or rate
+/// limiter the caller set explicitly is kept.
+pub(crate) fn apply(mut opts: KyOptions) -> KyOptions {
+    let shared = shared();
+    if opts.semaphore.is_none() {
+        opts.semaphore = Some(shared.semaphore.clone());
+    }
+    if opts.rate_limiter.is_none() {
+        opts.rate_limiter = Some(shared.limiter.clone());
+        opts.rate_limit_bucket = Some(BUCKET.to_string());
+    }
+    opts
+}
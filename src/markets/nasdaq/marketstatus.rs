@@ -3,16 +3,28 @@
 //! Provides high-level methods to fetch market data and calculate operational
 //! timings. This module is designed to be used by an orchestrator to manage
 //! polling intervals and execution timing.
+//!
+//! When the market-info endpoint is unreachable,
+//! [`MarketStatus::fetch_status_or_computed`] computes the status from the
+//! [`TradingCalendar`] instead (regular hours, early closes and holidays),
+//! and flags it as computed rather than confirmed by Nasdaq.
 
-use chrono::{Duration as ChronoDuration, NaiveDate, NaiveTime, TimeZone, Utc};
-use chrono_tz::US::Eastern;
+use arc_swap::ArcSwapOption;
+use rand::Rng;
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::{Tz, US::Eastern};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
 
 use crate::core::error::NgError;
-use crate::{error, info};
+use crate::{info, warn};
 use crate::loggers::Logger;
 use crate::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use crate::markets::nasdaq::calendar::TradingCalendar;
+use crate::retrieve::clock::{Clock, TokioClock};
 use crate::retrieve::ky_http::KyOptions;
 
 /// Represents the deserialized market information from Nasdaq.
@@ -35,60 +47,256 @@ pub struct MarketStatusData {
     pub mrkt_status: String,
 }
 
+impl MarketStatusData {
+    /// The status at `now` computed from `calendar`, in the same format as
+    /// the Nasdaq endpoint. On a non-trading day, like Nasdaq, it carries the
+    /// next session's times and no business day.
+    pub fn computed(calendar: &TradingCalendar, now: DateTime<Utc>) -> Self {
+        let today = now.with_timezone(&Eastern).date_naive();
+        let is_business_day = calendar.is_trading_day(today);
+        let next_trade_date = calendar.next_trading_day(today);
+        let session_day = if is_business_day { today } else { next_trade_date };
+        let times = calendar.session_times(session_day);
+
+        let stamp = |t: Option<DateTime<Utc>>| {
+            t.map(|t| t.with_timezone(&Eastern).format("%b %d, %Y %I:%M %p ET").to_string()).unwrap_or_default()
+        };
+        let session = match times {
+            Some(t) if is_business_day => {
+                session_within([t.pre_market_open, t.regular_open, t.regular_close, t.after_hours_close], now)
+            }
+            _ => Session::Closed,
+        };
+        let (indicator, mrkt_status) = match session {
+            Session::PreMarket => ("Pre-Market", "Pre-Market"),
+            Session::Regular => ("Market Open", "Open"),
+            Session::AfterHours => ("After-Hours", "After-Hours"),
+            Session::Closed => ("Market Closed", "Closed"),
+        };
+
+        Self {
+            country: "U.S.".to_string(),
+            market_indicator: indicator.to_string(),
+            ui_market_indicator: indicator.to_string(),
+            market_count_down: String::new(),
+            pre_market_opening_time: stamp(times.map(|t| t.pre_market_open)),
+            pre_market_closing_time: stamp(times.map(|t| t.regular_open)),
+            market_opening_time: stamp(times.map(|t| t.regular_open)),
+            market_closing_time: stamp(times.map(|t| t.regular_close)),
+            after_hours_market_opening_time: stamp(times.map(|t| t.regular_close)),
+            after_hours_market_closing_time: stamp(times.map(|t| t.after_hours_close)),
+            previous_trade_date: calendar.previous_trading_day(today).format("%b %d, %Y").to_string(),
+            next_trade_date: next_trade_date.format("%b %d, %Y").to_string(),
+            is_business_day,
+            mrkt_status: mrkt_status.to_string(),
+        }
+    }
+}
+
+/// A status from `fetch_status_or_computed`; `fetch_error` is set when the
+/// endpoint failed and `data` was computed from the calendar.
+#[derive(Debug, Clone)]
+pub struct ResolvedStatus {
+    pub data: MarketStatusData,
+    pub fetch_error: Option<String>,
+}
+
+impl ResolvedStatus {
+    /// Whether `data` was computed rather than confirmed by Nasdaq.
+    pub fn is_computed(&self) -> bool {
+        self.fetch_error.is_some()
+    }
+}
+
+/// Trading session of the US equity market.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Session {
+    PreMarket,
+    Regular,
+    AfterHours,
+    /// Overnight, weekends and holidays.
+    Closed,
+}
+
+/// Last status returned by `fetch_status_cached`.
+struct CachedStatus {
+    data: Arc<MarketStatusData>,
+    fetched_at: Instant,
+    /// ET date and session, by this status's own times, when it was fetched.
+    segment: (NaiveDate, Session),
+}
+
 /// Service to fetch and analyze Nasdaq market status.
 pub struct MarketStatus {
     api: NasdaqApi,
     logger: Logger,
+    calendar: TradingCalendar,
+    cache: ArcSwapOption<CachedStatus>,
+    /// Serializes cache refreshes so concurrent callers share one request.
+    refresh: tokio::sync::Mutex<()>,
+    clock: Arc<dyn Clock>,
 }
 
 impl MarketStatus {
     /// Creates a new instance of `MarketStatus`.
     pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
         Self {
-            api: NasdaqApi::new(logger.clone()),
+            api,
             logger,
+            calendar: TradingCalendar::fallback(),
+            cache: ArcSwapOption::empty(),
+            refresh: tokio::sync::Mutex::new(()),
+            clock: Arc::new(TokioClock),
         }
     }
 
+    /// Replaces the embedded calendar used by `fetch_status_or_computed`,
+    /// e.g. with `MarketCalendar::fetch_or_fallback`.
+    pub fn with_calendar(mut self, calendar: TradingCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Time source for "now": the current session, opening and closing
+    /// delays, computed statuses and the boundaries of `fetch_status_cached`.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Fetches the raw JSON response from the Nasdaq market-info endpoint.
     pub async fn fetch_raw(&self, options: Option<KyOptions>) -> Result<Value, NgError> {
-        let endpoint = "https://api.nasdaq.com/api/market-info/";
-        self.api.call(endpoint, options).await
+        // The trailing empty segment keeps the endpoint's trailing slash.
+        let endpoint = self.api.endpoint(["market-info", ""])?.build();
+        self.api.call(&endpoint, options).await
+    }
+
+    /// Like [`fetch_status`](Self::fetch_status), but reuses the last result
+    /// for up to `ttl`, or until a session boundary is crossed: midnight ET,
+    /// or one of the status's own session times (see [`session_at`](Self::session_at)),
+    /// so an early close expires a status cached as open.
+    ///
+    /// Concurrent callers that miss the cache wait for a single request.
+    /// Failed fetches are not cached.
+    pub async fn fetch_status_cached(&self, ttl: Duration) -> Result<Arc<MarketStatusData>, NgError> {
+        if let Some(hit) = self.cached(ttl) {
+            return Ok(hit);
+        }
+        let _guard = self.refresh.lock().await;
+        // Another caller may have refreshed while we waited.
+        if let Some(hit) = self.cached(ttl) {
+            return Ok(hit);
+        }
+        let data = Arc::new(self.fetch_status(None).await?);
+        self.cache.store(Some(Arc::new(CachedStatus {
+            segment: self.session_segment(&data),
+            data: data.clone(),
+            fetched_at: Instant::now(),
+        })));
+        Ok(data)
+    }
+
+    /// Drops the cached status, so the next `fetch_status_cached` refetches.
+    pub fn invalidate_cache(&self) {
+        self.cache.store(None);
+    }
+
+    fn cached(&self, ttl: Duration) -> Option<Arc<MarketStatusData>> {
+        let cached = self.cache.load_full()?;
+        let fresh = cached.fetched_at.elapsed() < ttl
+            && cached.segment == self.session_segment(&cached.data);
+        fresh.then(|| cached.data.clone())
+    }
+
+    fn session_segment(&self, status: &MarketStatusData) -> (NaiveDate, Session) {
+        let now = self.clock.now();
+        (now.with_timezone(&Eastern).date_naive(), self.session_at(status, now))
     }
 
     /// Fetches and deserializes the market status into typed data.
     pub async fn fetch_status(&self, options: Option<KyOptions>) -> Result<MarketStatusData, NgError> {
-        let json = self.fetch_raw(options).await?;
-        
-        let data = json.get("data").ok_or_else(|| {
-            NgError::MalformedResponse {
-                endpoint: "market-info".to_string(),
-                details: "Missing 'data' field".to_string(),
-            }
-        })?;
+        let endpoint = self.api.endpoint(["market-info", ""])?.build();
+        self.api.call_typed(&endpoint, options).await
+    }
 
-        serde_json::from_value(data.clone()).map_err(|e| {
-            error!(self.logger, "Deserialization error in MarketStatus", "error" => e.to_string());
-            NgError::MalformedResponse {
-                endpoint: "market-info".to_string(),
-                details: format!("JSON error: {}", e),
+    /// Like [`fetch_status`](Self::fetch_status), but computes the status
+    /// from the calendar when the endpoint fails. Never fails.
+    pub async fn fetch_status_or_computed(&self, options: Option<KyOptions>) -> ResolvedStatus {
+        match self.fetch_status(options).await {
+            Ok(data) => ResolvedStatus { data, fetch_error: None },
+            Err(e) => {
+                warn!(self.logger, "Market status unavailable, computing it from the calendar", "error" => e.to_string());
+                ResolvedStatus {
+                    data: MarketStatusData::computed(&self.calendar, self.clock.now()),
+                    fetch_error: Some(e.to_string()),
+                }
             }
-        })
+        }
     }
 
     /// Determines if the market is currently in the Regular Trading Session.
     ///
-    /// Checks if today is a business day and if the current Eastern Time 
-    /// is between 09:30 AM and 04:00 PM.
+    /// Shorthand for `current_session(status) == Session::Regular`, so early
+    /// closes reported by Nasdaq are honoured.
     pub fn is_regular_session(&self, status: &MarketStatusData) -> bool {
+        self.current_session(status) == Session::Regular
+    }
+
+    /// Whether the market is currently in the pre-market session.
+    pub fn is_premarket(&self, status: &MarketStatusData) -> bool {
+        self.current_session(status) == Session::PreMarket
+    }
+
+    /// Whether the market is currently in the after-hours session.
+    pub fn is_after_hours(&self, status: &MarketStatusData) -> bool {
+        self.current_session(status) == Session::AfterHours
+    }
+
+    /// The session the market is in right now.
+    pub fn current_session(&self, status: &MarketStatusData) -> Session {
+        self.session_at(status, self.clock.now())
+    }
+
+    /// The session the market is in at `now`.
+    ///
+    /// Boundaries come from the status's pre-market opening, market opening,
+    /// market closing and after-hours closing times, so early-close days end
+    /// the regular session early. When any of them cannot be parsed, the
+    /// standard 04:00 / 09:30 / 16:00 / 20:00 ET times of `now`'s date are used.
+    pub fn session_at(&self, status: &MarketStatusData, now: DateTime<Utc>) -> Session {
         if !status.is_business_day {
-            return false;
+            return Session::Closed;
         }
-        let now = Utc::now().with_timezone(&Eastern).time();
-        let open = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
-        let close = NaiveTime::from_hms_opt(16, 0, 0).unwrap();
+        let parsed = [
+            &status.pre_market_opening_time,
+            &status.market_opening_time,
+            &status.market_closing_time,
+            &status.after_hours_market_closing_time,
+        ]
+        .map(|raw| parse_session_time(raw).map(|t| t.with_timezone(&Utc)));
 
-        now >= open && now < close
+        let bounds = match parsed {
+            [Some(a), Some(b), Some(c), Some(d)] => [a, b, c, d],
+            _ => {
+                let date = now.with_timezone(&Eastern).date_naive();
+                let local = |h, m| {
+                    let t = NaiveTime::from_hms_opt(h, m, 0).unwrap_or(NaiveTime::MIN);
+                    Eastern.from_local_datetime(&date.and_time(t)).earliest().map(|t| t.with_timezone(&Utc))
+                };
+                match (local(4, 0), local(9, 30), local(16, 0), local(20, 0)) {
+                    (Some(a), Some(b), Some(c), Some(d)) => [a, b, c, d],
+                    _ => return Session::Closed,
+                }
+            }
+        };
+
+        session_within(bounds, now)
     }
 
     /// Calculates the precise duration until the next market opening.
@@ -97,7 +305,7 @@ impl MarketStatus {
     /// * `Ok(Duration)` representing the time until 09:30 AM ET on the next trade date.
     /// * `Err(NgError)` if the date string from Nasdaq cannot be parsed.
     pub fn get_next_opening_delay(&self, status: &MarketStatusData) -> Result<std::time::Duration, NgError> {
-        let now = Utc::now().with_timezone(&Eastern);
+        let now = self.clock.now().with_timezone(&Eastern);
         let fmt = "%b %d, %Y"; // e.g., "Feb 24, 2026"
         
         let d = NaiveDate::parse_from_str(&status.next_trade_date, fmt).map_err(|e| {
@@ -129,16 +337,101 @@ impl MarketStatus {
     /// Useful for transition alerts. If the market is already open or the 
     /// delay cannot be calculated, it returns immediately.
     pub async fn wait_until_open(&self, status: &MarketStatusData) {
-        if let Ok(delay) = self.get_next_opening_delay(status) {
-            if delay.as_secs() > 0 {
-                info!(
-                    self.logger, 
-                    "Market transition alert: Waiting for opening", 
-                    "wait_time" => self.format_duration(ChronoDuration::from_std(delay).unwrap_or(ChronoDuration::zero()))
-                );
-                tokio::time::sleep(delay).await;
-                info!(self.logger, "Market opening time reached.");
-            }
+        if let Ok(delay) = self.get_next_opening_delay(status)
+            && delay.as_secs() > 0
+        {
+            info!(
+                self.logger, 
+                "Market transition alert: Waiting for opening", 
+                "wait_time" => self.format_duration(ChronoDuration::from_std(delay).unwrap_or(ChronoDuration::zero()))
+            );
+            tokio::time::sleep(delay).await;
+            info!(self.logger, "Market opening time reached.");
+        }
+    }
+
+    /// Refetches the status until the regular session has started, and
+    /// returns the first status that confirms it.
+    ///
+    /// Between fetches it sleeps until the reported opening time (today's
+    /// `market_opening_time` or `get_next_opening_delay`) plus a small jitter.
+    /// A status whose opening already passed without a regular session, a
+    /// failed fetch or an unparsable date is retried after `interval`.
+    pub async fn poll_until_open(&self, interval: Duration, options: Option<KyOptions>) -> MarketStatusData {
+        loop {
+            let wait = match self.fetch_status(options.clone()).await {
+                Ok(status) => {
+                    let now = self.clock.now();
+                    if self.session_at(&status, now) == Session::Regular {
+                        info!(self.logger, "Regular session confirmed open");
+                        return status;
+                    }
+                    self.opening_wait(&status, now).unwrap_or(interval)
+                }
+                Err(e) => {
+                    warn!(self.logger, "Market status refresh failed while waiting for open", "error" => e.to_string());
+                    interval
+                }
+            };
+            // Zero means the status is stale: refresh it.
+            let wait = if wait.is_zero() { interval } else { wait };
+            let max_jitter = (wait / 10).min(interval).as_millis() as u64;
+            let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=max_jitter));
+            info!(
+                self.logger,
+                "Waiting for the regular session",
+                "wait_time" => self.format_duration(ChronoDuration::from_std(wait + jitter).unwrap_or(ChronoDuration::zero()))
+            );
+            tokio::time::sleep(wait + jitter).await;
+        }
+    }
+
+    /// Time until today's opening when it is still ahead, otherwise until the
+    /// next trade date's opening.
+    fn opening_wait(&self, status: &MarketStatusData, now: DateTime<Utc>) -> Result<Duration, NgError> {
+        if status.is_business_day
+            && let Some(open) = parse_session_time(&status.market_opening_time)
+            && let Ok(wait) = open.with_timezone(&Utc).signed_duration_since(now).to_std()
+        {
+            return Ok(wait);
+        }
+        self.get_next_opening_delay(status)
+    }
+
+    /// Calculates the duration until the current or next regular-session close.
+    ///
+    /// Uses the parsed `market_closing_time`, so early closes (e.g. 1:00 PM ET)
+    /// are honoured.
+    ///
+    /// # Returns
+    /// * `Ok(Duration)` until the close, or zero if it has already passed.
+    /// * `Err(NgError)` if the closing time from Nasdaq cannot be parsed.
+    pub fn get_next_closing_delay(&self, status: &MarketStatusData) -> Result<std::time::Duration, NgError> {
+        let close = parse_session_time(&status.market_closing_time).ok_or_else(|| NgError::MalformedResponse {
+            endpoint: "market-info".to_string(),
+            details: format!("Closing time parsing failed for '{}'", status.market_closing_time),
+        })?;
+
+        let secs = close.signed_duration_since(self.clock.now()).num_seconds();
+        // A passed close means the status is stale; zero tells the caller to refresh.
+        Ok(std::time::Duration::from_secs(secs.max(0) as u64))
+    }
+
+    /// Blocks the current task until the regular session closes.
+    ///
+    /// Useful for end-of-session flush work. If the close has passed or the
+    /// delay cannot be calculated, it returns immediately.
+    pub async fn wait_until_close(&self, status: &MarketStatusData) {
+        if let Ok(delay) = self.get_next_closing_delay(status)
+            && delay.as_secs() > 0
+        {
+            info!(
+                self.logger,
+                "Market transition alert: Waiting for closing",
+                "wait_time" => self.format_duration(ChronoDuration::from_std(delay).unwrap_or(ChronoDuration::zero()))
+            );
+            tokio::time::sleep(delay).await;
+            info!(self.logger, "Market closing time reached.");
         }
     }
 
@@ -150,4 +443,23 @@ impl MarketStatus {
         let seconds = secs % 60;
         format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
     }
-}
\ No newline at end of file
+}
+
+/// Parses a Nasdaq session time such as `"Feb 23, 2026 09:30 AM ET"`.
+pub(crate) fn parse_session_time(raw: &str) -> Option<DateTime<Tz>> {
+    let raw = raw.trim();
+    let raw = raw.strip_suffix("ET").unwrap_or(raw).trim();
+    let naive = chrono::NaiveDateTime::parse_from_str(raw, "%b %d, %Y %I:%M %p").ok()?;
+    Eastern.from_local_datetime(&naive).earliest()
+}
+
+/// Session at `now` given the pre-market open, regular open, regular close
+/// and after-hours close.
+fn session_within(bounds: [DateTime<Utc>; 4], now: DateTime<Utc>) -> Session {
+    match bounds.iter().filter(|b| now >= **b).count() {
+        1 => Session::PreMarket,
+        2 => Session::Regular,
+        3 => Session::AfterHours,
+        _ => Session::Closed,
+    }
+}
@@ -1,3 +1,19 @@
 pub mod apicallnasdaq;
+pub mod calendar;
+pub mod commodities;
+pub mod company;
+pub mod currencies;
+pub mod dividends;
+pub mod earnings;
+pub mod history;
+pub mod indices;
+pub mod insiders;
+pub mod limits;
 pub mod marketstatus;
-// pub mod datafeeds;
\ No newline at end of file
+pub mod options;
+mod parse;
+pub mod quote;
+pub mod screener;
+pub mod search;
+pub mod trades;
+pub mod datafeeds;
@@ -0,0 +1,205 @@
+//! # Nasdaq Option Chain Module
+//!
+//! Typed calls and puts from `/api/quote/{symbol}/option-chain`. The endpoint
+//! is paged (`offset` / `limit`); all pages are fetched. Each table row holds
+//! the call and the put of one strike (`c_*` / `p_*` columns), and rows are
+//! grouped under `expirygroup` header rows that carry the full expiry date.
+//! Greek columns (`c_Delta`, `p_IV`, ...) are read when the response has them.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::number;
+use crate::retrieve::ky_http::KyOptions;
+use crate::debug;
+
+/// Rows requested per page of the option-chain endpoint.
+pub const OPTION_CHAIN_PAGE_SIZE: usize = 200;
+
+/// Which expiries to return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpiryFilter {
+    #[default]
+    All,
+    /// A single expiry date.
+    On(NaiveDate),
+    /// Inclusive expiry date range.
+    Between { from: NaiveDate, to: NaiveDate },
+}
+
+impl ExpiryFilter {
+    fn bounds(&self) -> Option<(NaiveDate, NaiveDate)> {
+        match *self {
+            ExpiryFilter::All => None,
+            ExpiryFilter::On(date) => Some((date, date)),
+            ExpiryFilter::Between { from, to } => Some((from, to)),
+        }
+    }
+
+    /// Whether `expiry` passes the filter.
+    pub fn matches(&self, expiry: NaiveDate) -> bool {
+        self.bounds().is_none_or(|(from, to)| (from..=to).contains(&expiry))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OptionKind {
+    Call,
+    Put,
+}
+
+/// Greeks and implied volatility, when Nasdaq provides them.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Greeks {
+    pub delta: Option<f64>,
+    pub gamma: Option<f64>,
+    pub theta: Option<f64>,
+    pub vega: Option<f64>,
+    pub implied_volatility: Option<f64>,
+}
+
+/// One call or put.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OptionContract {
+    pub kind: OptionKind,
+    pub expiry: NaiveDate,
+    pub strike: f64,
+    pub last: Option<f64>,
+    pub change: Option<f64>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    pub volume: Option<u64>,
+    pub open_interest: Option<u64>,
+    /// `None` when the response has no greek columns for this contract.
+    pub greeks: Option<Greeks>,
+}
+
+/// Calls and puts of one symbol, ordered by expiry then strike.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct OptionChainData {
+    pub symbol: String,
+    pub calls: Vec<OptionContract>,
+    pub puts: Vec<OptionContract>,
+}
+
+/// Service fetching option chains from Nasdaq.
+pub struct OptionChain {
+    api: NasdaqApi,
+    logger: Logger,
+    page_size: usize,
+}
+
+impl OptionChain {
+    /// Creates a new instance of `OptionChain`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
+        Self { api, logger, page_size: OPTION_CHAIN_PAGE_SIZE }
+    }
+
+    /// Overrides the rows requested per page (at least 1).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Fetches every page of the chain of `symbol` for the expiries in `expiry_filter`.
+    pub async fn fetch(
+        &self,
+        symbol: &str,
+        asset_class: AssetClass,
+        expiry_filter: ExpiryFilter,
+        options: Option<KyOptions>,
+    ) -> Result<OptionChainData, NgError> {
+        let mut chain = OptionChainData { symbol: symbol.to_string(), ..Default::default() };
+        let mut offset = 0usize;
+        // Header rows only appear at the start of a group, which may be on an earlier page.
+        let mut expiry: Option<NaiveDate> = None;
+        let bounds = expiry_filter.bounds();
+        loop {
+            let endpoint = self
+                .api
+                .endpoint(["quote", symbol, "option-chain"])?
+                .query("assetclass", asset_class)
+                .query("limit", self.page_size)
+                .query("offset", offset)
+                .query_opt("fromdate", bounds.map(|(from, _)| from.format("%Y-%m-%d")))
+                .query_opt("todate", bounds.map(|(_, to)| to.format("%Y-%m-%d")))
+                .query("money", "all")
+                .query("type", "all")
+                .build();
+            let data = self.api.call_data(&endpoint, options.clone()).await?;
+
+            let rows = data.pointer("/table/rows").and_then(Value::as_array).map(Vec::as_slice).unwrap_or(&[]);
+            let mut contracts = 0usize;
+            for row in rows {
+                if let Some(group) = row.get("expirygroup").and_then(Value::as_str).filter(|g| !g.trim().is_empty()) {
+                    expiry = NaiveDate::parse_from_str(group.trim(), "%B %d, %Y").ok();
+                }
+                let (Some(expiry), Some(strike)) = (expiry, cell(row, "strike")) else { continue };
+                contracts += 1;
+                if !expiry_filter.matches(expiry) {
+                    continue;
+                }
+                chain.calls.push(contract(row, OptionKind::Call, expiry, strike));
+                chain.puts.push(contract(row, OptionKind::Put, expiry, strike));
+            }
+            offset += contracts;
+            let total = data.get("totalRecord").and_then(Value::as_u64).map(|t| t as usize);
+            debug!(self.logger, "Fetched Nasdaq option chain page", "symbol" => symbol, "rows" => contracts, "offset" => offset);
+
+            if contracts == 0 || rows.len() < self.page_size || total.is_some_and(|t| offset >= t) {
+                break;
+            }
+        }
+        for side in [&mut chain.calls, &mut chain.puts] {
+            side.sort_by(|a, b| a.expiry.cmp(&b.expiry).then(a.strike.total_cmp(&b.strike)));
+            side.dedup_by(|a, b| a.expiry == b.expiry && a.strike == b.strike);
+        }
+        Ok(chain)
+    }
+}
+
+fn cell(row: &Value, key: &str) -> Option<f64> {
+    match row.get(key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => number(s),
+        _ => None,
+    }
+}
+
+fn contract(row: &Value, kind: OptionKind, expiry: NaiveDate, strike: f64) -> OptionContract {
+    let prefix = match kind {
+        OptionKind::Call => "c_",
+        OptionKind::Put => "p_",
+    };
+    let col = |name: &str| cell(row, &format!("{}{}", prefix, name));
+    let whole = |name: &str| col(name).filter(|v| *v >= 0.0).map(|v| v as u64);
+    let greeks = Greeks {
+        delta: col("Delta"),
+        gamma: col("Gamma"),
+        theta: col("Theta"),
+        vega: col("Vega"),
+        implied_volatility: col("IV"),
+    };
+    OptionContract {
+        kind,
+        expiry,
+        strike,
+        last: col("Last"),
+        change: col("Change"),
+        bid: col("Bid"),
+        ask: col("Ask"),
+        volume: whole("Volume"),
+        open_interest: whole("Openinterest"),
+        greeks: (greeks != Greeks::default()).then_some(greeks),
+    }
+}
@@ -0,0 +1,130 @@
+//! # Nasdaq Field Parsing
+//!
+//! Nasdaq returns most numbers as display strings: `"$189.84"`, `"+0.14"`,
+//! `"-0.07%"`, `"57,807,697"`, with `"N/A"`, `"--"` or `""` when there is no
+//! value. These helpers turn them into plain numbers and back the
+//! `deserialize_with` attributes of the typed endpoint structs, which accept
+//! the display string, a JSON number or `null`. Dates come as `"02/09/2026"`,
+//! `"2/9/2026"`, `"2026-02-09"` or `"Feb 9, 2026"`.
+//!
+//! [`typed`] deserializes a whole response block and, when that fails, names
+//! the JSON path of the offending value.
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// Parses a Nasdaq display number; `None` for placeholders and garbage.
+pub(crate) fn number(raw: &str) -> Option<f64> {
+    let cleaned: String = raw
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, '$' | ',' | '%' | '+' | ' '))
+        .collect();
+    cleaned.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Parses a Nasdaq date in any of its display formats; `None` for placeholders.
+pub(crate) fn date(raw: &str) -> Option<NaiveDate> {
+    let raw = raw.trim();
+    ["%m/%d/%Y", "%Y-%m-%d", "%b %d, %Y", "%B %d, %Y"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(raw, fmt).ok())
+}
+
+/// Counts such as volumes must be non-negative whole numbers.
+fn whole(v: f64) -> Option<u64> {
+    (v >= 0.0 && v.fract() == 0.0).then_some(v as u64)
+}
+
+fn number_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => number(s),
+        _ => None,
+    }
+}
+
+/// `deserialize_with` for `Option<f64>` fields.
+pub(crate) fn de_number<'de, D: Deserializer<'de>>(d: D) -> Result<Option<f64>, D::Error> {
+    Ok(Option::<Value>::deserialize(d)?.as_ref().and_then(number_value))
+}
+
+/// `deserialize_with` for `Option<u64>` fields.
+pub(crate) fn de_count<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u64>, D::Error> {
+    Ok(Option::<Value>::deserialize(d)?.as_ref().and_then(number_value).and_then(whole))
+}
+
+/// `deserialize_with` for `Option<NaiveDate>` fields.
+pub(crate) fn de_date<'de, D: Deserializer<'de>>(d: D) -> Result<Option<NaiveDate>, D::Error> {
+    Ok(match Option::<Value>::deserialize(d)? {
+        Some(Value::String(s)) => date(&s),
+        _ => None,
+    })
+}
+
+/// `deserialize_with` for strings that may be `null`, which become `""`.
+pub(crate) fn de_string<'de, D: Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    Ok(match Option::<Value>::deserialize(d)? {
+        Some(Value::String(s)) => s,
+        Some(Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    })
+}
+
+/// `deserialize_with` for flags that may be `null` or a `"true"`/`"Y"` string.
+pub(crate) fn de_flag<'de, D: Deserializer<'de>>(d: D) -> Result<bool, D::Error> {
+    Ok(match Option::<Value>::deserialize(d)? {
+        Some(Value::Bool(b)) => b,
+        Some(Value::String(s)) => matches!(s.trim().to_ascii_lowercase().as_str(), "true" | "y" | "yes"),
+        _ => false,
+    })
+}
+
+/// Deserializes `value` into `T`. On failure, also returns the JSON pointer
+/// of the offending value (`""` for `value` itself).
+pub(crate) fn typed<T: DeserializeOwned>(value: &Value) -> Result<T, (String, serde_json::Error)> {
+    let err = match T::deserialize(value) {
+        Ok(typed) => return Ok(typed),
+        Err(e) => e,
+    };
+    // Value errors carry no position; the same error from the pretty-printed
+    // text names a line, which maps back to a node of `value`.
+    let path = serde_json::to_string_pretty(value)
+        .ok()
+        .and_then(|text| serde_json::from_str::<T>(&text).err())
+        .and_then(|e| pointer_at_line(value, 1, e.line(), String::new()))
+        .unwrap_or_default();
+    Err((path, err))
+}
+
+/// Lines `value` takes when pretty-printed.
+fn pretty_lines(value: &Value) -> usize {
+    match value {
+        Value::Array(items) if !items.is_empty() => 2 + items.iter().map(pretty_lines).sum::<usize>(),
+        Value::Object(map) if !map.is_empty() => 2 + map.values().map(pretty_lines).sum::<usize>(),
+        _ => 1,
+    }
+}
+
+/// Pointer of the innermost node of `value` (printed from line `start`) that
+/// contains pretty-printed line `line`.
+fn pointer_at_line(value: &Value, start: usize, line: usize, path: String) -> Option<String> {
+    if line < start || line >= start + pretty_lines(value) {
+        return None;
+    }
+    let children: Vec<(String, &Value)> = match value {
+        Value::Array(items) => items.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),
+        Value::Object(map) => map.iter().map(|(k, v)| (k.replace('~', "~0").replace('/', "~1"), v)).collect(),
+        _ => Vec::new(),
+    };
+    let mut child_start = start + 1;
+    for (key, child) in children {
+        if let Some(found) = pointer_at_line(child, child_start, line, format!("{}/{}", path, key)) {
+            return Some(found);
+        }
+        child_start += pretty_lines(child);
+    }
+    Some(path)
+}
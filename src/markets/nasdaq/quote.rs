@@ -0,0 +1,151 @@
+//! # Nasdaq Quote Module
+//!
+//! Typed access to `/api/quote/{symbol}/info`: last sale, change, bid/ask,
+//! volume and market status for one symbol. Display strings such as
+//! `"$189.84"` or `"57,807,697"` are parsed into numbers; `"N/A"` becomes `None`.
+
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::{info, warn};
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::{de_count, de_flag, de_number, de_string};
+use crate::retrieve::ky_http::KyOptions;
+
+/// Price block of a quote (`primaryData` / `secondaryData`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuoteDetail {
+    #[serde(deserialize_with = "de_number")]
+    pub last_sale_price: Option<f64>,
+    #[serde(deserialize_with = "de_number")]
+    pub net_change: Option<f64>,
+    #[serde(deserialize_with = "de_number")]
+    pub percentage_change: Option<f64>,
+    /// `"up"`, `"down"` or empty.
+    #[serde(deserialize_with = "de_string")]
+    pub delta_indicator: String,
+    /// As displayed by Nasdaq, e.g. `"Feb 23, 2026 4:00 PM ET"`.
+    #[serde(deserialize_with = "de_string")]
+    pub last_trade_timestamp: String,
+    #[serde(deserialize_with = "de_flag")]
+    pub is_real_time: bool,
+    #[serde(deserialize_with = "de_number")]
+    pub bid_price: Option<f64>,
+    #[serde(deserialize_with = "de_number")]
+    pub ask_price: Option<f64>,
+    #[serde(deserialize_with = "de_count")]
+    pub bid_size: Option<u64>,
+    #[serde(deserialize_with = "de_count")]
+    pub ask_size: Option<u64>,
+    #[serde(deserialize_with = "de_count")]
+    pub volume: Option<u64>,
+}
+
+/// Deserialized `data` block of a quote response.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct QuoteData {
+    #[serde(deserialize_with = "de_string")]
+    pub symbol: String,
+    #[serde(deserialize_with = "de_string")]
+    pub company_name: String,
+    #[serde(deserialize_with = "de_string")]
+    pub stock_type: String,
+    #[serde(deserialize_with = "de_string")]
+    pub exchange: String,
+    #[serde(deserialize_with = "de_flag")]
+    pub is_nasdaq_listed: bool,
+    #[serde(deserialize_with = "de_flag")]
+    pub is_nasdaq100: bool,
+    #[serde(deserialize_with = "de_string")]
+    pub asset_class: String,
+    /// e.g. `"Open"`, `"Closed"`, `"After-Hours"`.
+    #[serde(deserialize_with = "de_string")]
+    pub market_status: String,
+    /// Regular-session price block (or the pre/after-hours one while the
+    /// market is closed).
+    pub primary_data: QuoteDetail,
+    /// Last regular-session close while `primary_data` shows extended hours.
+    pub secondary_data: Option<QuoteDetail>,
+}
+
+/// Outcome of [`Quote::fetch_many`]: the quotes that could be fetched and the
+/// symbols that failed, both in input order.
+#[derive(Debug, Default)]
+pub struct QuoteBatch {
+    pub quotes: Vec<QuoteData>,
+    pub failures: Vec<(String, NgError)>,
+}
+
+/// Service fetching typed Nasdaq quotes.
+pub struct Quote {
+    api: NasdaqApi,
+    logger: Logger,
+}
+
+impl Quote {
+    /// Creates a new instance of `Quote`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
+        Self { api, logger }
+    }
+
+    /// Fetches the quote of `symbol` in `asset_class`.
+    ///
+    /// Unknown symbols surface as `NasdaqBusinessError`; a response without a
+    /// usable `data` block as `MalformedResponse`.
+    pub async fn fetch(&self, symbol: &str, asset_class: AssetClass, options: Option<KyOptions>) -> Result<QuoteData, NgError> {
+        let endpoint = self
+            .api
+            .endpoint(["quote", symbol, "info"])?
+            .query("assetclass", asset_class)
+            .build();
+        self.api.call_typed(&endpoint, options).await
+    }
+
+    /// Fetches the quotes of many symbols, at most `concurrency` at once (at
+    /// least one).
+    ///
+    /// Each request still takes a permit from the adapter's `KyHttp`
+    /// semaphore, so parallelism is also bounded by its `limit`. A failing
+    /// symbol is logged and recorded in `failures`; it does not abort the batch.
+    pub async fn fetch_many<I, S>(
+        &self,
+        symbols: I,
+        asset_class: AssetClass,
+        concurrency: usize,
+        options: Option<KyOptions>,
+    ) -> QuoteBatch
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let symbols: Vec<String> = symbols.into_iter().map(|s| s.as_ref().to_string()).collect();
+        let results: Vec<Result<QuoteData, NgError>> = stream::iter(symbols.iter())
+            .map(|symbol| self.fetch(symbol, asset_class, options.clone()))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut batch = QuoteBatch::default();
+        for (symbol, result) in symbols.into_iter().zip(results) {
+            match result {
+                Ok(quote) => batch.quotes.push(quote),
+                Err(e) => {
+                    warn!(self.logger, "Quote fetch failed", "symbol" => symbol.as_str(), "error" => e.to_string());
+                    batch.failures.push((symbol, e));
+                }
+            }
+        }
+        info!(self.logger, "Quote batch finished", "ok" => batch.quotes.len(), "failed" => batch.failures.len());
+        batch
+    }
+}
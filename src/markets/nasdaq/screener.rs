@@ -0,0 +1,315 @@
+//! # Nasdaq Stock Screener Module
+//!
+//! Typed wrapper over `/api/screener/stocks`. Filters are built with
+//! [`ScreenerFilter::builder`]; several values of one filter are OR-ed by
+//! Nasdaq (sent `|`-separated), different filters are AND-ed. Results are
+//! paged with `offset` / `limit`: [`Screener::fetch_page`] returns one page,
+//! [`Screener::fetch_all`] walks all of them.
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::markets::nasdaq::screener::*;
+//! # async fn run(screener: Screener) -> Result<(), rs_lib_ng::core::error::NgError> {
+//! let filter = ScreenerFilter::builder()
+//!     .exchange(Exchange::Nasdaq)
+//!     .market_cap_range(MarketCap::Mid, MarketCap::Mega)
+//!     .sector(Sector::Technology)
+//!     .region(Region::NorthAmerica)
+//!     .build();
+//! let rows = screener.fetch_all(&filter, None).await?;
+//! # Ok(()) }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use crate::markets::nasdaq::parse::{de_count, de_number, de_string};
+use crate::retrieve::ky_http::KyOptions;
+use crate::debug;
+
+/// Rows requested per page by [`Screener::fetch_all`].
+pub const SCREENER_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Exchange {
+    Nasdaq,
+    Nyse,
+    Amex,
+}
+
+impl Exchange {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Exchange::Nasdaq => "nasdaq",
+            Exchange::Nyse => "nyse",
+            Exchange::Amex => "amex",
+        }
+    }
+}
+
+/// Market capitalization buckets, smallest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MarketCap {
+    /// Under $50M.
+    Nano,
+    /// $50M to $300M.
+    Micro,
+    /// $300M to $2B.
+    Small,
+    /// $2B to $10B.
+    Mid,
+    /// $10B to $200B.
+    Large,
+    /// Over $200B.
+    Mega,
+}
+
+impl MarketCap {
+    const ALL: [MarketCap; 6] = [
+        MarketCap::Nano,
+        MarketCap::Micro,
+        MarketCap::Small,
+        MarketCap::Mid,
+        MarketCap::Large,
+        MarketCap::Mega,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarketCap::Nano => "nano",
+            MarketCap::Micro => "micro",
+            MarketCap::Small => "small",
+            MarketCap::Mid => "mid",
+            MarketCap::Large => "large",
+            MarketCap::Mega => "mega",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sector {
+    BasicMaterials,
+    ConsumerDiscretionary,
+    ConsumerStaples,
+    Energy,
+    Finance,
+    HealthCare,
+    Industrials,
+    RealEstate,
+    Technology,
+    Telecommunications,
+    Utilities,
+}
+
+impl Sector {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sector::BasicMaterials => "basic_materials",
+            Sector::ConsumerDiscretionary => "consumer_discretionary",
+            Sector::ConsumerStaples => "consumer_staples",
+            Sector::Energy => "energy",
+            Sector::Finance => "finance",
+            Sector::HealthCare => "health_care",
+            Sector::Industrials => "industrials",
+            Sector::RealEstate => "real_estate",
+            Sector::Technology => "technology",
+            Sector::Telecommunications => "telecommunications",
+            Sector::Utilities => "utilities",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    Africa,
+    Asia,
+    AustraliaAndSouthPacific,
+    Caribbean,
+    Europe,
+    MiddleEast,
+    NorthAmerica,
+    SouthAmerica,
+}
+
+impl Region {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Region::Africa => "africa",
+            Region::Asia => "asia",
+            Region::AustraliaAndSouthPacific => "australia_and_south_pacific",
+            Region::Caribbean => "caribbean",
+            Region::Europe => "europe",
+            Region::MiddleEast => "middle_east",
+            Region::NorthAmerica => "north_america",
+            Region::SouthAmerica => "south_america",
+        }
+    }
+}
+
+/// Screener filters; empty filters are not sent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScreenerFilter {
+    pub exchanges: Vec<Exchange>,
+    pub market_caps: Vec<MarketCap>,
+    pub sectors: Vec<Sector>,
+    pub regions: Vec<Region>,
+}
+
+impl ScreenerFilter {
+    pub fn builder() -> ScreenerFilterBuilder {
+        ScreenerFilterBuilder::default()
+    }
+}
+
+/// Chained setters over [`ScreenerFilter`]. Each setter adds a value;
+/// duplicates are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ScreenerFilterBuilder {
+    filter: ScreenerFilter,
+}
+
+fn add<T: PartialEq>(values: &mut Vec<T>, value: T) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+impl ScreenerFilterBuilder {
+    pub fn exchange(mut self, exchange: Exchange) -> Self {
+        add(&mut self.filter.exchanges, exchange);
+        self
+    }
+
+    pub fn market_cap(mut self, cap: MarketCap) -> Self {
+        add(&mut self.filter.market_caps, cap);
+        self
+    }
+
+    /// Adds every bucket from `min` to `max` inclusive (in either order).
+    pub fn market_cap_range(mut self, min: MarketCap, max: MarketCap) -> Self {
+        let (lo, hi) = if min <= max { (min, max) } else { (max, min) };
+        for cap in MarketCap::ALL.into_iter().filter(|c| (lo..=hi).contains(c)) {
+            add(&mut self.filter.market_caps, cap);
+        }
+        self
+    }
+
+    pub fn sector(mut self, sector: Sector) -> Self {
+        add(&mut self.filter.sectors, sector);
+        self
+    }
+
+    pub fn region(mut self, region: Region) -> Self {
+        add(&mut self.filter.regions, region);
+        self
+    }
+
+    pub fn build(self) -> ScreenerFilter {
+        self.filter
+    }
+}
+
+/// One screener result row.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct ScreenerRow {
+    #[serde(deserialize_with = "de_string")]
+    pub symbol: String,
+    #[serde(deserialize_with = "de_string")]
+    pub name: String,
+    #[serde(rename = "lastsale", deserialize_with = "de_number")]
+    pub last_sale: Option<f64>,
+    #[serde(rename = "netchange", deserialize_with = "de_number")]
+    pub net_change: Option<f64>,
+    #[serde(rename = "pctchange", deserialize_with = "de_number")]
+    pub pct_change: Option<f64>,
+    #[serde(rename = "marketCap", deserialize_with = "de_number")]
+    pub market_cap: Option<f64>,
+    #[serde(deserialize_with = "de_string")]
+    pub country: String,
+    #[serde(rename = "ipoyear", deserialize_with = "de_count")]
+    pub ipo_year: Option<u64>,
+    #[serde(deserialize_with = "de_count")]
+    pub volume: Option<u64>,
+    #[serde(deserialize_with = "de_string")]
+    pub sector: String,
+    #[serde(deserialize_with = "de_string")]
+    pub industry: String,
+}
+
+/// One page of screener results.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScreenerPage {
+    pub rows: Vec<ScreenerRow>,
+    /// Rows matching the filter across all pages.
+    pub total_records: usize,
+    /// Offset of the first row of this page.
+    pub offset: usize,
+}
+
+/// Service querying the Nasdaq stock screener.
+pub struct Screener {
+    api: NasdaqApi,
+    logger: Logger,
+    page_size: usize,
+}
+
+impl Screener {
+    /// Creates a new instance of `Screener`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
+        Self { api, logger, page_size: SCREENER_PAGE_SIZE }
+    }
+
+    /// Overrides the rows requested per page by `fetch_all` (at least 1).
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Fetches `limit` rows starting at `offset`.
+    pub async fn fetch_page(&self, filter: &ScreenerFilter, offset: usize, limit: usize, options: Option<KyOptions>) -> Result<ScreenerPage, NgError> {
+        let endpoint = self
+            .api
+            .endpoint(["screener", "stocks"])?
+            .query("tableonly", "true")
+            .query("limit", limit)
+            .query("offset", offset)
+            .query_list("exchange", filter.exchanges.iter().map(Exchange::as_str), "|")
+            .query_list("marketcap", filter.market_caps.iter().map(MarketCap::as_str), "|")
+            .query_list("sector", filter.sectors.iter().map(Sector::as_str), "|")
+            .query_list("region", filter.regions.iter().map(Region::as_str), "|")
+            .build();
+        let data = self.api.call_data(&endpoint, options).await?;
+
+        let rows = self.api.decode_rows(&endpoint, &data, "/table/rows")?;
+        let total_records = data
+            .get("totalrecords")
+            .and_then(|t| t.as_u64().or_else(|| t.as_str().and_then(|s| s.replace(',', "").parse().ok())))
+            .map(|t| t as usize)
+            .unwrap_or(0);
+        Ok(ScreenerPage { rows, total_records, offset })
+    }
+
+    /// Fetches every page matching `filter`.
+    pub async fn fetch_all(&self, filter: &ScreenerFilter, options: Option<KyOptions>) -> Result<Vec<ScreenerRow>, NgError> {
+        let mut rows = Vec::new();
+        loop {
+            let page = self.fetch_page(filter, rows.len(), self.page_size, options.clone()).await?;
+            let received = page.rows.len();
+            rows.extend(page.rows);
+            debug!(self.logger, "Fetched Nasdaq screener page", "rows" => received, "total" => page.total_records);
+
+            if received < self.page_size || rows.len() >= page.total_records {
+                break;
+            }
+        }
+        Ok(rows)
+    }
+}
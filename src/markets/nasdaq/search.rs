@@ -0,0 +1,71 @@
+//! # Nasdaq Symbol Search Module
+//!
+//! Resolves free text (a ticker prefix or part of a company name) to listed
+//! symbols through the autocomplete endpoint `/api/autocomplete/slookup/{limit}`.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::de_string;
+use crate::retrieve::ky_http::KyOptions;
+
+/// Most matches requested per lookup.
+pub const SEARCH_RESULT_LIMIT: usize = 10;
+
+/// One symbol matching a lookup, best match first.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SymbolMatch {
+    #[serde(deserialize_with = "de_string")]
+    pub symbol: String,
+    /// Security name, e.g. `"Apple Inc. Common Stock"`.
+    #[serde(deserialize_with = "de_string")]
+    pub name: String,
+    /// Listing exchange as reported, e.g. `"NASDAQ-GS"` or `"NYSE"`.
+    #[serde(deserialize_with = "de_string")]
+    pub exchange: String,
+    /// `None` for asset labels this crate does not know.
+    #[serde(rename(deserialize = "asset"), alias = "assetClass", deserialize_with = "de_asset")]
+    pub asset_class: Option<AssetClass>,
+}
+
+fn de_asset<'de, D: Deserializer<'de>>(d: D) -> Result<Option<AssetClass>, D::Error> {
+    Ok(AssetClass::parse(&de_string(d)?))
+}
+
+/// Service resolving free text to Nasdaq symbols.
+pub struct SymbolSearch {
+    api: NasdaqApi,
+}
+
+impl SymbolSearch {
+    /// Creates a new instance of `SymbolSearch`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        Self { api }
+    }
+
+    /// Symbols matching `query`, best match first. A query without matches
+    /// returns an empty list; a blank one does so without a request.
+    pub async fn lookup(&self, query: &str, options: Option<KyOptions>) -> Result<Vec<SymbolMatch>, NgError> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let endpoint = self
+            .api
+            .endpoint(["autocomplete", "slookup", &SEARCH_RESULT_LIMIT.to_string()])?
+            .query("search", query)
+            .build();
+
+        let body = self.api.call(&endpoint, options).await?;
+        // Nasdaq answers `"data": null` when nothing matches.
+        self.api.decode_rows(&endpoint, &body, "/data")
+    }
+}
@@ -0,0 +1,104 @@
+//! # Nasdaq Time & Sales Module
+//!
+//! Typed trades of the current session from `/api/quote/{symbol}/realtime-trades`.
+//!
+//! The endpoint serves one 30-minute window per request, selected by
+//! `fromTime` (`"09:30"`, `"10:00"`, ...). [`Trades::fetch`] walks the windows
+//! from the requested time to the 16:00 close until it has `limit` trades.
+//! Times are Eastern Time.
+
+use chrono::{NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::error::NgError;
+use crate::debug;
+use crate::loggers::Logger;
+use crate::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use crate::markets::nasdaq::parse::number;
+use crate::retrieve::ky_http::KyOptions;
+
+/// Width of one `fromTime` window, in minutes.
+const WINDOW_MINUTES: u32 = 30;
+
+/// Rows requested per window. Rows come newest first, so a smaller limit
+/// would drop the start of the window rather than its end.
+const WINDOW_ROW_LIMIT: usize = 10_000;
+
+/// One reported trade.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Trade {
+    /// Eastern Time of the trade.
+    pub time: NaiveTime,
+    pub price: f64,
+    pub shares: u64,
+}
+
+/// Service fetching time & sales from Nasdaq.
+pub struct Trades {
+    api: NasdaqApi,
+    logger: Logger,
+}
+
+impl Trades {
+    /// Creates a new instance of `Trades`.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_api(NasdaqApi::new(logger))
+    }
+
+    /// Uses an existing adapter, e.g. one with a custom base URL.
+    pub fn with_api(api: NasdaqApi) -> Self {
+        let logger = api.logger().clone();
+        Self { api, logger }
+    }
+
+    /// Up to `limit` trades of `symbol` at or after `from_time` (ET), oldest first.
+    pub async fn fetch(&self, symbol: &str, from_time: NaiveTime, limit: usize, options: Option<KyOptions>) -> Result<Vec<Trade>, NgError> {
+        let close = NaiveTime::from_hms_opt(16, 0, 0).unwrap_or(NaiveTime::MIN);
+        let mut window = window_start(from_time);
+        let mut trades = Vec::new();
+
+        while trades.len() < limit && window < close {
+            let endpoint = self
+                .api
+                .endpoint(["quote", symbol, "realtime-trades"])?
+                .query("assetclass", AssetClass::Stocks)
+                .query("limit", WINDOW_ROW_LIMIT)
+                .query("fromTime", window.format("%H:%M"))
+                .build();
+            let data = self.api.call_data(&endpoint, options.clone()).await?;
+
+            let mut page: Vec<Trade> = data
+                .get("rows")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or(&[])
+                .iter()
+                .filter_map(trade_row)
+                .filter(|t| t.time >= from_time)
+                .collect();
+            page.sort_by_key(|t| t.time);
+            debug!(self.logger, "Fetched Nasdaq trades window", "symbol" => symbol, "from" => window.format("%H:%M").to_string(), "rows" => page.len());
+            trades.extend(page);
+
+            window += chrono::Duration::minutes(i64::from(WINDOW_MINUTES));
+        }
+        trades.truncate(limit);
+        Ok(trades)
+    }
+}
+
+/// Start of the 30-minute window containing `time`.
+fn window_start(time: NaiveTime) -> NaiveTime {
+    let minute = time.minute() - time.minute() % WINDOW_MINUTES;
+    NaiveTime::from_hms_opt(time.hour(), minute, 0).unwrap_or(time)
+}
+
+/// One `rows[]` entry, e.g. `{"nlsTime": "15:59:59", "nlsPrice": "$ 189.84", "nlsShareVolume": "100"}`.
+fn trade_row(row: &Value) -> Option<Trade> {
+    let text = |k: &str| row.get(k).and_then(Value::as_str);
+    let time = NaiveTime::parse_from_str(text("nlsTime")?.trim(), "%H:%M:%S").ok()?;
+    let price = number(text("nlsPrice")?)?;
+    let shares = text("nlsShareVolume").and_then(number).filter(|v| *v >= 0.0).map(|v| v as u64).unwrap_or(0);
+    Some(Trade { time, price, shares })
+}
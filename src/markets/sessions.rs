@@ -0,0 +1,180 @@
+//! # Market Session Scheduler
+//!
+//! Background task that follows the US equity session and broadcasts a
+//! [`SessionEvent`] at each transition: pre-market open, regular open, regular
+//! close and after-hours close, or one `HolidayDetected` for a weekday without
+//! a session. Pollers and streamers [`subscribe`](MarketSessionScheduler::subscribe)
+//! instead of each running their own `wait_until_open` loop.
+//!
+//! Once per day the scheduler fetches `MarketStatus` and takes that day's
+//! session times from it (so early closes are honoured). When the API is
+//! unavailable, or its times are for another day, the [`TradingCalendar`]
+//! (the embedded fallback by default) provides the schedule. Transitions that
+//! already passed when a day is planned are not emitted.
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::markets::sessions::{MarketSessionScheduler, SessionEvent};
+//! # use rs_lib_ng::markets::nasdaq::marketstatus::MarketStatus;
+//! # use std::sync::Arc;
+//! # async fn run(status: Arc<MarketStatus>, logger: rs_lib_ng::loggers::Logger) {
+//! let scheduler = Arc::new(MarketSessionScheduler::new(status, logger));
+//! let mut events = scheduler.subscribe();
+//! tokio::spawn({ let s = scheduler.clone(); async move { s.run().await } });
+//! while let Ok(event) = events.recv().await {
+//!     if let SessionEvent::RegularOpen { .. } = event { /* start polling */ }
+//! }
+//! # }
+//! ```
+
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::US::Eastern;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::loggers::Logger;
+use crate::markets::nasdaq::calendar::TradingCalendar;
+use crate::markets::nasdaq::marketstatus::{parse_session_time, MarketStatus, MarketStatusData};
+use crate::retrieve::clock::{Clock, TokioClock};
+use crate::{info, warn};
+
+/// Events kept for slow subscribers before they start missing some.
+const CHANNEL_CAPACITY: usize = 32;
+
+/// A session transition on an Eastern Time trading date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionEvent {
+    PreMarketOpen { date: NaiveDate },
+    RegularOpen { date: NaiveDate },
+    RegularClose { date: NaiveDate },
+    AfterHoursClose { date: NaiveDate },
+    /// A weekday without a session.
+    HolidayDetected { date: NaiveDate },
+}
+
+/// What the scheduler will do on one ET date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DayPlan {
+    Weekend,
+    Holiday,
+    Session(Vec<(DateTime<Utc>, SessionEvent)>),
+}
+
+/// Follows the session and broadcasts its transitions.
+pub struct MarketSessionScheduler {
+    status: Arc<MarketStatus>,
+    calendar: TradingCalendar,
+    clock: Arc<dyn Clock>,
+    sender: broadcast::Sender<SessionEvent>,
+    logger: Logger,
+}
+
+impl MarketSessionScheduler {
+    /// Creates a scheduler on top of `status`, with the embedded calendar as fallback.
+    pub fn new(status: Arc<MarketStatus>, logger: Logger) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            status,
+            calendar: TradingCalendar::fallback(),
+            clock: Arc::new(TokioClock),
+            sender,
+            logger,
+        }
+    }
+
+    /// Replaces the fallback calendar, e.g. with `MarketCalendar::fetch_or_fallback`.
+    pub fn with_calendar(mut self, calendar: TradingCalendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    /// Time source for "now" and the waits between transitions.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// A new receiver of all events sent from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<SessionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Follows the session forever, broadcasting each transition.
+    pub async fn run(&self) {
+        loop {
+            let now = self.clock.now();
+            let today = now.with_timezone(&Eastern).date_naive();
+            let status = match self.status.fetch_status(None).await {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    warn!(self.logger, "Market status unavailable, using calendar", "error" => e.to_string());
+                    None
+                }
+            };
+
+            match plan_day(today, status.as_ref(), &self.calendar) {
+                DayPlan::Weekend => {}
+                DayPlan::Holiday => self.emit(SessionEvent::HolidayDetected { date: today }),
+                DayPlan::Session(transitions) => {
+                    for (at, event) in transitions.into_iter().filter(|(at, _)| *at > now) {
+                        self.sleep_until(at).await;
+                        self.emit(event);
+                    }
+                }
+            }
+
+            let tomorrow = Eastern
+                .from_local_datetime(&(today + ChronoDuration::days(1)).and_time(NaiveTime::MIN))
+                .earliest()
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_else(|| self.clock.now() + ChronoDuration::hours(24));
+            self.sleep_until(tomorrow).await;
+        }
+    }
+
+    fn emit(&self, event: SessionEvent) {
+        info!(self.logger, "Market session transition", "event" => format!("{:?}", event));
+        // No subscribers is not an error.
+        let _ = self.sender.send(event);
+    }
+
+    async fn sleep_until(&self, at: DateTime<Utc>) {
+        let delay = at.signed_duration_since(self.clock.now()).to_std().unwrap_or_default();
+        if !delay.is_zero() {
+            self.clock.sleep(delay).await;
+        }
+    }
+}
+
+/// Session transitions of `date`, from `status` when it describes that date,
+/// otherwise from `calendar`.
+fn plan_day(date: NaiveDate, status: Option<&MarketStatusData>, calendar: &TradingCalendar) -> DayPlan {
+    if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+        return DayPlan::Weekend;
+    }
+    let on_date = |raw: &str| parse_session_time(raw).filter(|t| t.date_naive() == date).map(|t| t.with_timezone(&Utc));
+
+    let from_status = status.and_then(|s| {
+        Some([
+            on_date(&s.pre_market_opening_time)?,
+            on_date(&s.market_opening_time)?,
+            on_date(&s.market_closing_time)?,
+            on_date(&s.after_hours_market_closing_time)?,
+        ])
+    });
+    let times = match (from_status, status) {
+        (Some(times), _) => times,
+        // On a weekday closure Nasdaq reports the next session's times and no business day.
+        (None, Some(s)) if !s.is_business_day => return DayPlan::Holiday,
+        _ => match calendar.session_times(date) {
+            Some(t) => [t.pre_market_open, t.regular_open, t.regular_close, t.after_hours_close],
+            None => return DayPlan::Holiday,
+        },
+    };
+    DayPlan::Session(vec![
+        (times[0], SessionEvent::PreMarketOpen { date }),
+        (times[1], SessionEvent::RegularOpen { date }),
+        (times[2], SessionEvent::RegularClose { date }),
+        (times[3], SessionEvent::AfterHoursClose { date }),
+    ])
+}
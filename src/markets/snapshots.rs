@@ -0,0 +1,267 @@
+//! # Intraday Snapshot Scheduler
+//!
+//! Periodically captures selected market endpoints (market status, Fear & Greed,
+//! or any custom source) at configured Eastern Time instants and stores only
+//! snapshots whose content changed since the previous capture of the same source.
+//!
+//! The stored series can be queried per source and per trading day, which is
+//! what the daily report consumes.
+
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::US::Eastern;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::markets::cnn::fearandgreed::FearAndGreed;
+use crate::markets::nasdaq::marketstatus::MarketStatus;
+use crate::{info, warn};
+
+/// Async producer of a snapshot payload.
+pub type SnapshotFetcher = Arc<dyn Fn() -> BoxFuture<'static, Result<Value, NgError>> + Send + Sync>;
+
+/// A single stored snapshot of one source.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Snapshot {
+    /// Name of the source that produced the payload.
+    pub source: String,
+    /// Instant the snapshot was captured.
+    pub taken_at: DateTime<Utc>,
+    /// Hex SHA-256 of the canonical JSON payload.
+    pub hash: String,
+    /// The captured payload.
+    pub data: Value,
+}
+
+/// Computes the content hash used for change detection.
+///
+/// `serde_json` serializes object keys in sorted order, so equal payloads
+/// always produce equal hashes.
+pub fn content_hash(data: &Value) -> String {
+    hex::encode(Sha256::digest(data.to_string().as_bytes()))
+}
+
+/// Deduplicating snapshot storage, optionally persisted as JSON lines.
+#[derive(Debug, Default)]
+pub struct SnapshotStore {
+    series: HashMap<String, Vec<Snapshot>>,
+    path: Option<PathBuf>,
+}
+
+impl SnapshotStore {
+    /// Creates an in-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store backed by a JSON-lines file, loading any existing entries.
+    pub fn with_file(path: impl Into<PathBuf>) -> Result<Self, NgError> {
+        let path = path.into();
+        let mut store = Self { series: HashMap::new(), path: None };
+
+        if path.exists() {
+            let file = std::fs::File::open(&path)
+                .map_err(|e| NgError::InternalError(format!("Snapshot store open failed: {}", e)))?;
+            for line in std::io::BufReader::new(file).lines() {
+                let line = line.map_err(|e| NgError::InternalError(format!("Snapshot store read failed: {}", e)))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let snap: Snapshot = serde_json::from_str(&line)
+                    .map_err(|e| NgError::InternalError(format!("Corrupt snapshot entry: {}", e)))?;
+                store.series.entry(snap.source.clone()).or_default().push(snap);
+            }
+        }
+
+        store.path = Some(path);
+        Ok(store)
+    }
+
+    /// Records a payload. Returns `true` if it differed from the last stored one.
+    pub fn record(&mut self, source: &str, taken_at: DateTime<Utc>, data: Value) -> Result<bool, NgError> {
+        let hash = content_hash(&data);
+        let entries = self.series.entry(source.to_string()).or_default();
+        if entries.last().is_some_and(|last| last.hash == hash) {
+            return Ok(false);
+        }
+
+        let snap = Snapshot { source: source.to_string(), taken_at, hash, data };
+        if let Some(path) = &self.path {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| NgError::InternalError(format!("Snapshot store open failed: {}", e)))?;
+            let line = serde_json::to_string(&snap).map_err(|e| NgError::InternalError(e.to_string()))?;
+            writeln!(file, "{}", line)
+                .map_err(|e| NgError::InternalError(format!("Snapshot store write failed: {}", e)))?;
+        }
+        entries.push(snap);
+        Ok(true)
+    }
+
+    /// Returns all stored snapshots of a source, oldest first.
+    pub fn series(&self, source: &str) -> Vec<Snapshot> {
+        self.series.get(source).cloned().unwrap_or_default()
+    }
+
+    /// Returns the snapshots of a source captured on the given Eastern Time date.
+    pub fn series_for_day(&self, source: &str, day: NaiveDate) -> Vec<Snapshot> {
+        self.series(source)
+            .into_iter()
+            .filter(|s| s.taken_at.with_timezone(&Eastern).date_naive() == day)
+            .collect()
+    }
+
+    /// Returns the names of all sources with stored snapshots.
+    pub fn sources(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.series.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// Captures registered sources at fixed Eastern Time instants every day.
+pub struct SnapshotScheduler {
+    sources: Vec<(String, SnapshotFetcher)>,
+    times: Vec<NaiveTime>,
+    store: Arc<Mutex<SnapshotStore>>,
+    logger: Logger,
+}
+
+impl SnapshotScheduler {
+    /// Creates a scheduler with an in-memory store and no sources.
+    pub fn new(logger: Logger) -> Self {
+        Self::with_store(logger, SnapshotStore::new())
+    }
+
+    /// Creates a scheduler writing into the given store.
+    pub fn with_store(logger: Logger, store: SnapshotStore) -> Self {
+        Self {
+            sources: Vec::new(),
+            times: Vec::new(),
+            store: Arc::new(Mutex::new(store)),
+            logger,
+        }
+    }
+
+    /// Sets the Eastern Time instants at which snapshots are taken each day.
+    pub fn at_times(mut self, times: Vec<NaiveTime>) -> Self {
+        let mut times = times;
+        times.sort();
+        times.dedup();
+        self.times = times;
+        self
+    }
+
+    /// Registers a custom source under the given name.
+    pub fn with_source(mut self, name: &str, fetcher: SnapshotFetcher) -> Self {
+        self.sources.push((name.to_string(), fetcher));
+        self
+    }
+
+    /// Registers the Nasdaq market-info endpoint as the `market_status` source.
+    pub fn with_market_status(self, service: Arc<MarketStatus>) -> Self {
+        self.with_source("market_status", Arc::new(move || {
+            let service = service.clone();
+            Box::pin(async move {
+                let status = service.fetch_status(None).await?;
+                serde_json::to_value(status).map_err(|e| NgError::InternalError(e.to_string()))
+            })
+        }))
+    }
+
+    /// Registers the CNN Fear & Greed index as the `fear_and_greed` source.
+    ///
+    /// Only the current reading is stored so the 125-day history does not
+    /// defeat change detection.
+    pub fn with_fear_and_greed(self, service: Arc<FearAndGreed>) -> Self {
+        self.with_source("fear_and_greed", Arc::new(move || {
+            let service = service.clone();
+            Box::pin(async move {
+                let status = service.fetch_latest(None).await?;
+                serde_json::to_value(status.current).map_err(|e| NgError::InternalError(e.to_string()))
+            })
+        }))
+    }
+
+    /// Shared handle to the underlying store.
+    pub fn store(&self) -> Arc<Mutex<SnapshotStore>> {
+        self.store.clone()
+    }
+
+    /// Returns the stored series for a source.
+    pub fn series(&self, source: &str) -> Vec<Snapshot> {
+        self.store.lock().map(|s| s.series(source)).unwrap_or_default()
+    }
+
+    /// Returns the stored series for a source on the given Eastern Time date.
+    pub fn series_for_day(&self, source: &str, day: NaiveDate) -> Vec<Snapshot> {
+        self.store.lock().map(|s| s.series_for_day(source, day)).unwrap_or_default()
+    }
+
+    /// Computes the next scheduled instant strictly after `now`.
+    ///
+    /// Returns `None` if no times are configured.
+    pub fn next_run_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let now_et = now.with_timezone(&Eastern);
+        for day_offset in 0..=1 {
+            let day = now_et.date_naive() + ChronoDuration::days(day_offset);
+            for t in &self.times {
+                if let Some(candidate) = Eastern.from_local_datetime(&day.and_time(*t)).earliest()
+                    && candidate > now_et
+                {
+                    return Some(candidate.with_timezone(&Utc));
+                }
+            }
+        }
+        None
+    }
+
+    /// Captures every registered source once.
+    ///
+    /// Returns the names of the sources whose payload changed. Failed fetches
+    /// are logged and skipped so one broken endpoint does not stop the others.
+    pub async fn snapshot_now(&self) -> Vec<String> {
+        let mut changed = Vec::new();
+        for (name, fetcher) in &self.sources {
+            match (fetcher)().await {
+                Ok(data) => {
+                    let recorded = self.store.lock()
+                        .map_err(|_| NgError::InternalError("Snapshot store poisoned".into()))
+                        .and_then(|mut s| s.record(name, Utc::now(), data));
+                    match recorded {
+                        Ok(true) => changed.push(name.clone()),
+                        Ok(false) => {}
+                        Err(e) => warn!(self.logger, "Snapshot store failure", "source" => name, "error" => e.to_string()),
+                    }
+                }
+                Err(e) => warn!(self.logger, "Snapshot fetch failed", "source" => name, "error" => e.to_string()),
+            }
+        }
+        changed
+    }
+
+    /// Runs the schedule forever, capturing all sources at each configured time.
+    ///
+    /// Returns immediately with an error if no times are configured.
+    pub async fn run(&self) -> Result<(), NgError> {
+        loop {
+            let next = self.next_run_after(Utc::now())
+                .ok_or_else(|| NgError::ConfigError("SnapshotScheduler has no times configured".into()))?;
+            let delay = next.signed_duration_since(Utc::now()).to_std().unwrap_or_default();
+            info!(self.logger, "Next snapshot scheduled", "at" => next.to_rfc3339());
+            tokio::time::sleep(delay).await;
+
+            let changed = self.snapshot_now().await;
+            info!(self.logger, "Snapshot round complete", "changed" => changed);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! src/retrieve/auth.rs
+//!
+//! Request authentication for `KyHttp`.
+//!
+//! An [`AuthProvider`] is consulted before every attempt and returns the
+//! `Authorization` header value to send. When a response is `401`, KyHttp asks
+//! the provider to [`invalidate`](AuthProvider::invalidate) the rejected
+//! credentials and, if it agrees, repeats the attempt once with fresh ones.
+//! Requests whose headers already carry `Authorization` are left untouched.
+//!
+//! [`BearerTokenProvider`] covers the common case: it caches a token obtained
+//! from a fetch callback and refreshes it shortly before it expires or after
+//! it was rejected.
+
+use futures::future::BoxFuture;
+use reqwest::header::HeaderValue;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::core::error::NgError;
+
+/// Source of `Authorization` header values.
+pub trait AuthProvider: Send + Sync {
+    /// Returns the header value for the next attempt.
+    fn authorization<'a>(&'a self) -> BoxFuture<'a, Result<HeaderValue, NgError>>;
+
+    /// Called when `rejected` earned a `401`. Returns `true` if new
+    /// credentials are available and the attempt should be repeated once.
+    fn invalidate<'a>(&'a self, rejected: &'a HeaderValue) -> BoxFuture<'a, Result<bool, NgError>> {
+        let _ = rejected;
+        Box::pin(async { Ok(false) })
+    }
+}
+
+/// A bearer token and its expiry.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    pub token: String,
+    /// `None` for tokens that only expire by being rejected.
+    pub expires_at: Option<Instant>,
+}
+
+impl AccessToken {
+    /// Token valid for `expires_in` from now (e.g. an OAuth `expires_in`).
+    pub fn new(token: &str, expires_in: Option<Duration>) -> Self {
+        Self { token: token.to_string(), expires_at: expires_in.map(|d| Instant::now() + d) }
+    }
+
+    fn is_fresh(&self, margin: Duration) -> bool {
+        self.expires_at.is_none_or(|at| Instant::now() + margin < at)
+    }
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessToken").field("token", &"***").field("expires_at", &self.expires_at).finish()
+    }
+}
+
+/// Callback obtaining a new token, e.g. from a login or token endpoint.
+pub type TokenFetchFn = Arc<dyn Fn() -> BoxFuture<'static, Result<AccessToken, NgError>> + Send + Sync>;
+
+/// Default time before expiry at which [`BearerTokenProvider`] refreshes.
+pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// Caching `Authorization: Bearer <token>` provider.
+///
+/// Concurrent callers share one refresh: the cache is locked while a token is
+/// fetched, and a `401` only drops the token if it is still the cached one.
+pub struct BearerTokenProvider {
+    fetch: TokenFetchFn,
+    refresh_margin: Duration,
+    cached: Mutex<Option<AccessToken>>,
+}
+
+impl BearerTokenProvider {
+    /// Provider fetching tokens with `fetch`.
+    pub fn new(fetch: TokenFetchFn) -> Self {
+        Self { fetch, refresh_margin: DEFAULT_REFRESH_MARGIN, cached: Mutex::new(None) }
+    }
+
+    /// Refreshes tokens `margin` before they expire.
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.refresh_margin = margin;
+        self
+    }
+
+    /// Returns a fresh token, fetching one if needed.
+    pub async fn token(&self) -> Result<AccessToken, NgError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref().filter(|t| t.is_fresh(self.refresh_margin)) {
+            return Ok(token.clone());
+        }
+        let token = (self.fetch)().await?;
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+
+    fn header(token: &AccessToken) -> Result<HeaderValue, NgError> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token.token))
+            .map_err(|_| NgError::InternalError("Access token is not a valid header value".into()))?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+}
+
+impl AuthProvider for BearerTokenProvider {
+    fn authorization<'a>(&'a self) -> BoxFuture<'a, Result<HeaderValue, NgError>> {
+        Box::pin(async move { Self::header(&self.token().await?) })
+    }
+
+    fn invalidate<'a>(&'a self, rejected: &'a HeaderValue) -> BoxFuture<'a, Result<bool, NgError>> {
+        Box::pin(async move {
+            let mut cached = self.cached.lock().await;
+            let is_rejected = cached.as_ref().and_then(|t| Self::header(t).ok()).is_some_and(|h| h == rejected);
+            if is_rejected {
+                *cached = None;
+            }
+            // Either the rejected token was dropped or another caller already replaced it.
+            Ok(true)
+        })
+    }
+}
@@ -0,0 +1,149 @@
+//! src/retrieve/cache.rs
+//!
+//! Conditional-request cache for KyHttp GETs.
+//!
+//! Successful responses carrying an `ETag` or `Last-Modified` header are stored.
+//! The next GET of the same URL and headers revalidates with `If-None-Match` /
+//! `If-Modified-Since`; a `304 Not Modified` answer is served from the stored
+//! copy as a regular `200`.
+//!
+//! Storage is pluggable through [`CacheStore`]; [`LruCacheStore`] keeps a bounded
+//! number of entries in memory.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A stored response, as replayed on `304 Not Modified`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// Original status code (always a 2xx).
+    pub status: u16,
+    /// Response headers, in received order.
+    pub headers: Vec<(String, String)>,
+    /// Response body.
+    pub body: String,
+}
+
+impl CachedResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// The stored `ETag` validator.
+    pub fn etag(&self) -> Option<&str> {
+        self.header("etag")
+    }
+
+    /// The stored `Last-Modified` validator.
+    pub fn last_modified(&self) -> Option<&str> {
+        self.header("last-modified")
+    }
+}
+
+/// Backing storage for cached responses.
+pub trait CacheStore: Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedResponse>;
+    fn put(&self, key: &str, response: CachedResponse);
+    fn remove(&self, key: &str);
+}
+
+/// In-memory store evicting the least recently used entry beyond `capacity`.
+#[derive(Debug)]
+pub struct LruCacheStore {
+    capacity: usize,
+    inner: Mutex<LruInner>,
+}
+
+#[derive(Debug, Default)]
+struct LruInner {
+    entries: HashMap<String, CachedResponse>,
+    order: VecDeque<String>,
+}
+
+impl LruInner {
+    fn touch(&mut self, key: &str) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(key.to_string());
+    }
+}
+
+impl LruCacheStore {
+    /// Creates a store holding at most `capacity` responses (minimum 1).
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), inner: Mutex::new(LruInner::default()) }
+    }
+
+    /// Number of stored responses.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).entries.len()
+    }
+
+    /// Returns true if nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CacheStore for LruCacheStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let hit = inner.entries.get(key).cloned();
+        if hit.is_some() {
+            inner.touch(key);
+        }
+        hit
+    }
+
+    fn put(&self, key: &str, response: CachedResponse) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.entries.insert(key.to_string(), response);
+        inner.touch(key);
+        while inner.entries.len() > self.capacity {
+            let Some(oldest) = inner.order.pop_front() else { break };
+            inner.entries.remove(&oldest);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.entries.remove(key);
+        inner.order.retain(|k| k != key);
+    }
+}
+
+/// Caching behaviour of a KyHttp instance.
+#[derive(Clone, Default)]
+pub enum CachePolicy {
+    /// Every GET goes to the network unconditionally.
+    #[default]
+    Disabled,
+    /// GETs are revalidated against the given store.
+    Revalidate(Arc<dyn CacheStore>),
+}
+
+impl CachePolicy {
+    /// Revalidating cache backed by an in-memory LRU of `capacity` entries.
+    pub fn in_memory(capacity: usize) -> Self {
+        CachePolicy::Revalidate(Arc::new(LruCacheStore::new(capacity)))
+    }
+
+    /// Returns the store, if caching is enabled.
+    pub fn store(&self) -> Option<&Arc<dyn CacheStore>> {
+        match self {
+            CachePolicy::Disabled => None,
+            CachePolicy::Revalidate(store) => Some(store),
+        }
+    }
+}
+
+impl fmt::Debug for CachePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CachePolicy::Disabled => write!(f, "Disabled"),
+            CachePolicy::Revalidate(_) => write!(f, "Revalidate(..)"),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! src/retrieve/cassette.rs
+//!
+//! Record/replay ("VCR") support for `KyHttp`.
+//!
+//! With a [`Cassette`] in `KyOptions::cassette`, every attempt goes through it:
+//!
+//! - [`CassetteMode::Record`] sends the request, stores the response in a
+//!   JSON cassette file and hands the caller an identical response.
+//! - [`CassetteMode::Replay`] answers from the file without network access.
+//!
+//! Interactions are matched on method, URL and request body. Identical
+//! requests replay their recordings in order, so retry sequences (e.g. a 503
+//! followed by a 200) are reproduced. Once all are used, the last one repeats.
+//! A request without a recording gets a `404` whose body names the request.
+//! Request headers are not stored, so credentials never end up in the file;
+//! `Set-Cookie` response headers are dropped for the same reason. Query
+//! parameters carrying keys (`api_key`, `apikey`, `token`, `access_token` by
+//! default, see [`Cassette::with_redacted_params`]) are stored as `REDACTED`,
+//! and replayed requests are redacted the same way before matching. The file is
+//! rewritten after each recorded interaction; call [`Cassette::save`] to
+//! check that it was written.
+
+use base64::Engine;
+use bytes::Bytes;
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_LENGTH, SET_COOKIE};
+use reqwest::{Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::core::error::NgError;
+
+/// Query parameters redacted by default.
+pub const DEFAULT_REDACTED_PARAMS: [&str; 4] = ["api_key", "apikey", "token", "access_token"];
+
+/// Stored in place of a redacted query parameter value.
+const REDACTED: &str = "REDACTED";
+
+/// Whether a cassette talks to the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Send requests and append them to the cassette file.
+    Record,
+    /// Serve responses from the cassette file only.
+    Replay,
+}
+
+/// The request side of an interaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// The response side of an interaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Body text, or base64 when `base64` is set (non UTF-8 bodies).
+    pub body: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub base64: bool,
+}
+
+/// One recorded request/response pair.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Default)]
+struct Tape {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+/// A cassette file shared by the clients recording into or replaying from it.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    tape: Mutex<Tape>,
+    /// Lowercased names of the query parameters to redact.
+    redacted_params: Vec<String>,
+}
+
+impl Cassette {
+    /// Records into `path`, replacing its contents on the first interaction.
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::new(path.as_ref(), CassetteMode::Record, Tape::default())
+    }
+
+    /// Replays the interactions stored in `path`.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, NgError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| NgError::ConfigError(format!("Cannot read cassette {}: {}", path.display(), e)))?;
+        let interactions: Vec<Interaction> = serde_json::from_str(&text)
+            .map_err(|e| NgError::ConfigError(format!("Invalid cassette {}: {}", path.display(), e)))?;
+        let used = vec![false; interactions.len()];
+        Ok(Self::new(path, CassetteMode::Replay, Tape { interactions, used }))
+    }
+
+    fn new(path: &Path, mode: CassetteMode, tape: Tape) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            mode,
+            tape: Mutex::new(tape),
+            redacted_params: DEFAULT_REDACTED_PARAMS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    /// Replaces the query parameters (matched case-insensitively) whose values
+    /// are stored as `REDACTED`; defaults to [`DEFAULT_REDACTED_PARAMS`].
+    pub fn with_redacted_params<'a>(mut self, params: impl IntoIterator<Item = &'a str>) -> Self {
+        self.redacted_params = params.into_iter().map(str::to_ascii_lowercase).collect();
+        self
+    }
+
+    /// Replays `path` if it exists, otherwise records into it. Handy for tests
+    /// that capture live traffic once and run offline afterwards.
+    pub fn replay_or_record(path: impl AsRef<Path>) -> Result<Self, NgError> {
+        if path.as_ref().exists() { Self::replay(path) } else { Ok(Self::record(path)) }
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Interactions recorded or loaded so far.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().interactions.clone()
+    }
+
+    /// Sends `req` with `client` (recording) or answers it from the tape (replaying).
+    pub(crate) async fn execute(&self, client: &reqwest::Client, req: Request) -> Result<Response, reqwest::Error> {
+        let recorded = self.recorded_request(&req);
+        if self.mode == CassetteMode::Replay {
+            return Ok(self.replay_response(&recorded));
+        }
+
+        let resp = client.execute(req).await?;
+        let status = resp.status();
+        let headers: Vec<(String, String)> = resp
+            .headers()
+            .iter()
+            // The body is stored as sent, so KyHttp decodes replays like live
+            // responses; the length is recomputed when it is replayed.
+            .filter(|(k, _)| ![SET_COOKIE, CONTENT_LENGTH].contains(k))
+            .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.to_string(), v.to_string())))
+            .collect();
+        let body = resp.bytes().await?;
+        let (text, base64) = match std::str::from_utf8(&body) {
+            Ok(s) => (s.to_string(), false),
+            Err(_) => (base64::engine::general_purpose::STANDARD.encode(&body), true),
+        };
+        let response = RecordedResponse { status: status.as_u16(), headers, body: text, base64 };
+        let replayed = Self::to_response(&response, body);
+        self.append(Interaction { request: recorded, response });
+        Ok(replayed)
+    }
+
+    fn recorded_request(&self, req: &Request) -> RecordedRequest {
+        RecordedRequest {
+            method: req.method().to_string(),
+            url: self.redacted_url(req.url()),
+            body: req.body().and_then(|b| b.as_bytes()).map(|b| String::from_utf8_lossy(b).into_owned()),
+        }
+    }
+
+    /// `url` with the values of redacted query parameters replaced. URLs
+    /// without any are returned unchanged.
+    fn redacted_url(&self, url: &reqwest::Url) -> String {
+        let redact = |name: &str| self.redacted_params.iter().any(|p| p.eq_ignore_ascii_case(name));
+        if !url.query_pairs().any(|(k, _)| redact(&k)) {
+            return url.to_string();
+        }
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(k, v)| {
+                let v = if redact(&k) { REDACTED.to_string() } else { v.into_owned() };
+                (k.into_owned(), v)
+            })
+            .collect();
+        let mut redacted = url.clone();
+        redacted.query_pairs_mut().clear().extend_pairs(pairs);
+        redacted.to_string()
+    }
+
+    fn replay_response(&self, request: &RecordedRequest) -> Response {
+        let mut tape = self.lock();
+        let matching: Vec<usize> = (0..tape.interactions.len())
+            .filter(|&i| tape.interactions[i].request == *request)
+            .collect();
+        let Some(&idx) = matching.iter().find(|&&i| !tape.used[i]).or(matching.last()) else {
+            let body = format!("No cassette interaction for {} {}", request.method, request.url);
+            let miss = RecordedResponse { status: 404, headers: Vec::new(), body: body.clone(), base64: false };
+            return Self::to_response(&miss, Bytes::from(body));
+        };
+        tape.used[idx] = true;
+        let response = &tape.interactions[idx].response;
+        let body = if response.base64 {
+            base64::engine::general_purpose::STANDARD.decode(&response.body).unwrap_or_default().into()
+        } else {
+            Bytes::from(response.body.clone())
+        };
+        Self::to_response(response, body)
+    }
+
+    fn to_response(recorded: &RecordedResponse, body: Bytes) -> Response {
+        let mut resp = http::Response::new(body);
+        *resp.status_mut() = StatusCode::from_u16(recorded.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        for (k, v) in &recorded.headers {
+            if let (Ok(k), Ok(v)) = (HeaderName::from_bytes(k.as_bytes()), HeaderValue::from_str(v)) {
+                resp.headers_mut().append(k, v);
+            }
+        }
+        Response::from(resp)
+    }
+
+    /// Writes all interactions to the cassette file.
+    pub fn save(&self) -> Result<(), NgError> {
+        Self::write(&self.path, &self.lock().interactions)
+    }
+
+    fn write(path: &Path, interactions: &[Interaction]) -> Result<(), NgError> {
+        let json = serde_json::to_string_pretty(interactions)
+            .map_err(|e| NgError::InternalError(format!("Cassette encode failed: {}", e)))?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| NgError::InternalError(format!("Cassette write failed: {}", e)))?;
+        }
+        std::fs::write(path, json).map_err(|e| NgError::InternalError(format!("Cassette write failed: {}", e)))
+    }
+
+    /// Appends an interaction and rewrites the cassette file.
+    fn append(&self, interaction: Interaction) {
+        let mut tape = self.lock();
+        tape.interactions.push(interaction);
+        tape.used.push(false);
+        // Best effort: a failed write only loses the recording, not the response;
+        // `save` reports it.
+        let _ = Self::write(&self.path, &tape.interactions);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Tape> {
+        self.tape.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
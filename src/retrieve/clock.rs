@@ -0,0 +1,109 @@
+//! src/retrieve/clock.rs
+//!
+//! Time source for `KyHttp` retry timing.
+//!
+//! Backoff, Retry-After and hedge waits go through a [`Clock`], as does the
+//! "now" that an HTTP-date `Retry-After` is measured against. The default
+//! [`TokioClock`] uses `tokio::time::sleep`, so tests running under
+//! `tokio::time::pause()` advance instantly. [`MockClock`] goes further: its sleeps return at once,
+//! advance a virtual wall clock and are recorded, so tests can assert the exact
+//! waits of a retry sequence.
+//!
+//! ```rust
+//! # use rs_lib_ng::retrieve::clock::MockClock;
+//! # use rs_lib_ng::retrieve::ky_http::KyOptions;
+//! # use std::sync::Arc;
+//! let clock = MockClock::new(chrono::Utc::now());
+//! let opts = KyOptions::builder().clock(Arc::new(clock.clone())).build()?;
+//! // ... run requests, then inspect `clock.sleeps()`.
+//! # Ok::<(), rs_lib_ng::core::error::NgError>(())
+//! ```
+//!
+//! `total_deadline`, per-attempt timeouts and the permit re-acquire bound
+//! are tokio timers and are not affected by the clock: a [`MockClock`] ends
+//! every sleep at once, which would expire them immediately.
+
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Wall-clock time and sleeping, as used by the retry loop.
+pub trait Clock: Send + Sync {
+    /// Current time, used to turn an HTTP-date `Retry-After` into a delay.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Waits for `duration`.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// System time and `tokio::time::sleep`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+#[derive(Debug)]
+struct MockState {
+    now: DateTime<Utc>,
+    sleeps: Vec<Duration>,
+}
+
+/// Virtual clock whose sleeps complete immediately.
+///
+/// Clones share the same time and sleep log.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockClock {
+    /// Starts the virtual clock at `start`.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { state: Arc::new(Mutex::new(MockState { now: start, sleeps: Vec::new() })) }
+    }
+
+    /// Moves the virtual clock forward without recording a sleep.
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.lock();
+        state.now = chrono::Duration::from_std(duration)
+            .ok()
+            .and_then(|d| state.now.checked_add_signed(d))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+    }
+
+    /// Every sleep requested so far, in order.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.lock().sleeps.clone()
+    }
+
+    /// Sum of all sleeps requested so far.
+    pub fn total_slept(&self) -> Duration {
+        self.lock().sleeps.iter().sum()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.lock().now
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.advance(duration);
+        self.lock().sleeps.push(duration);
+        // Still yield, so a retry loop cannot starve other tasks.
+        Box::pin(tokio::task::yield_now())
+    }
+}
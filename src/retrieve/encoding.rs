@@ -0,0 +1,113 @@
+//! src/retrieve/encoding.rs
+//!
+//! Content-Encoding decoding for buffered `KyHttp` responses.
+//!
+//! Decoding is opt-in per coding through [`Decompression`]. Enabled codings
+//! are advertised in `Accept-Encoding` (unless the request or
+//! `KyOptions::accept_encoding` sets one) and decoded after the body has been
+//! read, so `ApiResponse::encoded_size` still reports the bytes on the wire.
+//! `download` and `get_stream` hand out the body as sent and never decode.
+
+use bytes::Bytes;
+use std::io::Read;
+
+/// A content coding KyHttp can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentCoding {
+    Gzip,
+    Brotli,
+    Zstd,
+}
+
+impl ContentCoding {
+    /// The coding's `Content-Encoding` token, e.g. `"br"`.
+    pub fn token(&self) -> &'static str {
+        match self {
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Brotli => "br",
+            ContentCoding::Zstd => "zstd",
+        }
+    }
+
+    /// Parses a `Content-Encoding` token; `x-gzip` is gzip.
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(ContentCoding::Gzip),
+            "br" => Some(ContentCoding::Brotli),
+            "zstd" => Some(ContentCoding::Zstd),
+            _ => None,
+        }
+    }
+
+    fn decode(&self, body: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        match self {
+            ContentCoding::Gzip => flate2::read::MultiGzDecoder::new(body).read_to_end(&mut out)?,
+            ContentCoding::Brotli => brotli::Decompressor::new(body, 4096).read_to_end(&mut out)?,
+            ContentCoding::Zstd => zstd::stream::read::Decoder::new(body)?.read_to_end(&mut out)?,
+        };
+        Ok(out)
+    }
+}
+
+/// Which codings are decoded automatically. All are off by default, so
+/// requests ask for identity bodies as before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Decompression {
+    pub gzip: bool,
+    pub brotli: bool,
+    pub zstd: bool,
+}
+
+impl Decompression {
+    /// Decodes every supported coding.
+    pub fn all() -> Self {
+        Self { gzip: true, brotli: true, zstd: true }
+    }
+
+    /// Enables or disables one coding.
+    pub fn set(&mut self, coding: ContentCoding, enabled: bool) {
+        match coding {
+            ContentCoding::Gzip => self.gzip = enabled,
+            ContentCoding::Brotli => self.brotli = enabled,
+            ContentCoding::Zstd => self.zstd = enabled,
+        }
+    }
+
+    pub fn is_enabled(&self, coding: ContentCoding) -> bool {
+        match coding {
+            ContentCoding::Gzip => self.gzip,
+            ContentCoding::Brotli => self.brotli,
+            ContentCoding::Zstd => self.zstd,
+        }
+    }
+
+    /// `Accept-Encoding` offering the enabled codings, or `None` when all are off.
+    pub(crate) fn accept_encoding(&self) -> Option<String> {
+        let offered: Vec<&str> = [ContentCoding::Gzip, ContentCoding::Brotli, ContentCoding::Zstd]
+            .into_iter()
+            .filter(|c| self.is_enabled(*c))
+            .map(|c| c.token())
+            .collect();
+        (!offered.is_empty()).then(|| offered.join(", "))
+    }
+
+    /// Decodes `body` sent with `Content-Encoding: header`.
+    ///
+    /// `Ok(None)` when a listed coding is unknown or disabled, so the body is
+    /// left as sent. Stacked codings (`gzip, br`) are undone last to first.
+    pub(crate) fn decode(&self, header: &str, body: &Bytes) -> std::io::Result<Option<Bytes>> {
+        let mut codings = Vec::new();
+        for token in header.split(',').filter(|t| !t.trim().eq_ignore_ascii_case("identity")) {
+            match ContentCoding::from_token(token) {
+                Some(coding) if self.is_enabled(coding) => codings.push(coding),
+                _ => return Ok(None),
+            }
+        }
+        let mut decoded = body.clone();
+        for coding in codings.iter().rev() {
+            decoded = Bytes::from(coding.decode(&decoded)?);
+        }
+        Ok(Some(decoded))
+    }
+}
@@ -4,17 +4,150 @@
 //! bounded permit re-acquisition, deterministic test hooks, and explicit Retry-After handling.
 use crate::core::error::NgError;
 use crate::loggers::Logger;
+use crate::retrieve::auth::AuthProvider;
+use crate::retrieve::cache::{CachePolicy, CachedResponse};
+use crate::retrieve::cassette::Cassette;
+use crate::retrieve::clock::{Clock, TokioClock};
+use crate::retrieve::encoding::{ContentCoding, Decompression};
+use crate::retrieve::metrics::KyMetrics;
+use crate::retrieve::paginate::Paginator;
+use crate::retrieve::proxy::{ProxyConfig, ProxyScheme};
+use crate::retrieve::rate_limit::{ObservedLimits, ObservedRateLimit, RateLimiter};
+use crate::retrieve::retry_budget::RetryBudget;
+use crate::retrieve::retry_policy::{RetryPolicy, StatusPolicy};
+use crate::retrieve::signing::RequestSigner;
+use crate::retrieve::tls::TlsConfig;
+use crate::retrieve::trace::TraceContext;
 use chrono::{DateTime, Utc};
-use reqwest::{header::HeaderMap, Client, Method, Request, RequestBuilder, StatusCode};
+use bytes::{Bytes, BytesMut};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING, ACCEPT_RANGES, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, RANGE};
+use reqwest::{Client, Method, Request, RequestBuilder, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use std::collections::HashSet;
-use std::sync::Arc;
+use std::borrow::Cow;
+use std::future::Future;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
-use tokio::time::{sleep, timeout};
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 
+/// Predicate deciding whether a failed attempt is retried.
+///
+/// Consulted for network errors and non-success statuses alike; see [`RetryContext`].
+pub type ShouldRetryFn = Arc<dyn Fn(&RetryContext<'_>) -> bool + Send + Sync>;
+
+/// A failed attempt, as seen by [`ShouldRetryFn`].
+///
+/// `status`, `headers` and `body_snippet` are `None` for network errors.
+/// Streaming calls (`download`, `get_stream`) leave the body unread, so their
+/// `body_snippet` is always `None`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryContext<'a> {
+    pub method: &'a Method,
+    pub url: &'a str,
+    pub status: Option<StatusCode>,
+    pub headers: Option<&'a HeaderMap>,
+    /// First 1024 bytes of the response body.
+    pub body_snippet: Option<&'a str>,
+    /// The error reported if the attempt is not retried.
+    pub error: &'a NgError,
+    /// 1-based number of the failed attempt.
+    pub attempt: usize,
+    /// Time since the logical request started.
+    pub elapsed: Duration,
+}
+
+/// Why an attempt is followed by another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryReason {
+    /// Non-success status retried with computed backoff.
+    Status,
+    /// Non-success status retried after the server's Retry-After.
+    RetryAfter,
+    /// Connection or protocol error.
+    Network,
+    /// The attempt timed out (`retry_on_timeout`).
+    Timeout,
+    /// Rejected credentials were refreshed; repeated outside the retry count.
+    AuthRefresh,
+    /// `validate_response` asked to retry a 2xx response.
+    Validation,
+}
+
+impl RetryReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetryReason::Status => "status",
+            RetryReason::RetryAfter => "retry_after",
+            RetryReason::Network => "network",
+            RetryReason::Timeout => "timeout",
+            RetryReason::AuthRefresh => "auth_refresh",
+            RetryReason::Validation => "validation",
+        }
+    }
+}
+
+/// One sent attempt, as logged (`"Request attempt"`) and passed to `on_attempt`.
+///
+/// `backoff` and `retry_reason` are `None` for the last attempt of a request.
+#[derive(Debug, Clone, Copy)]
+pub struct AttemptInfo<'a> {
+    pub method: &'a Method,
+    pub url: &'a str,
+    /// 1-based attempt number.
+    pub attempt: usize,
+    /// `None` for network errors.
+    pub status: Option<StatusCode>,
+    /// Time from sending the attempt to its response (or error).
+    pub elapsed: Duration,
+    /// Wait before the next attempt.
+    pub backoff: Option<Duration>,
+    pub retry_reason: Option<RetryReason>,
+}
+
+/// Callback receiving every [`AttemptInfo`].
+pub type AttemptFn = Arc<dyn Fn(&AttemptInfo<'_>) + Send + Sync>;
+
+/// Verdict of a [`ValidateFn`] on a 2xx response.
+#[derive(Debug, Clone)]
+pub enum Validation {
+    /// Return the response to the caller.
+    Accept,
+    /// Fail the request with this error.
+    Reject(NgError),
+    /// Retry with backoff while attempts remain, then fail with this error.
+    Retry(NgError),
+}
+
+/// Checks a 2xx JSON response before it counts as a success: `(status, headers, body)`.
+pub type ValidateFn = Arc<dyn Fn(StatusCode, &HeaderMap, &serde_json::Value) -> Validation + Send + Sync>;
+
+/// HTTP version selection for a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HttpProtocol {
+    /// Offer `h2` and `http/1.1` via ALPN on TLS connections and let the
+    /// server choose; plain `http://` uses HTTP/1.1.
+    #[default]
+    Negotiate,
+    /// HTTP/1.1 only; ALPN offers just `http/1.1`.
+    Http1Only,
+    /// HTTP/2 without negotiation ("prior knowledge"), also over plain
+    /// `http://`. Fails against servers that do not speak h2.
+    Http2PriorKnowledge,
+}
+
+/// Progress callback for streamed bodies: `(bytes_so_far, total)`.
+///
+/// `total` is the expected full size when the server announced it.
+pub type ProgressFn = Arc<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
 /// KyOptions
 ///
 /// Public options for KyHttp. Includes test hooks for deterministic backoff.
@@ -23,6 +156,15 @@ pub struct KyOptions {
     /// Optional timeout for the underlying reqwest client.
     pub timeout: Option<Duration>,
 
+    /// Upper bound on a whole `get`/`post`/... call, including all retries
+    /// and Retry-After/backoff sleeps. Exceeding it yields
+    /// [`NgError::DeadlineExceeded`]. Not applied to `download`/`get_stream`.
+    ///
+    /// Always measured on tokio time, not on `clock`: a [`MockClock`](crate::retrieve::clock::MockClock)
+    /// finishes its sleeps at once, so it would expire every deadline on the
+    /// first pending attempt. Use `tokio::time::pause()` to test deadlines.
+    pub total_deadline: Option<Duration>,
+
     /// Number of retries (not total attempts). Total attempts = retry + 1.
     pub retry: usize,
 
@@ -30,11 +172,15 @@ pub struct KyOptions {
     /// `semaphore` is not provided.
     pub limit: usize,
 
-    /// Status codes that are considered retryable.
-    pub status_codes: HashSet<StatusCode>,
+    /// Supplies the `Authorization` header of every attempt and fresh
+    /// credentials after a `401` (retried once).
+    pub auth: Option<Arc<dyn AuthProvider>>,
+
+    /// Signs every attempt (retries included) just before it is sent.
+    pub signer: Option<Arc<dyn RequestSigner>>,
 
-    /// Status codes that should be checked for Retry-After header.
-    pub after_status_codes: HashSet<StatusCode>,
+    /// Per-status retry behavior (backoff, Retry-After waits, no retry, hook).
+    pub retry_policy: RetryPolicy,
 
     /// Maximum allowed Retry-After duration (if set).
     pub max_retry_after: Option<Duration>,
@@ -45,10 +191,22 @@ pub struct KyOptions {
     /// Whether to retry on timeout errors.
     pub retry_on_timeout: bool,
 
-    /// Optional predicate to decide whether to retry.
-    /// Receives `Option<&reqwest::Response>` (None for network errors), `&NgError`, and attempt number (1-based).
-    pub should_retry:
-        Option<Arc<dyn Fn(Option<&reqwest::Response>, &NgError, usize) -> bool + Send + Sync>>,
+    /// Called after every chunk received by `download` and `get_stream`.
+    pub on_download_progress: Option<ProgressFn>,
+
+    /// Optional predicate deciding whether a failed attempt is retried.
+    /// For statuses it overrides the retry policy's yes/no, while the policy
+    /// still picks the delay (Retry-After or backoff). Timeouts with
+    /// `retry_on_timeout` off and exhausted attempts are never retried.
+    pub should_retry: Option<ShouldRetryFn>,
+
+    /// Called once per attempt sent, including retries and auth refreshes.
+    pub on_attempt: Option<AttemptFn>,
+
+    /// Optional check of 2xx JSON bodies inside the retry loop, e.g. for
+    /// APIs that report errors with HTTP 200. Non-JSON bodies and streaming
+    /// calls (`download`, `get_stream`) are not validated.
+    pub validate_response: Option<ValidateFn>,
 
     /// Allowed HTTP methods for requests.
     pub allowed_methods: HashSet<Method>,
@@ -59,27 +217,98 @@ pub struct KyOptions {
     /// If true, backoff jitter is deterministic and small for tests.
     pub test_mode: bool,
 
+    /// Time source for backoff, Retry-After and hedge waits (default: [`TokioClock`]).
+    pub clock: Option<Arc<dyn Clock>>,
+
     /// When true, disable jitter entirely.
     pub disable_jitter: bool,
 
     /// Threshold (ms) above which a permit will be released before sleeping.
     pub permit_release_threshold_ms: u64,
+
+    /// When set, a GET attempt that has not answered after this delay is
+    /// duplicated; the first successful response wins and the other is cancelled.
+    pub hedge_after: Option<Duration>,
+
+    /// Optional shared retry budget. Each retry spends a token and each 2xx
+    /// response earns some back; once it is empty, requests fail with
+    /// [`NgError::RetryBudgetExhausted`] instead of retrying.
+    pub retry_budget: Option<Arc<RetryBudget>>,
+
+    /// When true, concurrent identical GETs (same URL and headers) on this
+    /// instance collapse into a single network call whose result is shared.
+    pub single_flight: bool,
+
+    /// Optional shared rate limiter consulted before every attempt (and, for
+    /// the first attempt, before permit acquisition).
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+
+    /// Rate limiter bucket for this instance's requests. `None` keys the
+    /// limiter by the request URL's host.
+    pub rate_limit_bucket: Option<String>,
+
+    /// Pace requests by the `RateLimit-*` / `X-RateLimit-*` headers servers
+    /// send: once a host reports no requests remaining, later requests to it
+    /// wait for the reset (capped like Retry-After). Default: true.
+    pub rate_limit_headers: bool,
+
+    /// Conditional-request cache for GETs (ETag / Last-Modified revalidation).
+    pub cache: CachePolicy,
+
+    /// Proxy for `http://` requests. When neither proxy is set, the
+    /// `HTTP_PROXY`/`HTTPS_PROXY` env vars apply.
+    pub http_proxy: Option<ProxyConfig>,
+
+    /// Proxy for `https://` requests.
+    pub https_proxy: Option<ProxyConfig>,
+
+    /// Root CAs, mTLS client identity and certificate verification.
+    pub tls: TlsConfig,
+
+    /// HTTP/1.1 vs HTTP/2 selection; see [`ApiResponse::version`] for the
+    /// version actually used.
+    pub protocol: HttpProtocol,
+
+    /// Metrics handle to record into. `None` gives each client its own,
+    /// available via [`KyHttp::metrics`].
+    pub metrics: Option<KyMetrics>,
+
+    /// When true, every attempt carries a W3C `traceparent` (and `tracestate`)
+    /// header continuing the caller's trace.
+    pub trace_propagation: bool,
+
+    /// Parent context used when the request headers carry no `traceparent`.
+    /// `None` starts a new trace per logical request.
+    pub trace_context: Option<TraceContext>,
+
+    /// Cancels every request of this client when triggered, including ones
+    /// waiting in backoff or Retry-After sleeps. They fail with
+    /// [`NgError::Cancelled`].
+    pub cancellation: Option<CancellationToken>,
+
+    /// Records every attempt to, or replays it from, a cassette file
+    /// instead of (or in addition to) the network. Meant for tests.
+    pub cassette: Option<Arc<Cassette>>,
+
+    /// `Accept-Encoding` sent on every attempt unless the request headers set
+    /// one. Overrides the codings advertised for `decompression`; a coding
+    /// offered here but not enabled there is left undecoded, which suits
+    /// `download` of archives.
+    pub accept_encoding: Option<String>,
+
+    /// Codings decoded automatically in buffered responses (all off by
+    /// default). `encoded_size` still counts the bytes on the wire.
+    pub decompression: Decompression,
+
+    /// Header carrying a random UUID generated once per logical POST/PATCH
+    /// and reused by all of its attempts, so idempotency-aware upstreams
+    /// apply a retried write only once. Only added when `retry > 0` and the
+    /// caller did not set it; `None` disables it.
+    pub idempotency_header: Option<HeaderName>,
 }
 
 impl Default for KyOptions {
     fn default() -> Self {
-        // default status codes: 408 413 429 500 502 503 504
-        let mut status_codes = HashSet::new();
-        for &c in &[408u16, 413u16, 429u16, 500u16, 502u16, 503u16, 504u16] {
-            status_codes.insert(StatusCode::from_u16(c).unwrap());
-        }
-
-        // afterStatusCodes: 413, 429, 503
-        let mut after_status_codes = HashSet::new();
-        for &c in &[413u16, 429u16, 503u16] {
-            after_status_codes.insert(StatusCode::from_u16(c).unwrap());
-        }
-
         // allowed methods: GET, HEAD, OPTIONS by default (idempotent)
         let mut allowed_methods = HashSet::new();
         for m in &[
@@ -97,23 +326,368 @@ impl Default for KyOptions {
 
         Self {
             timeout: Some(Duration::from_secs(15)),
+            total_deadline: None,
             retry: 2,
             limit: 2,
-            status_codes,
-            after_status_codes,
+            auth: None,
+            signer: None,
+            retry_policy: RetryPolicy::default(),
             max_retry_after: None,
             backoff_limit: None,
             retry_on_timeout: false,
             should_retry: None,
+            on_attempt: None,
+            validate_response: None,
+            on_download_progress: None,
             allowed_methods,
             semaphore: None,
             test_mode: false,
+            clock: None,
             disable_jitter: false,
             permit_release_threshold_ms: 2000,
+            hedge_after: None,
+            retry_budget: None,
+            single_flight: false,
+            rate_limiter: None,
+            rate_limit_bucket: None,
+            rate_limit_headers: true,
+            cache: CachePolicy::Disabled,
+            http_proxy: None,
+            https_proxy: None,
+            tls: TlsConfig::default(),
+            protocol: HttpProtocol::default(),
+            metrics: None,
+            trace_propagation: false,
+            trace_context: None,
+            cancellation: None,
+            cassette: None,
+            accept_encoding: None,
+            decompression: Decompression::default(),
+            idempotency_header: Some(HeaderName::from_static(IDEMPOTENCY_KEY)),
         }
     }
 }
 
+/// Default [`KyOptions::idempotency_header`].
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Upper bound on `retry` accepted by [`KyOptionsBuilder`].
+pub const MAX_RETRIES: usize = 10;
+
+impl KyOptions {
+    /// Starts a validating builder from the default options.
+    pub fn builder() -> KyOptionsBuilder {
+        KyOptionsBuilder::default()
+    }
+
+    /// Preset for latency-sensitive polling: short timeouts, more retries
+    /// (including on timeouts), higher concurrency and short waits.
+    pub fn aggressive() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(5)),
+            retry: 5,
+            limit: 8,
+            max_retry_after: Some(Duration::from_secs(5)),
+            backoff_limit: Some(Duration::from_secs(2)),
+            retry_on_timeout: true,
+            ..Self::default()
+        }
+    }
+
+    /// Preset for rate-sensitive providers: one request at a time, long
+    /// timeouts, a single retry and generous waits.
+    pub fn conservative() -> Self {
+        Self {
+            timeout: Some(Duration::from_secs(30)),
+            retry: 1,
+            limit: 1,
+            backoff_limit: Some(Duration::from_secs(30)),
+            retry_on_timeout: false,
+            ..Self::default()
+        }
+    }
+
+    /// Checks the options for values KyHttp cannot work with.
+    pub fn validate(&self) -> Result<(), NgError> {
+        let mut problems = Vec::new();
+        if self.semaphore.is_none() && self.limit == 0 {
+            problems.push("limit must be >= 1".to_string());
+        }
+        if self.retry > MAX_RETRIES {
+            problems.push(format!("retry must be <= {}", MAX_RETRIES));
+        }
+        if self.timeout.is_some_and(|t| t.is_zero()) {
+            problems.push("timeout must be > 0 (use None to disable)".to_string());
+        }
+        if self.total_deadline.is_some_and(|t| t.is_zero()) {
+            problems.push("total_deadline must be > 0 (use None to disable)".to_string());
+        }
+        if self.hedge_after.is_some_and(|t| t.is_zero()) {
+            problems.push("hedge_after must be > 0".to_string());
+        }
+        if self.backoff_limit.is_some_and(|t| t.is_zero()) {
+            problems.push("backoff_limit must be > 0".to_string());
+        }
+        problems.extend(self.retry_policy.problems());
+        if let Some(value) = &self.accept_encoding
+            && HeaderValue::from_str(value).is_err()
+        {
+            problems.push(format!("accept_encoding '{}' is not a valid header value", value));
+        }
+        if self.allowed_methods.is_empty() {
+            problems.push("allowed_methods must not be empty".to_string());
+        }
+        for proxy in [&self.http_proxy, &self.https_proxy].into_iter().flatten() {
+            if let Err(NgError::ConfigError(msg)) = proxy.validate() {
+                problems.push(msg);
+            }
+        }
+        if let Err(NgError::ConfigError(msg)) = self.tls.validate() {
+            problems.push(msg);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(NgError::ConfigError(format!("Invalid KyOptions: {}", problems.join("; "))))
+        }
+    }
+}
+
+/// KyOptionsBuilder
+///
+/// Chained setters over [`KyOptions`], validated on [`build`](Self::build).
+#[derive(Clone, Default)]
+pub struct KyOptionsBuilder {
+    opts: KyOptions,
+}
+
+impl From<KyOptions> for KyOptionsBuilder {
+    /// Starts from existing options, e.g. a preset.
+    fn from(opts: KyOptions) -> Self {
+        Self { opts }
+    }
+}
+
+impl KyOptionsBuilder {
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.opts.timeout = timeout;
+        self
+    }
+
+    pub fn total_deadline(mut self, deadline: Option<Duration>) -> Self {
+        self.opts.total_deadline = deadline;
+        self
+    }
+
+    pub fn retry(mut self, retry: usize) -> Self {
+        self.opts.retry = retry;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.opts.limit = limit;
+        self
+    }
+
+    pub fn auth(mut self, provider: Arc<dyn AuthProvider>) -> Self {
+        self.opts.auth = Some(provider);
+        self
+    }
+
+    pub fn signer(mut self, signer: Arc<dyn RequestSigner>) -> Self {
+        self.opts.signer = Some(signer);
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.opts.retry_policy = policy;
+        self
+    }
+
+    /// Sets the retry policy of one status, keeping the rest of the map.
+    pub fn status_policy(mut self, status: StatusCode, policy: StatusPolicy) -> Self {
+        self.opts.retry_policy = self.opts.retry_policy.with_status(status, policy);
+        self
+    }
+
+    pub fn max_retry_after(mut self, max: Option<Duration>) -> Self {
+        self.opts.max_retry_after = max;
+        self
+    }
+
+    pub fn backoff_limit(mut self, limit: Option<Duration>) -> Self {
+        self.opts.backoff_limit = limit;
+        self
+    }
+
+    pub fn retry_on_timeout(mut self, enabled: bool) -> Self {
+        self.opts.retry_on_timeout = enabled;
+        self
+    }
+
+    pub fn on_download_progress(mut self, callback: ProgressFn) -> Self {
+        self.opts.on_download_progress = Some(callback);
+        self
+    }
+
+    pub fn should_retry(mut self, predicate: ShouldRetryFn) -> Self {
+        self.opts.should_retry = Some(predicate);
+        self
+    }
+
+    pub fn on_attempt(mut self, callback: AttemptFn) -> Self {
+        self.opts.on_attempt = Some(callback);
+        self
+    }
+
+    pub fn validate_response(mut self, validate: ValidateFn) -> Self {
+        self.opts.validate_response = Some(validate);
+        self
+    }
+
+    pub fn allowed_methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.opts.allowed_methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Shares a concurrency limit across instances; `limit` is then ignored.
+    pub fn semaphore(mut self, semaphore: Arc<Semaphore>) -> Self {
+        self.opts.semaphore = Some(semaphore);
+        self
+    }
+
+    pub fn test_mode(mut self, enabled: bool) -> Self {
+        self.opts.test_mode = enabled;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.opts.clock = Some(clock);
+        self
+    }
+
+    pub fn disable_jitter(mut self, disabled: bool) -> Self {
+        self.opts.disable_jitter = disabled;
+        self
+    }
+
+    pub fn permit_release_threshold_ms(mut self, ms: u64) -> Self {
+        self.opts.permit_release_threshold_ms = ms;
+        self
+    }
+
+    pub fn hedge_after(mut self, delay: Option<Duration>) -> Self {
+        self.opts.hedge_after = delay;
+        self
+    }
+
+    pub fn retry_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.opts.retry_budget = Some(budget);
+        self
+    }
+
+    pub fn single_flight(mut self, enabled: bool) -> Self {
+        self.opts.single_flight = enabled;
+        self
+    }
+
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.opts.rate_limiter = Some(limiter);
+        self
+    }
+
+    pub fn rate_limit_bucket(mut self, bucket: &str) -> Self {
+        self.opts.rate_limit_bucket = Some(bucket.to_string());
+        self
+    }
+
+    pub fn rate_limit_headers(mut self, enabled: bool) -> Self {
+        self.opts.rate_limit_headers = enabled;
+        self
+    }
+
+    pub fn cache(mut self, policy: CachePolicy) -> Self {
+        self.opts.cache = policy;
+        self
+    }
+
+    pub fn http_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.opts.http_proxy = Some(proxy);
+        self
+    }
+
+    pub fn https_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.opts.https_proxy = Some(proxy);
+        self
+    }
+
+    /// Routes both `http://` and `https://` requests through `proxy`.
+    pub fn proxy(self, proxy: ProxyConfig) -> Self {
+        self.http_proxy(proxy.clone()).https_proxy(proxy)
+    }
+
+    pub fn tls(mut self, tls: TlsConfig) -> Self {
+        self.opts.tls = tls;
+        self
+    }
+
+    pub fn protocol(mut self, protocol: HttpProtocol) -> Self {
+        self.opts.protocol = protocol;
+        self
+    }
+
+    pub fn metrics(mut self, metrics: KyMetrics) -> Self {
+        self.opts.metrics = Some(metrics);
+        self
+    }
+
+    pub fn trace_propagation(mut self, enabled: bool) -> Self {
+        self.opts.trace_propagation = enabled;
+        self
+    }
+
+    /// Continues `ctx` on every request (implies `trace_propagation`).
+    pub fn trace_context(mut self, ctx: TraceContext) -> Self {
+        self.opts.trace_propagation = true;
+        self.opts.trace_context = Some(ctx);
+        self
+    }
+
+    /// Cancels the request, including backoff and Retry-After waits, once `token` fires.
+    pub fn cancellation(mut self, token: CancellationToken) -> Self {
+        self.opts.cancellation = Some(token);
+        self
+    }
+
+    pub fn cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.opts.cassette = Some(cassette);
+        self
+    }
+
+    pub fn accept_encoding(mut self, value: &str) -> Self {
+        self.opts.accept_encoding = Some(value.to_string());
+        self
+    }
+
+    /// Enables or disables automatic decoding of one coding.
+    pub fn decompress(mut self, coding: ContentCoding, enabled: bool) -> Self {
+        self.opts.decompression.set(coding, enabled);
+        self
+    }
+
+    pub fn idempotency_header(mut self, header: Option<HeaderName>) -> Self {
+        self.opts.idempotency_header = header;
+        self
+    }
+
+    /// Validates and returns the options.
+    pub fn build(self) -> Result<KyOptions, NgError> {
+        self.opts.validate()?;
+        Ok(self.opts)
+    }
+}
+
 /// ApiResponse<T>
 ///
 /// Standard response wrapper returned by KyHttp methods.
@@ -133,6 +707,150 @@ pub struct ApiResponse<T> {
 
     /// Response headers.
     pub headers: HeaderMap,
+
+    /// Body bytes received on the wire (`Content-Length` when announced), for
+    /// bandwidth accounting. 0 for bodies answered from the cache.
+    pub encoded_size: u64,
+
+    /// HTTP version the response arrived over (e.g. `HTTP/2.0`).
+    pub version: reqwest::Version,
+}
+
+/// HeadMeta
+///
+/// Typed metadata from a HEAD response, returned by [`KyHttp::head_meta`].
+#[derive(Debug, Clone)]
+pub struct HeadMeta {
+    /// HTTP status code.
+    pub status: u16,
+
+    /// Whether the response was successful (2xx).
+    pub success: bool,
+
+    /// Announced body size in bytes.
+    pub content_length: Option<u64>,
+
+    /// `Content-Type`, e.g. `text/csv; charset=utf-8`.
+    pub content_type: Option<String>,
+
+    /// `Last-Modified` as UTC.
+    pub last_modified: Option<DateTime<Utc>>,
+
+    /// `ETag`, quotes and weak prefix included (usable as `If-None-Match`).
+    pub etag: Option<String>,
+
+    /// Whether the server accepts byte ranges (resumable downloads).
+    pub accept_ranges: bool,
+
+    /// Response headers.
+    pub headers: HeaderMap,
+}
+
+impl HeadMeta {
+    fn from_headers(status: StatusCode, headers: HeaderMap) -> Self {
+        let text = |name| headers.get(name).and_then(|v: &HeaderValue| v.to_str().ok()).map(str::trim);
+        let Validators { etag, last_modified } = Validators::from_headers(&headers);
+        Self {
+            status: status.as_u16(),
+            success: status.is_success(),
+            content_length: text(CONTENT_LENGTH).and_then(|v| v.parse().ok()),
+            content_type: text(CONTENT_TYPE).map(str::to_string),
+            last_modified,
+            etag,
+            accept_ranges: text(ACCEPT_RANGES).is_some_and(|v| v.eq_ignore_ascii_case("bytes")),
+            headers,
+        }
+    }
+}
+
+/// Validators of a previously fetched representation, sent as
+/// `If-None-Match` / `If-Modified-Since` by [`KyHttp::get_if_modified`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+impl Validators {
+    /// Validators carried by a response's headers.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let text = |name| headers.get(name).and_then(|v: &HeaderValue| v.to_str().ok()).map(str::trim);
+        Self {
+            etag: text(ETAG).map(str::to_string),
+            last_modified: text(LAST_MODIFIED)
+                .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+
+    /// True when neither validator is known (the request is unconditional).
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) {
+        if let Some(etag) = self.etag.as_deref().and_then(|e| HeaderValue::from_str(e).ok()) {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(date) = self.last_modified {
+            let http_date = date.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            if let Ok(value) = HeaderValue::from_str(&http_date) {
+                headers.insert(IF_MODIFIED_SINCE, value);
+            }
+        }
+    }
+}
+
+/// Result of [`KyHttp::get_if_modified`].
+#[derive(Debug)]
+pub enum Conditional<T> {
+    /// The resource changed (or no validators were given): the new response.
+    /// Keep `Validators::from_headers(&resp.headers)` for the next poll.
+    Fresh(ApiResponse<T>),
+    /// `304 Not Modified`: the caller's copy is current.
+    NotModified,
+}
+
+/// Outcome of applying the retry policy to one non-success response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RetryDecision {
+    Stop,
+    /// Wait for the (capped) server-provided delay, then retry.
+    RetryAfter(Duration),
+    /// Retry after computed exponential backoff.
+    Backoff,
+}
+
+/// Raw outcome of a request: final status, headers and the body read once.
+#[derive(Debug, Clone)]
+struct RawResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    /// Body bytes as received; JSON is parsed from these directly, so large
+    /// bodies are not copied into an intermediate `String` first.
+    body: Bytes,
+    encoded_size: u64,
+    version: reqwest::Version,
+}
+
+impl RawResponse {
+    /// Body as text, for error bodies, snippets and the cache.
+    fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+}
+
+/// Shared future of an in-flight single-flight GET.
+type InflightRequest = Shared<BoxFuture<'static, Result<Arc<RawResponse>, NgError>>>;
+
+/// Lightweight per-call overrides for [`KyHttp::with_overrides`].
+///
+/// `None` keeps the client's setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestOverrides {
+    pub timeout: Option<Duration>,
+    pub retry: Option<usize>,
+    pub backoff_limit: Option<Duration>,
 }
 
 /// KyHttp
@@ -144,6 +862,9 @@ pub struct KyHttp {
     logger: Logger,
     opts: KyOptions,
     semaphore: Arc<Semaphore>,
+    inflight: Arc<Mutex<HashMap<String, InflightRequest>>>,
+    metrics: KyMetrics,
+    observed_limits: Arc<ObservedLimits>,
 }
 
 impl KyHttp {
@@ -153,29 +874,158 @@ impl KyHttp {
 
     pub fn new_with_opts(logger: Logger, opts: Option<KyOptions>) -> Self {
         let opts = opts.unwrap_or_default();
+        let client = Self::build_client(&opts).unwrap_or_else(|e| {
+            crate::warn!(logger, "Falling back to default HTTP client", "error" => e.to_string());
+            Client::new()
+        });
+        Self::from_client(logger, opts, client)
+    }
+
+    /// Like [`new_with_opts`](Self::new_with_opts), but fails on invalid options
+    /// (e.g. a malformed proxy URL) instead of falling back to a default client.
+    pub fn try_new_with_opts(logger: Logger, opts: KyOptions) -> Result<Self, NgError> {
+        opts.validate()?;
+        let client = Self::build_client(&opts)?;
+        Ok(Self::from_client(logger, opts, client))
+    }
+
+    /// Returns a client that sends through `proxy` (for both schemes), or
+    /// directly when `None`, ignoring the configured and env proxies.
+    ///
+    /// The derived client shares this instance's concurrency permits; use it
+    /// for calls that need a different egress, e.g. a rotating scraping proxy.
+    pub fn with_proxy(&self, proxy: Option<ProxyConfig>) -> Result<Self, NgError> {
+        let mut opts = self.opts.clone();
+        opts.http_proxy = proxy.clone();
+        opts.https_proxy = proxy;
+        let mut builder = Self::client_builder(&opts)?;
+        if opts.http_proxy.is_none() {
+            builder = builder.no_proxy();
+        }
+        let client = builder.build().map_err(|e| NgError::ConfigError(format!("HTTP client build failed: {}", e)))?;
+        Ok(Self {
+            client,
+            logger: self.logger.clone(),
+            opts,
+            semaphore: self.semaphore.clone(),
+            inflight: Arc::new(Mutex::new(HashMap::new())),
+            metrics: self.metrics.clone(),
+            observed_limits: self.observed_limits.clone(),
+        })
+    }
+
+    /// Returns a client whose requests are cancelled by `token`.
+    ///
+    /// The derived client shares this instance's HTTP client, permits and
+    /// metrics, so it is cheap to create per call or per task.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        let mut derived = self.clone();
+        derived.opts.cancellation = Some(token);
+        derived
+    }
+
+    /// Returns a client using `opts`, for per-call overrides of retry, timeout
+    /// and similar settings.
+    ///
+    /// Unlike [`new_with_opts`](Self::new_with_opts), the derived client keeps
+    /// this instance's connection pool as long as the proxy and TLS settings
+    /// are unchanged; the timeout is applied per request and never requires a
+    /// new pool. Permits, metrics and single-flight state come from `opts` as
+    /// they would for a new client.
+    pub fn with_options(&self, opts: KyOptions) -> Result<Self, NgError> {
+        opts.validate()?;
+        let same_transport = opts.http_proxy == self.opts.http_proxy
+            && opts.https_proxy == self.opts.https_proxy
+            && opts.tls == self.opts.tls
+            && opts.protocol == self.opts.protocol;
+        let client = if same_transport { self.client.clone() } else { Self::build_client(&opts)? };
+        Ok(Self::from_client(self.logger.clone(), opts, client))
+    }
+
+    /// Returns this client with `overrides` applied.
+    ///
+    /// Everything else is shared: connection pool, permits, metrics, cache and
+    /// single-flight state, so this is cheap enough to call per request.
+    pub fn with_overrides(&self, overrides: &RequestOverrides) -> Self {
+        let mut derived = self.clone();
+        if let Some(timeout) = overrides.timeout {
+            derived.opts.timeout = Some(timeout);
+        }
+        if let Some(retry) = overrides.retry {
+            derived.opts.retry = retry;
+        }
+        if let Some(limit) = overrides.backoff_limit {
+            derived.opts.backoff_limit = Some(limit);
+        }
+        derived
+    }
+
+    fn client_builder(opts: &KyOptions) -> Result<reqwest::ClientBuilder, NgError> {
         let mut builder = Client::builder();
-        if let Some(timeout) = opts.timeout {
-            builder = builder.timeout(timeout);
+        builder = match opts.protocol {
+            HttpProtocol::Negotiate => builder,
+            HttpProtocol::Http1Only => builder.http1_only(),
+            HttpProtocol::Http2PriorKnowledge => builder.http2_prior_knowledge(),
+        };
+        if let Some(proxy) = &opts.http_proxy {
+            builder = builder.proxy(proxy.to_reqwest(ProxyScheme::Http)?);
+        }
+        if let Some(proxy) = &opts.https_proxy {
+            builder = builder.proxy(proxy.to_reqwest(ProxyScheme::Https)?);
         }
-        let client = builder.build().unwrap_or_else(|_| Client::new());
+        opts.tls.apply(builder)
+    }
+
+    fn build_client(opts: &KyOptions) -> Result<Client, NgError> {
+        Self::client_builder(opts)?
+            .build()
+            .map_err(|e| NgError::ConfigError(format!("HTTP client build failed: {}", e)))
+    }
 
+    fn from_client(logger: Logger, opts: KyOptions, client: Client) -> Self {
+        if opts.tls.danger_accept_invalid_certs {
+            crate::warn!(logger, "TLS certificate verification is disabled");
+        }
         let semaphore = if let Some(s) = &opts.semaphore {
             s.clone()
         } else {
             Arc::new(Semaphore::new(opts.limit.max(1)))
         };
 
+        let metrics = opts.metrics.clone().unwrap_or_default();
         Self {
             client,
             logger,
             opts,
             semaphore,
+            inflight: Arc::new(Mutex::new(HashMap::new())),
+            metrics,
+            observed_limits: Arc::new(ObservedLimits::default()),
         }
     }
 
+    /// Per-host request, status, retry and latency metrics of this client.
+    pub fn metrics(&self) -> &KyMetrics {
+        &self.metrics
+    }
+
+    /// Underlying reqwest client, for protocols layered on top (e.g. SSE).
+    pub(crate) fn http_client(&self) -> &Client {
+        &self.client
+    }
+
+    pub(crate) fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
     /// Prepare request hook (placeholder for auth/global headers).
     fn prepare_request(&self, rb: RequestBuilder) -> RequestBuilder {
-        rb
+        // Per request rather than on the Client, so clients derived with
+        // `with_options` can share one connection pool.
+        match self.opts.timeout {
+            Some(timeout) => rb.timeout(timeout),
+            None => rb,
+        }
     }
 
     /// Compute delay using the existing formula but with optional cap and jitter.
@@ -185,142 +1035,725 @@ impl KyHttp {
         let ms = 0.3f64 * pow * 1000.0;
         let mut dur = Duration::from_millis(ms.round() as u64);
 
-        if let Some(limit) = self.opts.backoff_limit {
-            if dur > limit {
-                dur = limit;
+        if let Some(limit) = self.opts.backoff_limit
+            && dur > limit
+        {
+            dur = limit;
+        }
+
+        dur
+    }
+
+    /// Compute exponential backoff with optional jitter. attempt starts at 1.
+    fn compute_backoff_with_jitter(&self, attempt: usize, rng: &mut SmallRng) -> Duration {
+        // Compute base backoff in milliseconds
+        let mut base_ms = self.compute_delay(attempt).as_millis() as u64;
+
+        // Apply backoff_limit if present
+        if let Some(limit) = self.opts.backoff_limit {
+            let limit_ms = limit.as_millis() as u64;
+            if base_ms > limit_ms {
+                base_ms = limit_ms;
+            }
+        }
+
+        // Determine jitter
+        let jitter_ms = if self.opts.disable_jitter {
+            0
+        } else {
+            let jitter_max = (base_ms / 10).max(1);
+            if self.opts.test_mode {
+                // small deterministic jitter in test mode
+                rng.gen_range(0..=jitter_max.min(5))
+            } else {
+                rng.gen_range(0..=jitter_max)
+            }
+        };
+
+        // Candidate backoff
+        let mut candidate = base_ms.saturating_add(jitter_ms);
+
+        // Cap by max_retry_after if set
+        if let Some(max_ra) = self.opts.max_retry_after {
+            let max_ra_ms = max_ra.as_millis() as u64;
+            if candidate > max_ra_ms {
+                candidate = max_ra_ms;
+            }
+        }
+
+        // Cap by backoff_limit if set
+        if let Some(limit) = self.opts.backoff_limit {
+            let limit_ms = limit.as_millis() as u64;
+            if candidate > limit_ms {
+                candidate = limit_ms;
+            }
+        }
+
+        Duration::from_millis(candidate)
+    }
+
+    /// Parse Retry-After header from headers. Supports numeric seconds and several date formats.
+    fn parse_retry_after_from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+        if let Some(v) = headers.get("retry-after")
+            && let Ok(s) = v.to_str()
+        {
+            let s_trim = s.trim();
+
+            // numeric seconds
+            if let Ok(secs) = s_trim.parse::<u64>() {
+                return Some(Duration::from_secs(if secs == 0 { 1 } else { secs }));
+            }
+
+            // IMF-fixdate
+            if let Ok(dt) = DateTime::parse_from_str(s_trim, "%a, %d %b %Y %H:%M:%S GMT") {
+                let dt_utc = dt.with_timezone(&Utc);
+                if dt_utc > now {
+                    let diff = dt_utc.signed_duration_since(now);
+                    return Some(Duration::from_secs(diff.num_seconds().max(1) as u64));
+                } else {
+                    return Some(Duration::from_secs(1));
+                }
+            }
+
+            // RFC2822
+            if let Ok(dt) = DateTime::parse_from_rfc2822(s_trim) {
+                let dt_utc = dt.with_timezone(&Utc);
+                if dt_utc > now {
+                    let diff = dt_utc.signed_duration_since(now);
+                    return Some(Duration::from_secs(diff.num_seconds().max(1) as u64));
+                } else {
+                    return Some(Duration::from_secs(1));
+                }
+            }
+
+            // RFC3339
+            if let Ok(dt) = DateTime::parse_from_rfc3339(s_trim) {
+                let dt_utc = dt.with_timezone(&Utc);
+                if dt_utc > now {
+                    let diff = dt_utc.signed_duration_since(now);
+                    return Some(Duration::from_secs(diff.num_seconds().max(1) as u64));
+                } else {
+                    return Some(Duration::from_secs(1));
+                }
+            }
+        }
+        None
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        match &self.opts.clock {
+            Some(clock) => clock.now(),
+            None => Utc::now(),
+        }
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        match &self.opts.clock {
+            Some(clock) => clock.sleep(duration).await,
+            None => TokioClock.sleep(duration).await,
+        }
+    }
+
+    /// Sleep helper that releases permit for long waits and attempts a bounded re-acquire.
+    ///
+    /// The sleep goes through the clock; the 200ms re-acquire bound is a tokio
+    /// timer, like `total_deadline`, so a mock clock cannot skip it.
+    async fn smart_sleep_and_maybe_reacquire(&self, duration: Duration, permit: &mut Option<OwnedSemaphorePermit>) {
+        if duration.as_millis() as u64 > self.opts.permit_release_threshold_ms {
+            // Release permit to avoid blocking throughput for long waits.
+            let _dropped = permit.take();
+            self.sleep(duration).await;
+            // Attempt a bounded re-acquire to avoid indefinite blocking.
+            if let Ok(Ok(p)) = timeout(Duration::from_millis(200), self.semaphore.clone().acquire_owned()).await {
+                *permit = Some(p);
+            }
+        } else {
+            // Short wait: keep permit to preserve logical ordering.
+            self.sleep(duration).await;
+        }
+    }
+
+    /// Ensures the headers of a logical request carry a trace parent, so all of
+    /// its attempts share one trace.
+    fn trace_root(&self, headers: &mut HeaderMap) {
+        if self.opts.trace_propagation && TraceContext::from_headers(headers).is_none() {
+            self.opts.trace_context.clone().unwrap_or_else(TraceContext::generate).inject(headers);
+        }
+    }
+
+    /// Adds an idempotency key to retried writes that lack one.
+    fn idempotency_key(&self, method: &Method, headers: &mut HeaderMap) {
+        let Some(name) = &self.opts.idempotency_header else { return };
+        if self.opts.retry == 0 || !(*method == Method::POST || *method == Method::PATCH) || headers.contains_key(name) {
+            return;
+        }
+        let mut bytes: [u8; 16] = rand::random();
+        // RFC 9562 version 4 (random) UUID.
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        let hex = hex::encode(bytes);
+        let uuid = format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..]);
+        if let Ok(value) = HeaderValue::from_str(&uuid) {
+            headers.insert(name.clone(), value);
+        }
+    }
+
+    /// Builds a single attempt's request (adding a child `traceparent` when
+    /// enabled) and signs it with the configured signer.
+    fn build_request<B>(
+        &self,
+        method: &Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: Option<&B>,
+    ) -> Result<Request, reqwest::Error>
+    where
+        B: Serialize + ?Sized,
+    {
+        let mut headers = headers.clone();
+        if let Some(value) = &self.opts.accept_encoding
+            && !headers.contains_key(ACCEPT_ENCODING)
+            && let Ok(value) = HeaderValue::from_str(value)
+        {
+            headers.insert(ACCEPT_ENCODING, value);
+        }
+        if self.opts.trace_propagation
+            && let Some(parent) = TraceContext::from_headers(&headers)
+        {
+            parent.child().inject(&mut headers);
+        }
+        let mut rb = self.client.request(method.clone(), url).headers(headers);
+        if let Some(b) = body {
+            rb = rb.json(b);
+        }
+        let mut req = self.prepare_request(rb).build()?;
+        if let Some(signer) = &self.opts.signer {
+            signer.sign(&mut req);
+        }
+        Ok(req)
+    }
+
+    /// Sends one attempt and records it in the metrics (latency up to the headers).
+    async fn execute(&self, url: &str, req: Request) -> Result<reqwest::Response, reqwest::Error> {
+        let started = std::time::Instant::now();
+        let res = match &self.opts.cassette {
+            Some(cassette) => cassette.execute(&self.client, req).await,
+            None => self.client.execute(req).await,
+        };
+        if let (Ok(resp), Some(budget)) = (&res, &self.opts.retry_budget)
+            && resp.status().is_success()
+        {
+            budget.deposit();
+        }
+        match &res {
+            Ok(resp) => self.metrics.record_response(url, resp.status().as_u16(), started.elapsed()),
+            Err(_) => self.metrics.record_network_error(url, started.elapsed()),
+        }
+        if self.opts.rate_limit_headers
+            && let Ok(resp) = &res
+            && let Some(host) = Self::host_of(url)
+        {
+            self.observed_limits.observe(&host, resp.headers(), self.now());
+        }
+        res
+    }
+
+    /// Sends one attempt, hedged for GETs when `hedge_after` is set.
+    async fn send_once<B>(
+        &self,
+        method: &Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: Option<&B>,
+    ) -> Result<RawResponse, reqwest::Error>
+    where
+        B: Serialize + ?Sized,
+    {
+        match self.opts.hedge_after {
+            Some(delay) if *method == Method::GET => self.send_hedged(url, headers, delay).await,
+            _ => self.send_single(method, url, headers, body).await,
+        }
+    }
+
+    /// Races a GET against a duplicate started after `delay`.
+    ///
+    /// The first successful response wins and the other request is dropped.
+    /// If the first to finish failed, the other one is awaited and preferred
+    /// when it succeeds. The duplicate runs under the same permit.
+    async fn send_hedged(&self, url: &str, headers: &HeaderMap, delay: Duration) -> Result<RawResponse, reqwest::Error> {
+        let get = || self.send_single(&Method::GET, url, headers, Option::<&()>::None);
+        let ok = |r: &Result<RawResponse, reqwest::Error>| r.as_ref().is_ok_and(|raw| raw.status.is_success());
+
+        let mut primary = std::pin::pin!(get());
+        tokio::select! {
+            res = &mut primary => return res,
+            _ = self.sleep(delay) => {}
+        }
+        crate::debug!(self.logger, "Hedging slow request", "url" => url, "after_ms" => delay.as_millis() as u64);
+        self.metrics.record_hedge(url);
+
+        let mut backup = std::pin::pin!(get());
+        let (first, other) = tokio::select! {
+            res = &mut primary => (res, backup.as_mut().left_future()),
+            res = &mut backup => (res, primary.as_mut().right_future()),
+        };
+        if ok(&first) {
+            return first;
+        }
+        let second = other.await;
+        if ok(&second) { second } else { first }
+    }
+
+    /// Builds and sends a single request, reading the body once.
+    async fn send_single<B>(
+        &self,
+        method: &Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: Option<&B>,
+    ) -> Result<RawResponse, reqwest::Error>
+    where
+        B: Serialize + ?Sized,
+    {
+        let offered;
+        let headers = match self.opts.decompression.accept_encoding() {
+            // Ranged bodies are slices of the identity representation.
+            Some(value)
+                if self.opts.accept_encoding.is_none()
+                    && !headers.contains_key(ACCEPT_ENCODING)
+                    && !headers.contains_key(RANGE) =>
+            {
+                let mut with_offer = headers.clone();
+                if let Ok(value) = HeaderValue::from_str(&value) {
+                    with_offer.insert(ACCEPT_ENCODING, value);
+                }
+                offered = with_offer;
+                &offered
+            }
+            _ => headers,
+        };
+        let req = self.build_request(method, url, headers, body)?;
+        let resp = self.execute(url, req).await?;
+        let status = resp.status();
+        let mut headers = resp.headers().clone();
+        let version = resp.version();
+        let content_length = resp.content_length();
+        let mut body = resp.bytes().await.unwrap_or_default();
+        let encoded_size = content_length.unwrap_or(body.len() as u64);
+        if let Some(coding) = headers.get(CONTENT_ENCODING).and_then(|v| v.to_str().ok()).map(str::to_string) {
+            match self.opts.decompression.decode(&coding, &body) {
+                Ok(Some(decoded)) => {
+                    // Like a transparent decoder: the headers now describe the decoded body.
+                    headers.remove(CONTENT_ENCODING);
+                    headers.remove(CONTENT_LENGTH);
+                    body = decoded;
+                }
+                Ok(None) => {}
+                Err(e) => crate::warn!(self.logger, "Response body could not be decoded", "url" => url, "encoding" => coding.as_str(), "error" => e.to_string()),
+            }
+        }
+        Ok(RawResponse { status, headers, body, encoded_size, version })
+    }
+
+    /// Converts a raw response into the typed `ApiResponse` returned to callers.
+    fn into_api_response<T: DeserializeOwned>(raw: RawResponse) -> Result<ApiResponse<T>, NgError> {
+        if raw.status.is_success() {
+            if let Some(coding) = raw.headers.get(CONTENT_ENCODING).and_then(|v| v.to_str().ok())
+                && !coding.trim().eq_ignore_ascii_case("identity")
+            {
+                return Err(NgError::HttpError(format!(
+                    "Response body is {}-encoded and was not decoded; enable the coding with decompress, or offer only identity in accept_encoding",
+                    coding
+                )));
+            }
+            let parsed = serde_json::from_slice::<T>(&raw.body)
+                .map_err(|e| NgError::HttpError(format!("JSON decode: {}", e)))?;
+            Ok(ApiResponse {
+                data: Some(parsed),
+                error_body: None,
+                status: raw.status.as_u16(),
+                success: true,
+                headers: raw.headers,
+                encoded_size: raw.encoded_size,
+                version: raw.version,
+            })
+        } else {
+            Ok(ApiResponse {
+                data: None,
+                error_body: if raw.body.is_empty() { None } else { Some(raw.text().into_owned()) },
+                status: raw.status.as_u16(),
+                success: false,
+                headers: raw.headers,
+                encoded_size: raw.encoded_size,
+                version: raw.version,
+            })
+        }
+    }
+
+    /// Caps a server-provided Retry-After: prefer max_retry_after, then backoff_limit if set.
+    fn cap_retry_after(&self, retry_after: Duration) -> Duration {
+        if let Some(max) = self.opts.max_retry_after {
+            std::cmp::min(retry_after, max)
+        } else if let Some(limit) = self.opts.backoff_limit {
+            std::cmp::min(retry_after, limit)
+        } else {
+            retry_after
+        }
+    }
+
+    /// Applies the retry policy to a non-success response.
+    ///
+    /// `waits` counts the Retry-After waits of the logical request so far.
+    fn retry_decision(&self, status: StatusCode, url: &str, headers: &HeaderMap, waits: &mut usize) -> RetryDecision {
+        let retry_after = || Self::parse_retry_after_from_headers(headers, self.now()).map(|ra| self.cap_retry_after(ra));
+        match self.opts.retry_policy.policy_for(status) {
+            StatusPolicy::NoRetry => RetryDecision::Stop,
+            StatusPolicy::Hook => {
+                if let Some(hook) = self.opts.retry_policy.hook() {
+                    crate::info!(self.logger, "Running status hook", "url" => url, "status" => status.as_u16());
+                    hook(status, url, headers);
+                }
+                RetryDecision::Stop
+            }
+            StatusPolicy::Backoff => retry_after().map_or(RetryDecision::Backoff, RetryDecision::RetryAfter),
+            StatusPolicy::RetryAfter { max_waits } => match retry_after() {
+                Some(delay) if *waits < max_waits => {
+                    *waits += 1;
+                    RetryDecision::RetryAfter(delay)
+                }
+                _ => RetryDecision::Stop,
+            },
+        }
+    }
+
+    /// Lets `should_retry`, if set, override a status decision of the retry policy.
+    fn consult_should_retry(&self, ctx: &RetryContext<'_>, decision: RetryDecision) -> RetryDecision {
+        let Some(pred) = &self.opts.should_retry else { return decision };
+        match (pred(ctx), decision) {
+            (false, _) => RetryDecision::Stop,
+            (true, RetryDecision::Stop) => RetryDecision::Backoff,
+            (true, decision) => decision,
+        }
+    }
+
+    /// Whether `should_retry`, if set, allows retrying a network error.
+    fn allows_network_retry(&self, ctx: &RetryContext<'_>) -> bool {
+        self.opts.should_retry.as_ref().is_none_or(|pred| pred(ctx))
+    }
+
+    /// Runs `validate_response`, if set, on a 2xx response with a JSON body.
+    fn validate(&self, raw: &RawResponse) -> Validation {
+        let Some(validate) = &self.opts.validate_response else { return Validation::Accept };
+        match serde_json::from_slice::<serde_json::Value>(&raw.body) {
+            Ok(json) => validate(raw.status, &raw.headers, &json),
+            Err(_) => Validation::Accept,
+        }
+    }
+
+    /// Logs one attempt and passes it to `on_attempt`.
+    ///
+    /// `retry` holds the reason and wait when another attempt follows.
+    fn record_attempt(
+        &self,
+        method: &Method,
+        url: &str,
+        attempt: usize,
+        sent_at: std::time::Instant,
+        status: Option<StatusCode>,
+        retry: Option<(RetryReason, Duration)>,
+    ) {
+        let info = AttemptInfo {
+            method,
+            url,
+            attempt,
+            status,
+            elapsed: sent_at.elapsed(),
+            backoff: retry.map(|(_, wait)| wait),
+            retry_reason: retry.map(|(reason, _)| reason),
+        };
+        crate::info!(
+            self.logger,
+            "Request attempt",
+            "method" => method.as_str(),
+            "url" => url,
+            "attempt" => attempt,
+            "status" => status.map(|s| s.as_u16()),
+            "elapsed_ms" => info.elapsed.as_millis() as u64,
+            "backoff_ms" => info.backoff.map(|d| d.as_millis() as u64),
+            "retry_reason" => info.retry_reason.map(|r| r.as_str())
+        );
+        if let Some(cb) = &self.opts.on_attempt {
+            cb(&info);
+        }
+    }
+
+    /// Acquires the permit held for the duration of one logical request.
+    async fn acquire_permit(&self) -> Result<OwnedSemaphorePermit, NgError> {
+        self.semaphore.clone().acquire_owned().await
+            .map_err(|_| NgError::InternalError("Semaphore closed".into()))
+    }
+
+    fn host_of(url: &str) -> Option<String> {
+        reqwest::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_string))
+    }
+
+    /// Waits for the host's announced quota and the rate limiter, if
+    /// configured, before sending to `url`.
+    async fn throttle(&self, url: &str) {
+        if self.opts.rate_limit_headers
+            && let Some(host) = Self::host_of(url)
+            && let Some(wait) = self.observed_limits.reserve(&host, self.now())
+        {
+            let wait = self.cap_retry_after(wait);
+            crate::info!(self.logger, "Waiting for server rate limit reset", "url" => url, "wait_ms" => wait.as_millis() as u64);
+            self.sleep(wait).await;
+        }
+        let Some(limiter) = &self.opts.rate_limiter else { return };
+        let key = match &self.opts.rate_limit_bucket {
+            Some(bucket) => bucket.clone(),
+            None => Self::host_of(url).unwrap_or_default(),
+        };
+        limiter.acquire(&key).await;
+    }
+
+    /// Quotas servers reported in their rate-limit headers, by host.
+    pub fn rate_limits(&self) -> BTreeMap<String, ObservedRateLimit> {
+        self.observed_limits.snapshot()
+    }
+
+    /// Adds the auth provider's `Authorization` header, unless the caller set one.
+    ///
+    /// Returns the headers to send and the value added, if any.
+    async fn authorize<'h>(&self, headers: &'h HeaderMap) -> Result<(Cow<'h, HeaderMap>, Option<HeaderValue>), NgError> {
+        match &self.opts.auth {
+            Some(auth) if !headers.contains_key(AUTHORIZATION) => {
+                let value = auth.authorization().await?;
+                let mut with_auth = headers.clone();
+                with_auth.insert(AUTHORIZATION, value.clone());
+                Ok((Cow::Owned(with_auth), Some(value)))
+            }
+            _ => Ok((Cow::Borrowed(headers), None)),
+        }
+    }
+
+    /// After a `401` for credentials `rejected` added by [`authorize`](Self::authorize),
+    /// asks the provider for new ones. Returns true if the attempt should be repeated.
+    async fn refresh_auth(&self, url: &str, rejected: &HeaderValue) -> Result<bool, NgError> {
+        let Some(auth) = &self.opts.auth else { return Ok(false) };
+        crate::info!(self.logger, "Refreshing credentials after 401", "url" => url);
+        auth.invalidate(rejected).await
+    }
+
+    /// Runs `fut` unless the cancellation token fires first.
+    ///
+    /// Dropping `fut` on cancellation aborts pending sleeps and releases the permit.
+    async fn cancellable<R>(&self, url: &str, fut: impl Future<Output = Result<R, NgError>>) -> Result<R, NgError> {
+        let Some(token) = &self.opts.cancellation else { return fut.await };
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => {
+                crate::info!(self.logger, "Request cancelled", "url" => url);
+                Err(NgError::Cancelled { url: url.to_string() })
             }
+            res = fut => res,
         }
-
-        dur
     }
 
-    /// Compute exponential backoff with optional jitter. attempt starts at 1.
-    fn compute_backoff_with_jitter(&self, attempt: usize, rng: &mut SmallRng) -> Duration {
-        // Compute base backoff in milliseconds
-        let mut base_ms = self.compute_delay(attempt).as_millis() as u64;
+    /// Ends `body` with [`NgError::Cancelled`] when the cancellation token fires.
+    fn cancellable_stream<S>(&self, url: &str, body: S) -> impl Stream<Item = Result<Bytes, NgError>> + Send + 'static
+    where
+        S: Stream<Item = Result<Bytes, NgError>> + Send + 'static,
+    {
+        let Some(token) = self.opts.cancellation.clone() else { return body.left_stream() };
+        let state = (Box::pin(body), token, self.logger.clone(), url.to_string());
+        stream::unfold(Some(state), |state| async move {
+            let (mut body, token, logger, url) = state?;
+            tokio::select! {
+                biased;
+                _ = token.cancelled() => {
+                    crate::info!(logger, "Request cancelled", "url" => url.as_str());
+                    Some((Err(NgError::Cancelled { url }), None))
+                }
+                item = body.next() => item.map(|item| (item, Some((body, token, logger, url)))),
+            }
+        })
+        .right_stream()
+    }
 
-        // Apply backoff_limit if present
-        if let Some(limit) = self.opts.backoff_limit {
-            let limit_ms = limit.as_millis() as u64;
-            if base_ms > limit_ms {
-                base_ms = limit_ms;
+    /// Takes a token from the retry budget, if configured, before a retry.
+    fn spend_retry(&self, url: &str) -> Result<(), NgError> {
+        match &self.opts.retry_budget {
+            Some(budget) if !budget.try_spend() => {
+                crate::warn!(self.logger, "Retry budget exhausted", "url" => url);
+                Err(NgError::RetryBudgetExhausted { url: url.to_string() })
             }
+            _ => Ok(()),
         }
+    }
 
-        // Determine jitter
-        let jitter_ms = if self.opts.disable_jitter {
-            0
-        } else {
-            let jitter_max = (base_ms / 10).max(1);
-            if self.opts.test_mode {
-                // small deterministic jitter in test mode
-                rng.gen_range(0..=jitter_max.min(5))
+    /// Backoff RNG; deterministic in test_mode.
+    fn backoff_rng(&self) -> SmallRng {
+        if self.opts.test_mode { SmallRng::seed_from_u64(0xC0FFEE) } else { SmallRng::from_entropy() }
+    }
+
+    // Core request logic with retries and concurrency control.
+    async fn request_with_retry<T, B>(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Option<&B>,
+    ) -> Result<ApiResponse<T>, NgError>
+    where
+        T: DeserializeOwned + Send + 'static,
+        B: Serialize + ?Sized,
+    {
+        let raw = self.request_raw(method, url, headers, body).await?;
+        Self::into_api_response(raw)
+    }
+
+    /// Runs a logical request (cache, single-flight, retries) under the
+    /// cancellation token and total deadline, returning the final raw response.
+    async fn request_raw<B>(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Option<&B>,
+    ) -> Result<RawResponse, NgError>
+    where
+        B: Serialize + ?Sized,
+    {
+        let call = async {
+            if method == Method::GET && body.is_none() {
+                if self.opts.single_flight {
+                    self.single_flight_get(url, headers).await
+                } else {
+                    self.cached_get(url, headers).await
+                }
             } else {
-                rng.gen_range(0..=jitter_max)
+                self.execute_with_retry(method, url, headers, body).await
             }
         };
+        self.guarded(url, call).await
+    }
 
-        // Candidate backoff
-        let mut candidate = base_ms.saturating_add(jitter_ms);
-
-        // Cap by max_retry_after if set
-        if let Some(max_ra) = self.opts.max_retry_after {
-            let max_ra_ms = max_ra.as_millis() as u64;
-            if candidate > max_ra_ms {
-                candidate = max_ra_ms;
-            }
+    /// Applies the cancellation token and total deadline to a logical request.
+    async fn guarded<R>(&self, url: &str, call: impl Future<Output = Result<R, NgError>>) -> Result<R, NgError> {
+        let call = self.cancellable(url, call);
+        // Dropping the call on expiry also aborts any pending backoff sleep
+        // and releases the permit. The deadline is a tokio timer on purpose,
+        // see `KyOptions::total_deadline`.
+        match self.opts.total_deadline {
+            Some(deadline) => timeout(deadline, call).await.map_err(|_| {
+                crate::warn!(self.logger, "Total deadline exceeded", "url" => url, "deadline_ms" => deadline.as_millis() as u64);
+                NgError::DeadlineExceeded { url: url.to_string(), deadline_ms: deadline.as_millis() as u64 }
+            })?,
+            None => call.await,
         }
+    }
 
-        // Cap by backoff_limit if set
-        if let Some(limit) = self.opts.backoff_limit {
-            let limit_ms = limit.as_millis() as u64;
-            if candidate > limit_ms {
-                candidate = limit_ms;
+    /// Collapses concurrent identical GETs into one network call.
+    ///
+    /// Callers with the same URL and headers await a shared future; the entry is
+    /// removed as soon as that call completes, so later calls fetch fresh data.
+    async fn single_flight_get(&self, url: &str, headers: HeaderMap) -> Result<RawResponse, NgError> {
+        let key = Self::request_key(url, &headers);
+
+        let shared = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(existing) = inflight.get(&key) {
+                crate::debug!(self.logger, "Joining in-flight request", "url" => url);
+                existing.clone()
+            } else {
+                let this = self.clone();
+                let url = url.to_string();
+                let cleanup_key = key.clone();
+                let fut = async move {
+                    let res = this.cached_get(&url, headers).await.map(Arc::new);
+                    this.inflight
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .remove(&cleanup_key);
+                    res
+                }
+                .boxed()
+                .shared();
+                inflight.insert(key, fut.clone());
+                fut
             }
-        }
+        };
 
-        Duration::from_millis(candidate)
+        shared.await.map(|raw| (*raw).clone())
     }
 
-    /// Parse Retry-After header from headers. Supports numeric seconds and several date formats.
-    fn parse_retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
-        if let Some(v) = headers.get("retry-after") {
-            if let Ok(s) = v.to_str() {
-                let s_trim = s.trim();
-
-                // numeric seconds
-                if let Ok(secs) = s_trim.parse::<u64>() {
-                    return Some(Duration::from_secs(if secs == 0 { 1 } else { secs }));
-                }
-
-                // IMF-fixdate
-                if let Ok(dt) = DateTime::parse_from_str(s_trim, "%a, %d %b %Y %H:%M:%S GMT") {
-                    let dt_utc = dt.with_timezone(&Utc);
-                    let now = Utc::now();
-                    if dt_utc > now {
-                        let diff = dt_utc.signed_duration_since(now);
-                        return Some(Duration::from_secs(diff.num_seconds().max(1) as u64));
-                    } else {
-                        return Some(Duration::from_secs(1));
-                    }
-                }
+    /// Identifies a GET by URL and sorted headers (single-flight and cache key).
+    fn request_key(url: &str, headers: &HeaderMap) -> String {
+        let mut header_pairs: Vec<String> = headers
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, String::from_utf8_lossy(v.as_bytes())))
+            .collect();
+        header_pairs.sort();
+        format!("GET {} {}", url, header_pairs.join("&"))
+    }
 
-                // RFC2822
-                if let Ok(dt) = DateTime::parse_from_rfc2822(s_trim) {
-                    let dt_utc = dt.with_timezone(&Utc);
-                    let now = Utc::now();
-                    if dt_utc > now {
-                        let diff = dt_utc.signed_duration_since(now);
-                        return Some(Duration::from_secs(diff.num_seconds().max(1) as u64));
-                    } else {
-                        return Some(Duration::from_secs(1));
-                    }
-                }
+    /// GET through the conditional-request cache, if enabled.
+    ///
+    /// Adds the stored validators to the request (unless the caller set its own),
+    /// replays the stored response on `304`, and stores fresh 2xx responses
+    /// that carry a validator.
+    async fn cached_get(&self, url: &str, headers: HeaderMap) -> Result<RawResponse, NgError> {
+        let Some(store) = self.opts.cache.store() else {
+            return self.execute_with_retry(Method::GET, url, headers, Option::<&()>::None).await;
+        };
 
-                // RFC3339
-                if let Ok(dt) = DateTime::parse_from_rfc3339(s_trim) {
-                    let dt_utc = dt.with_timezone(&Utc);
-                    let now = Utc::now();
-                    if dt_utc > now {
-                        let diff = dt_utc.signed_duration_since(now);
-                        return Some(Duration::from_secs(diff.num_seconds().max(1) as u64));
-                    } else {
-                        return Some(Duration::from_secs(1));
-                    }
+        let key = Self::request_key(url, &headers);
+        let cached = store.get(&key);
+        let mut conditional = headers;
+        if let Some(entry) = &cached {
+            for (name, value) in [(IF_NONE_MATCH, entry.etag()), (IF_MODIFIED_SINCE, entry.last_modified())] {
+                if let Some(v) = value.and_then(|v| HeaderValue::from_str(v).ok())
+                    && !conditional.contains_key(&name)
+                {
+                    conditional.insert(name, v);
                 }
             }
         }
-        None
-    }
 
-    /// Sleep helper that releases permit for long waits and attempts a bounded re-acquire.
-    async fn smart_sleep_and_maybe_reacquire(&self, duration: Duration, permit: &mut Option<OwnedSemaphorePermit>) {
-        if duration.as_millis() as u64 > self.opts.permit_release_threshold_ms {
-            // Release permit to avoid blocking throughput for long waits.
-            let _dropped = permit.take();
-            sleep(duration).await;
-            // Attempt a bounded re-acquire to avoid indefinite blocking.
-            if let Ok(sem_res) = timeout(Duration::from_millis(200), self.semaphore.clone().acquire_owned()).await {
-                if let Ok(p) = sem_res {
-                    *permit = Some(p);
+        let raw = self.execute_with_retry(Method::GET, url, conditional, Option::<&()>::None).await?;
+
+        if raw.status == StatusCode::NOT_MODIFIED
+            && let Some(entry) = cached
+        {
+            crate::debug!(self.logger, "Serving revalidated response from cache", "url" => url);
+            let mut headers = HeaderMap::new();
+            for (k, v) in &entry.headers {
+                if let (Ok(k), Ok(v)) = (HeaderName::from_bytes(k.as_bytes()), HeaderValue::from_str(v)) {
+                    headers.append(k, v);
                 }
             }
-        } else {
-            // Short wait: keep permit to preserve logical ordering.
-            sleep(duration).await;
+            let status = StatusCode::from_u16(entry.status).unwrap_or(StatusCode::OK);
+            return Ok(RawResponse { status, headers, body: Bytes::from(entry.body), encoded_size: raw.encoded_size, version: raw.version });
+        }
+
+        if raw.status.is_success() && (raw.headers.contains_key(ETAG) || raw.headers.contains_key(LAST_MODIFIED)) {
+            let headers = raw.headers.iter()
+                .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.as_str().to_string(), v.to_string())))
+                .collect();
+            store.put(&key, CachedResponse { status: raw.status.as_u16(), headers, body: raw.text().into_owned() });
         }
+        Ok(raw)
     }
 
-    // Core request logic with retries and concurrency control.
-    async fn request_with_retry<T, B>(
+    /// Retry loop returning the final raw response (any status) or a network error.
+    async fn execute_with_retry<B>(
         &self,
         method: Method,
         url: &str,
         headers: HeaderMap,
         body: Option<&B>,
-    ) -> Result<ApiResponse<T>, NgError>
+    ) -> Result<RawResponse, NgError>
     where
-        T: DeserializeOwned + Send + 'static,
         B: Serialize + ?Sized,
     {
         // Validate allowed method
@@ -344,282 +1777,198 @@ impl KyHttp {
             "url" => url
         );
 
+        let mut headers = headers;
+        self.trace_root(&mut headers);
+        self.idempotency_key(&method, &mut headers);
+
         // total attempts = retry + 1
         let max_attempts = self.opts.retry.saturating_add(1);
+        let started = std::time::Instant::now();
+
+        // Rate limit before acquiring the permit so throttled requests do not hold one.
+        self.throttle(url).await;
 
         // Acquire permit once for the logical request (RAII guard)
-        let mut permit: Option<OwnedSemaphorePermit> = Some(self.semaphore.clone().acquire_owned().await
-            .map_err(|_| NgError::InternalError("Semaphore closed".into()))?);
+        let mut permit: Option<OwnedSemaphorePermit> = Some(self.acquire_permit().await?);
 
         // Deterministic RNG for test_mode
-        let mut rng = if self.opts.test_mode { SmallRng::seed_from_u64(0xC0FFEE) } else { SmallRng::from_entropy() };
+        let mut rng = self.backoff_rng();
 
         // last error for enriched diagnostics
         let mut last_err: Option<NgError> = None;
         let mut last_status: Option<u16> = None;
         let mut last_body_snippet: Option<String> = None;
+        let mut retry_after_waits = 0;
+        let mut auth_refreshed = false;
 
         for attempt in 1..=max_attempts {
             if attempt > 1 {
                 crate::info!(self.logger, "Retry attempt", "url" => url, "attempt" => attempt);
+                self.metrics.record_retry(url);
+                self.throttle(url).await;
             }
 
-            // Build request
-            let mut rb = self.client.request(method.clone(), url).headers(headers.clone());
-            if let Some(b) = body {
-                rb = rb.json(b);
-            }
-            let rb = self.prepare_request(rb);
-
-            // Build and execute
-            let built_req_result: Result<Request, reqwest::Error> = rb.build();
-            let resp_result = match built_req_result {
-                Ok(req) => self.client.execute(req).await.map_err(|e| e),
-                Err(e) => Err(e),
+            let (sent, sent_at) = loop {
+                let (attempt_headers, added_auth) = self.authorize(&headers).await?;
+                let sent_at = std::time::Instant::now();
+                let res = self.send_once(&method, url, &attempt_headers, body).await;
+                // Rejected provider credentials: refresh and repeat once, outside the retry count.
+                if let (Ok(raw), Some(rejected)) = (&res, &added_auth)
+                    && raw.status == StatusCode::UNAUTHORIZED
+                    && !auth_refreshed
+                {
+                    auth_refreshed = true;
+                    if self.refresh_auth(url, rejected).await? {
+                        let retry = Some((RetryReason::AuthRefresh, Duration::ZERO));
+                        self.record_attempt(&method, url, attempt, sent_at, Some(raw.status), retry);
+                        self.throttle(url).await;
+                        continue;
+                    }
+                }
+                break (res, sent_at);
             };
 
-            match resp_result {
-                Ok(resp) => {
-                    let status = resp.status();
+            match sent {
+                Ok(raw) => {
+                    let status = raw.status;
                     let status_u16 = status.as_u16();
-                    let resp_headers = resp.headers().clone();
-                    // Read body once and reuse
-                    let body_text = resp.text().await.unwrap_or_default();
-                    let snippet = if body_text.len() > 1024 { format!("{}...[truncated]", &body_text[..1024]) } else { body_text.clone() };
 
                     if status.is_success() {
-                        match serde_json::from_str::<T>(&body_text) {
-                            Ok(parsed) => {
+                        match self.validate(&raw) {
+                            Validation::Accept => {
+                                self.record_attempt(&method, url, attempt, sent_at, Some(status), None);
                                 drop(permit);
-                                return Ok(ApiResponse {
-                                    data: Some(parsed),
-                                    error_body: None,
-                                    status: status_u16,
-                                    success: true,
-                                    headers: resp_headers,
-                                });
+                                return Ok(raw);
+                            }
+                            Validation::Retry(err) if attempt < max_attempts => {
+                                crate::warn!(self.logger, "Response rejected by validator", "url" => url, "error" => err.to_string());
+                                let backoff = self.compute_backoff_with_jitter(attempt, &mut rng);
+                                let retry = Some((RetryReason::Validation, backoff));
+                                self.record_attempt(&method, url, attempt, sent_at, Some(status), retry);
+                                self.spend_retry(url)?;
+                                self.smart_sleep_and_maybe_reacquire(backoff, &mut permit).await;
+                                continue;
                             }
-                            Err(e) => {
+                            Validation::Reject(err) | Validation::Retry(err) => {
+                                self.record_attempt(&method, url, attempt, sent_at, Some(status), None);
                                 drop(permit);
-                                return Err(NgError::HttpError(format!("JSON decode: {}", e)));
+                                return Err(err);
                             }
                         }
                     }
 
+                    let snippet = if raw.body.len() > 1024 {
+                        format!("{}...[truncated]", String::from_utf8_lossy(&raw.body[..1024]))
+                    } else {
+                        raw.text().into_owned()
+                    };
+
                     // Non-success: decide retry behavior
+                    let err = NgError::HttpError(format!("Status: {}", status_u16));
+                    let decision = self.retry_decision(status, url, &raw.headers, &mut retry_after_waits);
+                    let decision = self.consult_should_retry(
+                        &RetryContext {
+                            method: &method,
+                            url,
+                            status: Some(status),
+                            headers: Some(&raw.headers),
+                            body_snippet: Some(&snippet),
+                            error: &err,
+                            attempt,
+                            elapsed: started.elapsed(),
+                        },
+                        decision,
+                    );
                     last_status = Some(status_u16);
-                    last_body_snippet = Some(snippet.clone());
-                    last_err = Some(NgError::HttpError(format!("Status: {}", status_u16)));
-
-                    // First, if this status is one of the after_status_codes, prefer honoring
-                    // the server-provided Retry-After header (numeric seconds or HTTP-date).
-                    if self.opts.after_status_codes.contains(&status) {
-                        if let Some(retry_after) = Self::parse_retry_after_from_headers(&resp_headers) {
-                            // Apply caps: prefer max_retry_after, then backoff_limit if set.
-                            let capped = if let Some(max) = self.opts.max_retry_after {
-                                std::cmp::min(retry_after, max)
-                            } else if let Some(limit) = self.opts.backoff_limit {
-                                std::cmp::min(retry_after, limit)
-                            } else {
-                                retry_after
-                            };
-
-                            crate::info!(
-                                self.logger,
-                                "Respecting Retry-After header",
-                                "url" => url,
-                                "retry_after_secs" => capped.as_secs()
-                            );
-
-                            // If there are attempts remaining, sleep and then continue to next attempt.
-                            if attempt < max_attempts {
-                                self.smart_sleep_and_maybe_reacquire(capped, &mut permit).await;
-                                continue;
-                            } else {
-                                // This is the last configured attempt but server asked to wait.
-                                // Sleep and then perform one final request attempt (instead of giving up).
-                                self.smart_sleep_and_maybe_reacquire(capped, &mut permit).await;
-
-                                // Build a fresh request and execute it as the final attempt.
-                                let mut final_rb = self.client.request(method.clone(), url).headers(headers.clone());
-                                if let Some(b) = body {
-                                    final_rb = final_rb.json(b);
-                                }
-                                let final_rb = self.prepare_request(final_rb);
-
-                                match final_rb.build() {
-                                    Ok(req) => match self.client.execute(req).await {
-                                        Ok(final_resp) => {
-                                            let final_status = final_resp.status();
-                                            let final_status_u16 = final_status.as_u16();
-                                            let final_headers = final_resp.headers().clone();
-                                            let final_body_text = final_resp.text().await.unwrap_or_default();
-
-                                            if final_status.is_success() {
-                                                match serde_json::from_str::<T>(&final_body_text) {
-                                                    Ok(parsed) => {
-                                                        drop(permit);
-                                                        return Ok(ApiResponse {
-                                                            data: Some(parsed),
-                                                            error_body: None,
-                                                            status: final_status_u16,
-                                                            success: true,
-                                                            headers: final_headers,
-                                                        });
-                                                    }
-                                                    Err(e) => {
-                                                        drop(permit);
-                                                        return Err(NgError::HttpError(format!("JSON decode: {}", e)));
-                                                    }
-                                                }
-                                            } else {
-                                                drop(permit);
-                                                return Ok(ApiResponse {
-                                                    data: None,
-                                                    error_body: if final_body_text.is_empty() { None } else { Some(final_body_text) },
-                                                    status: final_status_u16,
-                                                    success: false,
-                                                    headers: final_headers,
-                                                });
-                                            }
-                                        }
-                                        Err(e) => {
-                                            drop(permit);
-                                            return Err(NgError::HttpError(e.to_string()));
-                                        }
-                                    },
-                                    Err(e) => {
-                                        drop(permit);
-                                        return Err(NgError::InternalError(e.to_string()));
-                                    }
-                                }
-                            }
+                    last_body_snippet = Some(snippet);
+                    last_err = Some(err);
+
+                    // A server-provided Retry-After (numeric seconds or HTTP-date) takes
+                    // precedence over computed backoff.
+                    if let RetryDecision::RetryAfter(capped) = decision {
+                        crate::info!(
+                            self.logger,
+                            "Respecting Retry-After header",
+                            "url" => url,
+                            "retry_after_secs" => capped.as_secs()
+                        );
+
+                        let retry = Some((RetryReason::RetryAfter, capped));
+                        self.record_attempt(&method, url, attempt, sent_at, Some(status), retry);
+                        self.spend_retry(url)?;
+                        self.smart_sleep_and_maybe_reacquire(capped, &mut permit).await;
+
+                        // If there are attempts remaining, continue to next attempt.
+                        if attempt < max_attempts {
+                            continue;
                         }
-                    }
-
-                    let is_idempotent = self.opts.allowed_methods.contains(&method);
-                    let allow_retries = is_idempotent;
-                    let is_retryable_status = status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
-
-
-                    if allow_retries && is_retryable_status {
-                        // Prefer server Retry-After if present
-                        if let Some(retry_after) = Self::parse_retry_after_from_headers(&resp_headers) {
-                            let capped = if let Some(max) = self.opts.max_retry_after {
-                                std::cmp::min(retry_after, max)
-                            } else if let Some(limit) = self.opts.backoff_limit {
-                                std::cmp::min(retry_after, limit)
-                            } else {
-                                retry_after
-                            };
-
-                            // If there are attempts remaining, sleep and continue normally.
-                            if attempt < max_attempts {
-                                self.smart_sleep_and_maybe_reacquire(capped, &mut permit).await;
-                                continue;
-                            }
 
-                            // If this is the LAST attempt but server asked to wait, sleep and then
-                            // perform one final request attempt after sleeping (instead of returning immediately).
-                            self.smart_sleep_and_maybe_reacquire(capped, &mut permit).await;
-
-                            // Build and execute a fresh final request attempt after sleeping.
-                            let mut final_rb = self.client.request(method.clone(), url).headers(headers.clone());
-                            if let Some(b) = body {
-                                final_rb = final_rb.json(b);
-                            }
-                            let final_rb = self.prepare_request(final_rb);
-
-                            match final_rb.build() {
-                                Ok(req) => match self.client.execute(req).await {
-                                    Ok(final_resp) => {
-                                        let final_status = final_resp.status();
-                                        let final_status_u16 = final_status.as_u16();
-                                        let final_headers = final_resp.headers().clone();
-                                        let final_body_text = final_resp.text().await.unwrap_or_default();
-
-                                        if final_status.is_success() {
-                                            match serde_json::from_str::<T>(&final_body_text) {
-                                                Ok(parsed) => {
-                                                    drop(permit);
-                                                    return Ok(ApiResponse {
-                                                        data: Some(parsed),
-                                                        error_body: None,
-                                                        status: final_status_u16,
-                                                        success: true,
-                                                        headers: final_headers,
-                                                    });
-                                                }
-                                                Err(e) => {
-                                                    drop(permit);
-                                                    return Err(NgError::HttpError(format!("JSON decode: {}", e)));
-                                                }
-                                            }
-                                        } else {
-                                            drop(permit);
-                                            return Ok(ApiResponse {
-                                                data: None,
-                                                error_body: if final_body_text.is_empty() { None } else { Some(final_body_text) },
-                                                status: final_status_u16,
-                                                success: false,
-                                                headers: final_headers,
-                                            });
-                                        }
-                                    }
-                                    Err(e) => {
-                                        drop(permit);
-                                        return Err(NgError::HttpError(e.to_string()));
-                                    }
-                                },
-                                Err(e) => {
-                                    drop(permit);
-                                    return Err(NgError::InternalError(e.to_string()));
-                                }
-                            }
+                        // This is the last configured attempt but server asked to wait.
+                        // Perform one final request attempt after sleeping (instead of giving up).
+                        self.throttle(url).await;
+                        self.metrics.record_retry(url);
+                        let (attempt_headers, _) = self.authorize(&headers).await?;
+                        let sent_at = std::time::Instant::now();
+                        let final_resp = self.send_once(&method, url, &attempt_headers, body).await;
+                        let final_status = final_resp.as_ref().ok().map(|raw| raw.status);
+                        self.record_attempt(&method, url, attempt + 1, sent_at, final_status, None);
+                        drop(permit);
+                        if let Ok(raw) = &final_resp
+                            && raw.status.is_success()
+                            && let Validation::Reject(err) | Validation::Retry(err) = self.validate(raw)
+                        {
+                            return Err(err);
                         }
+                        return final_resp.map_err(|e| NgError::HttpError(e.to_string()));
+                    }
 
+                    if decision == RetryDecision::Backoff && attempt < max_attempts {
                         // Otherwise compute backoff with jitter (only if attempts remain)
-                        if attempt < max_attempts {
-                            let backoff = self.compute_backoff_with_jitter(attempt, &mut rng);
-                            self.smart_sleep_and_maybe_reacquire(backoff, &mut permit).await;
-                            continue;
-                        }
+                        let backoff = self.compute_backoff_with_jitter(attempt, &mut rng);
+                        let retry = Some((RetryReason::Status, backoff));
+                        self.record_attempt(&method, url, attempt, sent_at, Some(status), retry);
+                        self.spend_retry(url)?;
+                        self.smart_sleep_and_maybe_reacquire(backoff, &mut permit).await;
+                        continue;
                     }
 
-
-                    // Not retryable or exhausted attempts: return ApiResponse with error body
+                    // Not retryable or exhausted attempts: return the error response
+                    self.record_attempt(&method, url, attempt, sent_at, Some(status), None);
                     drop(permit);
-                    return Ok(ApiResponse {
-                        data: None,
-                        error_body: if body_text.is_empty() { None } else { Some(body_text) },
-                        status: status_u16,
-                        success: false,
-                        headers: resp_headers,
-                    });
+                    return Ok(raw);
                 }
                 Err(e) => {
                     // Network-level failure
                     crate::error!(self.logger, "Network failure", "url" => url, "error" => e.to_string());
 
                     if e.is_timeout() && !self.opts.retry_on_timeout {
+                        self.record_attempt(&method, url, attempt, sent_at, None, None);
                         drop(permit);
                         return Err(NgError::HttpError(e.to_string()));
                     }
 
-                    last_err = Some(NgError::HttpError(e.to_string()));
-
-                    // consult predicate if present
-                    let should = if let Some(pred) = &self.opts.should_retry {
-                        (pred)(None, last_err.as_ref().unwrap(), attempt)
-                    } else {
-                        true
-                    };
+                    let err = NgError::HttpError(e.to_string());
+                    let should = self.allows_network_retry(&RetryContext {
+                        method: &method,
+                        url,
+                        status: None,
+                        headers: None,
+                        body_snippet: None,
+                        error: &err,
+                        attempt,
+                        elapsed: started.elapsed(),
+                    });
+                    last_err = Some(err);
 
                     if should && attempt < max_attempts {
                         let backoff = self.compute_backoff_with_jitter(attempt, &mut rng);
+                        let reason = if e.is_timeout() { RetryReason::Timeout } else { RetryReason::Network };
+                        self.record_attempt(&method, url, attempt, sent_at, None, Some((reason, backoff)));
+                        self.spend_retry(url)?;
                         self.smart_sleep_and_maybe_reacquire(backoff, &mut permit).await;
                         continue;
                     } else {
+                        self.record_attempt(&method, url, attempt, sent_at, None, None);
                         drop(permit);
                         return Err(last_err.unwrap_or_else(|| NgError::InternalError("Network failure".into())));
                     }
@@ -637,6 +1986,366 @@ impl KyHttp {
         Err(NgError::InternalError(parts.join(", ")))
     }
 
+    /// Sends GET attempts until a response arrives that is not retried, leaving
+    /// its body unread.
+    ///
+    /// Applies the network and status retry policy of `execute_with_retry`.
+    /// `attempt` counts attempts across calls so that callers which resume
+    /// interrupted bodies share one retry budget; `started` is when the
+    /// logical request began.
+    async fn open_streaming_get(
+        &self,
+        url: &str,
+        headers: &HeaderMap,
+        permit: &mut Option<OwnedSemaphorePermit>,
+        rng: &mut SmallRng,
+        attempt: &mut usize,
+        started: std::time::Instant,
+    ) -> Result<reqwest::Response, NgError> {
+        let max_attempts = self.opts.retry.saturating_add(1);
+        let mut retry_after_waits = 0;
+        let mut auth_refreshed = false;
+        loop {
+            *attempt += 1;
+            if *attempt > 1 {
+                crate::info!(self.logger, "Retry attempt", "url" => url, "attempt" => *attempt);
+                self.metrics.record_retry(url);
+                self.throttle(url).await;
+            }
+
+            let (attempt_headers, added_auth) = self.authorize(headers).await?;
+            let req = self.build_request(&Method::GET, url, &attempt_headers, Option::<&()>::None)
+                .map_err(|e| NgError::HttpError(e.to_string()))?;
+            let sent_at = std::time::Instant::now();
+            match self.execute(url, req).await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if let Some(rejected) = &added_auth
+                        && status == StatusCode::UNAUTHORIZED
+                        && !auth_refreshed
+                    {
+                        auth_refreshed = true;
+                        if self.refresh_auth(url, rejected).await? {
+                            let retry = Some((RetryReason::AuthRefresh, Duration::ZERO));
+                            self.record_attempt(&Method::GET, url, *attempt, sent_at, Some(status), retry);
+                            *attempt -= 1;
+                            continue;
+                        }
+                    }
+                    if status.is_success() || *attempt >= max_attempts {
+                        self.record_attempt(&Method::GET, url, *attempt, sent_at, Some(status), None);
+                        return Ok(resp);
+                    }
+                    let err = NgError::HttpError(format!("Status: {}", status.as_u16()));
+                    let decision = self.retry_decision(status, url, resp.headers(), &mut retry_after_waits);
+                    let ctx = RetryContext {
+                        method: &Method::GET,
+                        url,
+                        status: Some(status),
+                        headers: Some(resp.headers()),
+                        body_snippet: None,
+                        error: &err,
+                        attempt: *attempt,
+                        elapsed: started.elapsed(),
+                    };
+                    let (reason, delay) = match self.consult_should_retry(&ctx, decision) {
+                        RetryDecision::Stop => {
+                            self.record_attempt(&Method::GET, url, *attempt, sent_at, Some(status), None);
+                            return Ok(resp);
+                        }
+                        RetryDecision::RetryAfter(delay) => (RetryReason::RetryAfter, delay),
+                        RetryDecision::Backoff => (RetryReason::Status, self.compute_backoff_with_jitter(*attempt, rng)),
+                    };
+                    self.record_attempt(&Method::GET, url, *attempt, sent_at, Some(status), Some((reason, delay)));
+                    self.spend_retry(url)?;
+                    self.smart_sleep_and_maybe_reacquire(delay, permit).await;
+                }
+                Err(e) => {
+                    crate::error!(self.logger, "Network failure", "url" => url, "error" => e.to_string());
+                    let err = NgError::HttpError(e.to_string());
+                    let retry = (!e.is_timeout() || self.opts.retry_on_timeout)
+                        && *attempt < max_attempts
+                        && self.allows_network_retry(&RetryContext {
+                            method: &Method::GET,
+                            url,
+                            status: None,
+                            headers: None,
+                            body_snippet: None,
+                            error: &err,
+                            attempt: *attempt,
+                            elapsed: started.elapsed(),
+                        });
+                    if !retry {
+                        self.record_attempt(&Method::GET, url, *attempt, sent_at, None, None);
+                        return Err(err);
+                    }
+                    let backoff = self.compute_backoff_with_jitter(*attempt, rng);
+                    let reason = if e.is_timeout() { RetryReason::Timeout } else { RetryReason::Network };
+                    self.record_attempt(&Method::GET, url, *attempt, sent_at, None, Some((reason, backoff)));
+                    self.spend_retry(url)?;
+                    self.smart_sleep_and_maybe_reacquire(backoff, permit).await;
+                }
+            }
+        }
+    }
+
+    /// Full body size announced by a response that starts at byte `offset`:
+    /// the `Content-Range` total, else `Content-Length` plus the offset.
+    fn expected_total(resp: &reqwest::Response, offset: u64) -> Option<u64> {
+        resp.headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit_once('/'))
+            .and_then(|(_, total)| total.parse().ok())
+            .or_else(|| resp.content_length().map(|len| len + offset))
+    }
+
+    fn report_progress(&self, so_far: u64, total: Option<u64>) {
+        if let Some(cb) = &self.opts.on_download_progress {
+            cb(so_far, total);
+        }
+    }
+
+    /// Streams a GET response body to `dest`, returning the number of bytes written.
+    ///
+    /// Uses the same permit, retry, backoff and Retry-After handling as [`get`](Self::get),
+    /// but never buffers the body. If a transfer breaks off and the server
+    /// advertised `Accept-Ranges: bytes`, the next attempt requests only the
+    /// missing range and appends to the partial file; otherwise it starts over.
+    ///
+    /// Note that `timeout` bounds each attempt including the body transfer, so
+    /// large files may need a longer timeout (or none) to finish.
+    pub async fn download(&self, url: &str, headers: HeaderMap, dest: impl AsRef<Path>) -> Result<u64, NgError> {
+        self.cancellable(url, self.download_to(url, headers, dest.as_ref())).await
+    }
+
+    async fn download_to(&self, url: &str, headers: HeaderMap, dest: &Path) -> Result<u64, NgError> {
+        crate::info!(self.logger, "Download start", "url" => url, "dest" => dest.display().to_string());
+        let mut headers = headers;
+        self.trace_root(&mut headers);
+
+        let max_attempts = self.opts.retry.saturating_add(1);
+        self.throttle(url).await;
+        let mut permit = Some(self.acquire_permit().await?);
+        let mut rng = self.backoff_rng();
+        let mut attempt = 0;
+        let mut written: u64 = 0;
+        let mut supports_range = false;
+        let started = std::time::Instant::now();
+
+        loop {
+            let resume_from = (supports_range && written > 0).then_some(written);
+            let mut attempt_headers = headers.clone();
+            if let Some(offset) = resume_from {
+                let range = HeaderValue::from_str(&format!("bytes={}-", offset))
+                    .map_err(|e| NgError::InternalError(e.to_string()))?;
+                attempt_headers.insert(RANGE, range);
+            }
+
+            let mut resp = self.open_streaming_get(url, &attempt_headers, &mut permit, &mut rng, &mut attempt, started).await?;
+            let status = resp.status();
+            if status == StatusCode::RANGE_NOT_SATISFIABLE && resume_from.is_some() {
+                // The partial file already holds the whole body.
+                return Ok(written);
+            }
+            if !status.is_success() {
+                return Err(NgError::HttpError(format!("Status: {}", status.as_u16())));
+            }
+
+            supports_range |= resp.headers().get(ACCEPT_RANGES).is_some_and(|v| v.as_bytes() == b"bytes");
+            let append = resume_from.is_some() && status == StatusCode::PARTIAL_CONTENT;
+            if !append {
+                written = 0;
+            }
+            let total = Self::expected_total(&resp, written);
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(append)
+                .truncate(!append)
+                .open(dest)
+                .await
+                .map_err(|e| NgError::InternalError(format!("Download open failed: {}", e)))?;
+
+            let interrupted = loop {
+                match resp.chunk().await {
+                    Ok(Some(chunk)) => {
+                        file.write_all(&chunk).await
+                            .map_err(|e| NgError::InternalError(format!("Download write failed: {}", e)))?;
+                        written += chunk.len() as u64;
+                        self.report_progress(written, total);
+                    }
+                    Ok(None) => break None,
+                    Err(e) => break Some(e),
+                }
+            };
+            file.flush().await
+                .map_err(|e| NgError::InternalError(format!("Download write failed: {}", e)))?;
+
+            let Some(e) = interrupted else {
+                crate::info!(self.logger, "Download complete", "url" => url, "bytes" => written);
+                return Ok(written);
+            };
+            crate::warn!(self.logger, "Download interrupted", "url" => url, "written" => written, "error" => e.to_string());
+            if attempt >= max_attempts {
+                return Err(NgError::HttpError(e.to_string()));
+            }
+            let backoff = self.compute_backoff_with_jitter(attempt, &mut rng);
+            self.spend_retry(url)?;
+            self.smart_sleep_and_maybe_reacquire(backoff, &mut permit).await;
+        }
+    }
+
+    /// Streams a GET response body chunk by chunk.
+    ///
+    /// The request is sent when the stream is first polled. Connection failures
+    /// and retryable statuses are retried as for [`get`](Self::get) until the
+    /// response headers arrive; a final non-success status is yielded as a single
+    /// `HttpError`. Errors while reading the body end the stream, since chunks
+    /// already yielded cannot be replayed. The concurrency permit is held until
+    /// the stream is dropped or exhausted.
+    pub fn get_stream(&self, url: &str, headers: HeaderMap) -> impl Stream<Item = Result<Bytes, NgError>> + Send + 'static {
+        let this = self.clone();
+        let target = url;
+        let url = url.to_string();
+        let mut headers = headers;
+        this.trace_root(&mut headers);
+
+        let open = async move {
+            this.throttle(&url).await;
+            let mut permit = Some(this.acquire_permit().await?);
+            let mut rng = this.backoff_rng();
+            let mut attempt = 0;
+            let started = std::time::Instant::now();
+            let resp = this.open_streaming_get(&url, &headers, &mut permit, &mut rng, &mut attempt, started).await?;
+            if !resp.status().is_success() {
+                return Err(NgError::HttpError(format!("Status: {}", resp.status().as_u16())));
+            }
+            let total = Self::expected_total(&resp, 0);
+            Ok((this, resp, permit, 0u64, total))
+        };
+
+        let body = stream::once(open).flat_map(|opened| match opened {
+            Ok(state) => stream::unfold(Some(state), |state| async move {
+                let (this, mut resp, permit, so_far, total) = state?;
+                match resp.chunk().await {
+                    Ok(Some(chunk)) => {
+                        let so_far = so_far + chunk.len() as u64;
+                        this.report_progress(so_far, total);
+                        Some((Ok(chunk), Some((this, resp, permit, so_far, total))))
+                    }
+                    Ok(None) => None,
+                    Err(e) => Some((Err(NgError::HttpError(e.to_string())), None)),
+                }
+            })
+            .left_stream(),
+            Err(e) => stream::once(std::future::ready(Err(e))).right_stream(),
+        });
+        self.cancellable_stream(target, body)
+    }
+
+    /// Streams a newline-delimited JSON (NDJSON) body, one record per line.
+    ///
+    /// Built on [`get_stream`](Self::get_stream): the body is read incrementally,
+    /// so only the current line is buffered. Blank lines are skipped and a final
+    /// line without a trailing newline is still parsed. A line that fails to
+    /// decode yields an `HttpError` and the stream continues with the next one;
+    /// request and transport errors end it as for `get_stream`.
+    pub fn get_ndjson<T>(&self, url: &str, headers: HeaderMap) -> impl Stream<Item = Result<T, NgError>> + Send + 'static
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let chunks = Box::pin(self.get_stream(url, headers));
+        // State: body stream, buffered bytes, and how far `buf` was already searched for '\n'.
+        let lines = stream::unfold(Some((chunks, BytesMut::new(), 0usize)), |state| async move {
+            let (mut chunks, mut buf, mut scanned) = state?;
+            loop {
+                if let Some(pos) = buf[scanned..].iter().position(|&b| b == b'\n') {
+                    let line = buf.split_to(scanned + pos + 1).freeze();
+                    return Some((Ok(line), Some((chunks, buf, 0))));
+                }
+                scanned = buf.len();
+                match chunks.next().await {
+                    Some(Ok(chunk)) => buf.extend_from_slice(&chunk),
+                    Some(Err(e)) => return Some((Err(e), None)),
+                    None if buf.is_empty() => return None,
+                    None => return Some((Ok(buf.freeze()), None)),
+                }
+            }
+        });
+        lines.filter_map(|line| async move {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            let line = line.trim_ascii();
+            if line.is_empty() {
+                return None;
+            }
+            Some(serde_json::from_slice::<T>(line).map_err(|e| NgError::HttpError(format!("NDJSON decode: {}", e))))
+        })
+    }
+
+    /// GETs several URLs concurrently, returning one result per URL in input order.
+    ///
+    /// At most `concurrency` requests are started at once (at least one); each
+    /// still takes a permit from the client's semaphore, so the effective
+    /// parallelism is also bounded by `limit`. A failing URL does not affect
+    /// the others.
+    pub async fn get_many<T, I, S>(&self, urls: I, headers: HeaderMap, concurrency: usize) -> Vec<Result<ApiResponse<T>, NgError>>
+    where
+        T: DeserializeOwned + Send + 'static,
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let urls: Vec<String> = urls.into_iter().map(|u| u.as_ref().to_string()).collect();
+        stream::iter(urls.iter())
+            .map(|url| self.get::<T>(url, headers.clone()))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Streams the pages of a paginated GET endpoint, decoded as `T`.
+    ///
+    /// Pages are fetched lazily, one at a time, each through [`get`](Self::get)
+    /// (so retries and the rate limiter apply between pages). `paginator`
+    /// locates the next page; the stream ends after the last one. A
+    /// non-success status or undecodable page is yielded as an error and ends
+    /// the stream. Use `.take(n)` to cap the number of pages.
+    pub fn get_paginated<T>(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        paginator: Paginator,
+    ) -> impl Stream<Item = Result<T, NgError>> + Send + 'static
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let first = paginator.first_url(url);
+        let state = (self.clone(), headers, paginator, Some(first));
+        stream::unfold(state, |(this, headers, paginator, next)| async move {
+            let url = match next? {
+                Ok(url) => url,
+                Err(e) => return Some((Err(e), (this, headers, paginator, None))),
+            };
+            crate::debug!(this.logger, "Fetching page", "url" => url.as_str());
+            let (page, next) = match this.get::<serde_json::Value>(url.as_str(), headers.clone()).await {
+                Ok(resp) if resp.success => {
+                    let body = resp.data.unwrap_or_default();
+                    let next = paginator.next_url(&url, &resp.headers, &body);
+                    match serde_json::from_value::<T>(body) {
+                        Ok(page) => (Ok(page), next.map(Ok)),
+                        Err(e) => (Err(NgError::HttpError(format!("JSON decode: {}", e))), None),
+                    }
+                }
+                Ok(resp) => (Err(NgError::HttpError(format!("Status: {}", resp.status))), None),
+                Err(e) => (Err(e), None),
+            };
+            Some((page, (this, headers, paginator, next)))
+        })
+    }
+
     /// Public GET convenience
     pub async fn get<T: DeserializeOwned + Send + 'static>(
         &self,
@@ -646,6 +2355,36 @@ impl KyHttp {
         self.request_with_retry(Method::GET, url, headers, Option::<&()>::None).await
     }
 
+    /// /// get_if_modified
+    ///
+    /// Conditional GET: sends `validators` as `If-None-Match` /
+    /// `If-Modified-Since` and returns [`Conditional::NotModified`] on `304`.
+    /// Bypasses the response cache and single-flight so the caller's own
+    /// validators decide freshness.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Request URL.
+    /// * `headers` - Request headers.
+    /// * `validators` - ETag / Last-Modified of the caller's current copy.
+    pub async fn get_if_modified<T: DeserializeOwned + Send + 'static>(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        validators: &Validators,
+    ) -> Result<Conditional<T>, NgError> {
+        let mut headers = headers;
+        validators.apply(&mut headers);
+        let raw = self
+            .guarded(url, self.execute_with_retry(Method::GET, url, headers, Option::<&()>::None))
+            .await?;
+        if raw.status == StatusCode::NOT_MODIFIED {
+            crate::debug!(self.logger, "Not modified", "url" => url);
+            return Ok(Conditional::NotModified);
+        }
+        Self::into_api_response(raw).map(Conditional::Fresh)
+    }
+
     /// /// put
     ///
     /// Public PUT request with JSON body and JSON response parsing.
@@ -735,6 +2474,21 @@ impl KyHttp {
             .await
     }
 
+    /// /// head_meta
+    ///
+    /// HEAD request returning typed size, type and freshness metadata, e.g.
+    /// to check a file before a large [`download`](Self::download).
+    /// Non-success statuses are returned (with `success = false`), not errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Request URL.
+    /// * `headers` - Request headers.
+    pub async fn head_meta(&self, url: &str, headers: HeaderMap) -> Result<HeadMeta, NgError> {
+        let raw = self.request_raw(Method::HEAD, url, headers, Option::<&()>::None).await?;
+        Ok(HeadMeta::from_headers(raw.status, raw.headers))
+    }
+
     /// /// options
     ///
     /// Public OPTIONS request that parses JSON into `T`.
@@ -0,0 +1,143 @@
+//! src/retrieve/metrics.rs
+//!
+//! In-process request metrics for `KyHttp`, aggregated per upstream host.
+//!
+//! Every attempt (including retries) counts as a request, is classified by
+//! status class (or as a network error) and has its latency observed up to the
+//! arrival of the response headers. A [`KyMetrics`] handle is cheap to clone;
+//! pass one through `KyOptions::metrics` to aggregate several clients.
+//! [`KyMetrics::snapshot`] returns plain serializable data for export.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Upper bounds (ms) of the latency histogram buckets; a final bucket catches the rest.
+pub const LATENCY_BUCKETS_MS: [u64; 10] = [10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Fixed-bucket latency histogram.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LatencyHistogram {
+    /// Observation counts per bucket of [`LATENCY_BUCKETS_MS`], plus one overflow bucket.
+    pub buckets: Vec<u64>,
+    pub count: u64,
+    pub sum_ms: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self { buckets: vec![0; LATENCY_BUCKETS_MS.len() + 1], count: 0, sum_ms: 0 }
+    }
+}
+
+impl LatencyHistogram {
+    fn observe(&mut self, latency: Duration) {
+        let ms = latency.as_millis() as u64;
+        let idx = LATENCY_BUCKETS_MS.iter().position(|&b| ms <= b).unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[idx] += 1;
+        self.count += 1;
+        self.sum_ms += ms;
+    }
+
+    /// Mean latency, if anything was observed.
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0).then(|| Duration::from_millis(self.sum_ms / self.count))
+    }
+
+    /// Upper bound of the bucket containing quantile `q` (0.0..=1.0).
+    /// `None` if empty or if the quantile falls in the overflow bucket.
+    pub fn quantile_upper_bound(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let target = ((q.clamp(0.0, 1.0) * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (i, n) in self.buckets.iter().enumerate() {
+            seen += n;
+            if seen >= target {
+                return LATENCY_BUCKETS_MS.get(i).map(|&ms| Duration::from_millis(ms));
+            }
+        }
+        None
+    }
+}
+
+/// Counters for one host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HostMetrics {
+    /// Attempts sent, including retries.
+    pub requests: u64,
+    /// Attempts that were retries of an earlier attempt.
+    pub retries: u64,
+    /// Attempts that were duplicated because the first request was slow.
+    pub hedges: u64,
+    /// Attempts that failed without a response (connect, timeout, ...).
+    pub network_errors: u64,
+    /// Responses per status class, keyed `"1xx"` .. `"5xx"`.
+    pub status_classes: BTreeMap<String, u64>,
+    pub latency: LatencyHistogram,
+}
+
+/// Shared, thread-safe metrics handle.
+#[derive(Debug, Clone, Default)]
+pub struct KyMetrics {
+    hosts: Arc<Mutex<BTreeMap<String, HostMetrics>>>,
+}
+
+impl KyMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy of the metrics of every host seen so far.
+    pub fn snapshot(&self) -> BTreeMap<String, HostMetrics> {
+        self.lock().clone()
+    }
+
+    /// Copy of the metrics of one host.
+    pub fn host(&self, host: &str) -> Option<HostMetrics> {
+        self.lock().get(host).cloned()
+    }
+
+    /// Clears all counters.
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    pub(crate) fn record_response(&self, url: &str, status: u16, latency: Duration) {
+        self.with_host(url, |m| {
+            m.requests += 1;
+            *m.status_classes.entry(format!("{}xx", status / 100)).or_default() += 1;
+            m.latency.observe(latency);
+        });
+    }
+
+    pub(crate) fn record_network_error(&self, url: &str, latency: Duration) {
+        self.with_host(url, |m| {
+            m.requests += 1;
+            m.network_errors += 1;
+            m.latency.observe(latency);
+        });
+    }
+
+    pub(crate) fn record_retry(&self, url: &str) {
+        self.with_host(url, |m| m.retries += 1);
+    }
+
+    pub(crate) fn record_hedge(&self, url: &str) {
+        self.with_host(url, |m| m.hedges += 1);
+    }
+
+    fn with_host(&self, url: &str, f: impl FnOnce(&mut HostMetrics)) {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        f(self.lock().entry(host).or_default());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, HostMetrics>> {
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
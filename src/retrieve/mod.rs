@@ -1 +1,18 @@
-pub mod ky_http;
\ No newline at end of file
+pub mod auth;
+pub mod cache;
+pub mod cassette;
+pub mod clock;
+pub mod encoding;
+pub mod ky_http;
+pub mod metrics;
+pub mod oauth2;
+pub mod paginate;
+pub mod proxy;
+pub mod rate_limit;
+pub mod retry_budget;
+pub mod retry_policy;
+pub mod signing;
+pub mod sse;
+pub mod tls;
+pub mod trace;
+pub mod url_builder;
@@ -0,0 +1,228 @@
+//! src/retrieve/oauth2.rs
+//!
+//! OAuth2 client-credentials grant (RFC 6749 §4.4) as an [`AuthProvider`].
+//!
+//! [`ClientCredentialsProvider`] posts `grant_type=client_credentials` to the
+//! token endpoint, caches the access token and refreshes it ahead of its
+//! `expires_in` (or after a `401`) through [`BearerTokenProvider`].
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::retrieve::oauth2::{ClientCredentials, ClientCredentialsProvider};
+//! # use rs_lib_ng::retrieve::ky_http::KyOptions;
+//! # use std::sync::Arc;
+//! # fn demo() -> Result<(), rs_lib_ng::core::error::NgError> {
+//! let creds = ClientCredentials::new("https://auth.example.com/oauth/token", "my-id", "my-secret")
+//!     .with_scopes(["marketdata.read"]);
+//! let opts = KyOptions::builder()
+//!     .auth(Arc::new(ClientCredentialsProvider::new(creds)))
+//!     .build()?;
+//! # Ok(()) }
+//! ```
+
+use base64::Engine;
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::Client;
+use serde::Deserialize;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::error::NgError;
+use crate::retrieve::auth::{AccessToken, AuthProvider, BearerTokenProvider};
+
+/// Timeout of token endpoint requests made with the default client.
+pub const TOKEN_REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How the client authenticates to the token endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientAuthStyle {
+    /// HTTP basic auth with the client id and secret (RFC 6749 §2.3.1).
+    #[default]
+    BasicHeader,
+    /// `client_id` / `client_secret` form parameters, for providers that require them.
+    RequestBody,
+}
+
+/// Client-credentials grant parameters.
+#[derive(Clone)]
+pub struct ClientCredentials {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scopes: Vec<String>,
+    pub auth_style: ClientAuthStyle,
+    /// Additional form parameters, e.g. `audience` for some providers.
+    pub extra_params: Vec<(String, String)>,
+}
+
+impl ClientCredentials {
+    pub fn new(token_url: &str, client_id: &str, client_secret: &str) -> Self {
+        Self {
+            token_url: token_url.to_string(),
+            client_id: client_id.to_string(),
+            client_secret: client_secret.to_string(),
+            scopes: Vec::new(),
+            auth_style: ClientAuthStyle::default(),
+            extra_params: Vec::new(),
+        }
+    }
+
+    /// Requests these scopes (sent space-separated).
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_auth_style(mut self, style: ClientAuthStyle) -> Self {
+        self.auth_style = style;
+        self
+    }
+
+    /// Adds a form parameter to token requests.
+    pub fn with_param(mut self, key: &str, value: &str) -> Self {
+        self.extra_params.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Performs one token request.
+    pub async fn request_token(&self, client: &Client) -> Result<AccessToken, NgError> {
+        let mut req = client
+            .post(&self.token_url)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(self.form_body());
+        if self.auth_style == ClientAuthStyle::BasicHeader {
+            let encoded = base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", form_encode(&self.client_id), form_encode(&self.client_secret)));
+            let mut value = HeaderValue::from_str(&format!("Basic {}", encoded))
+                .map_err(|_| NgError::ConfigError("OAuth2 client credentials are not header-safe".into()))?;
+            value.set_sensitive(true);
+            req = req.header(AUTHORIZATION, value);
+        }
+
+        let resp = req.send().await
+            .map_err(|e| NgError::HttpError(format!("OAuth2 token request failed: {}", e)))?;
+        let status = resp.status();
+        let body = resp.text().await
+            .map_err(|e| NgError::HttpError(format!("OAuth2 token request failed: {}", e)))?;
+
+        if !status.is_success() {
+            let detail = serde_json::from_str::<TokenError>(&body)
+                .map(|e| match e.error_description {
+                    Some(desc) => format!("{}: {}", e.error, desc),
+                    None => e.error,
+                })
+                .unwrap_or_else(|_| body.chars().take(200).collect());
+            return Err(NgError::HttpError(format!("OAuth2 token endpoint returned {}: {}", status.as_u16(), detail)));
+        }
+
+        let token: TokenResponse = serde_json::from_str(&body).map_err(|e| NgError::MalformedResponse {
+            endpoint: self.token_url.clone(),
+            details: format!("Invalid OAuth2 token response: {}", e),
+        })?;
+        if token.token_type.as_deref().is_some_and(|t| !t.eq_ignore_ascii_case("bearer")) {
+            return Err(NgError::MalformedResponse {
+                endpoint: self.token_url.clone(),
+                details: format!("Unsupported OAuth2 token_type '{}'", token.token_type.unwrap_or_default()),
+            });
+        }
+        Ok(AccessToken::new(&token.access_token, token.expires_in.map(Duration::from_secs)))
+    }
+
+    fn form_body(&self) -> String {
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        form.append_pair("grant_type", "client_credentials");
+        if !self.scopes.is_empty() {
+            form.append_pair("scope", &self.scopes.join(" "));
+        }
+        if self.auth_style == ClientAuthStyle::RequestBody {
+            form.append_pair("client_id", &self.client_id);
+            form.append_pair("client_secret", &self.client_secret);
+        }
+        for (k, v) in &self.extra_params {
+            form.append_pair(k, v);
+        }
+        form.finish()
+    }
+}
+
+/// Client ids and secrets are form-encoded before basic auth (RFC 6749 §2.3.1).
+fn form_encode(value: &str) -> String {
+    form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+impl fmt::Debug for ClientCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientCredentials")
+            .field("token_url", &self.token_url)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"***")
+            .field("scopes", &self.scopes)
+            .field("auth_style", &self.auth_style)
+            .finish()
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TokenError {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+/// [`AuthProvider`] obtaining bearer tokens with the client-credentials grant.
+pub struct ClientCredentialsProvider {
+    inner: BearerTokenProvider,
+}
+
+impl ClientCredentialsProvider {
+    /// Provider using its own HTTP client with [`TOKEN_REQUEST_TIMEOUT`].
+    pub fn new(creds: ClientCredentials) -> Self {
+        let client = Client::builder().timeout(TOKEN_REQUEST_TIMEOUT).build().unwrap_or_else(|_| Client::new());
+        Self::with_http_client(creds, client)
+    }
+
+    /// Provider sending token requests with `client` (e.g. one with proxy or TLS settings).
+    pub fn with_http_client(creds: ClientCredentials, client: Client) -> Self {
+        let creds = Arc::new(creds);
+        let fetch = Arc::new(move || {
+            let (creds, client) = (creds.clone(), client.clone());
+            Box::pin(async move { creds.request_token(&client).await }) as BoxFuture<'static, _>
+        });
+        Self { inner: BearerTokenProvider::new(fetch) }
+    }
+
+    /// Refreshes tokens `margin` before they expire.
+    pub fn with_refresh_margin(mut self, margin: Duration) -> Self {
+        self.inner = self.inner.with_refresh_margin(margin);
+        self
+    }
+
+    /// Returns a fresh token, requesting one if needed.
+    pub async fn token(&self) -> Result<AccessToken, NgError> {
+        self.inner.token().await
+    }
+}
+
+impl AuthProvider for ClientCredentialsProvider {
+    fn authorization<'a>(&'a self) -> BoxFuture<'a, Result<HeaderValue, NgError>> {
+        self.inner.authorization()
+    }
+
+    fn invalidate<'a>(&'a self, rejected: &'a HeaderValue) -> BoxFuture<'a, Result<bool, NgError>> {
+        self.inner.invalidate(rejected)
+    }
+}
@@ -0,0 +1,121 @@
+//! src/retrieve/paginate.rs
+//!
+//! Pagination strategies for `KyHttp::get_paginated`.
+//!
+//! A [`Paginator`] derives the URL of the next page from the page just
+//! received: its `Link` header, a cursor inside its JSON body, or an
+//! offset/limit window. Each page is fetched with a regular `get`, so retries,
+//! auth and the shared rate limiter apply between pages as well.
+
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::core::error::NgError;
+
+/// How the next page is located.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Paginator {
+    /// Follows `Link: <url>; rel="next"` (RFC 8288) until a page has none.
+    LinkHeader,
+    /// Reads the next cursor at JSON pointer `cursor_pointer` (e.g.
+    /// `"/meta/next_cursor"`) and sends it as query parameter `param`.
+    /// Stops when the cursor is missing, null or empty.
+    Cursor { cursor_pointer: String, param: String },
+    /// Sends `offset_param` / `limit_param` query parameters, starting at 0.
+    /// Stops after a page whose array at `items_pointer` holds fewer than
+    /// `limit` items.
+    OffsetLimit {
+        offset_param: String,
+        limit_param: String,
+        limit: usize,
+        items_pointer: String,
+    },
+}
+
+impl Paginator {
+    /// Cursor pagination with the cursor at `cursor_pointer`, sent as `param`.
+    pub fn cursor(cursor_pointer: &str, param: &str) -> Self {
+        Paginator::Cursor { cursor_pointer: cursor_pointer.to_string(), param: param.to_string() }
+    }
+
+    /// `offset` / `limit` pagination over the array at `items_pointer`.
+    pub fn offset_limit(limit: usize, items_pointer: &str) -> Self {
+        Paginator::OffsetLimit {
+            offset_param: "offset".to_string(),
+            limit_param: "limit".to_string(),
+            limit,
+            items_pointer: items_pointer.to_string(),
+        }
+    }
+
+    pub(crate) fn first_url(&self, url: &str) -> Result<Url, NgError> {
+        let mut url = Url::parse(url).map_err(|e| NgError::HttpError(format!("Invalid URL '{}': {}", url, e)))?;
+        if let Paginator::OffsetLimit { offset_param, limit_param, limit, .. } = self {
+            set_query_param(&mut url, offset_param, "0");
+            set_query_param(&mut url, limit_param, &limit.to_string());
+        }
+        Ok(url)
+    }
+
+    /// URL of the page after `current`, or `None` on the last page.
+    pub(crate) fn next_url(&self, current: &Url, headers: &HeaderMap, body: &Value) -> Option<Url> {
+        let next = match self {
+            Paginator::LinkHeader => headers
+                .get_all(LINK)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .find_map(next_link)
+                .and_then(|href| current.join(&href).ok()),
+            Paginator::Cursor { cursor_pointer, param } => {
+                let cursor = match body.pointer(cursor_pointer)? {
+                    Value::String(s) if !s.is_empty() => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    _ => return None,
+                };
+                let mut url = current.clone();
+                set_query_param(&mut url, param, &cursor);
+                Some(url)
+            }
+            Paginator::OffsetLimit { offset_param, limit, items_pointer, .. } => {
+                let items = body.pointer(items_pointer)?.as_array()?.len();
+                if items < *limit {
+                    return None;
+                }
+                let offset = current
+                    .query_pairs()
+                    .find(|(k, _)| k == offset_param.as_str())
+                    .and_then(|(_, v)| v.parse::<usize>().ok())
+                    .unwrap_or(0);
+                let mut url = current.clone();
+                set_query_param(&mut url, offset_param, &(offset + items).to_string());
+                Some(url)
+            }
+        };
+        // A next link equal to the current page would loop forever.
+        next.filter(|n| n != current)
+    }
+}
+
+/// Target of the `rel="next"` entry of one `Link` header value.
+fn next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|entry| {
+        let (target, params) = entry.trim().split_once(';')?;
+        let is_next = params.split(';').any(|p| {
+            let Some((k, v)) = p.trim().split_once('=') else { return false };
+            k.trim().eq_ignore_ascii_case("rel")
+                && v.trim().trim_matches('"').split_whitespace().any(|r| r.eq_ignore_ascii_case("next"))
+        });
+        is_next.then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+/// Sets `key=value` in the query of `url`, replacing any existing value.
+fn set_query_param(url: &mut Url, key: &str, value: &str) {
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(k, _)| k != key)
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    url.query_pairs_mut().clear().extend_pairs(kept).append_pair(key, value);
+}
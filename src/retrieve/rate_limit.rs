@@ -0,0 +1,180 @@
+//! src/retrieve/rate_limit.rs
+//!
+//! Token-bucket request rate limiting, keyed by host or an explicit bucket name.
+//!
+//! A single [`RateLimiter`] is meant to be shared (via `Arc`) by every `KyHttp`
+//! instance talking to the same provider, so upstream quotas hold crate-wide
+//! rather than per client.
+//!
+//! Independently of any configured limiter, `KyHttp` records the quota servers
+//! announce in `RateLimit-*` / `X-RateLimit-*` headers ([`ObservedRateLimit`])
+//! and holds back requests to a host whose window is used up until it resets.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// A request quota: at most `requests` per `per`, with bursts up to `requests`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    /// `n` requests per second.
+    pub fn per_second(n: u32) -> Self {
+        Self { requests: n, per: Duration::from_secs(1) }
+    }
+
+    /// `n` requests per minute.
+    pub fn per_minute(n: u32) -> Self {
+        Self { requests: n, per: Duration::from_secs(60) }
+    }
+
+    fn capacity(&self) -> f64 {
+        self.requests.max(1) as f64
+    }
+
+    fn tokens_per_sec(&self) -> f64 {
+        self.capacity() / self.per.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Shared rate limiter with one token bucket per key.
+#[derive(Debug)]
+pub struct RateLimiter {
+    default_limit: RateLimit,
+    limits: HashMap<String, RateLimit>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter applying `default_limit` to every key.
+    pub fn new(default_limit: RateLimit) -> Self {
+        Self { default_limit, limits: HashMap::new(), buckets: Mutex::new(HashMap::new()) }
+    }
+
+    /// Overrides the quota of one key (a host such as `api.nasdaq.com` or a bucket name).
+    pub fn with_limit(mut self, key: &str, limit: RateLimit) -> Self {
+        self.limits.insert(key.to_string(), limit);
+        self
+    }
+
+    /// Returns the quota applied to `key`.
+    pub fn limit_for(&self, key: &str) -> RateLimit {
+        self.limits.get(key).copied().unwrap_or(self.default_limit)
+    }
+
+    /// Waits until a request for `key` fits its quota, then consumes one token.
+    pub async fn acquire(&self, key: &str) {
+        let limit = self.limit_for(key);
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let bucket = buckets.entry(key.to_string()).or_insert(Bucket { tokens: limit.capacity(), refilled_at: now });
+
+                let elapsed = now.saturating_duration_since(bucket.refilled_at).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * limit.tokens_per_sec()).min(limit.capacity());
+                bucket.refilled_at = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / limit.tokens_per_sec())
+            };
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Reset values above this are Unix timestamps rather than delays in seconds.
+const RESET_EPOCH_THRESHOLD: f64 = 1_000_000_000.0;
+
+/// A host's quota as last reported in its rate-limit response headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObservedRateLimit {
+    /// Requests allowed per window, when reported.
+    pub limit: Option<u64>,
+    /// Requests left in the window, less those sent since the report.
+    pub remaining: u64,
+    /// When the window resets, when reported.
+    pub reset_at: Option<DateTime<Utc>>,
+}
+
+impl ObservedRateLimit {
+    /// Reads `RateLimit-Limit/Remaining/Reset`, falling back to the
+    /// `X-RateLimit-*` variants. Returns `None` without a Remaining header.
+    ///
+    /// Reset is accepted as seconds from `now` or as a Unix timestamp.
+    pub fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Self> {
+        let field = |name: &str| {
+            [format!("ratelimit-{}", name), format!("x-ratelimit-{}", name)]
+                .iter()
+                .find_map(|h| headers.get(h.as_str()))
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+        };
+        let remaining = field("remaining")?.parse::<u64>().ok()?;
+        let limit = field("limit").and_then(|v| v.parse::<u64>().ok());
+        let reset_at = field("reset").and_then(|v| v.parse::<f64>().ok()).and_then(|reset| {
+            if reset >= RESET_EPOCH_THRESHOLD {
+                DateTime::from_timestamp_millis((reset * 1000.0) as i64)
+            } else {
+                let delay = chrono::Duration::milliseconds((reset.max(0.0) * 1000.0) as i64);
+                now.checked_add_signed(delay)
+            }
+        });
+        Some(Self { limit, remaining, reset_at })
+    }
+}
+
+/// Per-host [`ObservedRateLimit`]s of one client.
+#[derive(Debug, Default)]
+pub(crate) struct ObservedLimits {
+    hosts: Mutex<HashMap<String, ObservedRateLimit>>,
+}
+
+impl ObservedLimits {
+    /// Records the quota reported by a response from `host`, if any.
+    pub(crate) fn observe(&self, host: &str, headers: &HeaderMap, now: DateTime<Utc>) {
+        if let Some(observed) = ObservedRateLimit::from_headers(headers, now) {
+            self.lock().insert(host.to_string(), observed);
+        }
+    }
+
+    /// Counts a request to `host` against its observed quota and returns how
+    /// long to wait first: until the reset when no requests remain.
+    pub(crate) fn reserve(&self, host: &str, now: DateTime<Utc>) -> Option<Duration> {
+        let mut hosts = self.lock();
+        let observed = hosts.get_mut(host)?;
+        if observed.reset_at.is_some_and(|reset| reset <= now) {
+            hosts.remove(host);
+            return None;
+        }
+        if observed.remaining > 0 {
+            observed.remaining -= 1;
+            return None;
+        }
+        observed.reset_at.and_then(|reset| reset.signed_duration_since(now).to_std().ok())
+    }
+
+    pub(crate) fn snapshot(&self) -> BTreeMap<String, ObservedRateLimit> {
+        self.lock().iter().map(|(k, v)| (k.clone(), *v)).collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ObservedRateLimit>> {
+        self.hosts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
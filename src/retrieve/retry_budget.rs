@@ -0,0 +1,58 @@
+//! src/retrieve/retry_budget.rs
+//!
+//! Retry budget: a token bucket that caps retries relative to successful traffic.
+//!
+//! Each retry spends one token; each successful (2xx) response earns
+//! `refill_per_success` tokens, up to `max_tokens`. During a sustained outage
+//! nothing refills the bucket, so once the burst allowance is spent requests
+//! fail fast with `NgError::RetryBudgetExhausted` instead of multiplying load
+//! on the struggling upstream. Share one budget (via `Arc`) between clones or
+//! clients that should draw from the same pool.
+
+use std::sync::Mutex;
+
+/// Shared retry token bucket.
+#[derive(Debug)]
+pub struct RetryBudget {
+    tokens: Mutex<f64>,
+    max_tokens: f64,
+    refill_per_success: f64,
+}
+
+impl RetryBudget {
+    /// Budget allowing bursts of `max_tokens` retries, earning
+    /// `refill_per_success` retries per successful response. Starts full.
+    ///
+    /// E.g. `RetryBudget::new(10, 0.1)` keeps retries at roughly 10% of
+    /// successful traffic once the initial 10 are used.
+    pub fn new(max_tokens: u32, refill_per_success: f64) -> Self {
+        let max_tokens = max_tokens as f64;
+        Self { tokens: Mutex::new(max_tokens), max_tokens, refill_per_success: refill_per_success.max(0.0) }
+    }
+
+    /// Takes one retry token; returns false if the budget is exhausted.
+    pub fn try_spend(&self) -> bool {
+        let mut tokens = self.lock();
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Credits a successful response.
+    pub fn deposit(&self) {
+        let mut tokens = self.lock();
+        *tokens = (*tokens + self.refill_per_success).min(self.max_tokens);
+    }
+
+    /// Retries currently available (whole tokens).
+    pub fn available(&self) -> u32 {
+        *self.lock() as u32
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, f64> {
+        self.tokens.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,128 @@
+//! src/retrieve/retry_policy.rs
+//!
+//! Per-status retry behavior for `KyHttp`.
+//!
+//! A [`RetryPolicy`] maps response statuses to a [`StatusPolicy`]. Statuses
+//! that are not mapped fall back to `server_errors` for 5xx and to
+//! [`StatusPolicy::NoRetry`] otherwise. The default policy reproduces the
+//! historical behavior: 5xx and 429 back off (preferring Retry-After), 413
+//! waits only when the server sends Retry-After.
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Callback for statuses mapped to [`StatusPolicy::Hook`].
+///
+/// Receives the status, the request URL and the response headers; typically
+/// rotates request headers or credentials for subsequent calls.
+pub type StatusHookFn = Arc<dyn Fn(StatusCode, &str, &HeaderMap) + Send + Sync>;
+
+/// How one response status is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusPolicy {
+    /// Return the response without retrying.
+    NoRetry,
+    /// Retry with exponential backoff; a Retry-After header takes precedence.
+    Backoff,
+    /// Retry only when the response carries Retry-After, waiting at most
+    /// `max_waits` times per request.
+    RetryAfter { max_waits: usize },
+    /// Return the response without retrying and invoke the policy's hook.
+    Hook,
+}
+
+impl StatusPolicy {
+    /// True for policies that may retry.
+    pub fn retries(&self) -> bool {
+        matches!(self, StatusPolicy::Backoff | StatusPolicy::RetryAfter { .. })
+    }
+}
+
+/// Status → policy map with a fallback for unmapped server errors.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    statuses: HashMap<StatusCode, StatusPolicy>,
+    server_errors: StatusPolicy,
+    hook: Option<StatusHookFn>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+            .with_server_errors(StatusPolicy::Backoff)
+            .with_status(StatusCode::TOO_MANY_REQUESTS, StatusPolicy::Backoff)
+            .with_status(StatusCode::PAYLOAD_TOO_LARGE, StatusPolicy::RetryAfter { max_waits: usize::MAX })
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries a status (network errors are unaffected).
+    pub fn none() -> Self {
+        Self { statuses: HashMap::new(), server_errors: StatusPolicy::NoRetry, hook: None }
+    }
+
+    /// Sets the policy of one status.
+    pub fn with_status(mut self, status: StatusCode, policy: StatusPolicy) -> Self {
+        self.statuses.insert(status, policy);
+        self
+    }
+
+    /// Sets the policy of 5xx statuses that are not mapped explicitly.
+    pub fn with_server_errors(mut self, policy: StatusPolicy) -> Self {
+        self.server_errors = policy;
+        self
+    }
+
+    /// Sets the callback run for statuses mapped to [`StatusPolicy::Hook`].
+    pub fn with_hook(mut self, hook: StatusHookFn) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Effective policy of `status`.
+    pub fn policy_for(&self, status: StatusCode) -> StatusPolicy {
+        match self.statuses.get(&status) {
+            Some(p) => *p,
+            None if status.is_server_error() => self.server_errors,
+            None => StatusPolicy::NoRetry,
+        }
+    }
+
+    /// Explicitly mapped statuses.
+    pub fn statuses(&self) -> impl Iterator<Item = (StatusCode, StatusPolicy)> + '_ {
+        self.statuses.iter().map(|(s, p)| (*s, *p))
+    }
+
+    pub(crate) fn hook(&self) -> Option<&StatusHookFn> {
+        self.hook.as_ref()
+    }
+
+    /// Problems that make the policy unusable, for `KyOptions::validate`.
+    pub(crate) fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut mapped: Vec<_> = self.statuses().collect();
+        mapped.sort_by_key(|(s, _)| *s);
+        for (status, policy) in mapped {
+            if policy.retries() && !status.is_client_error() && !status.is_server_error() {
+                problems.push(format!("retry_policy may only retry 4xx/5xx, got {}", status.as_u16()));
+            }
+            if policy == StatusPolicy::Hook && self.hook.is_none() {
+                problems.push(format!("retry_policy maps {} to Hook but no hook is set", status.as_u16()));
+            }
+        }
+        problems
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("statuses", &self.statuses)
+            .field("server_errors", &self.server_errors)
+            .field("hook", &self.hook.as_ref().map(|_| "<fn>"))
+            .finish()
+    }
+}
@@ -0,0 +1,212 @@
+//! src/retrieve/signing.rs
+//!
+//! Request signing for APIs that authenticate each call with a signature
+//! header (typically crypto and broker exchanges).
+//!
+//! A [`RequestSigner`] is applied by `KyHttp` to every attempt right before it
+//! is sent, after all other headers are in place. Retries and hedged
+//! duplicates are therefore signed again with a fresh timestamp rather than
+//! replaying a stale signature the server would reject.
+//!
+//! [`HmacSigner`] covers the common scheme: HMAC-SHA256 over
+//! `timestamp + METHOD + path?query + body`, sent as hex or base64 alongside
+//! the API key and timestamp headers. The canonical string is replaceable for
+//! APIs that sign something else.
+//!
+//! ```rust,no_run
+//! # use rs_lib_ng::retrieve::signing::{HmacSigner, SignatureEncoding};
+//! # use rs_lib_ng::retrieve::ky_http::KyOptions;
+//! # use reqwest::header::HeaderName;
+//! # use std::sync::Arc;
+//! # fn demo() -> Result<(), rs_lib_ng::core::error::NgError> {
+//! let signer = HmacSigner::new("my-key", b"my-secret")?
+//!     .with_header_names(
+//!         HeaderName::from_static("ok-access-key"),
+//!         HeaderName::from_static("ok-access-timestamp"),
+//!         HeaderName::from_static("ok-access-sign"),
+//!     )
+//!     .with_encoding(SignatureEncoding::Base64);
+//! let opts = KyOptions::builder().signer(Arc::new(signer)).build()?;
+//! # Ok(()) }
+//! ```
+
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Method, Request};
+use sha2::Sha256;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::core::error::NgError;
+
+/// Signs one outgoing attempt in place.
+pub trait RequestSigner: Send + Sync {
+    /// Adds the signature (and any companion headers) to `req`.
+    fn sign(&self, req: &mut Request);
+}
+
+/// The parts of a request covered by a signature.
+#[derive(Debug, Clone, Copy)]
+pub struct SigningInput<'a> {
+    pub method: &'a Method,
+    /// Path plus `?query` when present, e.g. `/api/v1/order?symbol=AAPL`.
+    pub path_and_query: &'a str,
+    /// Exact body bytes sent; empty for requests without a body.
+    pub body: &'a [u8],
+    /// Timestamp sent in the timestamp header for this attempt.
+    pub timestamp: &'a str,
+}
+
+/// Builds the string to sign from a [`SigningInput`].
+pub type CanonicalizeFn = Arc<dyn Fn(&SigningInput<'_>) -> String + Send + Sync>;
+
+/// Canonical string `timestamp + METHOD + path?query + body`.
+pub fn default_canonical(input: &SigningInput<'_>) -> String {
+    format!(
+        "{}{}{}{}",
+        input.timestamp,
+        input.method.as_str(),
+        input.path_and_query,
+        String::from_utf8_lossy(input.body)
+    )
+}
+
+/// Unit of the timestamp header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampFormat {
+    #[default]
+    UnixMillis,
+    UnixSeconds,
+}
+
+impl TimestampFormat {
+    fn now(&self) -> String {
+        let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        match self {
+            TimestampFormat::UnixMillis => since_epoch.as_millis().to_string(),
+            TimestampFormat::UnixSeconds => since_epoch.as_secs().to_string(),
+        }
+    }
+}
+
+/// Text encoding of the signature header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureEncoding {
+    /// Lowercase hex.
+    #[default]
+    Hex,
+    /// Standard base64 with padding.
+    Base64,
+}
+
+/// HMAC-SHA256 signer with a configurable canonical string.
+#[derive(Clone)]
+pub struct HmacSigner {
+    api_key: HeaderValue,
+    secret: Vec<u8>,
+    api_key_header: HeaderName,
+    timestamp_header: HeaderName,
+    signature_header: HeaderName,
+    timestamp_format: TimestampFormat,
+    encoding: SignatureEncoding,
+    canonicalize: CanonicalizeFn,
+}
+
+impl HmacSigner {
+    /// Signer sending `api_key` in `x-api-key`, the timestamp (ms) in
+    /// `x-timestamp` and the hex signature in `x-signature`.
+    pub fn new(api_key: &str, secret: &[u8]) -> Result<Self, NgError> {
+        let mut api_key = HeaderValue::from_str(api_key)
+            .map_err(|_| NgError::ConfigError("Signing API key is not a valid header value".into()))?;
+        api_key.set_sensitive(true);
+        Ok(Self {
+            api_key,
+            secret: secret.to_vec(),
+            api_key_header: HeaderName::from_static("x-api-key"),
+            timestamp_header: HeaderName::from_static("x-timestamp"),
+            signature_header: HeaderName::from_static("x-signature"),
+            timestamp_format: TimestampFormat::default(),
+            encoding: SignatureEncoding::default(),
+            canonicalize: Arc::new(default_canonical),
+        })
+    }
+
+    /// Sets the names of the API key, timestamp and signature headers.
+    pub fn with_header_names(mut self, api_key: HeaderName, timestamp: HeaderName, signature: HeaderName) -> Self {
+        self.api_key_header = api_key;
+        self.timestamp_header = timestamp;
+        self.signature_header = signature;
+        self
+    }
+
+    pub fn with_timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    pub fn with_encoding(mut self, encoding: SignatureEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Replaces the canonical string (default: [`default_canonical`]).
+    pub fn with_canonicalize(mut self, canonicalize: CanonicalizeFn) -> Self {
+        self.canonicalize = canonicalize;
+        self
+    }
+
+    /// Signature of `input`, encoded for the signature header.
+    pub fn signature(&self, input: &SigningInput<'_>) -> String {
+        // HMAC accepts keys of any length, so this cannot fail.
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update((self.canonicalize)(input).as_bytes());
+        let digest = mac.finalize().into_bytes();
+        match self.encoding {
+            SignatureEncoding::Hex => hex::encode(digest),
+            SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(digest),
+        }
+    }
+}
+
+impl RequestSigner for HmacSigner {
+    fn sign(&self, req: &mut Request) {
+        let timestamp = self.timestamp_format.now();
+        let path_and_query = match req.url().query() {
+            Some(q) => format!("{}?{}", req.url().path(), q),
+            None => req.url().path().to_string(),
+        };
+        let body = req.body().and_then(|b| b.as_bytes()).unwrap_or_default();
+        let signature = self.signature(&SigningInput {
+            method: req.method(),
+            path_and_query: &path_and_query,
+            body,
+            timestamp: &timestamp,
+        });
+
+        let headers = req.headers_mut();
+        headers.insert(self.api_key_header.clone(), self.api_key.clone());
+        // Digits and hex/base64 output are always valid header values.
+        if let Ok(ts) = HeaderValue::from_str(&timestamp) {
+            headers.insert(self.timestamp_header.clone(), ts);
+        }
+        if let Ok(sig) = HeaderValue::from_str(&signature) {
+            headers.insert(self.signature_header.clone(), sig);
+        }
+    }
+}
+
+impl fmt::Debug for HmacSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HmacSigner")
+            .field("api_key", &"***")
+            .field("secret", &"***")
+            .field("api_key_header", &self.api_key_header)
+            .field("timestamp_header", &self.timestamp_header)
+            .field("signature_header", &self.signature_header)
+            .field("timestamp_format", &self.timestamp_format)
+            .field("encoding", &self.encoding)
+            .finish()
+    }
+}
@@ -0,0 +1,375 @@
+//! src/retrieve/sse.rs
+//!
+//! Server-Sent Events (`text/event-stream`) client.
+//!
+//! [`SseClient`] opens an event stream with a `KyHttp` instance's reqwest
+//! client (sharing its TLS, proxy and connection pool settings) and yields
+//! parsed [`SseEvent`]s. When the connection drops it reconnects after the
+//! server's `retry` delay, sending `Last-Event-ID` so the server can resume
+//! where the previous connection stopped. Streams do not hold one of the
+//! client's concurrency permits, since they may stay open indefinitely.
+//!
+//! The stream ends when the server answers `204 No Content`; a non-success
+//! status or a non event-stream content type is yielded as an error and also
+//! ends it.
+
+use bytes::Bytes;
+use futures::stream::{self, Stream};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CACHE_CONTROL, CONTENT_TYPE};
+use reqwest::{Client, Response, StatusCode};
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+use crate::core::error::NgError;
+use crate::loggers::Logger;
+use crate::retrieve::ky_http::KyHttp;
+
+/// `Last-Event-ID` request header.
+pub const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// Reconnection delay used until the server sends a `retry` field.
+pub const DEFAULT_RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// Per-request timeout replacing the client's, which would cut off long-lived streams.
+const STREAM_TIMEOUT: Duration = Duration::from_secs(365 * 24 * 3600);
+
+/// One dispatched event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Last event id seen on the stream, if any.
+    pub id: Option<String>,
+    /// Event type; `"message"` when the server sent none.
+    pub event: String,
+    /// Data lines joined with `\n`.
+    pub data: String,
+    /// Reconnection delay requested by the server with this event.
+    pub retry: Option<Duration>,
+}
+
+/// Incremental `text/event-stream` parser.
+///
+/// Accepts arbitrary chunk boundaries (including ones splitting a line, a
+/// UTF-8 sequence or a `\r\n` pair) and follows the WHATWG dispatch rules:
+/// comments are skipped, unknown fields ignored and events without data
+/// dropped.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    skip_lf: bool,
+    data: String,
+    has_data: bool,
+    event: Option<String>,
+    retry: Option<Duration>,
+    last_event_id: Option<String>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Last event id seen, sent as `Last-Event-ID` on reconnect.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Parses a chunk, returning the events it completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        for &b in chunk {
+            if std::mem::take(&mut self.skip_lf) && b == b'\n' {
+                continue;
+            }
+            match b {
+                b'\r' | b'\n' => {
+                    self.skip_lf = b == b'\r';
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                        events.push(event);
+                    }
+                }
+                _ => self.line.push(b),
+            }
+        }
+        events
+    }
+
+    /// Discards a partially received line and event, keeping the last event id.
+    pub fn reset(&mut self) {
+        *self = Self { last_event_id: self.last_event_id.take(), ..Self::default() };
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((f, v)) => (f, v.strip_prefix(' ').unwrap_or(v)),
+            None => (line, ""),
+        };
+        match field {
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+                self.has_data = true;
+            }
+            "event" => self.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(ms) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        let retry = self.retry.take();
+        let mut data = std::mem::take(&mut self.data);
+        if !std::mem::take(&mut self.has_data) {
+            return None;
+        }
+        data.pop();
+        Some(SseEvent {
+            id: self.last_event_id.clone(),
+            event: event.filter(|e| !e.is_empty()).unwrap_or_else(|| "message".to_string()),
+            data,
+            retry,
+        })
+    }
+}
+
+/// Reconnecting SSE client for one URL.
+#[derive(Clone)]
+pub struct SseClient {
+    client: Client,
+    logger: Logger,
+    url: String,
+    headers: HeaderMap,
+    reconnect_delay: Duration,
+    max_reconnects: Option<usize>,
+    idle_timeout: Option<Duration>,
+    last_event_id: Option<String>,
+}
+
+impl SseClient {
+    /// Client for `url` using `http`'s reqwest client and logger.
+    pub fn new(http: &KyHttp, url: &str) -> Self {
+        Self {
+            client: http.http_client().clone(),
+            logger: http.logger().clone(),
+            url: url.to_string(),
+            headers: HeaderMap::new(),
+            reconnect_delay: DEFAULT_RECONNECT_DELAY,
+            max_reconnects: None,
+            idle_timeout: None,
+            last_event_id: None,
+        }
+    }
+
+    /// Extra headers sent on every (re)connect.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Initial reconnection delay (the server's `retry` field overrides it).
+    pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+        self.reconnect_delay = delay;
+        self
+    }
+
+    /// Consecutive failed reconnects tolerated before the stream ends with an
+    /// error. `None` (the default) reconnects forever.
+    pub fn with_max_reconnects(mut self, max: Option<usize>) -> Self {
+        self.max_reconnects = max;
+        self
+    }
+
+    /// Reconnects when no bytes (events or `:` heartbeats) arrive for this long.
+    pub fn with_idle_timeout(mut self, idle: Duration) -> Self {
+        self.idle_timeout = Some(idle);
+        self
+    }
+
+    /// Resumes after `id`, e.g. one persisted by a previous run.
+    pub fn with_last_event_id(mut self, id: &str) -> Self {
+        self.last_event_id = Some(id.to_string());
+        self
+    }
+
+    /// Opens the stream. Nothing is sent until it is first polled.
+    pub fn events(&self) -> impl Stream<Item = Result<SseEvent, NgError>> + Send + 'static {
+        let mut parser = SseParser::new();
+        parser.last_event_id = self.last_event_id.clone();
+        let state = StreamState {
+            sse: self.clone(),
+            delay: self.reconnect_delay,
+            parser,
+            resp: None,
+            pending: VecDeque::new(),
+            connected_once: false,
+            failures: 0,
+            done: false,
+        };
+        stream::unfold(state, |mut state| async move {
+            let item = state.next().await?;
+            Some((item, state))
+        })
+    }
+
+    fn request_headers(&self, last_event_id: Option<&str>) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        if let Some(id) = last_event_id.and_then(|id| HeaderValue::from_str(id).ok()) {
+            headers.insert(LAST_EVENT_ID, id);
+        }
+        headers
+    }
+}
+
+/// Connection and parser state of one `events()` stream.
+struct StreamState {
+    sse: SseClient,
+    delay: Duration,
+    parser: SseParser,
+    resp: Option<Response>,
+    pending: VecDeque<SseEvent>,
+    connected_once: bool,
+    failures: usize,
+    done: bool,
+}
+
+enum Connect {
+    Open(Response),
+    /// `204 No Content`: the server asks the client to stop.
+    Closed,
+    /// Final error; the stream ends after yielding it.
+    Fatal(NgError),
+    /// Connection failure worth retrying.
+    Retry(NgError),
+}
+
+impl StreamState {
+    async fn next(&mut self) -> Option<Result<SseEvent, NgError>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+            let Some(resp) = self.resp.as_mut() else {
+                if let Err(e) = self.reconnect().await {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                continue;
+            };
+            match self.sse.idle_timeout {
+                Some(idle) => match timeout(idle, resp.chunk()).await {
+                    Ok(res) => self.on_chunk(res),
+                    Err(_) => {
+                        crate::warn!(self.sse.logger, "SSE stream idle, reconnecting", "url" => self.sse.url.as_str(), "idle_ms" => idle.as_millis() as u64);
+                        self.resp = None;
+                    }
+                },
+                None => {
+                    let res = resp.chunk().await;
+                    self.on_chunk(res);
+                }
+            }
+        }
+    }
+
+    fn on_chunk(&mut self, res: Result<Option<Bytes>, reqwest::Error>) {
+        match res {
+            Ok(Some(chunk)) => {
+                for event in self.parser.feed(&chunk) {
+                    if let Some(retry) = event.retry {
+                        self.delay = retry;
+                    }
+                    self.pending.push_back(event);
+                }
+            }
+            Ok(None) => {
+                crate::info!(self.sse.logger, "SSE stream closed by server, reconnecting", "url" => self.sse.url.as_str());
+                self.resp = None;
+            }
+            Err(e) => {
+                crate::warn!(self.sse.logger, "SSE stream failed, reconnecting", "url" => self.sse.url.as_str(), "error" => e.to_string());
+                self.resp = None;
+            }
+        }
+    }
+
+    /// Connects (after the reconnection delay, except the first time) until a
+    /// stream is open, the server closes it for good or reconnects run out.
+    async fn reconnect(&mut self) -> Result<(), NgError> {
+        loop {
+            if self.connected_once || self.failures > 0 {
+                sleep(self.delay).await;
+            }
+            self.parser.reset();
+            match self.connect().await {
+                Connect::Open(resp) => {
+                    crate::info!(self.sse.logger, "SSE stream open", "url" => self.sse.url.as_str(), "last_event_id" => self.parser.last_event_id());
+                    self.resp = Some(resp);
+                    self.connected_once = true;
+                    self.failures = 0;
+                    return Ok(());
+                }
+                Connect::Closed => {
+                    crate::info!(self.sse.logger, "SSE stream ended by server (204)", "url" => self.sse.url.as_str());
+                    self.done = true;
+                    return Ok(());
+                }
+                Connect::Fatal(e) => return Err(e),
+                Connect::Retry(e) => {
+                    self.failures += 1;
+                    if self.sse.max_reconnects.is_some_and(|max| self.failures > max) {
+                        crate::error!(self.sse.logger, "SSE reconnects exhausted", "url" => self.sse.url.as_str(), "failures" => self.failures);
+                        return Err(e);
+                    }
+                    crate::warn!(self.sse.logger, "SSE connect failed", "url" => self.sse.url.as_str(), "error" => e.to_string(), "delay_ms" => self.delay.as_millis() as u64);
+                }
+            }
+        }
+    }
+
+    async fn connect(&self) -> Connect {
+        let headers = self.sse.request_headers(self.parser.last_event_id());
+        let res = self.sse.client.get(&self.sse.url).headers(headers).timeout(STREAM_TIMEOUT).send().await;
+        let resp = match res {
+            Ok(resp) => resp,
+            Err(e) => return Connect::Retry(NgError::HttpError(e.to_string())),
+        };
+        let status = resp.status();
+        if status == StatusCode::NO_CONTENT {
+            return Connect::Closed;
+        }
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return Connect::Retry(NgError::HttpError(format!("Status: {}", status.as_u16())));
+        }
+        if !status.is_success() {
+            return Connect::Fatal(NgError::HttpError(format!("Status: {}", status.as_u16())));
+        }
+        let is_event_stream = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.trim_start().to_ascii_lowercase().starts_with("text/event-stream"));
+        if !is_event_stream {
+            return Connect::Fatal(NgError::HttpError(format!("Expected text/event-stream from {}", self.sse.url)));
+        }
+        Connect::Open(resp)
+    }
+}
@@ -0,0 +1,135 @@
+//! src/retrieve/tls.rs
+//!
+//! TLS settings for `KyHttp`: extra (or exclusive) root CAs, a client
+//! certificate for mTLS endpoints, and an escape hatch for lab environments.
+//!
+//! PEM material is held in memory; the `*_file` constructors read it eagerly so
+//! a missing file fails when options are built rather than on the first request.
+
+use reqwest::{Certificate, ClientBuilder, Identity};
+use std::fmt;
+use std::path::Path;
+
+use crate::core::error::NgError;
+
+/// TLS options applied when the reqwest client is built.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    /// PEM bundle of additional trusted root certificates.
+    pub root_ca_pem: Option<Vec<u8>>,
+    /// Trust only `root_ca_pem`, not the built-in roots.
+    pub custom_roots_only: bool,
+    /// PEM client certificate chain for mTLS.
+    pub client_cert_pem: Option<Vec<u8>>,
+    /// PEM private key matching `client_cert_pem` (RSA, SEC1 or PKCS#8).
+    pub client_key_pem: Option<Vec<u8>>,
+    /// Disables certificate verification entirely. Lab use only: any
+    /// man-in-the-middle can read and alter the traffic.
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl TlsConfig {
+    /// Default TLS: built-in roots, no client certificate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trusts the certificates in a PEM bundle in addition to the built-in roots.
+    pub fn with_root_ca_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_ca_pem = Some(pem.into());
+        self
+    }
+
+    /// Reads a PEM root CA bundle from disk.
+    pub fn with_root_ca_file(self, path: impl AsRef<Path>) -> Result<Self, NgError> {
+        Ok(self.with_root_ca_pem(read_pem(path.as_ref())?))
+    }
+
+    /// Trusts only the configured root CAs.
+    pub fn with_custom_roots_only(mut self, only: bool) -> Self {
+        self.custom_roots_only = only;
+        self
+    }
+
+    /// Presents a client certificate (PEM chain and private key) for mTLS.
+    pub fn with_client_identity_pem(mut self, cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        self.client_cert_pem = Some(cert.into());
+        self.client_key_pem = Some(key.into());
+        self
+    }
+
+    /// Reads the client certificate and key from disk.
+    pub fn with_client_identity_files(self, cert: impl AsRef<Path>, key: impl AsRef<Path>) -> Result<Self, NgError> {
+        let (cert, key) = (read_pem(cert.as_ref())?, read_pem(key.as_ref())?);
+        Ok(self.with_client_identity_pem(cert, key))
+    }
+
+    /// Disables certificate verification. Never use against production hosts.
+    pub fn with_danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Checks that the PEM material parses and is complete.
+    pub fn validate(&self) -> Result<(), NgError> {
+        self.root_certs()?;
+        self.identity()?;
+        if self.custom_roots_only && self.root_ca_pem.is_none() {
+            return Err(NgError::ConfigError("TLS custom_roots_only requires root_ca_pem".into()));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder, NgError> {
+        let roots = self.root_certs()?;
+        builder = if self.custom_roots_only { builder.tls_certs_only(roots) } else { builder.tls_certs_merge(roots) };
+        if let Some(identity) = self.identity()? {
+            builder = builder.identity(identity);
+        }
+        if self.danger_accept_invalid_certs {
+            builder = builder.tls_danger_accept_invalid_certs(true);
+        }
+        Ok(builder)
+    }
+
+    fn root_certs(&self) -> Result<Vec<Certificate>, NgError> {
+        let Some(pem) = &self.root_ca_pem else { return Ok(Vec::new()) };
+        let certs = Certificate::from_pem_bundle(pem)
+            .map_err(|e| NgError::ConfigError(format!("Invalid TLS root CA bundle: {}", e)))?;
+        if certs.is_empty() {
+            return Err(NgError::ConfigError("Invalid TLS root CA bundle: no certificates found".into()));
+        }
+        Ok(certs)
+    }
+
+    fn identity(&self) -> Result<Option<Identity>, NgError> {
+        match (&self.client_cert_pem, &self.client_key_pem) {
+            (None, None) => Ok(None),
+            (Some(cert), Some(key)) => {
+                let mut pem = cert.clone();
+                pem.push(b'\n');
+                pem.extend_from_slice(key);
+                Identity::from_pem(&pem)
+                    .map(Some)
+                    .map_err(|e| NgError::ConfigError(format!("Invalid TLS client identity: {}", e)))
+            }
+            _ => Err(NgError::ConfigError("TLS client certificate and key must be set together".into())),
+        }
+    }
+}
+
+fn read_pem(path: &Path) -> Result<Vec<u8>, NgError> {
+    std::fs::read(path).map_err(|e| NgError::ConfigError(format!("Cannot read {}: {}", path.display(), e)))
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("root_ca_pem", &self.root_ca_pem.as_ref().map(|p| format!("{} bytes", p.len())))
+            .field("custom_roots_only", &self.custom_roots_only)
+            .field("client_cert_pem", &self.client_cert_pem.as_ref().map(|p| format!("{} bytes", p.len())))
+            .field("client_key_pem", &self.client_key_pem.as_ref().map(|_| "***"))
+            .field("danger_accept_invalid_certs", &self.danger_accept_invalid_certs)
+            .finish()
+    }
+}
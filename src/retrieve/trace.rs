@@ -0,0 +1,98 @@
+//! src/retrieve/trace.rs
+//!
+//! W3C Trace Context (`traceparent` / `tracestate`) for outgoing requests.
+//!
+//! When propagation is enabled, each attempt is sent as a new child span of
+//! the caller's context: the `traceparent` already present in the request
+//! headers, else `KyOptions::trace_context`, else a freshly generated trace.
+
+use rand::RngCore;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+pub const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
+
+/// A parsed or generated trace context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub parent_id: [u8; 8],
+    /// Trace flags; bit 0 is `sampled`.
+    pub flags: u8,
+    /// Vendor-specific `tracestate`, forwarded unchanged.
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// Starts a new sampled trace.
+    pub fn generate() -> Self {
+        let mut rng = rand::thread_rng();
+        let mut trace_id = [0u8; 16];
+        let mut parent_id = [0u8; 8];
+        while trace_id == [0; 16] {
+            rng.fill_bytes(&mut trace_id);
+        }
+        while parent_id == [0; 8] {
+            rng.fill_bytes(&mut parent_id);
+        }
+        Self { trace_id, parent_id, flags: 0x01, tracestate: None }
+    }
+
+    /// Parses a `traceparent` value (`00-<32 hex>-<16 hex>-<2 hex>`).
+    ///
+    /// Returns `None` for malformed values and the all-zero ids the spec marks invalid.
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let (version, trace, parent, flags) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+        let trace_id: [u8; 16] = hex::decode(trace).ok()?.try_into().ok()?;
+        let parent_id: [u8; 8] = hex::decode(parent).ok()?.try_into().ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok().filter(|_| flags.len() == 2)?;
+        if trace_id == [0; 16] || parent_id == [0; 8] {
+            return None;
+        }
+        Some(Self { trace_id, parent_id, flags, tracestate: tracestate.map(str::to_string) })
+    }
+
+    /// Reads the context from request headers.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let parent = headers.get(TRACEPARENT)?.to_str().ok()?;
+        let state = headers.get(TRACESTATE).and_then(|v| v.to_str().ok());
+        Self::parse(parent, state)
+    }
+
+    /// Same trace with a new span id, as sent on an outgoing request.
+    pub fn child(&self) -> Self {
+        let mut parent_id = [0u8; 8];
+        let mut rng = rand::thread_rng();
+        while parent_id == [0; 8] {
+            rng.fill_bytes(&mut parent_id);
+        }
+        Self { parent_id, ..self.clone() }
+    }
+
+    /// Hex trace id, as used to correlate with tracing backends.
+    pub fn trace_id_hex(&self) -> String {
+        hex::encode(self.trace_id)
+    }
+
+    /// Writes `traceparent` (and `tracestate`, if any) into `headers`.
+    pub fn inject(&self, headers: &mut HeaderMap) {
+        if let Ok(v) = HeaderValue::from_str(&self.to_string()) {
+            headers.insert(TRACEPARENT, v);
+        }
+        if let Some(state) = self.tracestate.as_deref().and_then(|s| HeaderValue::from_str(s).ok()) {
+            headers.insert(TRACESTATE, state);
+        }
+    }
+}
+
+impl fmt::Display for TraceContext {
+    /// Formats as a version-00 `traceparent` value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "00-{}-{}-{:02x}", hex::encode(self.trace_id), hex::encode(self.parent_id), self.flags)
+    }
+}
@@ -0,0 +1,94 @@
+//! src/retrieve/url_builder.rs
+//!
+//! `UrlBuilder`: endpoint URLs from a base, path segments and query parameters.
+//!
+//! Segments and values are percent-encoded, so symbols such as `BRK/B` or
+//! `^GSPC` cannot break the path or smuggle extra parameters, and optional
+//! parameters are skipped when `None` instead of rendering as empty values.
+//!
+//! ```rust
+//! # use rs_lib_ng::retrieve::url_builder::UrlBuilder;
+//! let url = UrlBuilder::parse("https://api.nasdaq.com/api/quote/")?
+//!     .segment("BRK/B")
+//!     .segment("info")
+//!     .query("assetclass", "stocks")
+//!     .query_opt("limit", None::<u32>)
+//!     .build();
+//! assert_eq!(url, "https://api.nasdaq.com/api/quote/BRK%2FB/info?assetclass=stocks");
+//! # Ok::<(), rs_lib_ng::core::error::NgError>(())
+//! ```
+
+use reqwest::Url;
+use std::fmt::Display;
+
+use crate::core::error::NgError;
+
+/// Incremental, encoding-safe URL construction.
+#[derive(Debug, Clone)]
+pub struct UrlBuilder {
+    url: Url,
+}
+
+impl UrlBuilder {
+    /// Starts from an absolute `http(s)` base URL, which may already carry
+    /// path and query parts.
+    pub fn parse(base: &str) -> Result<Self, NgError> {
+        let url = Url::parse(base).map_err(|e| NgError::ConfigError(format!("Invalid base URL '{}': {}", base, e)))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(NgError::ConfigError(format!("Base URL '{}' must be http(s)", base)));
+        }
+        Ok(Self { url })
+    }
+
+    /// Appends one path segment; `/`, `?`, `#` and the like are encoded.
+    pub fn segment(mut self, segment: impl Display) -> Self {
+        if let Ok(mut path) = self.url.path_segments_mut() {
+            path.pop_if_empty().push(&segment.to_string());
+        }
+        self
+    }
+
+    /// Appends several path segments.
+    pub fn segments<I, S>(self, segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Display,
+    {
+        segments.into_iter().fold(self, Self::segment)
+    }
+
+    /// Appends `key=value` to the query.
+    pub fn query(mut self, key: &str, value: impl Display) -> Self {
+        self.url.query_pairs_mut().append_pair(key, &value.to_string());
+        self
+    }
+
+    /// Appends `key=value` when `value` is `Some`.
+    pub fn query_opt(self, key: &str, value: Option<impl Display>) -> Self {
+        match value {
+            Some(v) => self.query(key, v),
+            None => self,
+        }
+    }
+
+    /// Appends `key=a<sep>b<sep>c`, e.g. comma-separated symbol lists.
+    /// Skipped when `values` is empty.
+    pub fn query_list<I, S>(self, key: &str, values: I, sep: &str) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Display,
+    {
+        let joined = values.into_iter().map(|v| v.to_string()).collect::<Vec<_>>().join(sep);
+        if joined.is_empty() { self } else { self.query(key, joined) }
+    }
+
+    /// The URL built so far.
+    pub fn as_url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Finishes the URL as a string, as taken by `KyHttp` calls.
+    pub fn build(self) -> String {
+        self.url.into()
+    }
+}
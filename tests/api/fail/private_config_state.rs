@@ -0,0 +1,9 @@
+//! ConfigManager state is only reachable through its accessor methods.
+
+use rs_lib_ng::configs::ConfigManager;
+
+fn uses(manager: ConfigManager) {
+    let _ = manager.current;
+}
+
+fn main() {}
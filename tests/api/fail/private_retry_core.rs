@@ -0,0 +1,5 @@
+//! The raw retry-layer response type is an implementation detail of KyHttp.
+
+use rs_lib_ng::retrieve::ky_http::RawResponse;
+
+fn main() {}
@@ -0,0 +1,53 @@
+//! Documented `configs`, `core` and `loggers` surface.
+
+use rs_lib_ng::configs::{ConfigBuilder, ConfigDefaults, ConfigManager};
+use rs_lib_ng::core::error::{BCodeKind, BCodeMessage, NgError};
+use rs_lib_ng::loggers::{LogLevel, Logger, LoggerBuilder};
+use std::sync::Arc;
+
+#[allow(dead_code)]
+async fn uses() -> Result<(), NgError> {
+    let local = ConfigManager::get_local_config("config.json")?;
+    let _: Arc<serde_json::Value> = local.get();
+    local.apply_cli_args(["--set", "a.b=1"])?;
+    let _ = ConfigManager::get_local_config_with_defaults("config.json", ConfigDefaults::new())?;
+    let _ = ConfigManager::get_cloud_config("https://example.com/config").await?;
+    let _ = ConfigManager::get_cloud_config_named("https://example.com/config", "bin").await?;
+    let built: ConfigManager = ConfigBuilder::new()
+        .with_defaults(serde_json::json!({}))
+        .with_cloud("https://example.com/config")
+        .with_file("config.json")
+        .with_env("WEBLIB_")
+        .build()
+        .await?;
+    let _: &str = built.source_info();
+    built.require(&["a.b:integer"])?;
+    let _: serde_json::Value = built.snapshot_redacted();
+
+    let logger: Logger = LoggerBuilder::new("api").with_level(LogLevel::Debug).build()?;
+    rs_lib_ng::info!(logger, "message", "key" => 1);
+
+    let err = NgError::ConfigError("x".into());
+    let _: &[BCodeMessage] = err.b_code_messages();
+    let _: bool = err.is_invalid_symbol();
+    let _: bool = err.is_no_data();
+    let _: bool = err.is_throttled();
+    let _: Option<BCodeKind> = err.b_code_kind();
+    let _: BCodeKind = BCodeMessage { code: 0, error_message: String::new() }.kind();
+    match err {
+        NgError::ConfigError(_)
+        | NgError::InternalError(_)
+        | NgError::HttpError(_)
+        | NgError::NonJsonResponse { .. }
+        | NgError::NasdaqBusinessError { .. }
+        | NgError::MalformedResponse { .. }
+        | NgError::DeadlineExceeded { .. }
+        | NgError::RetryBudgetExhausted { .. }
+        | NgError::Cancelled { .. } => {}
+        #[allow(unreachable_patterns)]
+        _ => {}
+    }
+    Ok(())
+}
+
+fn main() {}
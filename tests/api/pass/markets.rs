@@ -0,0 +1,242 @@
+//! Documented `markets` surface: Nasdaq and CNN adapters and services.
+
+use rs_lib_ng::loggers::Logger;
+use rs_lib_ng::markets::cnn::apicallcnn::CnnApi;
+use rs_lib_ng::markets::cnn::fearandgreed::{FearAndGreed, FearAndGreedStatus, FngComponentHistory, FngData, BACKFILL_PROGRESS_KEY, GRAPHDATA_URL};
+use rs_lib_ng::markets::cnn::fngalerts::{AlertFn, FngAlert, FngAlerter, RatingBand};
+use rs_lib_ng::markets::cnn::fngseries::{FngSeries, Sentiment, Streak};
+use rs_lib_ng::markets::cnn::worldmarkets::{CnnQuote, WorldMarkets, MARKETS_URL};
+use rs_lib_ng::markets::headers::{BuiltinProfiles, HeaderProfile, HeaderProfileSource, HeaderRotation};
+use rs_lib_ng::markets::lastgood::{LastGood, LastGoodCache};
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::calendar::{trading_days, DayKind, MarketCalendar, MarketHoliday, SessionTimes, TradingCalendar, TradingDay};
+use rs_lib_ng::markets::nasdaq::commodities::{Commodities, Commodity, CommodityQuote};
+use rs_lib_ng::markets::nasdaq::company::{Company, CompanyProfile, KeyStats};
+use rs_lib_ng::markets::nasdaq::currencies::{Currencies, CurrencyPair, CurrencyQuote};
+use rs_lib_ng::markets::nasdaq::datafeeds::pricing::{pricing_data, PricingData};
+use rs_lib_ng::markets::nasdaq::datafeeds::yahoostreaming::{MarketHours, QuoteSender, StreamHandle, YahooQuote, YahooStreaming, STREAM_URL};
+use rs_lib_ng::markets::nasdaq::dividends::{DividendEvent, Dividends};
+use rs_lib_ng::markets::nasdaq::earnings::{Earnings, EarningsSurprise};
+use rs_lib_ng::markets::nasdaq::history::{Candle, ChartInterval, ChartRange, History};
+use rs_lib_ng::markets::nasdaq::indices::{IndexPoint, IndexQuote, Indices, MarketIndex};
+use rs_lib_ng::markets::nasdaq::insiders::{InsiderTransaction, InsiderTransactionType, Insiders};
+use rs_lib_ng::markets::nasdaq::limits::NasdaqLimits;
+use rs_lib_ng::markets::nasdaq::marketstatus::{MarketStatus, MarketStatusData, ResolvedStatus, Session};
+use rs_lib_ng::markets::nasdaq::options::{ExpiryFilter, Greeks, OptionChain, OptionChainData, OptionContract};
+use rs_lib_ng::markets::nasdaq::quote::{Quote, QuoteBatch, QuoteData, QuoteDetail};
+use rs_lib_ng::markets::nasdaq::screener::{
+    Exchange, MarketCap, Region, Screener, ScreenerFilter, ScreenerPage, ScreenerRow, Sector,
+};
+use rs_lib_ng::markets::nasdaq::search::{SymbolMatch, SymbolSearch};
+use rs_lib_ng::markets::nasdaq::trades::{Trade, Trades};
+use rs_lib_ng::markets::sessions::{MarketSessionScheduler, SessionEvent};
+use rs_lib_ng::NgError;
+
+#[allow(dead_code)]
+async fn uses(logger: Logger) -> Result<(), NgError> {
+    let limits = NasdaqLimits { concurrency: 2, min_interval: std::time::Duration::from_millis(200) };
+    limits.install()?;
+    let _: NasdaqLimits = NasdaqLimits::current();
+    let nasdaq = NasdaqApi::new(logger.clone());
+    let _: serde_json::Value = nasdaq.call("https://api.nasdaq.com/api/market-info/", None).await?;
+
+    let _: serde_json::Value = nasdaq.call_data("https://api.nasdaq.com/api/market-info/", None).await?;
+    let _: MarketStatusData = nasdaq.call_typed("https://api.nasdaq.com/api/market-info/", None).await?;
+    let _: &str = nasdaq.base_url();
+    let _: String = nasdaq.endpoint(["quote", "AAPL", "info"])?.build();
+
+    let quote = Quote::with_api(NasdaqApi::new(logger.clone()).with_base_url("https://api.nasdaq.com/api/"));
+    let data: QuoteData = quote.fetch("AAPL", AssetClass::Stocks, None).await?;
+    let _: QuoteDetail = data.primary_data;
+    let batch: QuoteBatch = quote.fetch_many(["AAPL", "MSFT"], AssetClass::Stocks, 4, None).await;
+    let _: (&Vec<QuoteData>, usize) = (&batch.quotes, batch.failures.len());
+    let _: &str = AssetClass::Etf.as_str();
+
+    let history = History::new(logger.clone()).with_page_size(100);
+    let candles: Vec<Candle> = history
+        .fetch_chart("AAPL", AssetClass::Stocks, ChartRange::Months(3), ChartInterval::Weekly, None)
+        .await?;
+    let _ = candles.first().map(|c| (c.time, c.open, c.high, c.low, c.close, c.volume));
+
+    let chain = OptionChain::new(logger.clone()).with_page_size(50);
+    let data: OptionChainData = chain.fetch("AAPL", AssetClass::Stocks, ExpiryFilter::All, None).await?;
+    let calls: &[OptionContract] = &data.calls;
+    let _: Option<&Greeks> = calls.first().and_then(|c| c.greeks.as_ref());
+
+    let dividends = Dividends::new(logger.clone());
+    let events: Vec<DividendEvent> = dividends.fetch_history("AAPL", AssetClass::Stocks, None).await?;
+    let _ = events.first().map(|e| (e.ex_date, e.record_date, e.payment_date, e.amount));
+    let _ = dividends.fetch_calendar(chrono::NaiveDate::MIN, None).await?;
+
+    let profile: CompanyProfile = Company::new(logger.clone()).fetch_profile("AAPL", None).await?;
+    let stats: &KeyStats = &profile.key_stats;
+    let _ = (profile.sector.as_str(), profile.description.as_str(), stats.market_cap, stats.pe_ratio, stats.average_volume);
+    let surprises: Vec<EarningsSurprise> = Earnings::new(logger.clone()).fetch_surprises("AAPL", None).await?;
+    let _ = surprises.first().map(|s| (s.date_reported, s.actual_eps, s.estimated_eps, s.surprise_percent, s.surprise(), s.is_beat()));
+
+    let filter = ScreenerFilter::builder()
+        .exchange(Exchange::Nasdaq)
+        .market_cap(MarketCap::Mega)
+        .market_cap_range(MarketCap::Small, MarketCap::Large)
+        .sector(Sector::Technology)
+        .region(Region::NorthAmerica)
+        .build();
+    let screener = Screener::new(logger.clone()).with_page_size(50);
+    let page: ScreenerPage = screener.fetch_page(&filter, 0, 25, None).await?;
+    let _: usize = page.total_records;
+    let _: Vec<ScreenerRow> = screener.fetch_all(&filter, None).await?;
+
+    let indices = Indices::new(logger.clone());
+    let quote: IndexQuote = indices.fetch_quote(MarketIndex::Nasdaq100, None).await?;
+    let _ = (quote.index, &quote.name, quote.value, quote.net_change, quote.percentage_change);
+    let _: Vec<IndexQuote> = indices.fetch_quotes(&MarketIndex::ALL, None).await?;
+    let points: Vec<IndexPoint> = indices.fetch_intraday(MarketIndex::Sp500, None).await?;
+    let _ = points.first().map(|p| (p.time, p.value));
+    let _: &str = MarketIndex::DowJones.symbol();
+
+    let gold: CommodityQuote = Commodities::new(logger.clone()).fetch(Commodity::Gold, None).await?;
+    let _ = (gold.commodity, &gold.name, gold.price, gold.net_change, gold.percentage_change);
+    let _: &str = Commodity::ALL[0].symbol();
+    let pair = CurrencyPair::parse("EURUSD")?;
+    let fx: CurrencyQuote = Currencies::new(logger.clone()).fetch(&pair, None).await?;
+    let _ = (fx.rate, fx.bid, fx.ask, fx.pair.base(), fx.pair.quote(), CurrencyPair::new("USD", "JPY")?.symbol());
+
+    let matches: Vec<SymbolMatch> = SymbolSearch::new(logger.clone()).lookup("apple", None).await?;
+    let _ = matches.first().map(|m| (&m.symbol, &m.name, &m.exchange, m.asset_class));
+    let _: Option<AssetClass> = AssetClass::parse("STOCKS");
+
+    let trades: Vec<InsiderTransaction> = Insiders::new(logger.clone()).fetch("AAPL", None).await?;
+    let _ = trades.first().map(|t| (&t.insider_name, &t.relation, t.shares, t.price, t.date));
+    let _: bool = trades.iter().any(|t| t.transaction_type == InsiderTransactionType::Buy);
+
+    let from = chrono::NaiveTime::from_hms_opt(9, 30, 0).unwrap_or_default();
+    let tape: Vec<Trade> = Trades::new(logger.clone()).fetch("AAPL", from, 500, None).await?;
+    let _ = tape.first().map(|t| (t.time, t.price, t.shares));
+
+    let calendar = MarketCalendar::new(logger.clone());
+    let _: TradingCalendar = calendar.fetch(None).await?;
+    let cal: TradingCalendar = calendar.fetch_or_fallback(None).await;
+    let day = chrono::NaiveDate::MIN;
+    let _: bool = cal.is_trading_day(day);
+    let _: Option<chrono::NaiveTime> = cal.close_time(day);
+    let _: chrono::NaiveDate = cal.next_trading_day(day);
+    let _: Vec<TradingDay> = cal.trading_days(day, day).filter(TradingDay::is_early_close).collect();
+    let _: Option<chrono::NaiveTime> = trading_days(day, day).first().and_then(|d| d.early_close);
+    let _: Option<SessionTimes> = cal.session_times(day).filter(|t| t.regular_open < t.regular_close);
+    let _: Option<&MarketHoliday> = cal.holidays().next();
+    let _: bool = cal.early_closes().any(|d| matches!(d.kind, DayKind::EarlyClose(_)));
+
+    let status = MarketStatus::new(logger.clone());
+    let data: MarketStatusData = status.fetch_status(None).await?;
+    let _: std::sync::Arc<MarketStatusData> = status.fetch_status_cached(std::time::Duration::from_secs(30)).await?;
+    status.invalidate_cache();
+    let _ = MarketStatus::with_api(NasdaqApi::new(logger.clone()))
+        .with_calendar(cal.clone())
+        .with_clock(std::sync::Arc::new(rs_lib_ng::retrieve::clock::TokioClock));
+    let resolved: ResolvedStatus = status.fetch_status_or_computed(None).await;
+    let _: MarketStatusData = status.poll_until_open(std::time::Duration::from_secs(30), None).await;
+    let _: (bool, Option<String>) = (resolved.is_computed(), resolved.fetch_error);
+    let _: MarketStatusData = MarketStatusData::computed(&cal, chrono::Utc::now());
+
+    let scheduler = MarketSessionScheduler::new(std::sync::Arc::new(MarketStatus::new(logger.clone())), logger.clone())
+        .with_calendar(TradingCalendar::fallback())
+        .with_clock(std::sync::Arc::new(rs_lib_ng::retrieve::clock::TokioClock));
+    let mut events = scheduler.subscribe();
+    if let Ok(SessionEvent::RegularOpen { date }) = events.try_recv() {
+        let _: chrono::NaiveDate = date;
+    }
+    let _ = scheduler.run();
+    let cache = LastGoodCache::new("/tmp/markets", logger.clone())?.with_max_age(std::time::Duration::from_secs(3600));
+    let last: LastGood<MarketStatusData> = cache.fetch_or_stale("market-info", || status.fetch_status(None)).await?;
+    let _ = (last.is_stale(), last.age(), last.fetched_at, &last.stale_error, cache.dir());
+    let _: Option<LastGood<MarketStatusData>> = cache.load("market-info")?;
+    cache.store("market-info", &last.value)?;
+    cache.remove("market-info")?;
+    let _: bool = status.is_regular_session(&data);
+    let _: bool = status.is_premarket(&data) || status.is_after_hours(&data);
+    let session: Session = status.current_session(&data);
+    let _ = session == status.session_at(&data, chrono::Utc::now());
+    let _: std::time::Duration = status.get_next_opening_delay(&data)?;
+    status.wait_until_open(&data).await;
+    let _: std::time::Duration = status.get_next_closing_delay(&data)?;
+    status.wait_until_close(&data).await;
+    let _: String = status.format_duration(chrono::Duration::seconds(1));
+
+    let profiles: Vec<HeaderProfile> = BuiltinProfiles.profiles();
+    let custom = vec![HeaderProfile::new("mine").header("user-agent", "Agent/1.0")];
+    let rotated = NasdaqApi::new(logger.clone()).with_header_profiles(&custom);
+    let rotation: &HeaderRotation = rotated.header_profiles();
+    let _: (&str, usize, &HeaderProfile) = (&rotation.current().name, rotation.profiles().len(), rotation.rotate());
+    let mut headers = reqwest::header::HeaderMap::new();
+    profiles[0].apply(&mut headers);
+
+    let mut cnn = CnnApi::new(logger.clone()).with_header_profiles(&BuiltinProfiles);
+    let _: &HeaderRotation = cnn.header_profiles();
+    cnn.set_header("x-api-key", "k");
+    let _ = cnn.get_headers();
+    let _: serde_json::Value = cnn.call("https://production.dataviz.cnn.io/", None).await?;
+
+    let world = WorldMarkets::with_api(CnnApi::new(logger.clone()), logger.clone()).with_base_url(MARKETS_URL);
+    let futures: Vec<CnnQuote> = world.fetch_futures(None).await?;
+    let _: (bool, Option<f64>, Option<chrono::DateTime<chrono::Utc>>) = (futures[0].is_open(), futures[0].percent_change, futures[0].last_updated);
+    let _: Vec<CnnQuote> = WorldMarkets::new(logger.clone()).fetch_world_indices(None).await?;
+
+    let quote: YahooQuote = YahooQuote::from_frame(STREAM_URL)?;
+    let _: (String, f64, f64, f64, i64, MarketHours) = (quote.symbol, quote.price, quote.change, quote.change_percent, quote.day_volume, quote.market_hours);
+    let streamer = YahooStreaming::new(logger.clone())
+        .with_uri(STREAM_URL)
+        .with_reconnect_backoff(std::time::Duration::from_secs(1), std::time::Duration::from_secs(60))
+        .with_max_reconnects(10)
+        .with_heartbeat(std::time::Duration::from_secs(15))
+        .with_stale_timeout(Some(std::time::Duration::from_secs(90)))
+        .with_clock(std::sync::Arc::new(rs_lib_ng::retrieve::clock::TokioClock))
+        .with_cancellation(tokio_util::sync::CancellationToken::new());
+    let (sender, _receiver): (QuoteSender, _) = tokio::sync::mpsc::channel(64);
+    streamer.stream_quotes(vec!["AAPL"], sender).await?;
+    let _: u64 = streamer.reconnect_count();
+    let handle: StreamHandle = streamer.handle();
+    handle.subscribe(&["MSFT"]);
+    handle.unsubscribe(&["AAPL"]);
+    let _: Vec<String> = handle.clone().symbols();
+    let streamer = std::sync::Arc::new(streamer);
+    let mut quotes = Box::pin(streamer.quotes(vec!["AAPL", "MSFT"]));
+    let _: Option<Result<YahooQuote, NgError>> = futures_util::StreamExt::next(&mut quotes).await;
+    let _: YahooQuote = PricingData { quote_type: pricing_data::QuoteType::Equity as i32, ..Default::default() }.into();
+
+    let fng = FearAndGreed::new(logger.clone())
+        .with_graphdata_url(GRAPHDATA_URL)
+        .with_backfill_rate(rs_lib_ng::retrieve::rate_limit::RateLimit::per_minute(12));
+    let _: Vec<FngData> = fng.backfill_full_history(chrono::NaiveDate::MIN, Some(&cache), None).await?;
+    cache.remove(BACKFILL_PROGRESS_KEY)?;
+    let latest: FearAndGreedStatus = fng.fetch_latest(None).await?;
+    let _: (&FngData, &FngData, &FngData) = (&latest.junk_bond_demand, &latest.safe_haven_demand, &latest.market_volatility_vix);
+    let components: &FngComponentHistory = &latest.component_history;
+    latest.to_csv(std::io::sink())?;
+    let _: &[FngData] = &components.market_volatility_vix;
+    let series = FngSeries::from(latest.history.clone());
+    let _: (Option<f64>, Vec<(chrono::DateTime<chrono::Utc>, f64)>) = (series.sma(20), series.rolling_sma(20));
+    let _: (Option<&FngData>, Option<&FngData>, Option<f64>) = (series.min(), series.max(), series.current_percentile());
+    let _: Vec<Streak> = series.streaks();
+    let _: Option<Streak> = series.current_streak().or(series.longest_streak(Sentiment::of(50.0)));
+    let _: FngData = latest.current;
+    let _: FngData = fng.fetch_current(None).await?;
+    let cached: LastGood<FearAndGreedStatus> = fng.fetch_cached(std::time::Duration::from_secs(300), None).await?;
+    let _: (bool, chrono::Duration) = (cached.is_stale(), cached.age());
+    let _ = fng.fetch_at_date("2024-01-01", None).await?;
+    let _: Vec<FngData> = fng.fetch_range(chrono::NaiveDate::MIN, chrono::NaiveDate::MAX, None).await?;
+    let fng = std::sync::Arc::new(fng);
+    let interval = std::time::Duration::from_secs(60);
+    let _: tokio::sync::watch::Receiver<Option<FearAndGreedStatus>> = fng.start_polling(interval);
+    let _ = fng.start_polling_with_sessions(interval, interval * 10, scheduler.subscribe());
+
+    let on_alert: AlertFn = std::sync::Arc::new(|_: &FngAlert| {});
+    let mut alerter = FngAlerter::new(logger.clone()).below(20.0).above(80.0).with_hysteresis(2.0).on_alert(on_alert);
+    let _: tokio::sync::broadcast::Receiver<FngAlert> = alerter.subscribe();
+    let _: Vec<FngAlert> = alerter.observe(&latest.current);
+    let _: (RatingBand, &str) = (RatingBand::of(50.0), RatingBand::Neutral.as_str());
+    let _: tokio::task::JoinHandle<()> = alerter.start(&fng, interval);
+    let _ = FngAlerter::new(logger.clone()).spawn(fng.start_polling(interval));
+    Ok(())
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+//! Documented `retrieve` surface: KyHttp construction, options, and verbs.
+
+use reqwest::header::HeaderMap;
+use rs_lib_ng::loggers::Logger;
+use rs_lib_ng::retrieve::ky_http::{ApiResponse, KyHttp, KyOptions};
+use rs_lib_ng::retrieve::retry_policy::{RetryPolicy, StatusPolicy};
+use rs_lib_ng::NgError;
+use std::time::Duration;
+
+#[allow(dead_code)]
+async fn uses(logger: Logger) -> Result<(), NgError> {
+    let opts = KyOptions {
+        retry: 3,
+        limit: 4,
+        timeout: Some(Duration::from_secs(10)),
+        backoff_limit: Some(Duration::from_secs(2)),
+        max_retry_after: None,
+        retry_on_timeout: false,
+        test_mode: false,
+        disable_jitter: true,
+        single_flight: false,
+        retry_policy: RetryPolicy::default()
+            .with_status(reqwest::StatusCode::TOO_MANY_REQUESTS, StatusPolicy::RetryAfter { max_waits: 3 }),
+        ..KyOptions::default()
+    };
+
+    let client = KyHttp::new(logger.clone());
+    let custom = KyHttp::new_with_opts(logger, Some(opts));
+
+    let res: ApiResponse<serde_json::Value> = client.get("http://localhost", HeaderMap::new()).await?;
+    let _: (Option<serde_json::Value>, Option<String>, u16, bool, HeaderMap) =
+        (res.data, res.error_body, res.status, res.success, res.headers);
+
+    let body = serde_json::json!({});
+    let _ = custom.post::<serde_json::Value, _>("http://localhost", HeaderMap::new(), &body).await?;
+    let _ = custom.put::<serde_json::Value, _>("http://localhost", HeaderMap::new(), &body).await?;
+    let _ = custom.patch::<serde_json::Value, _>("http://localhost", HeaderMap::new(), &body).await?;
+    let _ = custom.delete::<serde_json::Value>("http://localhost", HeaderMap::new()).await?;
+    let _ = custom.head("http://localhost", HeaderMap::new()).await?;
+    let _ = custom.options::<serde_json::Value>("http://localhost", HeaderMap::new()).await?;
+    let _ = custom.trace::<serde_json::Value>("http://localhost", HeaderMap::new()).await?;
+    Ok(())
+}
+
+fn main() {}
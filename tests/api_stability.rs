@@ -0,0 +1,15 @@
+//! # Public API Stability Suite
+//!
+//! Compiles small downstream-style programs against the crate:
+//! - `tests/api/pass/*.rs` exercise the documented public API and must compile.
+//! - `tests/api/fail/*.rs` use items that are intentionally not public (or were
+//!   removed) and must keep failing with the recorded diagnostics.
+//!
+//! Regenerate the expected `.stderr` files with `TRYBUILD=overwrite cargo test --test api_stability`.
+
+#[test]
+fn public_api_compiles() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/api/pass/*.rs");
+    t.compile_fail("tests/api/fail/*.rs");
+}
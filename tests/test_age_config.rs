@@ -0,0 +1,38 @@
+//! # age Configuration Payload Test Suite
+//!
+//! Validates that age files are recognised by their magic and decrypted with
+//! either an X25519 identity or a passphrase.
+
+#![cfg(feature = "age")]
+
+use age::secrecy::{ExposeSecret, SecretString};
+use rs_lib_ng::configs::cloud::{decrypt_bytes, is_age_payload};
+use rs_lib_ng::core::error::NgError;
+
+const PLAIN: &[u8] = br#"{"commonAll":{"region":"eu"}}"#;
+
+#[test]
+fn test_age_x25519_armored() {
+    //! Scenario: Armored file encrypted to a recipient, decrypted with its identity.
+    let identity = age::x25519::Identity::generate();
+    let armored = age::encrypt_and_armor(&identity.to_public(), PLAIN).unwrap();
+    assert!(is_age_payload(armored.as_bytes()));
+
+    let value = decrypt_bytes(armored.as_bytes(), identity.to_string().expose_secret()).unwrap();
+    assert_eq!(value["commonAll"]["region"], "eu");
+
+    let other = age::x25519::Identity::generate();
+    let res = decrypt_bytes(armored.as_bytes(), other.to_string().expose_secret());
+    assert!(matches!(res, Err(NgError::ConfigError(_))));
+}
+
+#[test]
+fn test_age_passphrase_binary() {
+    //! Scenario: Binary passphrase-encrypted file, as produced by `age -p`.
+    let recipient = age::scrypt::Recipient::new(SecretString::from("rotate-me".to_string()));
+    let out = age::encrypt(&recipient, PLAIN).unwrap();
+
+    assert!(is_age_payload(&out));
+    let value = decrypt_bytes(&out, "rotate-me").unwrap();
+    assert_eq!(value["commonAll"]["region"], "eu");
+}
@@ -72,15 +72,38 @@ async fn test_cnn_custom_header_injection() {
     assert!(result.is_ok());
 }
 
+#[tokio::test]
+async fn test_cnn_custom_header_overrides_profile() {
+    //! Scenario: a custom user agent is set on top of the rotating profiles.
+    //! Goal: the custom value wins; the profile's other headers are still sent.
+    use rs_lib_ng::markets::headers::HeaderProfile;
+    let (service, server) = setup_cnn_test().await;
+    let profiles = vec![HeaderProfile::new("p").header("user-agent", "ProfileAgent").header("accept-language", "de-DE")];
+    let mut service = service.with_header_profiles(&profiles);
+    service.set_header("user-agent", "CustomAgent");
+
+    Mock::given(method("GET"))
+        .and(header("user-agent", "CustomAgent"))
+        .and(header("accept-language", "de-DE"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "ok"})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(service.call(&server.uri(), None).await.is_ok());
+}
+
 #[tokio::test]
 async fn test_cnn_options_override() {
     //! Scenario: Passing KyOptions to override behavior.
     //! Goal: Verify that transient options are respected (e.g., lower timeout).
     let (service, server) = setup_cnn_test().await;
 
-    let mut opts = KyOptions::default();
     // Fixed: timeout is Option<Duration>, not a raw integer
-    opts.timeout = Some(Duration::from_millis(100)); 
+    let opts = KyOptions {
+        timeout: Some(Duration::from_millis(100)),
+        ..KyOptions::default()
+    };
 
     Mock::given(method("GET"))
         .respond_with(
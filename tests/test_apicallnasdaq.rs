@@ -10,7 +10,7 @@ use serde_json::json;
 use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
 use rs_lib_ng::retrieve::ky_http::KyOptions;
 use rs_lib_ng::loggers::builder::LoggerBuilder;
-use rs_lib_ng::core::error::NgError;
+use rs_lib_ng::core::error::{BCodeKind, NgError};
 
 /// Helper function to initialize a logger and the NasdaqApi instance.
 ///
@@ -78,6 +78,63 @@ async fn test_business_error_non_200_rcode() {
     }
 }
 
+#[tokio::test]
+async fn test_business_error_typed_bcode_messages() {
+    //! Verifies that `bCodeMessage` arrays are parsed into typed entries with helpers.
+    let (api, server) = setup_api().await;
+
+    let error_body = json!({
+        "data": null,
+        "status": {
+            "rCode": 400,
+            "bCodeMessage": [
+                { "code": 1001, "errorMessage": "Symbol not exists." }
+            ]
+        }
+    });
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(error_body))
+        .mount(&server)
+        .await;
+
+    let err = api.call(&server.uri(), None).await.unwrap_err();
+
+    assert_eq!(err.b_code_messages().len(), 1);
+    assert_eq!(err.b_code_messages()[0].code, 1001);
+    assert_eq!(err.b_code_messages()[0].error_message, "Symbol not exists.");
+    assert!(err.is_invalid_symbol());
+}
+
+#[tokio::test]
+async fn test_business_error_classifies_bcode_kinds() {
+    //! Verifies that known `bCodeMessage` texts are classified, and the first known kind wins.
+    let (api, server) = setup_api().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": null,
+            "status": {
+                "rCode": 400,
+                "bCodeMessage": [
+                    { "code": 1500, "errorMessage": "Request could not be completed." },
+                    { "code": 1502, "errorMessage": "Too many requests, please try again later." },
+                    { "code": 1600, "errorMessage": "No data available for this symbol." }
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let err = api.call(&server.uri(), None).await.unwrap_err();
+
+    let kinds: Vec<BCodeKind> = err.b_code_messages().iter().map(|m| m.kind()).collect();
+    assert_eq!(kinds, [BCodeKind::Other, BCodeKind::Throttled, BCodeKind::NoData]);
+    assert_eq!(err.b_code_kind(), Some(BCodeKind::Throttled));
+    assert!(err.is_throttled() && err.is_no_data() && !err.is_invalid_symbol());
+    assert_eq!(NgError::ConfigError("x".into()).b_code_kind(), None);
+}
+
 #[tokio::test]
 async fn test_maintenance_mode_non_json_response() {
     //! Verifies handling of non-JSON content (e.g., HTML maintenance pages).
@@ -131,8 +188,10 @@ async fn test_custom_options_override() {
     let (api, server) = setup_api().await;
 
     // Configure options with 0 retries to fail fast - field name is 'retry'
-    let mut opts = KyOptions::default();
-    opts.retry = 0;
+    let opts = KyOptions {
+        retry: 0,
+        ..KyOptions::default()
+    };
 
     // Server will return an error
     Mock::given(method("GET"))
@@ -163,4 +222,91 @@ async fn test_invalid_url_parameter() {
         Err(NgError::HttpError(msg)) => assert!(msg.contains("Invalid URL")),
         _ => panic!("Expected HttpError, got {:?}", result),
     }
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_call_with_request_overrides() {
+    //! Verifies that `call_with` applies the retry override on the shared client.
+    use rs_lib_ng::retrieve::ky_http::RequestOverrides;
+    let (api, server) = setup_api().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let overrides = RequestOverrides { retry: Some(0), ..RequestOverrides::default() };
+    let result = api.call_with(&server.uri(), &overrides).await;
+
+    // Assertions: one attempt only, instead of the default three
+    assert!(matches!(result, Err(NgError::NonJsonResponse { status: 503, .. })));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_blocked_response_rotates_header_profile() {
+    //! Scenario: the CDN answers 403 to the first browser profile only.
+    //! Goal: the call is retried once with the next profile, which stays current afterwards.
+    use rs_lib_ng::markets::headers::HeaderProfile;
+    let (api, server) = setup_api().await;
+    let profiles = vec![
+        HeaderProfile::new("flagged").header("user-agent", "FlaggedAgent/1.0"),
+        HeaderProfile::new("fresh").header("user-agent", "FreshAgent/2.0"),
+    ];
+    let api = api.with_header_profiles(&profiles);
+
+    Mock::given(method("GET"))
+        .and(header("user-agent", "FlaggedAgent/1.0"))
+        .respond_with(ResponseTemplate::new(403).set_body_string("<html>Access Denied</html>"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(header("user-agent", "FreshAgent/2.0"))
+        .and(header("authority", "api.nasdaq.com"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": {}, "status": { "rCode": 200 } })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    api.call(&server.uri(), None).await.expect("retry with the next profile should succeed");
+    assert_eq!(api.header_profiles().current().name, "fresh");
+    api.call(&server.uri(), None).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_call_typed_reports_offending_path() {
+    //! Verifies that `call_typed` decodes `data` and names the JSON path of a value of the wrong type.
+    #[derive(serde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Row {
+        symbol: String,
+        volume: u64,
+    }
+    #[derive(serde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Table {
+        rows: Vec<Row>,
+    }
+    let (api, server) = setup_api().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "rows": [
+                { "symbol": "AAPL", "volume": 100 },
+                { "symbol": "MSFT", "volume": "n/a" }
+            ] },
+            "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    match api.call_typed::<Table>(&server.uri(), None).await {
+        Err(NgError::MalformedResponse { details, .. }) => {
+            assert!(details.starts_with("JSON error at /rows/1/volume:"), "{}", details);
+        }
+        other => panic!("Expected MalformedResponse, got {:?}", other),
+    }
+
+    let table: serde_json::Value = api.call_typed(&server.uri(), None).await.unwrap();
+    assert_eq!(table["rows"][0]["symbol"], "AAPL");
+}
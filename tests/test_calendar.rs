@@ -0,0 +1,97 @@
+//! # Nasdaq Market Calendar Test Suite
+//!
+//! Tests for `MarketCalendar` and `TradingCalendar`: schedule parsing, the
+//! embedded fallback and trading-day helpers.
+
+use chrono::{NaiveDate, NaiveTime};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::calendar::{trading_days, DayKind, MarketCalendar, TradingCalendar};
+use rs_lib_ng::retrieve::ky_http::KyOptions;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the MarketCalendar service against a mock server.
+async fn setup_calendar_test() -> (MarketCalendar, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("calendar_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (MarketCalendar::with_api(api), server)
+}
+
+fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[tokio::test]
+async fn test_fetch_parses_closures_and_early_closes() {
+    //! Scenario: the schedule lists a closure, an early close and an unparsable row.
+    //! Goal: typed days for the first two; the bad row is skipped.
+    let (calendar, server) = setup_calendar_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/holidays"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "rows": [
+                { "date": "November 26, 2026", "holiday": "Thanksgiving Day", "status": "Closed" },
+                { "date": "November 27, 2026", "holiday": "Day After Thanksgiving", "status": "Early Close 1:00 p.m." },
+                { "date": "TBD", "holiday": "Unknown", "status": "Closed" }
+            ]},
+            "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    let cal = calendar.fetch(None).await.expect("schedule should parse");
+    assert_eq!(cal.days().count(), 2);
+    assert_eq!(cal.day(ymd(2026, 11, 26)).unwrap().kind, DayKind::Closed);
+    assert_eq!(cal.close_time(ymd(2026, 11, 27)), NaiveTime::from_hms_opt(13, 0, 0));
+}
+
+#[tokio::test]
+async fn test_fetch_or_fallback_uses_embedded_table() {
+    //! Scenario: the API is down.
+    //! Goal: the embedded calendar is returned instead of an error.
+    let (calendar, server) = setup_calendar_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/holidays"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let opts = KyOptions::builder().retry(0).build().unwrap();
+    let cal = calendar.fetch_or_fallback(Some(opts)).await;
+    assert_eq!(cal, TradingCalendar::fallback());
+    assert!(cal.covers(2026));
+}
+
+#[test]
+fn test_trading_day_helpers() {
+    //! Goal: weekends and holidays are skipped; regular days close at 16:00.
+    let cal = TradingCalendar::fallback();
+    // Good Friday 2026, then the weekend.
+    assert!(!cal.is_trading_day(ymd(2026, 4, 3)));
+    assert_eq!(cal.next_trading_day(ymd(2026, 4, 2)), ymd(2026, 4, 6));
+    assert_eq!(cal.previous_trading_day(ymd(2026, 4, 6)), ymd(2026, 4, 2));
+    assert_eq!(cal.close_time(ymd(2026, 4, 2)), NaiveTime::from_hms_opt(16, 0, 0));
+    assert_eq!(cal.close_time(ymd(2026, 12, 24)), NaiveTime::from_hms_opt(13, 0, 0));
+    assert_eq!(cal.close_time(ymd(2026, 12, 25)), None);
+    assert!(cal.holidays().all(|d| d.kind == DayKind::Closed));
+    assert!(cal.early_closes().count() >= 3);
+}
+
+#[test]
+fn test_trading_days_range() {
+    //! Goal: Thanksgiving week 2026 lists four trading days, with the early close flagged.
+    let days = trading_days(ymd(2026, 11, 22), ymd(2026, 11, 29));
+    let dates: Vec<NaiveDate> = days.iter().map(|d| d.date).collect();
+    assert_eq!(dates, [ymd(2026, 11, 23), ymd(2026, 11, 24), ymd(2026, 11, 25), ymd(2026, 11, 27)]);
+    assert_eq!(days[3].early_close, NaiveTime::from_hms_opt(13, 0, 0));
+    assert!(days[..3].iter().all(|d| !d.is_early_close()));
+
+    let cal = TradingCalendar::fallback();
+    assert_eq!(cal.trading_days(ymd(2026, 11, 23), ymd(2026, 11, 23)).count(), 1);
+    assert_eq!(cal.trading_days(ymd(2026, 11, 24), ymd(2026, 11, 23)).count(), 0);
+}
@@ -0,0 +1,179 @@
+//! # Cloud Configuration Decryption Test Suite
+//!
+//! Validates the payload formats accepted by `configs::cloud::decrypt_payload`:
+//! the legacy header-less AES-256-CBC layout and the versioned layout with
+//! AES-256-GCM and passphrase-based key derivation. Also covers the layered
+//! cloud + local and encrypted local configuration modes of `ConfigManager`.
+
+use aes::Aes256;
+use aes_gcm::{Aes256Gcm, Nonce, aead::{Aead, KeyInit}};
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use cbc::Encryptor;
+use cipher::{BlockEncryptMut, KeyIvInit, block_padding::Pkcs7};
+use rs_lib_ng::configs::ConfigManager;
+use rs_lib_ng::configs::cloud::{decrypt_payload, CipherKind, KeyDerivation, PayloadHeader};
+use rs_lib_ng::core::error::NgError;
+use serde_json::json;
+use sha2::Sha256;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const HEX_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+/// Encrypts `plain` with AES-256-GCM and returns (nonce_b64, ciphertext_b64).
+fn gcm_encrypt(key: &[u8; 32], nonce: &[u8; 12], plain: &[u8]) -> (String, String) {
+    let gcm = Aes256Gcm::new(key.into());
+    let ct = gcm.encrypt(Nonce::from_slice(nonce), plain).unwrap();
+    (STANDARD.encode(nonce), STANDARD.encode(ct))
+}
+
+/// Encrypts `plain` into the legacy two-line AES-256-CBC layout keyed by `HEX_KEY`.
+fn legacy_encrypt(plain: &str) -> String {
+    let key = hex::decode(HEX_KEY).unwrap();
+    let iv = [7u8; 16];
+
+    let mut buf = plain.as_bytes().to_vec();
+    buf.resize(plain.len() + 16, 0);
+    let ct = Encryptor::<Aes256>::new(key[..].into(), (&iv).into())
+        .encrypt_padded_mut::<Pkcs7>(&mut buf, plain.len())
+        .unwrap()
+        .to_vec();
+    format!("{}\n{}\n", STANDARD.encode(iv), STANDARD.encode(ct))
+}
+
+#[test]
+fn test_legacy_cbc_payload() {
+    //! Scenario: Header-less payload produced by the existing tooling.
+    let content = legacy_encrypt(&json!({ "commonAll": { "a": 1 } }).to_string());
+
+    let value = decrypt_payload(&content, HEX_KEY).expect("legacy payload should decrypt");
+    assert_eq!(value["commonAll"]["a"], 1);
+}
+
+#[test]
+fn test_gcm_pbkdf2_payload() {
+    //! Scenario: Versioned header selecting AES-256-GCM with a PBKDF2 passphrase.
+    let salt = b"weblib-test-salt";
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(b"correct horse", salt, 1000, &mut key);
+
+    let (nonce, ct) = gcm_encrypt(&key, &[1u8; 12], br#"{"nasdaq":{"timeout":5}}"#);
+    let content = format!(
+        "WEBLIB-ENC/2 cipher=aes-256-gcm kdf=pbkdf2-sha256 iter=1000 salt={}\n{}\n{}",
+        STANDARD.encode(salt), nonce, ct
+    );
+
+    let value = decrypt_payload(&content, "correct horse").expect("gcm payload should decrypt");
+    assert_eq!(value["nasdaq"]["timeout"], 5);
+
+    // A wrong passphrase must fail authentication rather than yield garbage.
+    let res = decrypt_payload(&content, "wrong horse");
+    assert!(matches!(res, Err(NgError::ConfigError(msg)) if msg.contains("authentication")));
+}
+
+#[test]
+fn test_gcm_argon2_payload() {
+    //! Scenario: Versioned header selecting Argon2id with explicit cost parameters.
+    let salt = b"argon2-test-salt";
+    let params = argon2::Params::new(64, 1, 1, Some(32)).unwrap();
+    let mut key = [0u8; 32];
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(b"s3cret", salt, &mut key)
+        .unwrap();
+
+    let (nonce, ct) = gcm_encrypt(&key, &[2u8; 12], br#"{"ok":true}"#);
+    let content = format!(
+        "WEBLIB-ENC/2 cipher=aes-256-gcm kdf=argon2id m=64 t=1 p=1 salt={}\n{}\n{}",
+        STANDARD.encode(salt), nonce, ct
+    );
+
+    let value = decrypt_payload(&content, "s3cret").expect("argon2 payload should decrypt");
+    assert_eq!(value["ok"], true);
+}
+
+#[test]
+fn test_header_parsing() {
+    //! Scenario: Header defaults and rejection of unknown versions.
+    let h = PayloadHeader::parse("WEBLIB-ENC/2 kdf=raw").unwrap();
+    assert_eq!(h.cipher, CipherKind::Aes256Gcm);
+    assert_eq!(h.kdf, KeyDerivation::Raw);
+
+    assert!(PayloadHeader::parse("WEBLIB-ENC/9 cipher=aes-256-gcm").is_err());
+    assert!(PayloadHeader::parse("WEBLIB-ENC/2 cipher=rot13").is_err());
+}
+
+#[tokio::test]
+async fn test_layered_local_over_cloud() {
+    //! Scenario: The local file overrides shared cloud values and keeps the rest.
+    let server = MockServer::start().await;
+    let payload = json!({ "commonAll": { "nasdaq": { "retry": 2, "timeout": 15 }, "region": "eu" } });
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(legacy_encrypt(&payload.to_string())))
+        .mount(&server)
+        .await;
+
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_layered_{}.json", std::process::id()));
+    std::fs::write(&path, json!({ "nasdaq": { "timeout": 30 } }).to_string()).unwrap();
+
+    // SAFETY: no other test in this binary sets WEBLIB_AES_PASSWORD to another value.
+    unsafe { std::env::set_var("WEBLIB_AES_PASSWORD", HEX_KEY) };
+    let manager = ConfigManager::get_layered_config(path.to_str().unwrap(), &server.uri()).await.unwrap();
+    let cfg = manager.get();
+
+    assert_eq!(cfg["nasdaq"]["timeout"], 30);
+    assert_eq!(cfg["nasdaq"]["retry"], 2);
+    assert_eq!(cfg["region"], "eu");
+
+    let missing = ConfigManager::get_layered_config("/nonexistent/config.json", &server.uri()).await;
+    assert!(matches!(missing, Err(NgError::ConfigError(_))));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_encrypted_local_file() {
+    //! Scenario: A local file in the legacy payload layout is decrypted in full.
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_encrypted_{}.enc", std::process::id()));
+    let plain = json!({ "commonAll": { "a": 1 }, "nasdaq": { "retry": 4 } }).to_string();
+    std::fs::write(&path, legacy_encrypt(&plain)).unwrap();
+
+    // SAFETY: no other test in this binary sets WEBLIB_AES_PASSWORD to another value.
+    unsafe { std::env::set_var("WEBLIB_AES_PASSWORD", HEX_KEY) };
+    let manager = ConfigManager::get_local_config_encrypted(path.to_str().unwrap()).unwrap();
+    let cfg = manager.get();
+    assert_eq!(cfg["nasdaq"]["retry"], 4);
+    assert_eq!(cfg["commonAll"]["a"], 1);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_cloud_failover_to_mirror() {
+    //! Scenario: A failing and a hanging source are skipped in favour of a healthy mirror.
+    let server = MockServer::start().await;
+    Mock::given(method("GET")).and(path("/broken"))
+        .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+        .mount(&server).await;
+    Mock::given(method("GET")).and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+        .mount(&server).await;
+    Mock::given(method("GET")).and(path("/mirror"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(legacy_encrypt(r#"{"commonAll":{"ok":true}}"#)))
+        .mount(&server).await;
+
+    let broken = format!("{}/broken", server.uri());
+    let slow = format!("{}/slow", server.uri());
+    let mirror = format!("{}/mirror", server.uri());
+
+    // SAFETY: no other test in this binary sets WEBLIB_AES_PASSWORD to another value.
+    unsafe { std::env::set_var("WEBLIB_AES_PASSWORD", HEX_KEY) };
+    let manager = ConfigManager::get_cloud_config_failover(&[&broken, &slow, &mirror], Duration::from_millis(300))
+        .await
+        .unwrap();
+    assert_eq!(manager.get()["ok"], true);
+    assert!(manager.source_info().contains("/mirror"));
+
+    let res = ConfigManager::get_cloud_config_failover(&[&broken, &slow], Duration::from_millis(300)).await;
+    assert!(matches!(res, Err(NgError::ConfigError(msg)) if msg.contains("HTTP Error") && msg.contains("timed out")));
+}
@@ -0,0 +1,49 @@
+//! # Nasdaq Commodities Test Suite
+//!
+//! Integration tests for `Commodities`: futures symbols with
+//! `assetclass=commodities` and quote mapping.
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::commodities::{Commodities, Commodity};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Commodities service against a mock server.
+async fn setup_commodities_test() -> (Commodities, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("commodities_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Commodities::with_api(api), server)
+}
+
+#[tokio::test]
+async fn test_fetch_gold_quote() {
+    //! Scenario: gold futures quote with display strings.
+    //! Goal: the `GC:CMX` symbol is requested as a commodity; price and change are typed.
+    let (commodities, server) = setup_commodities_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/GC:CMX/info"))
+        .and(query_param("assetclass", "commodities"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "symbol": "GC:CMX", "companyName": "Gold", "assetClass": "COMMODITIES",
+                "primaryData": { "lastSalePrice": "$2,945.10", "netChange": "+12.40", "percentageChange": "+0.42%",
+                                 "lastTradeTimestamp": "Feb 23, 2026 5:00 PM ET" }
+            },
+            "status": { "rCode": 200 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let quote = commodities.fetch(Commodity::Gold, None).await.expect("quote should parse");
+    assert_eq!(quote.commodity, Commodity::Gold);
+    assert_eq!(quote.name, "Gold");
+    assert_eq!(quote.price, Some(2_945.10));
+    assert_eq!(quote.net_change, Some(12.40));
+    assert_eq!(quote.percentage_change, Some(0.42));
+}
@@ -0,0 +1,115 @@
+//! # Nasdaq Company Test Suite
+//!
+//! Integration tests for `Company`: profile fields, key statistics and failures.
+
+use chrono::NaiveDate;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::core::error::NgError;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::company::Company;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Company service against a mock server.
+async fn setup_company_test() -> (Company, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("company_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Company::with_api(api), server)
+}
+
+/// A `{ label, value }` field as Nasdaq reports it.
+fn field(label: &str, value: &str) -> serde_json::Value {
+    json!({ "label": label, "value": value })
+}
+
+#[tokio::test]
+async fn test_fetch_profile() {
+    //! Scenario: a company profile without a sector, and a quote summary with display strings.
+    //! Goal: profile fields are read, the sector comes from the summary, and statistics are parsed.
+    let (company, server) = setup_company_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/company/AAPL/company-profile"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "Symbol": field("Symbol", "aapl"),
+                "CompanyName": field("Company Name", "Apple Inc."),
+                "Industry": field("Industry", "Computer Manufacturing"),
+                "Sector": field("Sector", ""),
+                "CompanyDescription": field("Description", "Apple designs smartphones."),
+                "CompanyUrl": field("Website", "https://www.apple.com"),
+                "Address": field("Address", "One Apple Park Way, Cupertino, CA"),
+                "Phone": field("Phone", "+1 408 996-1010")
+            },
+            "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/summary"))
+        .and(query_param("assetclass", "stocks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "summaryData": {
+                "Exchange": field("Exchange", "NASDAQ-GS"),
+                "Sector": field("Sector", "Technology"),
+                "MarketCap": field("Market Cap", "3,845,101,465,920"),
+                "PERatio": field("P/E Ratio", "39.75"),
+                "ForwardPE1Yr": field("Forward P/E 1 Yr.", "N/A"),
+                "EarningsPerShare": field("Earnings Per Share(EPS)", "$6.42"),
+                "AnnualizedDividend": field("Annualized Dividend", "$1.04"),
+                "Yield": field("Current Yield", "0.41%"),
+                "ExDividendDate": field("Ex Dividend Date", "Feb 9, 2026"),
+                "Beta": field("Beta", "1.09"),
+                "AverageVolume": field("Average Volume", "47,806,624"),
+                "OneYrTarget": field("1 Year Target", "$287.00"),
+                "FiftTwoWeekHighLow": field("52 Week High/Low", "$288.62/$169.21")
+            }},
+            "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    let profile = company.fetch_profile("AAPL", None).await.expect("profile should parse");
+    assert_eq!(profile.symbol, "AAPL");
+    assert_eq!(profile.name, "Apple Inc.");
+    assert_eq!(profile.sector, "Technology");
+    assert_eq!(profile.industry, "Computer Manufacturing");
+    assert_eq!(profile.website, "https://www.apple.com");
+    let stats = &profile.key_stats;
+    assert_eq!(stats.exchange, "NASDAQ-GS");
+    assert_eq!(stats.market_cap, Some(3_845_101_465_920.0));
+    assert_eq!(stats.pe_ratio, Some(39.75));
+    assert_eq!(stats.forward_pe, None);
+    assert_eq!(stats.dividend_yield, Some(0.41));
+    assert_eq!(stats.ex_dividend_date, NaiveDate::from_ymd_opt(2026, 2, 9));
+    assert_eq!(stats.average_volume, Some(47_806_624));
+    assert_eq!((stats.fifty_two_week_high, stats.fifty_two_week_low), (Some(288.62), Some(169.21)));
+}
+
+#[tokio::test]
+async fn test_fetch_profile_fails_with_summary() {
+    //! Goal: an unknown symbol reported by either endpoint fails the whole call.
+    let (company, server) = setup_company_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/company/ZZZZ/company-profile"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "CompanyName": field("Company Name", "") }, "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/ZZZZ/summary"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": null,
+            "status": { "rCode": 400, "bCodeMessage": [{ "code": 1001, "errorMessage": "Symbol not exists." }] }
+        })))
+        .mount(&server)
+        .await;
+
+    let err = company.fetch_profile("ZZZZ", None).await.unwrap_err();
+    assert!(matches!(err, NgError::NasdaqBusinessError { .. }));
+    assert!(err.is_invalid_symbol());
+}
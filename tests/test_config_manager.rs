@@ -0,0 +1,335 @@
+//! # ConfigManager Test Suite
+//!
+//! Validates local configuration loading and the precedence of the
+//! additional configuration sources layered on top of it.
+
+use rs_lib_ng::configs::{resolve_config_name, select_sections, select_sections_with, ConfigBuilder, ConfigDefaults, ConfigManager, ConfigSource, EnvOptions};
+use rs_lib_ng::configs::merge::{deep_merge, ArrayStrategy};
+use rs_lib_ng::configs::cli::{parse_args, CliOverride};
+use rs_lib_ng::core::error::NgError;
+use serde_json::json;
+use std::path::PathBuf;
+
+/// Writes a JSON document to a unique temp file and returns its path.
+fn write_temp_config(name: &str, value: &serde_json::Value) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_{}_{}.json", name, std::process::id()));
+    std::fs::write(&path, value.to_string()).unwrap();
+    path
+}
+
+#[test]
+fn test_cli_override_parsing() {
+    //! Scenario: Mixed `--set` forms among unrelated arguments.
+    let args = ["bin", "--verbose", "--set", "nasdaq.retry=5", "--set=logging.level=debug"];
+    let overrides = parse_args(args).unwrap();
+
+    assert_eq!(overrides.len(), 2);
+    assert_eq!(overrides[0], CliOverride { path: "nasdaq.retry".into(), value: json!(5) });
+    assert_eq!(overrides[1].value, json!("debug"));
+
+    assert!(matches!(parse_args(["--set", "novalue"]), Err(NgError::ConfigError(_))));
+    assert!(matches!(parse_args(["--set"]), Err(NgError::ConfigError(_))));
+}
+
+#[test]
+fn test_cli_overrides_win_over_file() {
+    //! Scenario: A file value and a sibling key are overridden from the command line.
+    let path = write_temp_config("cli", &json!({ "nasdaq": { "retry": 2, "timeout": 15 } }));
+
+    let manager = ConfigManager::get_local_config_with_args(
+        path.to_str().unwrap(),
+        ["--set", "nasdaq.retry=7", "--set", "extra.flag=true"],
+    ).unwrap();
+
+    let cfg = manager.get();
+    assert_eq!(cfg["nasdaq"]["retry"], 7);
+    assert_eq!(cfg["nasdaq"]["timeout"], 15);
+    assert_eq!(cfg["extra"]["flag"], true);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_defaults_sit_below_file() {
+    //! Scenario: Defaults fill keys the file omits without overriding the ones it sets.
+    #[derive(serde::Serialize)]
+    struct LoggingDefaults { level: &'static str, buffer: u32 }
+
+    let path = write_temp_config("defaults", &json!({ "nasdaq": { "timeout": 30 } }));
+
+    let defaults = ConfigDefaults::new()
+        .with_value(json!({ "nasdaq": { "timeout": 15, "retry": 2 } }))
+        .set("nasdaq.base_url", "https://api.nasdaq.com").unwrap()
+        .with_struct(&json!({ "logging": LoggingDefaults { level: "info", buffer: 1024 } })).unwrap();
+
+    let manager = ConfigManager::get_local_config_with_defaults(path.to_str().unwrap(), defaults).unwrap();
+    let cfg = manager.get();
+
+    assert_eq!(cfg["nasdaq"]["timeout"], 30);
+    assert_eq!(cfg["nasdaq"]["retry"], 2);
+    assert_eq!(cfg["nasdaq"]["base_url"], "https://api.nasdaq.com");
+    assert_eq!(cfg["logging"]["level"], "info");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_deep_merge_objects() {
+    //! Scenario: Nested objects merge key by key; scalars and arrays are replaced.
+    let mut base = json!({ "a": { "x": 1, "y": [1, 2] }, "b": 1 });
+    deep_merge(&mut base, json!({ "a": { "y": [3] , "z": true }, "b": null }));
+    assert_eq!(base, json!({ "a": { "x": 1, "y": [3], "z": true }, "b": null }));
+}
+
+#[test]
+fn test_config_name_resolution() {
+    //! Scenario: An explicit name wins; otherwise WEBLIB_CONFIG_NAME is used.
+    assert_eq!(resolve_config_name(Some("collector")), "collector");
+
+    // SAFETY: no other test in this binary reads or writes WEBLIB_CONFIG_NAME.
+    unsafe { std::env::set_var("WEBLIB_CONFIG_NAME", "from-env") };
+    assert_eq!(resolve_config_name(None), "from-env");
+    assert_eq!(resolve_config_name(Some("explicit")), "explicit");
+    unsafe { std::env::remove_var("WEBLIB_CONFIG_NAME") };
+
+    assert!(!resolve_config_name(None).is_empty());
+}
+
+#[test]
+fn test_select_sections() {
+    //! Scenario: The named section overrides commonAll; other sections are ignored.
+    let payload = json!({
+        "commonAll": { "logging": { "level": "info" }, "region": "us", "nasdaq": { "retry": 2, "timeout": 15 } },
+        "collector": { "region": "eu", "nasdaq": { "timeout": 30 } },
+        "other": { "region": "ap" }
+    });
+    let merged = select_sections(&payload, "collector");
+    assert_eq!(merged["region"], "eu");
+    assert_eq!(merged["logging"]["level"], "info");
+    assert_eq!(merged["nasdaq"], json!({ "retry": 2, "timeout": 30 }));
+}
+
+#[test]
+fn test_select_sections_array_strategies() {
+    //! Scenario: Arrays defined in both sections are replaced, appended or unioned.
+    let payload = json!({
+        "commonAll": { "symbols": ["AAPL", "MSFT"] },
+        "collector": { "symbols": ["MSFT", "NVDA"] }
+    });
+
+    assert_eq!(select_sections(&payload, "collector")["symbols"], json!(["MSFT", "NVDA"]));
+    assert_eq!(
+        select_sections_with(&payload, "collector", ArrayStrategy::Append)["symbols"],
+        json!(["AAPL", "MSFT", "MSFT", "NVDA"])
+    );
+    assert_eq!(
+        select_sections_with(&payload, "collector", ArrayStrategy::Unique)["symbols"],
+        json!(["AAPL", "MSFT", "NVDA"])
+    );
+}
+
+#[test]
+fn test_custom_env_prefix_and_separator() {
+    //! Scenario: Only variables matching the configured naming convention are merged.
+    let path = write_temp_config("env", &json!({ "nasdaq": { "retry": 2, "timeout": 15 } }));
+
+    // SAFETY: no other test in this binary reads or writes these variables.
+    unsafe {
+        std::env::set_var("ACMECFG_NASDAQ.RETRY", "9");
+        std::env::set_var("WEBLIB_ACMECHECK__FLAG", "true");
+    }
+    let env = EnvOptions::new().with_prefix("ACMECFG_").with_separator(".");
+    let manager = ConfigManager::get_local_config_with_env(path.to_str().unwrap(), env).unwrap();
+    unsafe {
+        std::env::remove_var("ACMECFG_NASDAQ.RETRY");
+        std::env::remove_var("WEBLIB_ACMECHECK__FLAG");
+    }
+
+    let cfg = manager.get();
+    assert_eq!(cfg["nasdaq"]["retry"], 9);
+    assert_eq!(cfg["nasdaq"]["timeout"], 15);
+    assert!(cfg.get("acmecheck").is_none());
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_per_key_provenance() {
+    //! Scenario: Each leaf reports the highest layer that set it.
+    let path = write_temp_config("provenance", &json!({ "nasdaq": { "timeout": 30 }, "region": "eu" }));
+    let file = ConfigSource::File(path.to_str().unwrap().to_string());
+
+    // SAFETY: no other test in this binary reads or writes WEBLIB_PROVTEST__FLAG.
+    unsafe { std::env::set_var("WEBLIB_PROVTEST__FLAG", "1") };
+    let defaults = ConfigDefaults::new().with_value(json!({ "nasdaq": { "timeout": 15, "retry": 2 } }));
+    let manager = ConfigManager::get_local_config_with_defaults(path.to_str().unwrap(), defaults).unwrap();
+    unsafe { std::env::remove_var("WEBLIB_PROVTEST__FLAG") };
+    manager.apply_cli_args(["--set", "region=us"]).unwrap();
+
+    assert!(manager.source_info().starts_with("local:"));
+    assert_eq!(manager.provenance("nasdaq.retry"), Some(ConfigSource::Default));
+    assert_eq!(manager.provenance("nasdaq.timeout"), Some(file));
+    assert_eq!(manager.provenance("provtest.flag"), Some(ConfigSource::Env("WEBLIB_".into())));
+    assert_eq!(manager.provenance("region"), Some(ConfigSource::Cli));
+    assert_eq!(manager.provenance("missing.key"), None);
+    assert!(manager.provenance_report().entries().contains_key("nasdaq.retry"));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_provenance_ignores_empty_layers() {
+    //! Scenario: Empty defaults and an env layer without matching vars are merged.
+    let path = write_temp_config("provenance_empty", &json!({ "region": "eu" }));
+    let defaults = ConfigDefaults::new().with_value(json!({}));
+    let manager = ConfigManager::get_local_config_with_defaults(path.to_str().unwrap(), defaults).unwrap();
+
+    let report = manager.provenance_report();
+    assert!(!report.entries().contains_key(""), "unexpected root entry: {:?}", report.entries());
+    assert_eq!(manager.provenance(""), None);
+    assert_eq!(manager.provenance("region"), Some(ConfigSource::File(path.to_str().unwrap().to_string())));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_provenance_ignores_nested_empty_objects() {
+    //! Scenario: A file layer carries empty objects below the root.
+    let path = write_temp_config("provenance_nested_empty", &json!({ "db": {}, "cache": {} }));
+    let defaults = ConfigDefaults::new().with_value(json!({ "db": { "host": "localhost" } }));
+    let manager = ConfigManager::get_local_config_with_defaults(path.to_str().unwrap(), defaults).unwrap();
+
+    let report = manager.provenance_report();
+    assert!(!report.entries().contains_key("db"), "unexpected db entry: {:?}", report.entries());
+    assert!(!report.entries().contains_key("cache"), "unexpected cache entry: {:?}", report.entries());
+    assert_eq!(manager.provenance("db.host"), Some(ConfigSource::Default));
+    assert_eq!(manager.provenance("cache"), None);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_provenance_attributes_merged_arrays_to_last_layer() {
+    //! Scenario: An appended array mixes elements from defaults and a file.
+    let path = write_temp_config("provenance_arrays", &json!({ "symbols": ["NVDA"] }));
+    let file = path.to_str().unwrap().to_string();
+
+    let manager = ConfigBuilder::new()
+        .with_defaults(json!({ "symbols": ["AAPL"] }))
+        .with_file(&file)
+        .with_array_strategy(ArrayStrategy::Append)
+        .build_local()
+        .unwrap();
+    assert_eq!(manager.get()["symbols"], json!(["AAPL", "NVDA"]));
+
+    // The whole array, including the default element, names the file.
+    assert_eq!(manager.provenance("symbols"), Some(ConfigSource::File(file.clone())));
+    assert_eq!(manager.provenance("symbols.0"), Some(ConfigSource::File(file)));
+    assert!(!manager.provenance_report().entries().keys().any(|k| k.starts_with("symbols.")));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_require_reports_all_problems() {
+    //! Scenario: Missing, null and mistyped keys are reported together.
+    let path = write_temp_config("require", &json!({
+        "nasdaq": { "base_url": "https://api.nasdaq.com", "timeout": "slow" },
+        "logging": { "level": null }
+    }));
+    let manager = ConfigManager::get_local_config(path.to_str().unwrap()).unwrap();
+
+    assert!(manager.require(&["nasdaq.base_url:string", "nasdaq"]).is_ok());
+
+    let err = manager.require(&["nasdaq.base_url", "nasdaq.timeout:integer", "logging.level", "cnn.url"]).unwrap_err();
+    let NgError::ConfigError(msg) = err else { panic!("expected ConfigError") };
+    assert!(!msg.contains("base_url"));
+    assert!(msg.contains("nasdaq.timeout (expected integer, found string)"));
+    assert!(msg.contains("logging.level (missing)"));
+    assert!(msg.contains("cnn.url (missing)"));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_snapshot_redacted() {
+    //! Scenario: Configured secret paths are masked without touching the live config.
+    let path = write_temp_config("redact", &json!({
+        "nasdaq": { "api_key": "n-key", "timeout": 15 },
+        "cnn": { "api_key": "c-key" },
+        "db": { "url": "postgres://u:p@h/db", "pool": 4 },
+        "aes_password": "hex"
+    }));
+    let manager = ConfigManager::get_local_config(path.to_str().unwrap()).unwrap();
+    manager.add_secret_paths(&["*.api_key", "db.url", "absent.key"]);
+
+    let snap = manager.snapshot_redacted();
+    assert_eq!(snap["nasdaq"]["api_key"], "***");
+    assert_eq!(snap["cnn"]["api_key"], "***");
+    assert_eq!(snap["db"]["url"], "***");
+    assert_eq!(snap["aes_password"], "***");
+    assert_eq!(snap["db"]["pool"], 4);
+    assert!(snap.get("absent").is_none());
+    assert_eq!(manager.get()["nasdaq"]["api_key"], "n-key");
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn test_builder_layer_order() {
+    //! Scenario: Explicitly composed sources merge in the documented order.
+    let path = write_temp_config("builder", &json!({ "nasdaq": { "retry": 3, "timeout": 30 } }));
+
+    // SAFETY: no other test in this binary reads or writes these variables.
+    unsafe { std::env::set_var("BLDTEST_NASDAQ__TIMEOUT", "45") };
+    let manager = ConfigBuilder::new()
+        .with_defaults(json!({ "nasdaq": { "retry": 1, "timeout": 15, "base_url": "https://api.nasdaq.com" } }))
+        .with_file(path.to_str().unwrap())
+        .with_env("BLDTEST_")
+        .with_args(["--set", "nasdaq.retry=9"])
+        .build()
+        .await
+        .unwrap();
+    unsafe { std::env::remove_var("BLDTEST_NASDAQ__TIMEOUT") };
+
+    let cfg = manager.get();
+    assert_eq!(cfg["nasdaq"]["base_url"], "https://api.nasdaq.com");
+    assert_eq!(cfg["nasdaq"]["timeout"], 45);
+    assert_eq!(cfg["nasdaq"]["retry"], 9);
+    assert_eq!(manager.source_info(), format!("local:{}", path.to_str().unwrap()));
+
+    // Env vars are only read when requested.
+    let no_env = ConfigBuilder::new().with_file(path.to_str().unwrap()).build_local().unwrap();
+    assert_eq!(no_env.get()["nasdaq"]["timeout"], 30);
+
+    let cloud = ConfigBuilder::new().with_cloud("https://example.invalid/config").build_local();
+    assert!(matches!(cloud, Err(NgError::ConfigError(_))));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_builder_array_strategies() {
+    //! Scenario: One array extends across layers while others keep replace semantics.
+    let path = write_temp_config("arrays", &json!({ "symbols": ["MSFT", "NVDA"], "hosts": ["b"] }));
+
+    let manager = ConfigBuilder::new()
+        .with_defaults(json!({ "symbols": ["AAPL", "MSFT"], "hosts": ["a"] }))
+        .with_file(path.to_str().unwrap())
+        .with_array_strategy_at("symbols", ArrayStrategy::Unique)
+        .build_local()
+        .unwrap();
+    let cfg = manager.get();
+    assert_eq!(cfg["symbols"], json!(["AAPL", "MSFT", "NVDA"]));
+    assert_eq!(cfg["hosts"], json!(["b"]));
+
+    let appended = ConfigBuilder::new()
+        .with_defaults(json!({ "hosts": ["a"] }))
+        .with_file(path.to_str().unwrap())
+        .with_array_strategy(ArrayStrategy::Append)
+        .build_local()
+        .unwrap();
+    assert_eq!(appended.get()["hosts"], json!(["a", "b"]));
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,58 @@
+//! # Nasdaq Currencies Test Suite
+//!
+//! Tests for `Currencies` and `CurrencyPair`: pair validation, symbols and
+//! rate mapping.
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::currencies::{Currencies, CurrencyPair};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+/// Helper to initialize the Currencies service against a mock server.
+async fn setup_currencies_test() -> (Currencies, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("currencies_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Currencies::with_api(api), server)
+}
+
+#[tokio::test]
+async fn test_fetch_eurusd_rate() {
+    //! Scenario: EUR/USD quote with bid and ask.
+    //! Goal: the `EURUSD` symbol is requested as a currency; rate, bid and ask are typed.
+    let (currencies, server) = setup_currencies_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/EURUSD/info"))
+        .and(query_param("assetclass", "currencies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "symbol": "EURUSD", "companyName": "Euro/US Dollar", "assetClass": "CURRENCIES",
+                "primaryData": { "lastSalePrice": "1.0482", "netChange": "-0.0021", "percentageChange": "-0.20%",
+                                 "bidPrice": "1.0481", "askPrice": "1.0483", "lastTradeTimestamp": "Feb 23, 2026 5:00 PM ET" }
+            },
+            "status": { "rCode": 200 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let pair = CurrencyPair::new("eur", "usd").unwrap();
+    let quote = currencies.fetch(&pair, None).await.expect("rate should parse");
+    assert_eq!(quote.pair.to_string(), "EUR/USD");
+    assert_eq!(quote.rate, Some(1.0482));
+    assert_eq!((quote.bid, quote.ask), (Some(1.0481), Some(1.0483)));
+}
+
+#[test]
+fn test_currency_pair_validation() {
+    //! Goal: six-letter symbols parse with or without a slash; malformed codes are `ConfigError`.
+    assert_eq!(CurrencyPair::parse("usd/jpy").unwrap().symbol(), "USDJPY");
+    assert_eq!(CurrencyPair::parse("GBPUSD").unwrap().base(), "GBP");
+    assert!(matches!(CurrencyPair::parse("EURO"), Err(NgError::ConfigError(_))));
+    assert!(matches!(CurrencyPair::new("EU1", "USD"), Err(NgError::ConfigError(_))));
+}
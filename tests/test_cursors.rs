@@ -0,0 +1,54 @@
+//! # Pagination Cursor Test Suite
+//!
+//! Validates that `fetch_new_since_last` filters previously seen rows and that
+//! cursors survive a restart through the file-backed `CursorStore`.
+
+use chrono::{TimeZone, Utc};
+use rs_lib_ng::markets::cursors::{fetch_new_since_last, Cursor, CursorStore};
+
+#[derive(Debug, Clone)]
+struct Row {
+    id: u64,
+}
+
+async fn fetch_rows(ids: Vec<u64>) -> Result<Vec<Row>, rs_lib_ng::NgError> {
+    Ok(ids.into_iter().map(|id| Row { id }).collect())
+}
+
+#[tokio::test]
+async fn test_only_new_rows_after_restart() {
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_cursors_{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    {
+        let store = CursorStore::with_file(&path).unwrap();
+        let rows = fetch_new_since_last(&store, "insiders:AAPL", |last| {
+            assert!(last.is_none());
+            fetch_rows(vec![3, 1, 2])
+        }, |r| Some(Cursor::Offset(r.id))).await.unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(store.get("insiders:AAPL"), Some(Cursor::Offset(3)));
+    }
+
+    // A new store simulates a restarted collector.
+    let store = CursorStore::with_file(&path).unwrap();
+    let rows = fetch_new_since_last(&store, "insiders:AAPL", |last| {
+        assert_eq!(last, Some(Cursor::Offset(3)));
+        fetch_rows(vec![5, 4, 3, 2])
+    }, |r| Some(Cursor::Offset(r.id))).await.unwrap();
+
+    let ids: Vec<u64> = rows.iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![5, 4]);
+    assert_eq!(store.get("insiders:AAPL"), Some(Cursor::Offset(5)));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_cursor_ordering() {
+    let early = Cursor::Timestamp(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap());
+    let late = Cursor::Timestamp(Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap());
+    assert!(late.is_after(&early));
+    assert!(!early.is_after(&late));
+    assert!(!late.is_after(&Cursor::Offset(1)), "different kinds are never ordered");
+}
@@ -0,0 +1,111 @@
+//! # Nasdaq Dividends Test Suite
+//!
+//! Integration tests for `Dividends`: history and calendar row mapping and
+//! Nasdaq date format parsing.
+
+use chrono::NaiveDate;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::dividends::Dividends;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Dividends service against a mock server.
+async fn setup_dividends_test() -> (Dividends, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("dividends_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Dividends::with_api(api), server)
+}
+
+fn ymd(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(y, m, d)
+}
+
+#[tokio::test]
+async fn test_fetch_history_rows() {
+    //! Scenario: two history rows, one with an unknown record date.
+    //! Goal: dates become `NaiveDate`, "N/A" becomes `None`, amounts are parsed.
+    let (dividends, server) = setup_dividends_test().await;
+
+    let body = json!({
+        "data": {
+            "exDividendDate": "02/09/2026",
+            "dividends": { "rows": [
+                { "exOrEffDate": "02/09/2026", "type": "Cash", "amount": "$0.26", "declarationDate": "01/29/2026",
+                  "recordDate": "02/10/2026", "paymentDate": "02/13/2026", "currency": "USD" },
+                { "exOrEffDate": "11/10/2025", "type": "Cash", "amount": "$0.25", "declarationDate": "10/30/2025",
+                  "recordDate": "N/A", "paymentDate": "11/13/2025", "currency": "USD" }
+            ]}
+        },
+        "status": { "rCode": 200 }
+    });
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/dividends"))
+        .and(query_param("assetclass", "stocks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let events = dividends.fetch_history("AAPL", AssetClass::Stocks, None).await.expect("history should parse");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].symbol, "AAPL");
+    assert_eq!(events[0].ex_date, ymd(2026, 2, 9));
+    assert_eq!(events[0].payment_date, ymd(2026, 2, 13));
+    assert_eq!(events[0].amount, Some(0.26));
+    assert_eq!(events[0].kind, "Cash");
+    assert_eq!(events[1].record_date, None);
+}
+
+#[tokio::test]
+async fn test_fetch_calendar_rows() {
+    //! Scenario: the calendar uses unpadded dates and numeric rates.
+    //! Goal: rows map to events with company name and rate as amount.
+    let (dividends, server) = setup_dividends_test().await;
+
+    let body = json!({
+        "data": { "calendar": { "rows": [
+            { "companyName": "Coca-Cola Company (The)", "symbol": "KO", "dividend_Ex_Date": "2/23/2026",
+              "payment_Date": "3/13/2026", "record_Date": "2/23/2026", "dividend_Rate": 0.51,
+              "indicated_Annual_Dividend": 2.04, "announcement_Date": "2/10/2026" }
+        ]}},
+        "status": { "rCode": 200 }
+    });
+    Mock::given(method("GET"))
+        .and(path("/api/calendar/dividends"))
+        .and(query_param("date", "2026-02-23"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let events = dividends
+        .fetch_calendar(NaiveDate::from_ymd_opt(2026, 2, 23).unwrap(), None)
+        .await
+        .expect("calendar should parse");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].symbol, "KO");
+    assert_eq!(events[0].company_name, "Coca-Cola Company (The)");
+    assert_eq!(events[0].ex_date, ymd(2026, 2, 23));
+    assert_eq!(events[0].payment_date, ymd(2026, 3, 13));
+    assert_eq!(events[0].declaration_date, ymd(2026, 2, 10));
+    assert_eq!(events[0].amount, Some(0.51));
+}
+
+#[tokio::test]
+async fn test_fetch_calendar_empty_day() {
+    //! Goal: a day without dividends (null rows) is an empty list, not an error.
+    let (dividends, server) = setup_dividends_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/calendar/dividends"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "calendar": { "rows": null } }, "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    let events = dividends.fetch_calendar(NaiveDate::from_ymd_opt(2026, 12, 25).unwrap(), None).await.unwrap();
+    assert!(events.is_empty());
+}
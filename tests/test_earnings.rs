@@ -0,0 +1,68 @@
+//! # Nasdaq Earnings Test Suite
+//!
+//! Integration tests for `Earnings`: surprise rows, computed surprise and empty tables.
+
+use chrono::NaiveDate;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::earnings::Earnings;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Earnings service against a mock server.
+async fn setup_earnings_test() -> (Earnings, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("earnings_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Earnings::with_api(api), server)
+}
+
+#[tokio::test]
+async fn test_fetch_surprises() {
+    //! Scenario: a beat reported by Nasdaq, a miss without a surprise percentage, and a row without an estimate.
+    //! Goal: EPS values and dates are typed; the missing percentage is computed only when possible.
+    let (earnings, server) = setup_earnings_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/company/AAPL/earnings-surprise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "earningsSurpriseTable": { "rows": [
+                { "fiscalQtrEnd": "Dec 2025", "dateReported": "1/29/2026", "eps": 2.84, "consensusForecast": "2.67", "percentageSurprise": "6.37" },
+                { "fiscalQtrEnd": "Sep 2025", "dateReported": "10/30/2025", "eps": "1.50", "consensusForecast": "1.60", "percentageSurprise": "" },
+                { "fiscalQtrEnd": "Jun 2025", "dateReported": "7/31/2025", "eps": "1.57", "consensusForecast": "N/A", "percentageSurprise": null }
+            ]}},
+            "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    let rows = earnings.fetch_surprises("AAPL", None).await.expect("surprises should parse");
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].fiscal_quarter_end, "Dec 2025");
+    assert_eq!(rows[0].date_reported, NaiveDate::from_ymd_opt(2026, 1, 29));
+    assert_eq!(rows[0].actual_eps, Some(2.84));
+    assert_eq!(rows[0].estimated_eps, Some(2.67));
+    assert_eq!(rows[0].surprise_percent, Some(6.37));
+    assert_eq!(rows[0].is_beat(), Some(true));
+    assert!((rows[1].surprise_percent.unwrap() + 6.25).abs() < 1e-9);
+    assert_eq!(rows[1].is_beat(), Some(false));
+    assert_eq!(rows[2].surprise_percent, None);
+    assert_eq!(rows[2].surprise(), None);
+}
+
+#[tokio::test]
+async fn test_fetch_surprises_without_table_is_empty() {
+    //! Goal: a company without reported quarters (null table) returns an empty list.
+    let (earnings, server) = setup_earnings_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/company/ZZZZ/earnings-surprise"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "earningsSurpriseTable": null }, "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    assert!(earnings.fetch_surprises("ZZZZ", None).await.unwrap().is_empty());
+}
@@ -7,8 +7,9 @@
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 use serde_json::json;
-use chrono::{Utc, TimeZone};
-use rs_lib_ng::markets::cnn::fearandgreed::{FearAndGreed, FearAndGreedStatus};
+use chrono::{NaiveDate, Utc, TimeZone};
+use rs_lib_ng::markets::cnn::fearandgreed::{FearAndGreed, FearAndGreedStatus, FngComponentHistory};
+use rs_lib_ng::markets::sessions::SessionEvent;
 use rs_lib_ng::loggers::builder::LoggerBuilder;
 use rs_lib_ng::core::error::NgError;
 
@@ -128,4 +129,320 @@ async fn test_malformed_root_key_error() {
         assert!(details.contains("Missing 'fear_and_greed' root key"));
         assert!(!endpoint.is_empty());
     }
-}
\ No newline at end of file
+}
+/// Builds a FearAndGreed service pointed at `server`.
+fn service_for(server: &MockServer) -> FearAndGreed {
+    let logger = LoggerBuilder::new("fng_test").build().expect("Failed to build test logger");
+    FearAndGreed::new(logger).with_graphdata_url(&format!("{}/index/fearandgreed/graphdata", server.uri()))
+}
+
+#[tokio::test]
+async fn test_fetch_latest_maps_all_seven_components() {
+    //! Scenario: a graphdata payload with all seven sub-indicator blocks and their series.
+    //! Goal: junk bond, safe haven and VIX readings are mapped, along with every component's history.
+    let server = MockServer::start().await;
+    let block = |score: f64, rating: &str, points: &[(f64, f64)]| json!({
+        "timestamp": 1771881042000.0, "score": score, "rating": rating,
+        "data": points.iter().map(|(x, y)| json!({ "x": x, "y": y, "rating": rating })).collect::<Vec<_>>()
+    });
+    let body = json!({
+        "fear_and_greed": { "score": 38.0, "rating": "fear", "timestamp": "2026-02-23T21:10:42+00:00",
+                            "previous_close": 45.4, "previous_1_week": 37.7 },
+        "fear_and_greed_historical": { "data": [{ "x": 1771804800000.0, "y": 40.1, "rating": "fear" }, { "x": "bad" }] },
+        "market_momentum_sp500": block(15.2, "extreme fear", &[(1771804800000.0, 1.0)]),
+        "stock_price_strength": block(92.2, "extreme greed", &[]),
+        "stock_price_breadth": block(93.0, "extreme greed", &[]),
+        "put_call_options": block(2.4, "extreme fear", &[]),
+        "junk_bond_demand": block(61.0, "greed", &[(1771718400000.0, 0.03), (1771804800000.0, 0.031)]),
+        "safe_haven_demand": block(20.5, "extreme fear", &[(1771804800000.0, -1.2)]),
+        "market_volatility_vix": block(33.0, "fear", &[(1771804800000.0, 19.4)])
+    });
+    Mock::given(method("GET"))
+        .and(path("/index/fearandgreed/graphdata"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&body))
+        .mount(&server)
+        .await;
+
+    let status = service_for(&server).fetch_latest(None).await.expect("graphdata should parse");
+    assert_eq!(status.history.len(), 1);
+    assert_eq!(status.junk_bond_demand.value, 61.0);
+    assert_eq!(status.junk_bond_demand.rating, "greed");
+    assert_eq!(status.safe_haven_demand.value, 20.5);
+    assert_eq!(status.market_volatility_vix.rating, "fear");
+    let history = &status.component_history;
+    assert_eq!(history.junk_bond_demand.len(), 2);
+    assert_eq!(history.junk_bond_demand[1].value, 0.031);
+    assert_eq!(history.junk_bond_demand[0].date, Utc.timestamp_millis_opt(1771718400000).unwrap());
+    assert_eq!(history.market_volatility_vix[0].value, 19.4);
+    assert_eq!(history.market_momentum.len(), 1);
+    assert!(history.stock_price_strength.is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_range_merges_dated_calls() {
+    //! Scenario: the first dated call stops two days short of the range end; the second overlaps it by a day.
+    //! Goal: two calls, and one sorted reading per day inside the range.
+    let server = MockServer::start().await;
+    let day = |d: u32| Utc.with_ymd_and_hms(2026, 1, d, 0, 0, 0).unwrap().timestamp_millis() as f64;
+    let graph = |points: &[(u32, f64)]| json!({
+        "fear_and_greed": { "score": 50.0, "rating": "neutral", "timestamp": "2026-01-10T21:00:00+00:00" },
+        "fear_and_greed_historical": {
+            "data": points.iter().map(|(d, y)| json!({ "x": day(*d), "y": y, "rating": "neutral" })).collect::<Vec<_>>()
+        }
+    });
+    Mock::given(method("GET"))
+        .and(path("/index/fearandgreed/graphdata/2026-01-02"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph(&[(1, 10.0), (2, 20.0), (3, 30.0)])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/index/fearandgreed/graphdata/2026-01-04"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph(&[(3, 31.0), (4, 40.0), (5, 50.0), (6, 60.0)])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let start = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+    let end = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+    let range = service_for(&server).fetch_range(start, end, None).await.expect("range should merge");
+    let values: Vec<f64> = range.iter().map(|p| p.value).collect();
+    assert_eq!(values, [20.0, 31.0, 40.0, 50.0]);
+}
+
+/// Graphdata body with the given current score and rating.
+fn graph_with(score: f64, rating: &str) -> serde_json::Value {
+    json!({ "fear_and_greed": { "score": score, "rating": rating, "timestamp": "2026-02-23T21:10:42+00:00" } })
+}
+
+#[tokio::test]
+async fn test_start_polling_notifies_only_changes() {
+    //! Scenario: two polls return the same reading, later ones a new score.
+    //! Goal: subscribers see the first reading, then the new one, and nothing in between.
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph_with(38.0, "fear")))
+        .up_to_n_times(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph_with(41.0, "fear")))
+        .mount(&server)
+        .await;
+    let service = std::sync::Arc::new(service_for(&server));
+
+    let mut updates = service.start_polling(std::time::Duration::from_millis(20));
+    let timeout = std::time::Duration::from_secs(5);
+    tokio::time::timeout(timeout, updates.changed()).await.unwrap().unwrap();
+    assert_eq!(updates.borrow_and_update().as_ref().unwrap().current.value, 38.0);
+    tokio::time::timeout(timeout, updates.changed()).await.unwrap().unwrap();
+    assert_eq!(updates.borrow_and_update().as_ref().unwrap().current.value, 41.0);
+    assert!(server.received_requests().await.unwrap().len() >= 3);
+}
+
+#[tokio::test]
+async fn test_polling_slows_down_off_hours() {
+    //! Scenario: the session scheduler reports the regular close.
+    //! Goal: polling drops to the off-hours interval, so no further request follows quickly.
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph_with(38.0, "fear")))
+        .mount(&server)
+        .await;
+    let service = std::sync::Arc::new(service_for(&server));
+    let (events, receiver) = tokio::sync::broadcast::channel(4);
+    events.send(SessionEvent::RegularClose { date: NaiveDate::from_ymd_opt(2026, 2, 23).unwrap() }).unwrap();
+
+    let interval = std::time::Duration::from_millis(20);
+    let updates = service.start_polling_with_sessions(interval, std::time::Duration::from_secs(3600), receiver);
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    assert!(updates.borrow().is_some());
+    let off_hours_requests = server.received_requests().await.unwrap().len();
+    assert!(off_hours_requests <= 2, "{} requests", off_hours_requests);
+
+    // The regular open switches back to the short interval.
+    events.send(SessionEvent::RegularOpen { date: NaiveDate::from_ymd_opt(2026, 2, 24).unwrap() }).unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+    assert!(server.received_requests().await.unwrap().len() >= off_hours_requests + 3);
+}
+
+#[tokio::test]
+async fn test_fetch_current_returns_only_the_reading() {
+    //! Scenario: a graphdata payload with a current reading and history.
+    //! Goal: `fetch_current` returns the score, rating and timestamp; a missing root key is still an error.
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph_with(38.0, "fear")))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "unexpected_root": {} })))
+        .mount(&server)
+        .await;
+    let service = service_for(&server);
+
+    let current = service.fetch_current(None).await.expect("current reading");
+    assert_eq!((current.value, current.rating.as_str()), (38.0, "fear"));
+    assert_eq!(current.date, Utc.with_ymd_and_hms(2026, 2, 23, 21, 10, 42).unwrap());
+    assert!(matches!(service.fetch_current(None).await, Err(NgError::MalformedResponse { .. })));
+}
+
+#[tokio::test]
+async fn test_fetch_cached_shares_fetch_and_serves_stale() {
+    //! Scenario: two calls within the TTL, then a refetch that fails.
+    //! Goal: one request for the first two calls; the failed refetch returns the cached status marked stale.
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph_with(38.0, "fear")))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+    let service = service_for(&server);
+    let ttl = std::time::Duration::from_secs(60);
+
+    let first = service.fetch_cached(ttl, None).await.expect("first fetch");
+    let second = service.fetch_cached(ttl, None).await.expect("cached");
+    assert!(!first.is_stale() && !second.is_stale());
+    assert_eq!(second.fetched_at, first.fetched_at);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+
+    let no_retry = rs_lib_ng::retrieve::ky_http::KyOptions { retry: 0, ..Default::default() };
+    let stale = service.fetch_cached(std::time::Duration::ZERO, Some(no_retry)).await.expect("stale fallback");
+    assert!(stale.is_stale());
+    assert_eq!((stale.value.current.value, stale.fetched_at), (38.0, first.fetched_at));
+}
+
+#[test]
+fn test_to_csv_dumps_all_series() {
+    //! Goal: index and component histories become one CSV with a series column; odd ratings are quoted.
+    let at = |d: u32| Utc.with_ymd_and_hms(2026, 1, d, 0, 0, 0).unwrap();
+    let point = |d: u32, value: f64, rating: &str| rs_lib_ng::markets::cnn::fearandgreed::FngData { date: at(d), value, rating: rating.to_string() };
+    let status = FearAndGreedStatus {
+        current: point(2, 41.0, "fear"),
+        history: vec![point(1, 38.5, "fear"), point(2, 41.0, "fear")],
+        market_momentum: point(2, 10.0, ""),
+        stock_price_strength: point(2, 10.0, ""),
+        stock_price_breadth: point(2, 10.0, ""),
+        put_call_options: point(2, 10.0, ""),
+        junk_bond_demand: point(2, 10.0, ""),
+        safe_haven_demand: point(2, 10.0, ""),
+        market_volatility_vix: point(2, 10.0, ""),
+        component_history: FngComponentHistory {
+            market_volatility_vix: vec![point(1, 19.4, "fear, \"high\"")],
+            ..Default::default()
+        },
+        previous_close: 0.0,
+        previous_1_week: 0.0,
+    };
+
+    let mut out = Vec::new();
+    status.to_csv(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "series,date,value,rating\n\
+         fear_and_greed,2026-01-01T00:00:00+00:00,38.5,fear\n\
+         fear_and_greed,2026-01-02T00:00:00+00:00,41,fear\n\
+         market_volatility_vix,2026-01-01T00:00:00+00:00,19.4,\"fear, \"\"high\"\"\"\n"
+    );
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_to_parquet_round_trips() {
+    //! Goal: the Parquet export reads back as the same rows as the CSV, in the same order.
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::RowAccessor;
+    let at = |d: u32| Utc.with_ymd_and_hms(2026, 1, d, 0, 0, 0).unwrap();
+    let point = |d: u32, value: f64, rating: &str| rs_lib_ng::markets::cnn::fearandgreed::FngData { date: at(d), value, rating: rating.to_string() };
+    let status = FearAndGreedStatus {
+        current: point(2, 41.0, "fear"),
+        history: vec![point(1, 38.5, "fear"), point(2, 41.0, "fear")],
+        market_momentum: point(2, 10.0, ""),
+        stock_price_strength: point(2, 10.0, ""),
+        stock_price_breadth: point(2, 10.0, ""),
+        put_call_options: point(2, 10.0, ""),
+        junk_bond_demand: point(2, 10.0, ""),
+        safe_haven_demand: point(2, 10.0, ""),
+        market_volatility_vix: point(2, 10.0, ""),
+        component_history: FngComponentHistory {
+            market_volatility_vix: vec![point(1, 19.4, "fear, \"high\"")],
+            ..Default::default()
+        },
+        previous_close: 0.0,
+        previous_1_week: 0.0,
+    };
+
+    let mut out = Vec::new();
+    status.to_parquet(&mut out).unwrap();
+    let reader = SerializedFileReader::new(bytes::Bytes::from(out)).unwrap();
+    let rows: Vec<(String, i64, f64, String)> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| {
+            let row = row.unwrap();
+            (row.get_string(0).unwrap().clone(), row.get_timestamp_millis(1).unwrap(), row.get_double(2).unwrap(), row.get_string(3).unwrap().clone())
+        })
+        .collect();
+    let ms = |d: u32| at(d).timestamp_millis();
+    assert_eq!(
+        rows,
+        vec![
+            ("fear_and_greed".to_string(), ms(1), 38.5, "fear".to_string()),
+            ("fear_and_greed".to_string(), ms(2), 41.0, "fear".to_string()),
+            ("market_volatility_vix".to_string(), ms(1), 19.4, "fear, \"high\"".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_backfill_walks_back_and_resumes() {
+    //! Scenario: the latest history starts on Jul 1; a dated call reaches back to Jan 2, CNN's oldest reading.
+    //! Goal: both windows stitched from `since` on, newer readings winning; a second run resumes from the stored progress.
+    let server = MockServer::start().await;
+    let day = |m: u32, d: u32| Utc.with_ymd_and_hms(2025, m, d, 0, 0, 0).unwrap().timestamp_millis() as f64;
+    let graph = |points: &[(f64, f64)]| json!({
+        "fear_and_greed": { "score": 50.0, "rating": "neutral", "timestamp": "2025-07-02T21:00:00+00:00" },
+        "fear_and_greed_historical": {
+            "data": points.iter().map(|(x, y)| json!({ "x": x, "y": y, "rating": "neutral" })).collect::<Vec<_>>()
+        }
+    });
+    Mock::given(method("GET"))
+        .and(path("/index/fearandgreed/graphdata"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph(&[(day(7, 1), 71.0), (day(7, 2), 72.0)])))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/index/fearandgreed/graphdata/2025-01-02"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph(&[(day(1, 2), 12.0), (day(3, 3), 33.0), (day(7, 1), 0.0)])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    // Jul 1 minus 180 days is Jan 2; the next step starts at `since` and gets nothing older.
+    Mock::given(method("GET"))
+        .and(path("/index/fearandgreed/graphdata/2025-01-01"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(graph(&[(day(1, 2), 12.0)])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join(format!("fng_backfill_{}", std::process::id()));
+    let logger = LoggerBuilder::new("fng_test").build().expect("Failed to build test logger");
+    let progress = rs_lib_ng::markets::lastgood::LastGoodCache::new(&dir, logger).unwrap();
+    let service = service_for(&server).with_backfill_rate(rs_lib_ng::retrieve::rate_limit::RateLimit::per_second(100));
+    let since = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+    let values = |points: Vec<rs_lib_ng::markets::cnn::fearandgreed::FngData>| points.iter().map(|p| p.value).collect::<Vec<_>>();
+    let full = service.backfill_full_history(since, Some(&progress), None).await.expect("backfill");
+    assert_eq!(values(full), [12.0, 33.0, 71.0, 72.0]);
+
+    let resumed = service.backfill_full_history(since, Some(&progress), None).await.expect("resumed backfill");
+    assert_eq!(values(resumed), [12.0, 33.0, 71.0, 72.0]);
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,77 @@
+//! # Fear & Greed Alerter Test Suite
+//!
+//! Feeds readings straight into `FngAlerter` and checks thresholds, rating
+//! band changes and hysteresis.
+
+use chrono::{TimeZone, Utc};
+use std::sync::{Arc, Mutex};
+use rs_lib_ng::markets::cnn::fearandgreed::{FearAndGreedStatus, FngComponentHistory, FngData};
+use rs_lib_ng::markets::cnn::fngalerts::{FngAlert, FngAlerter, RatingBand};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+fn reading(value: f64) -> FngData {
+    FngData { date: Utc.with_ymd_and_hms(2026, 2, 23, 21, 0, 0).unwrap(), value, rating: RatingBand::of(value).as_str().to_string() }
+}
+
+fn alerter() -> FngAlerter {
+    let logger = LoggerBuilder::new("fng_alerts_test").build().expect("Failed to build test logger");
+    FngAlerter::new(logger).below(20.0).above(80.0).with_hysteresis(2.0)
+}
+
+#[tokio::test]
+async fn test_thresholds_fire_once_until_rearmed() {
+    //! Scenario: the index dips below 20, hovers around it, recovers past 22 and dips again.
+    //! Goal: one alert per dip; the hover within the hysteresis stays quiet; callbacks see every alert.
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut alerter = alerter().on_alert({
+        let seen = seen.clone();
+        Arc::new(move |alert: &FngAlert| seen.lock().unwrap().push(alert.clone()))
+    });
+
+    let crossings: Vec<usize> = [30.0, 19.0, 20.5, 19.5, 22.0, 18.0]
+        .iter()
+        .map(|v| alerter.observe(&reading(*v)).iter().filter(|a| matches!(a, FngAlert::CrossedBelow { .. })).count())
+        .collect();
+    assert_eq!(crossings, [0, 1, 0, 0, 0, 1]);
+    assert_eq!(alerter.observe(&reading(81.0)).first(), Some(&FngAlert::CrossedAbove { threshold: 80.0, reading: reading(81.0) }));
+    assert_eq!(seen.lock().unwrap().iter().filter(|a| !matches!(a, FngAlert::RatingChanged { .. })).count(), 3);
+}
+
+#[tokio::test]
+async fn test_rating_changes_respect_hysteresis() {
+    //! Scenario: readings from the poller's watch channel move from fear towards neutral and back.
+    //! Goal: a band change is broadcast only once the index is 2 points inside the new band.
+    let mut alerter = alerter();
+    let mut alerts = alerter.subscribe();
+    assert!(alerter.observe(&reading(40.0)).is_empty());
+    assert!(alerter.observe(&reading(46.0)).is_empty());
+    assert_eq!(
+        alerter.observe(&reading(47.0)),
+        [FngAlert::RatingChanged { from: RatingBand::Fear, to: RatingBand::Neutral, reading: reading(47.0) }]
+    );
+    assert!(alerter.observe(&reading(44.0)).is_empty());
+    assert_eq!(alerts.recv().await.unwrap(), FngAlert::RatingChanged { from: RatingBand::Fear, to: RatingBand::Neutral, reading: reading(47.0) });
+
+    // Spawned on a poller's channel, the alerter follows its updates.
+    let (updates, receiver) = tokio::sync::watch::channel(None);
+    let mut alerts = alerter.subscribe();
+    let task = alerter.spawn(receiver);
+    let status = FearAndGreedStatus {
+        current: reading(10.0),
+        history: Vec::new(),
+        market_momentum: reading(0.0),
+        stock_price_strength: reading(0.0),
+        stock_price_breadth: reading(0.0),
+        put_call_options: reading(0.0),
+        junk_bond_demand: reading(0.0),
+        safe_haven_demand: reading(0.0),
+        market_volatility_vix: reading(0.0),
+        component_history: FngComponentHistory::default(),
+        previous_close: 0.0,
+        previous_1_week: 0.0,
+    };
+    updates.send(Some(status)).unwrap();
+    assert_eq!(alerts.recv().await.unwrap(), FngAlert::CrossedBelow { threshold: 20.0, reading: reading(10.0) });
+    drop(updates);
+    task.await.unwrap();
+}
@@ -0,0 +1,50 @@
+//! # Fear & Greed Series Test Suite
+//!
+//! Tests for `FngSeries`: moving averages, extremes, percentile ranks and streaks.
+
+use chrono::{Duration, TimeZone, Utc};
+use rs_lib_ng::markets::cnn::fearandgreed::FngData;
+use rs_lib_ng::markets::cnn::fngseries::{FngSeries, Sentiment};
+
+/// Daily readings starting on 2026-01-01, given out of order to check sorting.
+fn series(values: &[f64]) -> FngSeries {
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let mut points: Vec<FngData> = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| FngData { date: start + Duration::days(i as i64), value: *v, rating: String::new() })
+        .collect();
+    points.reverse();
+    FngSeries::from(points)
+}
+
+#[test]
+fn test_averages_extremes_and_percentile() {
+    //! Goal: SMA, rolling SMA, min/max and the latest reading's percentile rank.
+    let s = series(&[20.0, 40.0, 60.0, 80.0, 50.0]);
+    assert_eq!(s.latest().unwrap().value, 50.0);
+    assert_eq!(s.sma(2), Some(65.0));
+    assert_eq!(s.sma(6), None);
+    let rolling: Vec<f64> = s.rolling_sma(3).into_iter().map(|(_, v)| v).collect();
+    assert_eq!(rolling, [40.0, 60.0, 63.333333333333336]);
+    assert_eq!(s.min().unwrap().value, 20.0);
+    assert_eq!(s.max().unwrap().date, Utc.with_ymd_and_hms(2026, 1, 4, 0, 0, 0).unwrap());
+    assert_eq!(s.current_percentile(), Some(60.0));
+    assert_eq!(FngSeries::default().current_percentile(), None);
+}
+
+#[test]
+fn test_streaks() {
+    //! Goal: runs are split at band changes; the current and longest runs are found.
+    let s = series(&[10.0, 30.0, 44.9, 50.0, 70.0, 90.0, 20.0, 25.0]);
+    let bands: Vec<(Sentiment, usize)> = s.streaks().iter().map(|st| (st.sentiment, st.len)).collect();
+    assert_eq!(
+        bands,
+        [(Sentiment::Fear, 3), (Sentiment::Neutral, 1), (Sentiment::Greed, 2), (Sentiment::Fear, 2)]
+    );
+    let current = s.current_streak().unwrap();
+    assert_eq!((current.sentiment, current.len), (Sentiment::Fear, 2));
+    assert_eq!(current.start, Utc.with_ymd_and_hms(2026, 1, 7, 0, 0, 0).unwrap());
+    assert_eq!(s.longest_streak(Sentiment::Fear).unwrap().len, 3);
+    assert_eq!(s.longest_streak(Sentiment::Greed).unwrap().end, Utc.with_ymd_and_hms(2026, 1, 6, 0, 0, 0).unwrap());
+}
@@ -0,0 +1,160 @@
+//! # Nasdaq History Test Suite
+//!
+//! Integration tests for `History`: paging through the historical endpoint,
+//! weekly aggregation, intraday minute candles and ET to UTC conversion.
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::{json, Value};
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::history::{ChartInterval, ChartRange, History};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+/// Helper to initialize the History service against a mock server.
+async fn setup_history_test() -> (History, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("history_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (History::with_api(api), server)
+}
+
+fn row(date: &str, open: &str, high: &str, low: &str, close: &str, volume: &str) -> Value {
+    json!({ "date": date, "open": open, "high": high, "low": low, "close": close, "volume": volume })
+}
+
+fn page(rows: Vec<Value>, total: u64) -> Value {
+    json!({
+        "data": { "symbol": "AAPL", "totalRecords": total, "tradesTable": { "rows": rows } },
+        "status": { "rCode": 200 }
+    })
+}
+
+/// Mounts three trading days (Thu, Fri, Mon), newest first, split into pages of two.
+async fn mount_three_days(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/historical"))
+        .and(query_param("offset", "0"))
+        .and(query_param("limit", "2"))
+        .and(query_param("fromdate", "2026-02-19"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(
+            vec![
+                row("02/23/2026", "$12.00", "$13.00", "$11.50", "$12.50", "3,000"),
+                row("02/20/2026", "$11.00", "$12.20", "$10.90", "$12.00", "2,000"),
+            ],
+            3,
+        )))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/historical"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(
+            vec![row("02/19/2026", "$10.00", "$11.10", "$9.80", "$11.00", "1,000")],
+            3,
+        )))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+fn feb(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 2, day).unwrap()
+}
+
+#[tokio::test]
+async fn test_daily_candles_walk_all_pages() {
+    //! Scenario: the range spans two pages of the historical endpoint.
+    //! Goal: both pages are fetched and candles come back oldest first, stamped 09:30 ET in UTC.
+    let (history, server) = setup_history_test().await;
+    mount_three_days(&server).await;
+
+    let range = ChartRange::Between { from: feb(19), to: feb(23) };
+    let candles = history
+        .with_page_size(2)
+        .fetch_chart("AAPL", AssetClass::Stocks, range, ChartInterval::Daily, None)
+        .await
+        .expect("history should parse");
+
+    assert_eq!(candles.len(), 3);
+    assert_eq!(candles[0].time, Utc.with_ymd_and_hms(2026, 2, 19, 14, 30, 0).unwrap());
+    assert_eq!(candles[0].open, 10.0);
+    assert_eq!(candles[2].close, 12.5);
+    assert_eq!(candles[2].volume, 3_000);
+}
+
+#[tokio::test]
+async fn test_weekly_candles_aggregate_daily_rows() {
+    //! Scenario: Thursday and Friday fall in one ISO week, Monday in the next.
+    //! Goal: two weekly candles with merged high/low/close/volume.
+    let (history, server) = setup_history_test().await;
+    mount_three_days(&server).await;
+
+    let range = ChartRange::Between { from: feb(19), to: feb(23) };
+    let candles = history
+        .with_page_size(2)
+        .fetch_chart("AAPL", AssetClass::Stocks, range, ChartInterval::Weekly, None)
+        .await
+        .expect("history should parse");
+
+    assert_eq!(candles.len(), 2);
+    assert_eq!((candles[0].open, candles[0].high, candles[0].low, candles[0].close), (10.0, 12.2, 9.8, 12.0));
+    assert_eq!(candles[0].volume, 3_000);
+    assert_eq!(candles[1].volume, 3_000);
+}
+
+#[tokio::test]
+async fn test_intraday_minute_candles_from_chart_points() {
+    //! Scenario: chart points carry ET wall-clock milliseconds.
+    //! Goal: points are bucketed into 5-minute candles whose times are real UTC instants.
+    let (history, server) = setup_history_test().await;
+
+    // 2026-02-23 09:30 and 09:33 ET wall clock, then 09:35.
+    let at = |h: u32, m: u32| Utc.with_ymd_and_hms(2026, 2, 23, h, m, 0).unwrap().timestamp_millis();
+    let body = json!({
+        "data": { "symbol": "AAPL", "chart": [
+            { "x": at(9, 33), "y": 101.0, "z": { "shares": "200" } },
+            { "x": at(9, 30), "y": 100.0, "z": { "shares": "1,000" } },
+            { "x": at(9, 35), "y": 99.5, "z": { "shares": "50" } }
+        ]},
+        "status": { "rCode": 200 }
+    });
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/chart"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let candles = history
+        .fetch_chart("AAPL", AssetClass::Stocks, ChartRange::Intraday, ChartInterval::Minutes(5), None)
+        .await
+        .expect("chart should parse");
+
+    assert_eq!(candles.len(), 2);
+    assert_eq!(candles[0].time, Utc.with_ymd_and_hms(2026, 2, 23, 14, 30, 0).unwrap());
+    assert_eq!((candles[0].open, candles[0].close, candles[0].volume), (100.0, 101.0, 1_200));
+    assert_eq!(candles[1].time, Utc.with_ymd_and_hms(2026, 2, 23, 14, 35, 0).unwrap());
+}
+
+#[tokio::test]
+async fn test_minute_interval_requires_intraday_range() {
+    //! Goal: minute candles over a multi-day range are rejected before any request.
+    let (history, _server) = setup_history_test().await;
+    let result = history
+        .fetch_chart("AAPL", AssetClass::Stocks, ChartRange::Days(5), ChartInterval::Minutes(1), None)
+        .await;
+    assert!(matches!(result, Err(NgError::ConfigError(_))));
+}
+
+#[test]
+fn test_chart_range_dates() {
+    //! Goal: relative ranges resolve against the given ET date.
+    let today = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+    assert_eq!(ChartRange::Months(1).dates(today), (NaiveDate::from_ymd_opt(2026, 2, 28).unwrap(), today));
+    assert_eq!(ChartRange::YearToDate.dates(today).0, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+    assert_eq!(ChartRange::Days(7).dates(today).0, NaiveDate::from_ymd_opt(2026, 3, 24).unwrap());
+}
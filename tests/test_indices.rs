@@ -0,0 +1,85 @@
+//! # Nasdaq Index Test Suite
+//!
+//! Integration tests for `Indices`: index symbols with `assetclass=index`,
+//! quote mapping and intraday values.
+
+use chrono::{TimeZone, Utc};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::indices::{Indices, MarketIndex};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Indices service against a mock server.
+async fn setup_indices_test() -> (Indices, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("indices_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Indices::with_api(api), server)
+}
+
+async fn mount_index_quote(server: &MockServer, symbol: &str, name: &str, value: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("/api/quote/{}/info", symbol)))
+        .and(query_param("assetclass", "index"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "symbol": symbol, "companyName": name, "assetClass": "INDEX", "marketStatus": "Closed",
+                "primaryData": { "lastSalePrice": value, "netChange": "-120.55", "percentageChange": "-0.55%",
+                                 "deltaIndicator": "down", "lastTradeTimestamp": "Feb 23, 2026 4:16 PM ET" },
+                "secondaryData": null
+            },
+            "status": { "rCode": 200 }
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_fetch_quotes_in_order() {
+    //! Scenario: Nasdaq-100 and S&P 500 quotes are requested together.
+    //! Goal: each uses its index symbol with `assetclass=index`; values are typed and ordered as asked.
+    let (indices, server) = setup_indices_test().await;
+    mount_index_quote(&server, "NDX", "NASDAQ-100", "21,845.33").await;
+    mount_index_quote(&server, "SPX", "S&P 500", "6,013.13").await;
+
+    let quotes = indices
+        .fetch_quotes(&[MarketIndex::Sp500, MarketIndex::Nasdaq100], None)
+        .await
+        .expect("index quotes should parse");
+
+    assert_eq!(quotes[0].index, MarketIndex::Sp500);
+    assert_eq!(quotes[0].value, Some(6_013.13));
+    assert_eq!(quotes[1].name, "NASDAQ-100");
+    assert_eq!(quotes[1].value, Some(21_845.33));
+    assert_eq!(quotes[1].percentage_change, Some(-0.55));
+}
+
+#[tokio::test]
+async fn test_fetch_intraday_points() {
+    //! Scenario: chart points carry ET wall-clock milliseconds, out of order.
+    //! Goal: points are sorted and stamped with real UTC instants.
+    let (indices, server) = setup_indices_test().await;
+    let at = |h: u32, m: u32| Utc.with_ymd_and_hms(2026, 2, 23, h, m, 0).unwrap().timestamp_millis();
+    Mock::given(method("GET"))
+        .and(path("/api/quote/COMP/chart"))
+        .and(query_param("assetclass", "index"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "symbol": "COMP", "chart": [
+                { "x": at(9, 31), "y": 19_610.5, "z": {} },
+                { "x": at(9, 30), "y": 19_600.0, "z": {} }
+            ]},
+            "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    let points = indices.fetch_intraday(MarketIndex::NasdaqComposite, None).await.expect("chart should parse");
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0].time, Utc.with_ymd_and_hms(2026, 2, 23, 14, 30, 0).unwrap());
+    assert_eq!(points[1].value, 19_610.5);
+}
@@ -0,0 +1,86 @@
+//! # Nasdaq Insider Trading Test Suite
+//!
+//! Integration tests for `Insiders`: transaction row mapping and type labels.
+
+use chrono::NaiveDate;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::insiders::{InsiderTransaction, InsiderTransactionType, Insiders};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Insiders service against a mock server.
+async fn setup_insiders_test() -> (Insiders, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("insiders_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Insiders::with_api(api), server)
+}
+
+#[tokio::test]
+async fn test_fetch_insider_transactions() {
+    //! Scenario: three transactions of different types.
+    //! Goal: names, relations, shares, prices and dates are typed; unknown labels are kept.
+    let (insiders, server) = setup_insiders_test().await;
+
+    let body = json!({
+        "data": { "transactionTable": { "rows": [
+            { "insider": "COOK TIMOTHY D", "relation": "Chief Executive Officer", "lastDate": "10/02/2025",
+              "transactionType": "Sell", "ownType": "Direct", "sharesTraded": "129,963",
+              "lastPrice": "$256.12", "sharesHeld": "3,280,295" },
+            { "insider": "LEVINSON ARTHUR D", "relation": "Director", "lastDate": "08/28/2025",
+              "transactionType": "Automatic Sell", "ownType": "Indirect", "sharesTraded": "90,000",
+              "lastPrice": "N/A", "sharesHeld": "" },
+            { "insider": "ADAMS KATHERINE L", "relation": "General Counsel", "lastDate": "04/01/2025",
+              "transactionType": "Disposition (Non Open Market)", "ownType": "Direct", "sharesTraded": "1,000",
+              "lastPrice": "", "sharesHeld": "N/A" }
+        ]}},
+        "status": { "rCode": 200 }
+    });
+    Mock::given(method("GET"))
+        .and(path("/api/company/AAPL/insider-trades"))
+        .and(query_param("sortOrder", "DESC"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let trades = insiders.fetch("AAPL", None).await.expect("insider trades should parse");
+    assert_eq!(trades.len(), 3);
+    assert_eq!(trades[0].insider_name, "COOK TIMOTHY D");
+    assert_eq!(trades[0].relation, "Chief Executive Officer");
+    assert_eq!(trades[0].transaction_type, InsiderTransactionType::Sell);
+    assert_eq!(trades[0].shares, Some(129_963));
+    assert_eq!(trades[0].price, Some(256.12));
+    assert_eq!(trades[0].shares_held, Some(3_280_295));
+    assert_eq!(trades[0].date, NaiveDate::from_ymd_opt(2025, 10, 2));
+    assert_eq!(trades[1].transaction_type, InsiderTransactionType::AutomaticSell);
+    assert_eq!(trades[1].ownership, "Indirect");
+    assert_eq!(trades[1].price, None);
+    assert_eq!(
+        trades[2].transaction_type,
+        InsiderTransactionType::Other("Disposition (Non Open Market)".to_string())
+    );
+
+    let json = serde_json::to_value(&trades[1]).unwrap();
+    assert_eq!(json["transactionType"], "Automatic Sell");
+    let back: InsiderTransaction = serde_json::from_value(json).unwrap();
+    assert_eq!(back, trades[1]);
+}
+
+#[tokio::test]
+async fn test_fetch_without_trades_is_empty() {
+    //! Goal: a company with no reported trades (null table) returns an empty list.
+    let (insiders, server) = setup_insiders_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/company/ZZZZ/insider-trades"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "transactionTable": null }, "status": { "rCode": 200 }
+        })))
+        .mount(&server)
+        .await;
+
+    assert!(insiders.fetch("ZZZZ", None).await.unwrap().is_empty());
+}
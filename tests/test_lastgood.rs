@@ -0,0 +1,70 @@
+//! # Last-Good Response Cache Test Suite
+//!
+//! Tests for `LastGoodCache`: fresh results are stored, failures fall back to
+//! the stored payload marked stale, and the maximum age is honoured.
+
+use std::path::PathBuf;
+use std::time::Duration;
+use serde_json::json;
+use rs_lib_ng::markets::lastgood::LastGoodCache;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rs_lib_ng_lastgood_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn cache(dir: &PathBuf) -> LastGoodCache {
+    let logger = LoggerBuilder::new("lastgood_test").build().expect("Failed to build test logger");
+    LastGoodCache::new(dir, logger).expect("cache dir should be created")
+}
+
+fn outage() -> NgError {
+    NgError::HttpError("connection refused".into())
+}
+
+#[tokio::test]
+async fn test_failure_serves_last_good_payload() {
+    //! Scenario: a successful fetch, then an outage, then a restart during the outage.
+    //! Goal: the stored payload is served marked stale with the error, also from a new cache instance.
+    let dir = temp_dir("serve");
+    let first = cache(&dir);
+    let fresh = first.fetch_or_stale("quote:BRK/B", || async { Ok(json!({ "price": 1.5 })) }).await.unwrap();
+    assert!(!fresh.is_stale());
+
+    let restarted = cache(&dir);
+    let stale = restarted
+        .fetch_or_stale::<serde_json::Value, _, _>("quote:BRK/B", || async { Err(outage()) })
+        .await
+        .expect("stored payload should be served");
+    assert!(stale.is_stale());
+    assert_eq!(stale.value["price"], 1.5);
+    assert_eq!(stale.fetched_at, fresh.fetched_at);
+    assert!(stale.stale_error.as_deref().unwrap().contains("connection refused"));
+    assert!(stale.age() >= chrono::Duration::zero());
+
+    // Other keys, including ones that only differ in escaped characters, have nothing stored.
+    let other = restarted.fetch_or_stale::<serde_json::Value, _, _>("quote:BRK_2fB", || async { Err(outage()) }).await;
+    assert!(matches!(other, Err(NgError::HttpError(_))));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn test_max_age_and_remove() {
+    //! Goal: payloads older than the maximum age, or removed ones, are not served.
+    let dir = temp_dir("age");
+    let cache = cache(&dir).with_max_age(Duration::from_millis(50));
+    cache.store("fng", &json!({ "score": 40 })).unwrap();
+    assert!(cache.load::<serde_json::Value>("fng").unwrap().is_some());
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    let result = cache.fetch_or_stale::<serde_json::Value, _, _>("fng", || async { Err(outage()) }).await;
+    assert!(matches!(result, Err(NgError::HttpError(_))));
+
+    cache.store("fng", &json!({ "score": 41 })).unwrap();
+    cache.remove("fng").unwrap();
+    assert!(cache.load::<serde_json::Value>("fng").unwrap().is_none());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,72 @@
+//! # Leader Election Test Suite
+//!
+//! Validates lease acquisition, standby behavior, expiry takeover, and
+//! resignation using the file-based lease backend.
+//!
+//! Run with: cargo test --features leader --test test_leader
+#![cfg(feature = "leader")]
+
+use rs_lib_ng::connections::leader::{FileLease, LeaderElector, LeaseBackend};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_single_leader_and_takeover() {
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_leader_{}.lock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let logger = LoggerBuilder::new("leader_test").build().unwrap();
+    let ttl = Duration::from_millis(200);
+
+    let a = LeaderElector::new(logger.clone(), Arc::new(FileLease::new(&path)), "region-a")
+        .with_timing(ttl, Duration::from_millis(50));
+    let b = LeaderElector::new(logger, Arc::new(FileLease::new(&path)), "region-b")
+        .with_timing(ttl, Duration::from_millis(50));
+
+    // First instance wins; the second stands by.
+    assert!(a.tick().await);
+    assert!(!b.tick().await);
+    assert!(a.tick().await, "leader should renew its own lease");
+
+    // Once the leader stops renewing, the lease expires and the standby takes over.
+    let mut rx = b.subscribe();
+    tokio::time::sleep(ttl + Duration::from_millis(50)).await;
+    assert!(b.tick().await);
+    assert!(*rx.borrow_and_update());
+    assert!(!a.tick().await, "former leader must notice the lost lease");
+
+    // Resigning frees the lease for others immediately.
+    b.resign().await.unwrap();
+    assert!(!b.is_leader());
+    assert!(a.tick().await);
+
+    a.resign().await.unwrap();
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_racing_acquires_elect_one_leader() {
+    //! Scenario: several instances try to take the same expired lease at once, many times over.
+    //! Goal: exactly one of them wins each round.
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_leader_race_{}.lock", std::process::id()));
+    let ttl = Duration::from_secs(60);
+
+    for round in 0..25 {
+        // An expired lease left behind by a dead leader.
+        std::fs::write(&path, r#"{"holder":"dead","expires_at_ms":0}"#).unwrap();
+        let attempts: Vec<_> = (0..4)
+            .map(|i| {
+                let lease = FileLease::new(&path);
+                tokio::spawn(async move { lease.try_acquire(&format!("region-{}", i), ttl).await.unwrap() })
+            })
+            .collect();
+        let mut winners = 0;
+        for attempt in attempts {
+            winners += attempt.await.unwrap() as usize;
+        }
+        assert_eq!(winners, 1, "round {} elected {} leaders", round, winners);
+    }
+
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(path.with_extension("lock.guard"));
+}
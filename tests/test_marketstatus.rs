@@ -6,9 +6,7 @@
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 use serde_json::json;
-use chrono::{Utc, TimeZone};
-use chrono_tz::US::Eastern;
-use rs_lib_ng::markets::nasdaq::marketstatus::{MarketStatus, MarketStatusData};
+use rs_lib_ng::markets::nasdaq::marketstatus::{MarketStatus, MarketStatusData, Session};
 use rs_lib_ng::loggers::builder::LoggerBuilder;
 use rs_lib_ng::core::error::NgError;
 
@@ -66,7 +64,7 @@ async fn test_fetch_status_success() {
     // use environment variables to point to the mock server. 
     // For this example, we test the logic methods with mock data.
     
-    let result = service.fetch_status(None).await;
+    let _result = service.fetch_status(None).await;
     // In actual CI, the URL in fetch_raw would be configurable.
     // For logic testing, we demonstrate the utility methods below.
 }
@@ -169,7 +167,7 @@ async fn test_format_duration() {
 async fn test_deserialization_error_handling() {
     //! Scenario: API returns "data" as a string instead of an object.
     //! Goal: Ensure specific NgError is returned.
-    let (service, server) = setup_market_test().await;
+    let (_service, server) = setup_market_test().await;
 
     let bad_json = json!({
         "data": "NotAnObject",
@@ -188,3 +186,286 @@ async fn test_deserialization_error_handling() {
     
     assert!(res.is_err());
 }
+
+/// Mounts a valid market-info response on `server`, expecting `hits` requests.
+async fn mount_market_info(server: &MockServer, hits: u64) {
+    let body = json!({
+        "data": {
+            "country": "U.S.", "marketIndicator": "Open", "uiMarketIndicator": "Market Open",
+            "marketCountDown": "", "preMarketOpeningTime": "", "preMarketClosingTime": "",
+            "marketOpeningTime": "", "marketClosingTime": "", "afterHoursMarketOpeningTime": "",
+            "afterHoursMarketClosingTime": "", "previousTradeDate": "Feb 20, 2026",
+            "nextTradeDate": "Feb 24, 2026", "isBusinessDay": true, "mrktStatus": "Open"
+        },
+        "status": { "rCode": 200 }
+    });
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(hits)
+        .mount(server)
+        .await;
+}
+
+/// Builds a MarketStatus pointed at `server`.
+fn service_for(server: &MockServer) -> MarketStatus {
+    let logger = LoggerBuilder::new("market_status_test").build().expect("Failed to build test logger");
+    let api = rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi::new(logger)
+        .with_base_url(&format!("{}/api/", server.uri()));
+    MarketStatus::with_api(api)
+}
+
+#[tokio::test]
+async fn test_fetch_status_cached_shares_one_request() {
+    //! Scenario: several tasks ask for the status at once, then again within the TTL.
+    //! Goal: a single request reaches the endpoint.
+    let server = MockServer::start().await;
+    mount_market_info(&server, 1).await;
+    let service = std::sync::Arc::new(service_for(&server));
+
+    let ttl = std::time::Duration::from_secs(60);
+    let calls = (0..5).map(|_| {
+        let service = service.clone();
+        tokio::spawn(async move { service.fetch_status_cached(ttl).await })
+    });
+    for call in calls {
+        let data = call.await.unwrap().expect("cached fetch should succeed");
+        assert_eq!(data.mrkt_status, "Open");
+    }
+    service.fetch_status_cached(ttl).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_status_cached_refetches_after_ttl_or_invalidate() {
+    //! Goal: an expired TTL and `invalidate_cache` each trigger a new request.
+    let server = MockServer::start().await;
+    mount_market_info(&server, 3).await;
+    let service = service_for(&server);
+
+    let ttl = std::time::Duration::from_millis(50);
+    service.fetch_status_cached(ttl).await.unwrap();
+    service.fetch_status_cached(ttl).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+    service.fetch_status_cached(ttl).await.unwrap();
+    service.invalidate_cache();
+    service.fetch_status_cached(ttl).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_status_cached_expires_at_early_close() {
+    //! Scenario: a status is cached at 12:30 ET on a day that closes at 1:00 PM.
+    //! Goal: it is reused until 13:00 ET and refetched after it, well within the TTL.
+    use chrono::{TimeZone, Utc};
+    use rs_lib_ng::retrieve::clock::MockClock;
+    let server = MockServer::start().await;
+    let body = json!({
+        "data": {
+            "country": "U.S.", "marketIndicator": "Open", "uiMarketIndicator": "Market Open",
+            "marketCountDown": "", "preMarketOpeningTime": "Nov 27, 2026 04:00 AM ET",
+            "preMarketClosingTime": "Nov 27, 2026 09:30 AM ET", "marketOpeningTime": "Nov 27, 2026 09:30 AM ET",
+            "marketClosingTime": "Nov 27, 2026 01:00 PM ET", "afterHoursMarketOpeningTime": "Nov 27, 2026 01:00 PM ET",
+            "afterHoursMarketClosingTime": "Nov 27, 2026 05:00 PM ET", "previousTradeDate": "Nov 25, 2026",
+            "nextTradeDate": "Nov 30, 2026", "isBusinessDay": true, "mrktStatus": "Open"
+        },
+        "status": { "rCode": 200 }
+    });
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    // 12:30 EST.
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2026, 11, 27, 17, 30, 0).unwrap());
+    let service = service_for(&server).with_clock(std::sync::Arc::new(clock.clone()));
+    let ttl = std::time::Duration::from_secs(3600);
+
+    service.fetch_status_cached(ttl).await.unwrap();
+    clock.advance(std::time::Duration::from_secs(20 * 60));
+    service.fetch_status_cached(ttl).await.unwrap();
+    clock.advance(std::time::Duration::from_secs(20 * 60));
+    service.fetch_status_cached(ttl).await.unwrap();
+}
+
+/// Status for `day` with the given regular close and after-hours close times.
+fn session_status(day: &str, close: &str, ah_close: &str) -> MarketStatusData {
+    MarketStatusData {
+        country: "U.S.".into(), market_indicator: "".into(), ui_market_indicator: "".into(), market_count_down: "".into(),
+        pre_market_opening_time: format!("{} 04:00 AM ET", day),
+        pre_market_closing_time: format!("{} 09:30 AM ET", day),
+        market_opening_time: format!("{} 09:30 AM ET", day),
+        market_closing_time: format!("{} {} ET", day, close),
+        after_hours_market_opening_time: format!("{} {} ET", day, close),
+        after_hours_market_closing_time: format!("{} {} ET", day, ah_close),
+        previous_trade_date: "".into(), next_trade_date: "".into(), is_business_day: true, mrkt_status: "".into(),
+    }
+}
+
+#[tokio::test]
+async fn test_session_at_uses_reported_early_close() {
+    //! Scenario: the day after Thanksgiving closes at 1:00 PM ET (18:00 UTC).
+    //! Goal: 14:00 ET is after-hours, not regular session.
+    use chrono::{TimeZone, Utc};
+    let (service, _) = setup_market_test().await;
+    let data = session_status("Nov 27, 2026", "01:00 PM", "05:00 PM");
+    let at = |h, m| Utc.with_ymd_and_hms(2026, 11, 27, h, m, 0).unwrap();
+
+    assert_eq!(service.session_at(&data, at(8, 0)), Session::Closed);
+    assert_eq!(service.session_at(&data, at(9, 0)), Session::PreMarket);
+    assert_eq!(service.session_at(&data, at(14, 30)), Session::Regular);
+    assert_eq!(service.session_at(&data, at(19, 0)), Session::AfterHours);
+    assert_eq!(service.session_at(&data, at(22, 0)), Session::Closed);
+}
+
+#[tokio::test]
+async fn test_session_at_falls_back_to_standard_hours() {
+    //! Scenario: session time strings are missing.
+    //! Goal: the standard 09:30-16:00 ET session applies; non-business days are closed.
+    use chrono::{TimeZone, Utc};
+    let (service, _) = setup_market_test().await;
+    let mut data = session_status("", "", "");
+    // 15:00 EST on a regular weekday.
+    let now = Utc.with_ymd_and_hms(2026, 3, 2, 20, 0, 0).unwrap();
+    assert_eq!(service.session_at(&data, now), Session::Regular);
+    data.is_business_day = false;
+    assert_eq!(service.session_at(&data, now), Session::Closed);
+}
+
+#[tokio::test]
+async fn test_closing_delay_honours_early_close() {
+    //! Scenario: a future early close (01:00 PM) versus a regular close (04:00 PM).
+    //! Goal: the delays differ by three hours; a malformed time is an error.
+    let (service, _) = setup_market_test().await;
+    let early = session_status("Nov 29, 2030", "01:00 PM", "05:00 PM");
+    let regular = session_status("Nov 29, 2030", "04:00 PM", "08:00 PM");
+
+    let early_delay = service.get_next_closing_delay(&early).expect("early close should parse");
+    let regular_delay = service.get_next_closing_delay(&regular).expect("regular close should parse");
+    let diff = regular_delay.as_secs() - early_delay.as_secs();
+    assert!((3 * 3600 - 1..=3 * 3600 + 1).contains(&diff));
+
+    let mut broken = early.clone();
+    broken.market_closing_time = "soon".into();
+    assert!(matches!(service.get_next_closing_delay(&broken), Err(NgError::MalformedResponse { .. })));
+
+    // A close in the past waits for nothing.
+    let past = session_status("Jan 05, 2024", "04:00 PM", "08:00 PM");
+    assert_eq!(service.get_next_closing_delay(&past).unwrap().as_secs(), 0);
+    service.wait_until_close(&past).await;
+}
+
+#[tokio::test]
+async fn test_computed_status_follows_calendar() {
+    //! Scenario: no API; the status is computed on an early-close day and on Thanksgiving.
+    //! Goal: the half-day ends at 1:00 PM ET, and the holiday reports the next session.
+    use chrono::{TimeZone, Utc};
+    use rs_lib_ng::markets::nasdaq::calendar::TradingCalendar;
+    let (service, _) = setup_market_test().await;
+    let calendar = TradingCalendar::fallback();
+
+    // 13:30 ET on the day after Thanksgiving 2026.
+    let now = Utc.with_ymd_and_hms(2026, 11, 27, 18, 30, 0).unwrap();
+    let data = MarketStatusData::computed(&calendar, now);
+    assert!(data.is_business_day);
+    assert_eq!(data.market_closing_time, "Nov 27, 2026 01:00 PM ET");
+    assert_eq!(data.after_hours_market_closing_time, "Nov 27, 2026 02:00 PM ET");
+    assert_eq!(data.next_trade_date, "Nov 30, 2026");
+    assert_eq!(service.session_at(&data, now), Session::AfterHours);
+
+    let holiday = Utc.with_ymd_and_hms(2026, 11, 26, 15, 0, 0).unwrap();
+    let data = MarketStatusData::computed(&calendar, holiday);
+    assert!(!data.is_business_day);
+    assert_eq!(data.market_opening_time, "Nov 27, 2026 09:30 AM ET");
+    assert_eq!(data.previous_trade_date, "Nov 25, 2026");
+    assert_eq!(data.mrkt_status, "Closed");
+    assert_eq!(service.session_at(&data, holiday), Session::Closed);
+}
+
+#[tokio::test]
+async fn test_fetch_status_or_computed_flags_fallback() {
+    //! Scenario: the endpoint answers with an HTML maintenance page, then recovers.
+    //! Goal: the first status is flagged as computed, the second as confirmed.
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>Maintenance</html>"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    mount_market_info(&server, 1).await;
+    let service = service_for(&server);
+
+    let computed = service.fetch_status_or_computed(None).await;
+    assert!(computed.is_computed());
+    assert_eq!(computed.data.country, "U.S.");
+
+    let confirmed = service.fetch_status_or_computed(None).await;
+    assert!(!confirmed.is_computed());
+    assert_eq!(confirmed.data.mrkt_status, "Open");
+}
+
+#[tokio::test]
+async fn test_computed_status_and_session_follow_clock() {
+    //! Scenario: the endpoint is down; the service runs on a MockClock set to 15:00 ET on a Monday.
+    //! Goal: the computed status and `current_session` both use the clock's time, not the wall clock.
+    use chrono::{TimeZone, Utc};
+    use rs_lib_ng::retrieve::clock::MockClock;
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("<html>Maintenance</html>"))
+        .mount(&server)
+        .await;
+    // 15:00 EST.
+    let clock = MockClock::new(Utc.with_ymd_and_hms(2026, 3, 2, 20, 0, 0).unwrap());
+    let service = service_for(&server).with_clock(std::sync::Arc::new(clock.clone()));
+
+    let resolved = service.fetch_status_or_computed(None).await;
+    assert!(resolved.is_computed());
+    assert_eq!(resolved.data.mrkt_status, "Open");
+    assert_eq!(service.current_session(&resolved.data), Session::Regular);
+
+    // Two hours later the same status reads as after-hours.
+    clock.advance(std::time::Duration::from_secs(2 * 3600));
+    assert_eq!(service.current_session(&resolved.data), Session::AfterHours);
+}
+
+#[tokio::test]
+async fn test_poll_until_open_refreshes_stale_status() {
+    //! Scenario: the first status is stale (its next trade date already passed), the next one is open.
+    //! Goal: the poller refreshes after `interval` and returns the open status.
+    use chrono::{Duration as ChronoDuration, Utc};
+    use chrono_tz::US::Eastern;
+    let now = Utc::now();
+    let stamp = |offset: i64| (now + ChronoDuration::hours(offset)).with_timezone(&Eastern).format("%b %d, %Y %I:%M %p ET").to_string();
+    let yesterday = (now - ChronoDuration::days(1)).with_timezone(&Eastern).format("%b %d, %Y").to_string();
+    let status = |business_day: bool, open: i64| json!({
+        "country": "U.S.", "marketIndicator": "", "uiMarketIndicator": "", "marketCountDown": "",
+        "preMarketOpeningTime": stamp(open - 1), "preMarketClosingTime": stamp(open),
+        "marketOpeningTime": stamp(open), "marketClosingTime": stamp(open + 2),
+        "afterHoursMarketOpeningTime": stamp(open + 2), "afterHoursMarketClosingTime": stamp(open + 3),
+        "previousTradeDate": "", "nextTradeDate": yesterday, "isBusinessDay": business_day, "mrktStatus": ""
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": status(false, -5), "status": { "rCode": 200 } })))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": status(true, -1), "status": { "rCode": 200 } })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let service = service_for(&server);
+
+    let poll = service.poll_until_open(std::time::Duration::from_millis(50), None);
+    let open = tokio::time::timeout(std::time::Duration::from_secs(5), poll).await.expect("poller should resolve");
+    assert!(open.is_business_day);
+    assert_eq!(service.session_at(&open, Utc::now()), Session::Regular);
+}
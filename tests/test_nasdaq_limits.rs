@@ -0,0 +1,65 @@
+//! # Nasdaq Request Limits Test Suite
+//!
+//! Tests for `NasdaqLimits`: reading the budget from configuration and pacing
+//! requests across separate adapters. The limits are process-wide, so only one
+//! test in this binary installs them.
+
+use std::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::configs::ConfigBuilder;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::limits::{NasdaqLimits, DEFAULT_CONCURRENCY};
+use rs_lib_ng::markets::nasdaq::quote::Quote;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+#[test]
+fn test_limits_from_config() {
+    //! Goal: configured keys override the defaults; missing keys keep them; bad types are rejected.
+    let config = ConfigBuilder::new()
+        .with_defaults(json!({ "nasdaq": { "min_interval_ms": 250 } }))
+        .build_local()
+        .unwrap();
+    let limits = NasdaqLimits::from_config(&config).unwrap();
+    assert_eq!(limits.min_interval, Duration::from_millis(250));
+    assert_eq!(limits.concurrency, DEFAULT_CONCURRENCY);
+
+    let bad = ConfigBuilder::new()
+        .with_defaults(json!({ "nasdaq": { "concurrency": "many" } }))
+        .build_local()
+        .unwrap();
+    assert!(matches!(NasdaqLimits::from_config(&bad), Err(NgError::ConfigError(_))));
+}
+
+#[tokio::test]
+async fn test_installed_limits_pace_all_adapters() {
+    //! Scenario: a 150 ms minimum interval, then requests from two different services.
+    //! Goal: the requests are spaced across adapters; a second install is refused.
+    NasdaqLimits { concurrency: 2, min_interval: Duration::from_millis(150) }.install().unwrap();
+    assert!(matches!(NasdaqLimits::default().install(), Err(NgError::ConfigError(_))));
+    assert_eq!(NasdaqLimits::current().min_interval, Duration::from_millis(150));
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "symbol": "AAPL" }, "status": { "rCode": 200 }
+        })))
+        .expect(4)
+        .mount(&server)
+        .await;
+    let logger = LoggerBuilder::new("limits_test").build().expect("Failed to build test logger");
+    let base = format!("{}/api/", server.uri());
+    let first = Quote::with_api(NasdaqApi::new(logger.clone()).with_base_url(&base));
+    let second = Quote::with_api(NasdaqApi::new(logger).with_base_url(&base));
+
+    let started = Instant::now();
+    let batch = first.fetch_many(["AAPL", "AAPL"], AssetClass::Stocks, 2, None);
+    let other = second.fetch_many(["AAPL", "AAPL"], AssetClass::Stocks, 2, None);
+    let (a, b) = tokio::join!(batch, other);
+    assert_eq!(a.quotes.len() + b.quotes.len(), 4);
+    // The first request goes out at once, the other three wait one interval each.
+    assert!(started.elapsed() >= Duration::from_millis(3 * 150 - 20), "elapsed {:?}", started.elapsed());
+}
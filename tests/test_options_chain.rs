@@ -0,0 +1,116 @@
+//! # Nasdaq Option Chain Test Suite
+//!
+//! Integration tests for `OptionChain`: paging, expiry group headers carried
+//! across pages, call/put column mapping and optional greeks.
+
+use chrono::NaiveDate;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::{json, Value};
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::options::{ExpiryFilter, OptionChain, OptionKind};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the OptionChain service against a mock server.
+async fn setup_chain_test() -> (OptionChain, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("options_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (OptionChain::with_api(api), server)
+}
+
+fn group(label: &str) -> Value {
+    json!({ "expirygroup": label, "strike": null })
+}
+
+fn strike(strike: &str, call_bid: &str, put_bid: &str) -> Value {
+    json!({
+        "expirygroup": "", "expiryDate": "Mar 20", "strike": strike,
+        "c_Last": "1.00", "c_Change": "-0.05", "c_Bid": call_bid, "c_Ask": "1.10", "c_Volume": "1,200", "c_Openinterest": "5,000",
+        "p_Last": "2.00", "p_Change": "+0.10", "p_Bid": put_bid, "p_Ask": "--", "p_Volume": "--", "p_Openinterest": "300"
+    })
+}
+
+fn page(rows: Vec<Value>, total: u64) -> Value {
+    json!({ "data": { "totalRecord": total, "table": { "rows": rows } }, "status": { "rCode": 200 } })
+}
+
+#[tokio::test]
+async fn test_chain_walks_pages_and_keeps_expiry_group() {
+    //! Scenario: two strikes per page; the second page continues the first page's expiry group.
+    //! Goal: all strikes are returned with the right expiry and call/put columns.
+    let (chain, server) = setup_chain_test().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/option-chain"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(
+            vec![group("March 20, 2026"), strike("100.00", "0.95", "1.90"), strike("105.00", "0.50", "2.40")],
+            3,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/option-chain"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![strike("110.00", "0.20", "3.10")], 3)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let data = chain
+        .with_page_size(2)
+        .fetch("AAPL", AssetClass::Stocks, ExpiryFilter::All, None)
+        .await
+        .expect("chain should parse");
+
+    let expiry = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+    assert_eq!(data.calls.len(), 3);
+    assert_eq!(data.puts.len(), 3);
+    assert!(data.calls.iter().all(|c| c.expiry == expiry && c.kind == OptionKind::Call));
+    assert_eq!(data.calls[2].strike, 110.0);
+    assert_eq!(data.calls[0].bid, Some(0.95));
+    assert_eq!(data.calls[0].volume, Some(1_200));
+    assert_eq!(data.calls[0].open_interest, Some(5_000));
+    assert_eq!(data.puts[1].bid, Some(2.40));
+    assert_eq!(data.puts[1].ask, None);
+    assert_eq!(data.puts[1].volume, None);
+    assert!(data.calls[0].greeks.is_none());
+}
+
+#[tokio::test]
+async fn test_chain_greeks_and_expiry_filter() {
+    //! Scenario: two expiry groups, greek columns present on calls.
+    //! Goal: the filter keeps one expiry (and is sent as fromdate/todate); greeks are mapped.
+    let (chain, server) = setup_chain_test().await;
+
+    let mut with_greeks = strike("100.00", "0.95", "1.90");
+    with_greeks["c_Delta"] = json!("0.52");
+    with_greeks["c_IV"] = json!("31.5%");
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/option-chain"))
+        .and(query_param("fromdate", "2026-04-17"))
+        .and(query_param("todate", "2026-04-17"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(
+            vec![group("March 20, 2026"), strike("95.00", "1.00", "1.00"), group("April 17, 2026"), with_greeks],
+            2,
+        )))
+        .mount(&server)
+        .await;
+
+    let april = NaiveDate::from_ymd_opt(2026, 4, 17).unwrap();
+    let data = chain
+        .fetch("AAPL", AssetClass::Stocks, ExpiryFilter::On(april), None)
+        .await
+        .expect("chain should parse");
+
+    assert_eq!(data.calls.len(), 1);
+    assert_eq!(data.calls[0].expiry, april);
+    let greeks = data.calls[0].greeks.clone().expect("call greeks");
+    assert_eq!(greeks.delta, Some(0.52));
+    assert_eq!(greeks.implied_volatility, Some(31.5));
+    assert!(data.puts[0].greeks.is_none());
+}
@@ -0,0 +1,127 @@
+//! # Nasdaq Quote Test Suite
+//!
+//! Integration tests for `Quote`, serving recorded-shape Nasdaq quote
+//! responses from WireMock and checking the typed `QuoteData` mapping.
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::quote::Quote;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+/// Helper to initialize the Quote service against a mock server.
+async fn setup_quote_test() -> (Quote, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("quote_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Quote::with_api(api), server)
+}
+
+#[tokio::test]
+async fn test_fetch_quote_typed_fields() {
+    //! Scenario: a quote with Nasdaq display strings.
+    //! Goal: prices, sizes and volume are parsed into numbers, "N/A" into `None`.
+    let (quote, server) = setup_quote_test().await;
+
+    let body = json!({
+        "data": {
+            "symbol": "AAPL",
+            "companyName": "Apple Inc. Common Stock",
+            "stockType": "Common Stock",
+            "exchange": "NASDAQ-GS",
+            "isNasdaqListed": true,
+            "isNasdaq100": true,
+            "assetClass": "STOCKS",
+            "marketStatus": "Open",
+            "primaryData": {
+                "lastSalePrice": "$189.84",
+                "netChange": "-1,234.14",
+                "percentageChange": "+0.07%",
+                "deltaIndicator": "up",
+                "lastTradeTimestamp": "Feb 23, 2026 11:32 AM ET",
+                "isRealTime": true,
+                "bidPrice": "$189.80",
+                "askPrice": "N/A",
+                "bidSize": "300",
+                "askSize": "",
+                "volume": "57,807,697"
+            },
+            "secondaryData": null
+        },
+        "status": { "rCode": 200 }
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/info"))
+        .and(query_param("assetclass", "stocks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(&server)
+        .await;
+
+    let data = quote.fetch("AAPL", AssetClass::Stocks, None).await.expect("quote should parse");
+    assert_eq!(data.symbol, "AAPL");
+    assert_eq!(data.market_status, "Open");
+    assert!(data.is_nasdaq100);
+    assert_eq!(data.primary_data.last_sale_price, Some(189.84));
+    assert_eq!(data.primary_data.net_change, Some(-1234.14));
+    assert_eq!(data.primary_data.percentage_change, Some(0.07));
+    assert_eq!(data.primary_data.bid_price, Some(189.80));
+    assert_eq!(data.primary_data.ask_price, None);
+    assert_eq!(data.primary_data.bid_size, Some(300));
+    assert_eq!(data.primary_data.ask_size, None);
+    assert_eq!(data.primary_data.volume, Some(57_807_697));
+    assert!(data.secondary_data.is_none());
+}
+
+#[tokio::test]
+async fn test_fetch_quote_null_data_is_malformed() {
+    //! Scenario: rCode 200 but `data` is null.
+    //! Goal: the caller gets `MalformedResponse` naming the endpoint.
+    let (quote, server) = setup_quote_test().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/quote/ZZZZ/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": null, "status": { "rCode": 200 } })))
+        .mount(&server)
+        .await;
+
+    match quote.fetch("ZZZZ", AssetClass::Etf, None).await {
+        Err(NgError::MalformedResponse { endpoint, .. }) => assert!(endpoint.contains("assetclass=etf")),
+        other => panic!("expected MalformedResponse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_many_records_failures() {
+    //! Scenario: a batch of three symbols where the middle one has no data.
+    //! Goal: the other quotes are returned in input order; the failure is recorded, not fatal.
+    let (quote, server) = setup_quote_test().await;
+
+    for symbol in ["AAPL", "MSFT"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/api/quote/{}/info", symbol)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "symbol": symbol, "primaryData": { "lastSalePrice": "$1.00" } },
+                "status": { "rCode": 200 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/api/quote/ZZZZ/info"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": null, "status": { "rCode": 200 } })))
+        .mount(&server)
+        .await;
+
+    let batch = quote.fetch_many(["MSFT", "ZZZZ", "AAPL"], AssetClass::Stocks, 2, None).await;
+    let symbols: Vec<&str> = batch.quotes.iter().map(|q| q.symbol.as_str()).collect();
+    assert_eq!(symbols, ["MSFT", "AAPL"]);
+    assert_eq!(batch.failures.len(), 1);
+    assert_eq!(batch.failures[0].0, "ZZZZ");
+    assert!(matches!(batch.failures[0].1, NgError::MalformedResponse { .. }));
+}
@@ -0,0 +1,104 @@
+//! # Nasdaq Screener Test Suite
+//!
+//! Integration tests for `Screener`: filter encoding, row mapping and paging.
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::{json, Value};
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::screener::{Exchange, MarketCap, Region, Screener, ScreenerFilter, Sector};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Screener service against a mock server.
+async fn setup_screener_test() -> (Screener, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("screener_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Screener::with_api(api), server)
+}
+
+fn row(symbol: &str) -> Value {
+    json!({
+        "symbol": symbol, "name": format!("{} Inc.", symbol), "lastsale": "$189.84", "netchange": "-0.14",
+        "pctchange": "-0.07%", "marketCap": "2,900,000,000,000", "country": "United States",
+        "ipoyear": "1980", "volume": "57,807,697", "sector": "Technology", "industry": "Computer Manufacturing"
+    })
+}
+
+fn page(rows: Vec<Value>, total: u64) -> Value {
+    json!({ "data": { "table": { "rows": rows }, "totalrecords": total }, "status": { "rCode": 200 } })
+}
+
+#[test]
+fn test_filter_builder_market_cap_range() {
+    //! Goal: a cap range expands to every bucket in between; duplicates are dropped.
+    let filter = ScreenerFilter::builder()
+        .market_cap_range(MarketCap::Mega, MarketCap::Mid)
+        .market_cap(MarketCap::Large)
+        .build();
+    assert_eq!(filter.market_caps, vec![MarketCap::Mid, MarketCap::Large, MarketCap::Mega]);
+}
+
+#[tokio::test]
+async fn test_fetch_all_sends_filters_and_walks_pages() {
+    //! Scenario: three matching rows served two per page.
+    //! Goal: filters are `|`-joined query parameters and both pages are collected.
+    let (screener, server) = setup_screener_test().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/screener/stocks"))
+        .and(query_param("exchange", "nasdaq|nyse"))
+        .and(query_param("marketcap", "large|mega"))
+        .and(query_param("sector", "technology"))
+        .and(query_param("region", "north_america"))
+        .and(query_param("offset", "0"))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![row("AAPL"), row("MSFT")], 3)))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/screener/stocks"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![row("NVDA")], 3)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let filter = ScreenerFilter::builder()
+        .exchange(Exchange::Nasdaq)
+        .exchange(Exchange::Nyse)
+        .market_cap_range(MarketCap::Large, MarketCap::Mega)
+        .sector(Sector::Technology)
+        .region(Region::NorthAmerica)
+        .build();
+    let rows = screener.with_page_size(2).fetch_all(&filter, None).await.expect("screener should parse");
+
+    assert_eq!(rows.iter().map(|r| r.symbol.as_str()).collect::<Vec<_>>(), ["AAPL", "MSFT", "NVDA"]);
+    assert_eq!(rows[0].last_sale, Some(189.84));
+    assert_eq!(rows[0].pct_change, Some(-0.07));
+    assert_eq!(rows[0].market_cap, Some(2.9e12));
+    assert_eq!(rows[0].ipo_year, Some(1980));
+    assert_eq!(rows[0].volume, Some(57_807_697));
+}
+
+#[tokio::test]
+async fn test_fetch_page_without_filters() {
+    //! Goal: an empty filter sends no filter parameters and reports the total.
+    let (screener, server) = setup_screener_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/screener/stocks"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page(vec![row("AAPL")], 7000)))
+        .mount(&server)
+        .await;
+
+    let page = screener.fetch_page(&ScreenerFilter::default(), 50, 1, None).await.unwrap();
+    assert_eq!(page.total_records, 7000);
+    assert_eq!(page.offset, 50);
+
+    let requests = server.received_requests().await.unwrap();
+    let query = requests[0].url.query().unwrap_or("").to_string();
+    assert!(!query.contains("sector") && !query.contains("exchange"), "unexpected filters in {}", query);
+}
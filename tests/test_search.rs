@@ -0,0 +1,67 @@
+//! # Nasdaq Symbol Search Test Suite
+//!
+//! Integration tests for `SymbolSearch`: match mapping and empty results.
+
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::{AssetClass, NasdaqApi};
+use rs_lib_ng::markets::nasdaq::search::SymbolSearch;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the SymbolSearch service against a mock server.
+async fn setup_search_test() -> (SymbolSearch, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("search_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (SymbolSearch::with_api(api), server)
+}
+
+#[tokio::test]
+async fn test_lookup_maps_matches() {
+    //! Scenario: a company-name query matching a stock, an ETF and an unknown asset label.
+    //! Goal: typed matches in response order; the unknown label has no asset class.
+    let (search, server) = setup_search_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/autocomplete/slookup/10"))
+        .and(query_param("search", "apple"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [
+                { "symbol": "AAPL", "name": "Apple Inc. Common Stock", "mrktCategory": "NGS",
+                  "exchange": "NASDAQ-GS", "asset": "STOCKS", "score": null },
+                { "symbol": "APLY", "name": "YieldMax AAPL Option Income Strategy ETF",
+                  "exchange": "NYSE", "asset": "ETF" },
+                { "symbol": "APLE", "name": "Apple Hospitality REIT", "exchange": null, "asset": "WARRANTS" }
+            ],
+            "status": { "rCode": 200 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let matches = search.lookup("  apple ", None).await.expect("matches should parse");
+    assert_eq!(matches.len(), 3);
+    assert_eq!(matches[0].symbol, "AAPL");
+    assert_eq!(matches[0].exchange, "NASDAQ-GS");
+    assert_eq!(matches[0].asset_class, Some(AssetClass::Stocks));
+    assert_eq!(matches[1].asset_class, Some(AssetClass::Etf));
+    assert_eq!(matches[2].exchange, "");
+    assert_eq!(matches[2].asset_class, None);
+}
+
+#[tokio::test]
+async fn test_lookup_without_matches() {
+    //! Goal: `"data": null` and a blank query both give an empty list; the blank one sends nothing.
+    let (search, server) = setup_search_test().await;
+    Mock::given(method("GET"))
+        .and(path("/api/autocomplete/slookup/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": null, "status": { "rCode": 200 } })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert!(search.lookup("zzzzqx", None).await.unwrap().is_empty());
+    assert!(search.lookup("   ", None).await.unwrap().is_empty());
+}
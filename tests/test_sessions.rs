@@ -0,0 +1,90 @@
+//! # Market Session Scheduler Test Suite
+//!
+//! Drives `MarketSessionScheduler` with a `MockClock`, so a full trading day
+//! of transitions is broadcast instantly.
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use std::sync::Arc;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::marketstatus::MarketStatus;
+use rs_lib_ng::markets::sessions::{MarketSessionScheduler, SessionEvent};
+use rs_lib_ng::retrieve::clock::MockClock;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Starts a mock market-info endpoint and a scheduler whose clock starts at `start` (UTC).
+async fn setup_scheduler(status: serde_json::Value, start: chrono::DateTime<Utc>) -> (Arc<MarketSessionScheduler>, MockServer) {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/market-info/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "data": status, "status": { "rCode": 200 } })))
+        .mount(&server)
+        .await;
+    let logger = LoggerBuilder::new("sessions_test").build().expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger.clone()).with_base_url(&format!("{}/api/", server.uri()));
+    let scheduler = MarketSessionScheduler::new(Arc::new(MarketStatus::with_api(api)), logger)
+        .with_clock(Arc::new(MockClock::new(start)));
+    (Arc::new(scheduler), server)
+}
+
+fn status(day: &str, business_day: bool, close: &str, ah_close: &str) -> serde_json::Value {
+    json!({
+        "country": "U.S.", "marketIndicator": "", "uiMarketIndicator": "", "marketCountDown": "",
+        "preMarketOpeningTime": format!("{} 04:00 AM ET", day),
+        "preMarketClosingTime": format!("{} 09:30 AM ET", day),
+        "marketOpeningTime": format!("{} 09:30 AM ET", day),
+        "marketClosingTime": format!("{} {} ET", day, close),
+        "afterHoursMarketOpeningTime": format!("{} {} ET", day, close),
+        "afterHoursMarketClosingTime": format!("{} {} ET", day, ah_close),
+        "previousTradeDate": "", "nextTradeDate": "", "isBusinessDay": business_day, "mrktStatus": ""
+    })
+}
+
+#[tokio::test]
+async fn test_scheduler_emits_day_transitions_in_order() {
+    //! Scenario: the scheduler starts at 03:00 ET on an early-close day.
+    //! Goal: all four transitions of the day are broadcast in order.
+    let start = Utc.with_ymd_and_hms(2026, 11, 27, 8, 0, 0).unwrap();
+    let (scheduler, _server) = setup_scheduler(status("Nov 27, 2026", true, "01:00 PM", "05:00 PM"), start).await;
+    let mut events = scheduler.subscribe();
+    let task = tokio::spawn({
+        let s = scheduler.clone();
+        async move { s.run().await }
+    });
+
+    let date = NaiveDate::from_ymd_opt(2026, 11, 27).unwrap();
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        seen.push(events.recv().await.expect("event"));
+    }
+    task.abort();
+
+    assert_eq!(
+        seen,
+        [
+            SessionEvent::PreMarketOpen { date },
+            SessionEvent::RegularOpen { date },
+            SessionEvent::RegularClose { date },
+            SessionEvent::AfterHoursClose { date },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_scheduler_detects_holiday() {
+    //! Scenario: a weekday on which Nasdaq reports no business day (times point to the next session).
+    //! Goal: `HolidayDetected` is broadcast for that date.
+    let start = Utc.with_ymd_and_hms(2026, 2, 16, 12, 0, 0).unwrap();
+    let (scheduler, _server) = setup_scheduler(status("Feb 17, 2026", false, "04:00 PM", "08:00 PM"), start).await;
+    let mut events = scheduler.subscribe();
+    let task = tokio::spawn({
+        let s = scheduler.clone();
+        async move { s.run().await }
+    });
+
+    let first = events.recv().await.expect("event");
+    task.abort();
+    assert_eq!(first, SessionEvent::HolidayDetected { date: NaiveDate::from_ymd_opt(2026, 2, 16).unwrap() });
+}
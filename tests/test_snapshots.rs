@@ -0,0 +1,73 @@
+//! # Intraday Snapshot Scheduler Test Suite
+//!
+//! Validates change detection, per-day series queries, file persistence, and
+//! schedule computation of `SnapshotScheduler`.
+
+use chrono::{NaiveTime, TimeZone, Utc};
+use chrono_tz::US::Eastern;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::markets::snapshots::{SnapshotScheduler, SnapshotStore};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[tokio::test]
+async fn test_only_changed_snapshots_are_stored() {
+    //! Scenario: a source returns the same payload twice, then a new one.
+    //! Goal: only two snapshots are kept.
+    let logger = LoggerBuilder::new("snapshot_test").build().unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+
+    let scheduler = SnapshotScheduler::new(logger).with_source("movers", Arc::new(move || {
+        let n = counter.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move { Ok(json!({ "top": if n < 2 { "AAPL" } else { "MSFT" } })) })
+    }));
+
+    assert_eq!(scheduler.snapshot_now().await, vec!["movers".to_string()]);
+    assert!(scheduler.snapshot_now().await.is_empty());
+    assert_eq!(scheduler.snapshot_now().await, vec!["movers".to_string()]);
+
+    let series = scheduler.series("movers");
+    assert_eq!(series.len(), 2);
+    assert_eq!(series[1].data["top"], "MSFT");
+
+    let today = Utc::now().with_timezone(&Eastern).date_naive();
+    assert_eq!(scheduler.series_for_day("movers", today).len(), 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_file_store_round_trip() {
+    //! Scenario: snapshots written to disk are reloaded by a new store.
+    let path = std::env::temp_dir().join(format!("rs_lib_ng_snapshots_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut store = SnapshotStore::with_file(&path).unwrap();
+    assert!(store.record("fng", Utc::now(), json!({ "score": 40 })).unwrap());
+    assert!(!store.record("fng", Utc::now(), json!({ "score": 40 })).unwrap());
+
+    let reloaded = SnapshotStore::with_file(&path).unwrap();
+    assert_eq!(reloaded.series("fng").len(), 1);
+    assert_eq!(reloaded.sources(), vec!["fng".to_string()]);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_next_run_after_wraps_to_next_day() {
+    //! Scenario: the current time is after the last configured instant.
+    let logger = LoggerBuilder::new("snapshot_sched").build().unwrap();
+    let scheduler = SnapshotScheduler::new(logger).at_times(vec![
+        NaiveTime::from_hms_opt(16, 5, 0).unwrap(),
+        NaiveTime::from_hms_opt(9, 35, 0).unwrap(),
+    ]);
+
+    let now = Eastern.with_ymd_and_hms(2026, 3, 2, 12, 0, 0).unwrap().with_timezone(&Utc);
+    let next = scheduler.next_run_after(now).unwrap().with_timezone(&Eastern);
+    assert_eq!(next, Eastern.with_ymd_and_hms(2026, 3, 2, 16, 5, 0).unwrap());
+
+    let late = Eastern.with_ymd_and_hms(2026, 3, 2, 17, 0, 0).unwrap().with_timezone(&Utc);
+    let next = scheduler.next_run_after(late).unwrap().with_timezone(&Eastern);
+    assert_eq!(next, Eastern.with_ymd_and_hms(2026, 3, 3, 9, 35, 0).unwrap());
+}
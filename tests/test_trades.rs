@@ -0,0 +1,72 @@
+//! # Nasdaq Time & Sales Test Suite
+//!
+//! Integration tests for `Trades`: window pagination by `fromTime`, ordering
+//! and the trade limit.
+
+use chrono::NaiveTime;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::{json, Value};
+use rs_lib_ng::markets::nasdaq::apicallnasdaq::NasdaqApi;
+use rs_lib_ng::markets::nasdaq::trades::Trades;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+
+/// Helper to initialize the Trades service against a mock server.
+async fn setup_trades_test() -> (Trades, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("trades_test")
+        .build()
+        .expect("Failed to build test logger");
+    let api = NasdaqApi::new(logger).with_base_url(&format!("{}/api/", server.uri()));
+    (Trades::with_api(api), server)
+}
+
+fn window(rows: &[(&str, &str, &str)]) -> Value {
+    let rows: Vec<Value> = rows
+        .iter()
+        .map(|(t, p, v)| json!({ "nlsTime": t, "nlsPrice": p, "nlsShareVolume": v }))
+        .collect();
+    json!({ "data": { "rows": rows }, "status": { "rCode": 200 } })
+}
+
+async fn mount_window(server: &MockServer, from: &str, body: Value) {
+    Mock::given(method("GET"))
+        .and(path("/api/quote/AAPL/realtime-trades"))
+        .and(query_param("fromTime", from))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+fn hms(h: u32, m: u32, s: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(h, m, s).unwrap()
+}
+
+#[tokio::test]
+async fn test_fetch_walks_windows_until_limit() {
+    //! Scenario: trades start mid-window; rows arrive newest first.
+    //! Goal: earlier trades of the first window are dropped, windows are walked in order, `limit` caps the result.
+    let (trades, server) = setup_trades_test().await;
+
+    mount_window(&server, "15:00", window(&[("15:29:59", "$ 189.90", "100"), ("15:10:00", "$ 189.80", "1,200"), ("15:01:00", "$ 189.00", "5")])).await;
+    mount_window(&server, "15:30", window(&[("15:45:00", "$ 190.10", "300"), ("15:31:00", "$ 190.00", "200")])).await;
+
+    let result = trades.fetch("AAPL", hms(15, 5, 0), 3, None).await.expect("trades should parse");
+
+    let times: Vec<NaiveTime> = result.iter().map(|t| t.time).collect();
+    assert_eq!(times, [hms(15, 10, 0), hms(15, 29, 59), hms(15, 31, 0)]);
+    assert_eq!(result[0].price, 189.80);
+    assert_eq!(result[0].shares, 1_200);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_fetch_stops_at_close() {
+    //! Goal: no window at or after 16:00 is requested.
+    let (trades, server) = setup_trades_test().await;
+    mount_window(&server, "15:30", window(&[("15:59:59", "$ 1.00", "1")])).await;
+
+    let result = trades.fetch("AAPL", hms(15, 30, 0), 100, None).await.unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
@@ -0,0 +1,70 @@
+//! # CNN World Markets Mock Test Suite
+//!
+//! Validates the mapping of CNN's futures and world index summaries.
+
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use serde_json::json;
+use chrono::{TimeZone, Utc};
+use rs_lib_ng::markets::cnn::worldmarkets::WorldMarkets;
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+/// Starts a mock server and a service pointed at it.
+async fn setup_world_test() -> (WorldMarkets, MockServer) {
+    let server = MockServer::start().await;
+    let logger = LoggerBuilder::new("world_markets_test").build().expect("Failed to build test logger");
+    let service = WorldMarkets::new(logger).with_base_url(&format!("{}/markets", server.uri()));
+    (service, server)
+}
+
+#[tokio::test]
+async fn test_fetch_futures_maps_quotes() {
+    //! Scenario: the futures summary lists two contracts and a row without a symbol.
+    //! Goal: both contracts are mapped, the percent change is computed, the bad row skipped.
+    let (service, server) = setup_world_test().await;
+    Mock::given(method("GET"))
+        .and(path("/markets/futures/summary/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "symbol": "ES1!", "name": "S&P 500", "current_price": 5050.0, "prev_close_price": 5000.0,
+                "price_change_from_prev_close": 50.0, "percent_change_from_prev_close": 0.01,
+                "last_price_timestamp": "2026-02-24T13:05:00+00:00", "status": "open"
+            },
+            { "symbol": "NQ1!", "current_price": 18000.0, "prev_close_price": 18200.0 },
+            { "name": "orphan" }
+        ])))
+        .mount(&server)
+        .await;
+
+    let quotes = service.fetch_futures(None).await.expect("futures");
+    assert_eq!(quotes.len(), 2);
+
+    let es = &quotes[0];
+    assert_eq!(es.name, "S&P 500");
+    assert_eq!(es.percent_change, Some(1.0));
+    assert_eq!(es.last_updated, Some(Utc.with_ymd_and_hms(2026, 2, 24, 13, 5, 0).unwrap()));
+    assert!(es.is_open());
+
+    // Missing change, name and status are derived or defaulted.
+    let nq = &quotes[1];
+    assert_eq!((nq.name.as_str(), nq.change), ("NQ1!", Some(-200.0)));
+    assert!(!nq.is_open() && nq.last_updated.is_none());
+}
+
+#[tokio::test]
+async fn test_fetch_world_indices_rejects_non_array() {
+    //! Scenario: the world summary returns an object instead of an instrument array.
+    //! Goal: a `MalformedResponse` naming the endpoint.
+    let (service, server) = setup_world_test().await;
+    Mock::given(method("GET"))
+        .and(path("/markets/world/summary/latest"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "error": "unavailable" })))
+        .mount(&server)
+        .await;
+
+    match service.fetch_world_indices(None).await {
+        Err(NgError::MalformedResponse { endpoint, .. }) => assert!(endpoint.ends_with("/markets/world/summary/latest")),
+        other => panic!("expected MalformedResponse, got {:?}", other.map(|q| q.len())),
+    }
+}
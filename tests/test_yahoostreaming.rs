@@ -0,0 +1,244 @@
+//! # Yahoo Streaming Test Suite
+//!
+//! Decodes protobuf `PricingData` frames the way the streamer sends them.
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{TimeZone, Utc};
+use prost::Message as _;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use rs_lib_ng::markets::nasdaq::datafeeds::pricing::pricing_data::MarketHoursType;
+use rs_lib_ng::markets::nasdaq::datafeeds::pricing::PricingData;
+use rs_lib_ng::markets::nasdaq::datafeeds::yahoostreaming::{MarketHours, YahooQuote, YahooStreaming};
+use rs_lib_ng::loggers::builder::LoggerBuilder;
+use rs_lib_ng::core::error::NgError;
+
+/// Base64 of an AAPL update in regular hours.
+fn aapl_frame() -> String {
+    let data = PricingData {
+        id: "AAPL".to_string(),
+        price: 189.34,
+        time: Utc.with_ymd_and_hms(2026, 2, 24, 15, 30, 0).unwrap().timestamp_millis(),
+        exchange: "NMS".to_string(),
+        market_hours: MarketHoursType::RegularMarket as i32,
+        change_percent: 1.25,
+        day_volume: 41_200_000,
+        change: 2.34,
+        ..Default::default()
+    };
+    general_purpose::STANDARD.encode(data.encode_to_vec())
+}
+
+#[test]
+fn test_from_frame_decodes_bare_and_enveloped_frames() {
+    //! Goal: a bare base64 frame and a version-2 JSON envelope decode to the same typed quote.
+    let bare = YahooQuote::from_frame(&aapl_frame()).expect("bare frame");
+    assert_eq!(bare.symbol, "AAPL");
+    assert_eq!((bare.price, bare.change, bare.change_percent), (189.34, 2.34, 1.25));
+    assert_eq!(bare.day_volume, 41_200_000);
+    assert_eq!(bare.market_hours, MarketHours::Regular);
+    assert_eq!(bare.time, Utc.with_ymd_and_hms(2026, 2, 24, 15, 30, 0).unwrap());
+    assert_eq!((bare.exchange.as_str(), bare.currency.as_str()), ("NMS", ""));
+
+    let envelope = json!({ "type": "pricing", "message": aapl_frame() }).to_string();
+    assert_eq!(YahooQuote::from_frame(&envelope).expect("enveloped frame"), bare);
+}
+
+#[test]
+fn test_from_frame_rejects_garbage() {
+    //! Goal: invalid base64, a truncated message and an envelope without payload are `MalformedResponse`s.
+    let truncated = {
+        let bytes = general_purpose::STANDARD.decode(aapl_frame()).unwrap();
+        general_purpose::STANDARD.encode(&bytes[..bytes.len() - 3])
+    };
+    for frame in ["not base64!".to_string(), truncated, json!({ "type": "pricing" }).to_string()] {
+        assert!(matches!(YahooQuote::from_frame(&frame), Err(NgError::MalformedResponse { .. })), "{}", frame);
+    }
+}
+
+fn streamer(uri: &str) -> YahooStreaming {
+    let logger = LoggerBuilder::new("yahoo_test").build().expect("Failed to build test logger");
+    YahooStreaming::new(logger).with_uri(uri).with_reconnect_backoff(Duration::from_millis(10), Duration::from_millis(50))
+}
+
+#[tokio::test]
+async fn test_stream_reconnects_and_resubscribes() {
+    //! Scenario: a local streamer sends one update and closes; the client connects again.
+    //! Goal: the update comes out of `quotes()`; the second connection gets the same subscription and one reconnect is counted.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let uri = format!("ws://{}", listener.local_addr().unwrap());
+    let (subscriptions, mut received) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        for connection in 0.. {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            if let Some(Ok(Message::Text(sub))) = ws.next().await {
+                subscriptions.send(sub).unwrap();
+            }
+            if connection == 0 {
+                ws.send(Message::Text(aapl_frame())).await.unwrap();
+                ws.close(None).await.unwrap();
+            } else {
+                // Keep later connections open.
+                tokio::spawn(async move { while ws.next().await.is_some() {} });
+            }
+        }
+    });
+
+    let service = Arc::new(streamer(&uri));
+    let mut quotes = Box::pin(service.quotes(vec!["AAPL", "MSFT"]));
+    let timeout = Duration::from_secs(5);
+    let first = tokio::time::timeout(timeout, quotes.next()).await.unwrap().unwrap().expect("decoded quote");
+    assert_eq!((first.symbol.as_str(), first.price), ("AAPL", 189.34));
+    for _ in 0..2 {
+        let sub = tokio::time::timeout(timeout, received.recv()).await.unwrap().unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&sub).unwrap(), json!({ "subscribe": ["AAPL", "MSFT"] }));
+    }
+    assert_eq!(service.reconnect_count(), 1);
+}
+
+#[tokio::test]
+async fn test_stream_gives_up_after_max_reconnects() {
+    //! Scenario: nothing listens on the streamer address.
+    //! Goal: with at most 2 reconnects, `stream_quotes` fails after exactly 2.
+    let uri = {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("ws://{}", listener.local_addr().unwrap())
+    };
+    let service = streamer(&uri).with_max_reconnects(2);
+    let (sender, _receiver) = tokio::sync::mpsc::channel(8);
+    let result = tokio::time::timeout(Duration::from_secs(5), service.stream_quotes(vec!["AAPL"], sender)).await.unwrap();
+    assert!(matches!(result, Err(NgError::InternalError(msg)) if msg.contains("2 reconnect attempts")));
+    assert_eq!(service.reconnect_count(), 2);
+}
+
+#[tokio::test]
+async fn test_handle_changes_symbols_on_live_connection() {
+    //! Scenario: a stream on AAPL; the handle adds MSFT, then drops AAPL.
+    //! Goal: the live connection receives the matching subscribe and unsubscribe messages, without reconnecting.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let uri = format!("ws://{}", listener.local_addr().unwrap());
+    let (messages, mut received) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        while let Some(Ok(Message::Text(text))) = ws.next().await {
+            messages.send(serde_json::from_str::<serde_json::Value>(&text).unwrap()).unwrap();
+        }
+    });
+
+    let service = Arc::new(streamer(&uri));
+    let handle = service.handle();
+    let _quotes = service.quotes(vec!["AAPL"]);
+    let timeout = Duration::from_secs(5);
+    let mut next = async || tokio::time::timeout(timeout, received.recv()).await.unwrap().unwrap();
+    assert_eq!(next().await, json!({ "subscribe": ["AAPL"] }));
+
+    handle.subscribe(&["MSFT", "AAPL"]);
+    assert_eq!(next().await, json!({ "subscribe": ["MSFT"] }));
+    handle.unsubscribe(&["AAPL"]);
+    assert_eq!(next().await, json!({ "unsubscribe": ["AAPL"] }));
+    assert_eq!(handle.symbols(), ["MSFT"]);
+    assert_eq!(service.reconnect_count(), 0);
+}
+
+/// A streamer that accepts connections, reports each one with its frames as
+/// `(connection, message)`, and never sends anything.
+async fn silent_server() -> (String, tokio::sync::mpsc::UnboundedReceiver<(usize, Message)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let uri = format!("ws://{}", listener.local_addr().unwrap());
+    let (frames, received) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        for connection in 0.. {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let frames = frames.clone();
+            tokio::spawn(async move {
+                while let Some(Ok(msg)) = ws.next().await {
+                    let _ = frames.send((connection, msg));
+                }
+            });
+        }
+    });
+    (uri, received)
+}
+
+#[tokio::test]
+async fn test_silent_stream_reconnects_only_in_market_hours() {
+    //! Scenario: the server accepts the subscription and then stays silent.
+    //! Goal: on a Tuesday morning the stale timeout forces a reconnect; on a Saturday it does not.
+    let timeout = Duration::from_secs(5);
+    let tuesday = Utc.with_ymd_and_hms(2026, 2, 24, 15, 0, 0).unwrap();
+    let (uri, mut frames) = silent_server().await;
+    let service = Arc::new(
+        streamer(&uri)
+            .with_stale_timeout(Some(Duration::from_millis(100)))
+            .with_clock(Arc::new(rs_lib_ng::retrieve::clock::MockClock::new(tuesday))),
+    );
+    let _quotes = service.quotes(vec!["AAPL"]);
+    loop {
+        let (connection, _) = tokio::time::timeout(timeout, frames.recv()).await.unwrap().unwrap();
+        if connection == 1 {
+            break;
+        }
+    }
+    assert!(service.reconnect_count() >= 1);
+
+    let saturday = Utc.with_ymd_and_hms(2026, 2, 28, 15, 0, 0).unwrap();
+    let (uri, _frames) = silent_server().await;
+    let service = Arc::new(
+        streamer(&uri)
+            .with_stale_timeout(Some(Duration::from_millis(50)))
+            .with_clock(Arc::new(rs_lib_ng::retrieve::clock::MockClock::new(saturday))),
+    );
+    let _quotes = service.quotes(vec!["AAPL"]);
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(service.reconnect_count(), 0);
+}
+
+#[tokio::test]
+async fn test_heartbeat_sends_pings() {
+    //! Goal: an idle connection receives pings at the heartbeat interval.
+    let (uri, mut frames) = silent_server().await;
+    let service = Arc::new(streamer(&uri).with_heartbeat(Duration::from_millis(30)));
+    let _quotes = service.quotes(vec!["AAPL"]);
+    let timeout = Duration::from_secs(5);
+    let mut pings = 0;
+    while pings < 2 {
+        if let (_, Message::Ping(_)) = tokio::time::timeout(timeout, frames.recv()).await.unwrap().unwrap() {
+            pings += 1;
+        }
+    }
+    assert_eq!(service.reconnect_count(), 0);
+}
+
+#[tokio::test]
+async fn test_cancellation_closes_stream_cleanly() {
+    //! Scenario: a running stream's cancellation token is cancelled.
+    //! Goal: the server receives a close frame and `stream_quotes` returns `Ok` without reconnecting.
+    let (uri, mut frames) = silent_server().await;
+    let token = tokio_util::sync::CancellationToken::new();
+    let service = Arc::new(streamer(&uri).with_cancellation(token.clone()));
+    let (sender, _receiver) = tokio::sync::mpsc::channel(8);
+    let task = tokio::spawn({
+        let s = service.clone();
+        async move { s.stream_quotes(vec!["AAPL"], sender).await }
+    });
+    let timeout = Duration::from_secs(5);
+    assert!(matches!(tokio::time::timeout(timeout, frames.recv()).await.unwrap(), Some((0, Message::Text(_)))));
+
+    token.cancel();
+    assert!(tokio::time::timeout(timeout, task).await.unwrap().unwrap().is_ok());
+    loop {
+        match tokio::time::timeout(timeout, frames.recv()).await.unwrap() {
+            Some((0, Message::Close(_))) => break,
+            Some((0, _)) => continue,
+            other => panic!("expected a close frame, got {:?}", other),
+        }
+    }
+    assert_eq!(service.reconnect_count(), 0);
+}